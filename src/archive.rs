@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+use crate::settings::Settings;
+use crate::state::STATE;
+
+/// Subfolder of the log directory uploaded logs go to when no archive directory is set
+const DEFAULT_ARCHIVE_FOLDER: &str = "Uploaded";
+
+/// What archiving a session's logs did
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArchiveResult {
+    pub moved: usize,
+    /// Already archived, gone from disk or a file of the same name in the target folder
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Folder all archived logs go below, e.g. "<log directory>/Uploaded"
+fn archive_root(settings: &Settings) -> Option<PathBuf> {
+    if settings.archive_directory.trim().is_empty() {
+        if settings.log_directory.is_empty() {
+            return None;
+        }
+        Some(Path::new(&settings.log_directory).join(DEFAULT_ARCHIVE_FOLDER))
+    } else {
+        Some(PathBuf::from(settings.archive_directory.trim()))
+    }
+}
+
+/// Folder today's uploaded logs are moved to, e.g. "<log directory>/Uploaded/2025-10-10"
+fn archive_folder(settings: &Settings) -> Option<PathBuf> {
+    archive_root(settings).map(|root| root.join(chrono::Local::now().format("%Y-%m-%d").to_string()))
+}
+
+/// Whether the directory is the archive folder, which scans, live mode, cleanup and organizing leave alone
+/// Locks the settings, don't call it while holding them
+pub fn is_archive_root(dir: &Path) -> bool {
+    let Some(root) = archive_root(&Settings::get()) else {
+        return false;
+    };
+    // Canonical paths, the log directory may be given with different case or separators
+    match (dir.canonicalize(), root.canonicalize()) {
+        (Ok(dir), Ok(root)) => dir == root,
+        _ => false,
+    }
+}
+
+/// Paths of the current session's logs, from the log list or live mode's queue
+fn session_log_paths() -> Vec<PathBuf> {
+    let filenames: Vec<String> = STATE
+        .uploaded_files
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|f| f.filename.clone())
+        .collect();
+    let logs = STATE.logs.lock().unwrap();
+    let live_queue = STATE.live_queue.lock().unwrap();
+
+    filenames
+        .iter()
+        .filter_map(|filename| {
+            logs.iter()
+                .find(|l| l.filename == *filename)
+                .map(|l| l.path.clone())
+                .or_else(|| live_queue.iter().find(|l| l.filename == *filename).map(|l| l.path.clone()))
+        })
+        .collect()
+}
+
+/// Moves the given logs into the archive folder, keeping their filenames
+fn archive_logs(paths: &[PathBuf], target_dir: &Path) -> ArchiveResult {
+    let mut result = ArchiveResult::default();
+    let mut moves = Vec::new();
+
+    for path in paths {
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        if path.parent() == Some(target_dir) || !path.exists() {
+            result.skipped += 1;
+            continue;
+        }
+        let target = target_dir.join(file_name);
+        if target.exists() {
+            log::warn!("Not archiving {:?}, {:?} already exists", path, target);
+            result.skipped += 1;
+            continue;
+        }
+
+        let move_result = std::fs::create_dir_all(target_dir).and_then(|_| {
+            std::fs::rename(path, &target)
+                .or_else(|_| std::fs::copy(path, &target).and_then(|_| std::fs::remove_file(path)))
+        });
+        match move_result {
+            Ok(()) => {
+                result.moved += 1;
+                moves.push((path.clone(), target));
+            }
+            Err(e) => {
+                log::warn!("Failed to archive {:?}: {}", path, e);
+                result.failed += 1;
+            }
+        }
+    }
+
+    crate::scanning::relocate_logs(&moves);
+    result
+}
+
+/// Moves the finished session's logs out of the log directory, when that is turned on
+/// Called once processing completes, the move runs in the background
+pub fn archive_after_report() {
+    let settings = Settings::get();
+    if !settings.archive_uploaded_logs {
+        return;
+    }
+    let Some(target_dir) = archive_folder(&settings) else {
+        log::warn!("Not archiving uploaded logs, no log directory configured");
+        return;
+    };
+    drop(settings);
+
+    let paths = session_log_paths();
+    if paths.is_empty() {
+        return;
+    }
+
+    crate::threads::spawn_tracked("archive-logs", move || {
+        let _activity = crate::threads::activity("Archiving uploaded logs");
+        crate::throttle::apply_background_priority();
+
+        let result = archive_logs(&paths, &target_dir);
+        log::info!(
+            "Archived uploaded logs to {:?}: {} moved, {} skipped, {} failed",
+            target_dir,
+            result.moved,
+            result.skipped,
+            result.failed
+        );
+    });
+}
@@ -0,0 +1,267 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{create_dir_all, File};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::report_history::ReportEntry;
+use crate::settings::Settings;
+
+/// An account that took part in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attendee {
+    pub account: String,
+    /// Logs of the session the account shows up in
+    pub fights: u32,
+}
+
+/// Who was in the squad for one processed session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceSession {
+    pub session_id: String,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub report_url: String,
+    /// Logs that were found and parsed
+    pub fights: u32,
+    pub attendees: Vec<Attendee>,
+}
+
+/// Attendance of one account across every recorded session
+#[derive(Debug, Clone)]
+pub struct AccountSummary {
+    pub account: String,
+    pub sessions: u32,
+    pub fights: u32,
+    pub last_seen: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceLedger {
+    /// Sessions are only recorded while this is on, parsing every log costs some CPU
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub sessions: Vec<AttendanceSession>,
+}
+
+static ATTENDANCE: Mutex<AttendanceLedger> = Mutex::new(AttendanceLedger {
+    enabled: false,
+    sessions: Vec::new(),
+});
+
+/// Session ids being parsed right now, so an import doesn't record them twice
+static IN_PROGRESS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Keeps a session listed in IN_PROGRESS until it is dropped, also when parsing panics
+struct InProgress(String);
+
+impl Drop for InProgress {
+    fn drop(&mut self) {
+        IN_PROGRESS.lock().unwrap().retain(|id| *id != self.0);
+    }
+}
+
+impl AttendanceLedger {
+    pub fn get() -> MutexGuard<'static, Self> {
+        ATTENDANCE.lock().unwrap()
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            let ledger: Self = serde_json::from_str(&contents)?;
+            log::info!("Loaded attendance for {} session(s)", ledger.sessions.len());
+            *ATTENDANCE.lock().unwrap() = ledger;
+        }
+        Ok(())
+    }
+
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let prefix = path.parent().unwrap();
+        create_dir_all(prefix)?;
+        let mut file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        Ok(())
+    }
+
+    pub fn has_session(&self, session_id: &str) -> bool {
+        self.sessions.iter().any(|s| s.session_id == session_id)
+    }
+
+    pub fn remove_session(&mut self, session_id: &str) {
+        self.sessions.retain(|s| s.session_id != session_id);
+    }
+
+    /// Totals per account, most sessions first
+    pub fn summary(&self) -> Vec<AccountSummary> {
+        let mut accounts: BTreeMap<&str, AccountSummary> = BTreeMap::new();
+        for session in &self.sessions {
+            for attendee in &session.attendees {
+                let entry = accounts.entry(&attendee.account).or_insert_with(|| AccountSummary {
+                    account: attendee.account.clone(),
+                    sessions: 0,
+                    fights: 0,
+                    last_seen: 0,
+                });
+                entry.sessions += 1;
+                entry.fights += attendee.fights;
+                entry.last_seen = entry.last_seen.max(session.timestamp);
+            }
+        }
+
+        let mut summary: Vec<AccountSummary> = accounts.into_values().collect();
+        summary.sort_by(|a, b| b.sessions.cmp(&a.sessions).then_with(|| a.account.cmp(&b.account)));
+        summary
+    }
+
+    /// One row per account and session, for spreadsheets
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("date,session_id,account,fights,session_fights,report_url\n");
+        let mut sessions: Vec<&AttendanceSession> = self.sessions.iter().collect();
+        sessions.sort_by_key(|s| s.timestamp);
+
+        for session in sessions {
+            let date = chrono::DateTime::from_timestamp(session.timestamp as i64, 0)
+                .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            for attendee in &session.attendees {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    date,
+                    crate::formatting::csv_field(&session.session_id),
+                    crate::formatting::csv_field(&attendee.account),
+                    attendee.fights,
+                    session.fights,
+                    crate::formatting::csv_field(&session.report_url)
+                ));
+            }
+        }
+        csv
+    }
+}
+
+fn save(ledger: &AttendanceLedger) {
+    if let Err(e) = ledger.store(crate::attendance_path()) {
+        log::error!("Failed to save attendance: {}", e);
+    }
+}
+
+/// Parses the logs of a saved report in the background and adds who was there to the ledger
+/// Does nothing while attendance tracking is off or the session is already recorded
+pub fn record_report(entry: &ReportEntry) {
+    {
+        let ledger = AttendanceLedger::get();
+        if !ledger.enabled || ledger.has_session(&entry.session_id) || entry.log_files.is_empty() {
+            return;
+        }
+    }
+    let in_progress = {
+        let mut in_progress = IN_PROGRESS.lock().unwrap();
+        if in_progress.contains(&entry.session_id) {
+            return;
+        }
+        in_progress.push(entry.session_id.clone());
+        InProgress(entry.session_id.clone())
+    };
+
+    let session_id = entry.session_id.clone();
+    let timestamp = entry.timestamp;
+    let report_url = entry.main_report_url.clone();
+    let log_files = entry.log_files.clone();
+    let log_directory = Settings::get().log_directory.clone();
+
+    crate::threads::spawn("attendance", move || {
+        let _in_progress = in_progress;
+        crate::throttle::apply_background_priority();
+
+        let wanted: HashSet<&str> = log_files.iter().map(String::as_str).collect();
+        let mut paths = Vec::new();
+        crate::reparse::find_log_paths(Path::new(&log_directory), &wanted, &mut paths);
+
+        let mut fights = 0;
+        let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+        for path in paths {
+            if crate::cancel::is_shutting_down() {
+                break;
+            }
+            crate::throttle::pace();
+            let Some(evtc) = crate::evtc::parse_file(&path) else {
+                log::warn!("Failed to parse {:?} for attendance", path);
+                continue;
+            };
+            fights += 1;
+            for account in evtc.squad_accounts() {
+                *counts.entry(account).or_default() += 1;
+            }
+        }
+
+        if fights == 0 {
+            log::warn!("No logs of session {} were found for attendance", session_id);
+            return;
+        }
+
+        log::info!(
+            "Recorded attendance of {} account(s) over {} fight(s) for session {}",
+            counts.len(),
+            fights,
+            session_id
+        );
+
+        let mut ledger = AttendanceLedger::get();
+        if ledger.has_session(&session_id) {
+            return;
+        }
+        ledger.sessions.push(AttendanceSession {
+            session_id,
+            timestamp,
+            report_url,
+            fights,
+            attendees: counts
+                .into_iter()
+                .map(|(account, fights)| Attendee { account, fights })
+                .collect(),
+        });
+        save(&ledger);
+    });
+}
+
+/// Records every report in the history that isn't in the ledger yet
+pub fn import_report_history() -> usize {
+    let entries: Vec<ReportEntry> = crate::report_history::ReportHistory::get().reports.clone();
+    let ledger_ids: Vec<String> = AttendanceLedger::get()
+        .sessions
+        .iter()
+        .map(|s| s.session_id.clone())
+        .collect();
+
+    let missing: Vec<&ReportEntry> = entries
+        .iter()
+        .filter(|e| !e.log_files.is_empty() && !ledger_ids.contains(&e.session_id))
+        .collect();
+    for entry in &missing {
+        record_report(entry);
+    }
+    missing.len()
+}
+
+/// Whether sessions are still being parsed
+pub fn is_busy() -> bool {
+    !IN_PROGRESS.lock().unwrap().is_empty()
+}
+
+/// Writes the ledger as CSV next to the settings, returns where it went
+pub fn export_csv() -> Result<std::path::PathBuf> {
+    let csv = AttendanceLedger::get().to_csv();
+    let path = crate::attendance_path().with_extension("csv");
+    std::fs::write(&path, csv)?;
+    log::info!("Exported attendance to {:?}", path);
+    Ok(path)
+}
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use chrono::TimeZone;
+
+use crate::report_history::ReportHistory;
+use crate::settings::Settings;
+use crate::state::STATE;
+use crate::uploaded_logs::UploadedLogs;
+
+/// A mismatch between the local upload tracking, the log folder and the server
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditFinding {
+    /// Marked as uploaded, but the log file is gone from the log directory
+    DeletedLocally { filename: String },
+    /// Marked as uploaded, but it still sits in a server session that was never processed
+    InUnprocessedSession { filename: String, session_id: String },
+    /// Marked as uploaded, but no report was saved to history after the log was recorded
+    MissingReport { filename: String },
+    /// Uploaded to an unprocessed server session without being marked as uploaded
+    NotMarked { filename: String, session_id: String },
+}
+
+impl AuditFinding {
+    pub fn filename(&self) -> &str {
+        match self {
+            AuditFinding::DeletedLocally { filename }
+            | AuditFinding::InUnprocessedSession { filename, .. }
+            | AuditFinding::MissingReport { filename }
+            | AuditFinding::NotMarked { filename, .. } => filename,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AuditFinding::DeletedLocally { .. } => "Marked uploaded, file deleted locally",
+            AuditFinding::InUnprocessedSession { .. } => "Marked uploaded, session never processed",
+            AuditFinding::MissingReport { .. } => "Marked uploaded, no report in history",
+            AuditFinding::NotMarked { .. } => "On the server, not marked uploaded",
+        }
+    }
+
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            AuditFinding::DeletedLocally { .. } => "Forget the entry, the log can't be uploaded again",
+            AuditFinding::InUnprocessedSession { .. } => {
+                "Resume the session under Unprocessed Server Sessions, or unmark the log to upload it again"
+            }
+            AuditFinding::MissingReport { .. } => "Unmark the log so it can be uploaded again",
+            AuditFinding::NotMarked { .. } => "Mark the log as uploaded",
+        }
+    }
+
+    /// Label of the button applying the local fix
+    pub fn fix_label(&self) -> &'static str {
+        match self {
+            AuditFinding::DeletedLocally { .. } => "Forget",
+            AuditFinding::InUnprocessedSession { .. } | AuditFinding::MissingReport { .. } => "Unmark",
+            AuditFinding::NotMarked { .. } => "Mark Uploaded",
+        }
+    }
+
+    /// Applies the local fix to the upload tracking (the caller saves it)
+    pub fn apply_fix(&self, uploaded: &mut UploadedLogs) {
+        match self {
+            AuditFinding::DeletedLocally { filename }
+            | AuditFinding::InUnprocessedSession { filename, .. }
+            | AuditFinding::MissingReport { filename } => {
+                uploaded.remove_log(filename);
+            }
+            AuditFinding::NotMarked { filename, .. } => {
+                uploaded.add_log(filename.clone());
+            }
+        }
+    }
+}
+
+/// Runs the upload audit in the background, storing the findings in STATE
+pub fn start_upload_audit() {
+    let settings = Settings::get();
+    let api_endpoint = settings.api_endpoint.clone();
+    let history_token = settings.history_token.clone();
+    let log_directory = settings.log_directory.clone();
+    drop(settings);
+
+    *STATE.audit_running.lock().unwrap() = true;
+    STATE.audit_message.lock().unwrap().clear();
+
+    crate::threads::spawn("upload-audit", move || {
+        let _activity = crate::threads::activity("Auditing uploads");
+        crate::throttle::apply_background_priority();
+
+        // Server records are optional, the local checks still run without them
+        let pending_sessions = if history_token.is_empty() {
+            Vec::new()
+        } else {
+            match crate::upload::list_pending_sessions(&api_endpoint, &history_token) {
+                Ok(sessions) => sessions,
+                Err(e) => {
+                    log::warn!("Audit could not reach the server: {}", e);
+                    *STATE.audit_message.lock().unwrap() =
+                        format!("Server check skipped: {}", e);
+                    Vec::new()
+                }
+            }
+        };
+
+        let server_files: HashMap<String, String> = pending_sessions
+            .iter()
+            .flat_map(|s| {
+                s.files
+                    .iter()
+                    .map(move |f| (f.name.clone(), s.session_id.clone()))
+            })
+            .collect();
+
+        let findings = run_upload_audit(&log_directory, &server_files);
+        log::info!("Upload audit found {} mismatch(es)", findings.len());
+
+        *STATE.audit_findings.lock().unwrap() = Some(findings);
+        *STATE.audit_running.lock().unwrap() = false;
+    });
+}
+
+/// Cross-checks the uploaded log tracking against the log folder, report history and server sessions
+/// `server_files` maps filenames in unprocessed server sessions to their session id
+pub fn run_upload_audit(log_directory: &str, server_files: &HashMap<String, String>) -> Vec<AuditFinding> {
+    // Without a readable log folder every log would look deleted
+    let log_dir = Path::new(log_directory);
+    let check_disk = log_dir.is_dir();
+    let mut on_disk = HashSet::new();
+    collect_log_filenames(log_dir, &mut on_disk);
+
+    let uploaded: Vec<String> = {
+        let uploaded = UploadedLogs::get();
+        let mut filenames: Vec<String> = uploaded.filenames.iter().cloned().collect();
+        filenames.sort();
+        filenames
+    };
+
+    let newest_report = ReportHistory::get()
+        .reports
+        .iter()
+        .map(|r| r.timestamp)
+        .max()
+        .unwrap_or(0);
+
+    // Uploaded but not processed yet on purpose: the current session and one waiting to be resumed
+    let mut in_progress: HashSet<String> = STATE
+        .uploaded_files
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|f| f.filename.clone())
+        .collect();
+    if let Some(resumable) = STATE.resumable_session.lock().unwrap().as_ref() {
+        in_progress.extend(resumable.files.iter().map(|f| f.filename.clone()));
+    }
+
+    let mut findings = Vec::new();
+
+    for filename in uploaded.iter() {
+        if let Some(session_id) = server_files.get(filename) {
+            findings.push(AuditFinding::InUnprocessedSession {
+                filename: filename.clone(),
+                session_id: session_id.clone(),
+            });
+        } else if check_disk && !on_disk.contains(filename) {
+            findings.push(AuditFinding::DeletedLocally {
+                filename: filename.clone(),
+            });
+        } else if !in_progress.contains(filename)
+            && log_recorded_at(filename).is_some_and(|recorded| recorded > newest_report)
+        {
+            // Reports are saved when processing finishes, so one must be newer than the log
+            findings.push(AuditFinding::MissingReport {
+                filename: filename.clone(),
+            });
+        }
+    }
+
+    let mut unmarked: Vec<(&String, &String)> = server_files
+        .iter()
+        .filter(|(filename, _)| !uploaded.contains(*filename))
+        .collect();
+    unmarked.sort();
+    for (filename, session_id) in unmarked {
+        findings.push(AuditFinding::NotMarked {
+            filename: filename.clone(),
+            session_id: session_id.clone(),
+        });
+    }
+
+    findings
+}
+
+/// Unix time a log was recorded at, taken from its local-time filename
+fn log_recorded_at(filename: &str) -> Option<u64> {
+    let naive = crate::formatting::parse_log_timestamp(filename)?;
+    let local = chrono::Local.from_local_datetime(&naive).earliest()?;
+    u64::try_from(local.timestamp()).ok()
+}
+
+/// Recursively collects the names of all log files below a directory
+fn collect_log_filenames(dir: &Path, filenames: &mut HashSet<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_log_filenames(&path, filenames);
+        } else if crate::logfile::is_log_file(&path) {
+            if let Some(name) = path.file_name() {
+                filenames.insert(name.to_string_lossy().into_owned());
+            }
+        }
+    }
+}
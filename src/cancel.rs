@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::state::STATE;
+
+/// Set on unload, counts as cancelling every token at once
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// How often a cancellable wait checks whether it should stop
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Tells background work to stop early
+/// Clones share the same flag, and every token is cancelled once the addon unloads
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed) || is_shutting_down()
+    }
+
+    /// Sleeps for the given time, waking up early when cancelled
+    /// Returns false if the wait was cut short
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let end = Instant::now() + duration;
+        loop {
+            if self.is_cancelled() {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= end {
+                return true;
+            }
+            std::thread::sleep(POLL_INTERVAL.min(end - now));
+        }
+    }
+}
+
+/// Token shared by the current upload session: uploads, processing and status polls
+pub fn upload_token() -> CancelToken {
+    STATE
+        .upload_cancel
+        .lock()
+        .unwrap()
+        .get_or_insert_with(CancelToken::new)
+        .clone()
+}
+
+/// Stops all work of the current upload session, the next session gets a fresh token
+pub fn cancel_uploads() {
+    let previous = STATE.upload_cancel.lock().unwrap().replace(CancelToken::new());
+    if let Some(token) = previous {
+        token.cancel();
+    }
+}
+
+/// Whether the addon is unloading and all background work should wrap up
+pub fn is_shutting_down() -> bool {
+    SHUTDOWN.load(Ordering::Relaxed)
+}
+
+/// Cancels every token, called first thing on unload
+pub fn shutdown() {
+    SHUTDOWN.store(true, Ordering::Relaxed);
+}
+
+/// Clears a shutdown left over from an earlier unload of the same library
+pub fn reset_shutdown() {
+    SHUTDOWN.store(false, Ordering::Relaxed);
+}
@@ -0,0 +1,76 @@
+use anyhow::anyhow;
+use std::sync::Mutex;
+
+/// Server endpoints newer than upload, process and status, not every parser server has them
+/// One the server answers with "unknown endpoint" is remembered and the features using it are hidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    PendingSessions,
+    DeleteSession,
+    DeleteReport,
+    ChunkedUpload,
+    FailurePatterns,
+    CancelSession,
+}
+
+impl Capability {
+    /// What the feature is called when telling the user it isn't available
+    fn feature(&self) -> &'static str {
+        match self {
+            Capability::PendingSessions => "listing unprocessed sessions",
+            Capability::DeleteSession => "deleting unprocessed sessions",
+            Capability::DeleteReport => "deleting reports",
+            Capability::ChunkedUpload => "uploads in parts",
+            Capability::FailurePatterns => "failure explanations",
+            Capability::CancelSession => "cancelling sessions",
+        }
+    }
+}
+
+/// Capabilities found missing, by API endpoint
+/// Only kept until the next start, so a server that gains them is used again
+static UNSUPPORTED: Mutex<Vec<(String, Capability)>> = Mutex::new(Vec::new());
+
+/// Whether the server may support the capability, true until it answered that it doesn't
+pub fn supports(api_endpoint: &str, capability: Capability) -> bool {
+    !UNSUPPORTED
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|(endpoint, missing)| endpoint == api_endpoint && *missing == capability)
+}
+
+pub fn mark_unsupported(api_endpoint: &str, capability: Capability) {
+    let mut unsupported = UNSUPPORTED.lock().unwrap();
+    if !unsupported.iter().any(|(endpoint, missing)| endpoint == api_endpoint && *missing == capability) {
+        log::info!("{} doesn't support {}, hiding it", api_endpoint, capability.feature());
+        unsupported.push((api_endpoint.to_string(), capability));
+    }
+}
+
+/// The error a request is refused with up front once the server is known not to support it
+pub fn unsupported_error(capability: Capability) -> anyhow::Error {
+    anyhow!("This server doesn't support {}", capability.feature())
+}
+
+/// Turns a failed request into an error, remembering the capability as missing when the endpoint is unknown
+/// Older servers answer 405 or 501, a plain 404, or 400 with an "unknown endpoint" message
+/// A 404 with an API answer in it (e.g. session not found) still means the endpoint exists
+pub fn check(api_endpoint: &str, capability: Capability, error: ureq::Error) -> anyhow::Error {
+    match error {
+        ureq::Error::Status(405 | 501, _) => {}
+        ureq::Error::Status(status @ (400 | 404), response) => {
+            let body = response.into_string().unwrap_or_default();
+            let lower = body.to_lowercase();
+            let unknown_endpoint = lower.contains("unknown endpoint") || lower.contains("invalid endpoint");
+            let api_answer = lower.contains("\"success\"");
+            if !unknown_endpoint && (status == 400 || api_answer) {
+                return anyhow!("Server answered {}: {}", status, body.trim());
+            }
+        }
+        other => return other.into(),
+    }
+
+    mark_unsupported(api_endpoint, capability);
+    unsupported_error(capability)
+}
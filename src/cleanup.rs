@@ -1,5 +1,5 @@
 use std::os::windows::ffi::OsStrExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use winapi::shared::minwindef::TRUE;
 use winapi::um::shellapi::{
     FO_DELETE, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_SILENT, SHFILEOPSTRUCTW, SHFileOperationW,
@@ -8,6 +8,15 @@ use winapi::um::shellapi::{
 use crate::settings::Settings;
 use crate::state::STATE;
 
+/// Name prefix of the temporary folder old logs are gathered in before recycling
+const CLEANUP_FOLDER_PREFIX: &str = "WvW_Insights_Cleanup";
+/// File inside a cleanup folder listing where each moved log originally lived
+const RESTORE_MANIFEST: &str = "restore_manifest.txt";
+/// Minimum time between scheduled server report cleanups
+const SERVER_CLEANUP_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+/// Length of a month when working out which reports are old enough
+const SECS_PER_MONTH: u64 = 30 * 24 * 60 * 60;
+
 /// Checks if auto-cleanup should run on plugin load and executes it if enabled
 pub fn check_auto_cleanup_on_load() {
     let settings = Settings::get();
@@ -33,21 +42,401 @@ pub fn check_auto_cleanup_on_load() {
         days
     );
 
-    std::thread::spawn(move || match cleanup_old_logs(&log_dir, days) {
-        Ok((files, bytes)) => {
-            let mb = bytes as f64 / 1024.0 / 1024.0;
-            log::info!(
-                "Auto-cleanup complete: {} files ({:.2} MB) moved to Recycle Bin",
-                files,
-                mb
-            );
+    crate::threads::spawn_tracked("auto-cleanup", move || {
+        let _activity = crate::threads::activity("Cleaning up old logs");
+        crate::throttle::apply_background_priority();
+
+        match cleanup_old_logs(&log_dir, days) {
+            Ok((files, bytes)) => {
+                let mb = bytes as f64 / 1024.0 / 1024.0;
+                log::info!(
+                    "Auto-cleanup complete: {} files ({:.2} MB) moved to Recycle Bin",
+                    files,
+                    mb
+                );
+            }
+            Err(e) => {
+                log::warn!("Auto-cleanup failed: {}", e);
+            }
         }
-        Err(e) => {
-            log::warn!("Auto-cleanup failed: {}", e);
+    });
+}
+
+/// Runs the server report cleanup on load when it is enabled and a week has passed since the last run
+pub fn check_server_cleanup_on_load() {
+    let settings = Settings::get();
+    let enabled = settings.server_cleanup_enabled;
+    let last_run = settings.last_server_cleanup;
+    drop(settings);
+
+    if !enabled || !crate::consent::is_granted() {
+        return;
+    }
+
+    let now = unix_now();
+    if now.saturating_sub(last_run) < SERVER_CLEANUP_INTERVAL_SECS {
+        return;
+    }
+
+    log::info!("Server report cleanup is due, running it");
+    start_server_cleanup();
+}
+
+/// The server a report was processed on
+/// Sessions processed on an overridden endpoint live on that server instead of the configured one
+fn report_endpoint(report: &crate::report_history::ReportEntry, api_endpoint: &str) -> String {
+    report
+        .details
+        .as_ref()
+        .map(|d| d.endpoint_override.clone())
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| api_endpoint.to_string())
+}
+
+/// Asks the server to delete our reports older than the configured number of months
+/// Deleted sessions are removed from the local report history as well
+pub fn start_server_cleanup() {
+    let settings = Settings::get();
+    let api_endpoint = settings.api_endpoint.clone();
+    let current_token = settings.history_token.clone();
+    let months = settings.server_cleanup_months.max(1);
+    drop(settings);
+
+    *STATE.server_cleanup_running.lock().unwrap() = true;
+
+    crate::threads::spawn("server-cleanup", move || {
+        let _activity = crate::threads::activity("Cleaning up server reports");
+        crate::throttle::apply_background_priority();
+
+        let cutoff = unix_now().saturating_sub(months as u64 * SECS_PER_MONTH);
+        let old_reports: Vec<crate::report_history::ReportEntry> = crate::report_history::ReportHistory::get()
+            .reports
+            .iter()
+            // Favorites are kept on the server as well as in the history
+            .filter(|r| r.timestamp < cutoff && !r.favorite)
+            .cloned()
+            .collect();
+
+        let mut deleted = Vec::new();
+        let mut skipped = 0;
+        let mut unsupported = 0;
+        let mut failed = 0;
+
+        for report in old_reports.iter() {
+            if crate::cancel::is_shutting_down() {
+                break;
+            }
+
+            // Without the ownership token the server can't tell the session is ours
+            if report.ownership_token.is_empty() {
+                skipped += 1;
+                continue;
+            }
+
+            let endpoint = report_endpoint(report, &api_endpoint);
+            // Servers that answered they can't delete reports are not asked again
+            if !crate::capabilities::supports(&endpoint, crate::capabilities::Capability::DeleteReport) {
+                unsupported += 1;
+                continue;
+            }
+
+            let history_token =
+                crate::tokens::resolve_token_key(&report.token_key).unwrap_or_else(|| current_token.clone());
+
+            match crate::upload::delete_report(
+                &endpoint,
+                &report.session_id,
+                &history_token,
+                &report.ownership_token,
+            ) {
+                Ok(_) => deleted.push(report.session_id.clone()),
+                Err(e) => {
+                    log::warn!("Failed to delete report {}: {}", report.session_id, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        if !deleted.is_empty() {
+            let mut history = crate::report_history::ReportHistory::get();
+            for session_id in deleted.iter() {
+                if let Some(index) = history.reports.iter().position(|r| &r.session_id == session_id) {
+                    history.remove_report(index);
+                }
+            }
+            if let Err(e) = history.store(crate::report_history_path()) {
+                log::error!("Failed to save report history after server cleanup: {}", e);
+            }
+        }
+
+        let mut summary = format!(
+            "Deleted {} older than {} from the server",
+            crate::formatting::plural(deleted.len() as u64, "report", "reports"),
+            crate::formatting::plural(months as u64, "month", "months")
+        );
+        if skipped > 0 {
+            summary.push_str(&format!(", {} saved before deletion was supported", skipped));
         }
+        if unsupported > 0 {
+            summary.push_str(&format!(", {} on a server that can't delete reports", unsupported));
+        }
+        if failed > 0 {
+            summary.push_str(&format!(", {} failed", failed));
+        }
+        log::info!("Server report cleanup: {}", summary);
+
+        let mut settings = Settings::get();
+        settings.last_server_cleanup = unix_now();
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings after server cleanup: {}", e);
+        }
+        drop(settings);
+
+        *STATE.server_cleanup_summary.lock().unwrap() = summary;
+        *STATE.server_cleanup_running.lock().unwrap() = false;
     });
 }
 
+/// How deleting one history entry from the server went
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkDeleteOutcome {
+    Waiting,
+    Deleted,
+    /// Left on the server and in the history, with the reason
+    Skipped(String),
+    Failed(String),
+}
+
+/// One entry of a bulk delete, shown in the progress dialog of the history tab
+#[derive(Debug, Clone)]
+pub struct BulkDeleteResult {
+    pub session_id: String,
+    /// Label or parse time of the session
+    pub name: String,
+    pub outcome: BulkDeleteOutcome,
+}
+
+/// Deletes the given history entries from the server one by one, then removes the deleted ones from the history
+/// Progress is kept in STATE.bulk_delete_results so the history tab can show it
+pub fn start_bulk_delete(reports: Vec<crate::report_history::ReportEntry>) {
+    let settings = Settings::get();
+    let api_endpoint = settings.api_endpoint.clone();
+    let current_token = settings.history_token.clone();
+    drop(settings);
+
+    *STATE.bulk_delete_results.lock().unwrap() = reports
+        .iter()
+        .map(|r| BulkDeleteResult {
+            session_id: r.session_id.clone(),
+            name: if r.label.is_empty() {
+                crate::formatting::format_report_timestamp(r.timestamp)
+            } else {
+                r.label.clone()
+            },
+            outcome: BulkDeleteOutcome::Waiting,
+        })
+        .collect();
+    *STATE.bulk_delete_running.lock().unwrap() = true;
+
+    crate::threads::spawn("bulk-delete", move || {
+        let _activity = crate::threads::activity("Deleting reports");
+        let mut deleted = Vec::new();
+
+        for (index, report) in reports.iter().enumerate() {
+            if crate::cancel::is_shutting_down() {
+                break;
+            }
+
+            let outcome = if !report.dps_report_urls.is_empty() {
+                BulkDeleteOutcome::Skipped("uploaded to dps.report, delete it there".to_string())
+            } else if report.ownership_token.is_empty() {
+                BulkDeleteOutcome::Skipped("saved before deletion was supported".to_string())
+            } else {
+                let history_token =
+                    crate::tokens::resolve_token_key(&report.token_key).unwrap_or_else(|| current_token.clone());
+                let endpoint = report_endpoint(report, &api_endpoint);
+
+                match crate::upload::delete_report(
+                    &endpoint,
+                    &report.session_id,
+                    &history_token,
+                    &report.ownership_token,
+                ) {
+                    Ok(_) => {
+                        deleted.push(report.session_id.clone());
+                        BulkDeleteOutcome::Deleted
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to delete report {}: {}", report.session_id, e);
+                        BulkDeleteOutcome::Failed(e.to_string())
+                    }
+                }
+            };
+
+            if let Some(result) = STATE.bulk_delete_results.lock().unwrap().get_mut(index) {
+                result.outcome = outcome;
+            }
+        }
+
+        if !deleted.is_empty() {
+            let mut history = crate::report_history::ReportHistory::get();
+            for session_id in deleted.iter() {
+                if let Some(index) = history.reports.iter().position(|r| &r.session_id == session_id) {
+                    history.remove_report(index);
+                }
+            }
+            if let Err(e) = history.store(crate::report_history_path()) {
+                log::error!("Failed to save report history after bulk delete: {}", e);
+            }
+        }
+
+        log::info!(
+            "Bulk delete: {} of {} deleted from the server",
+            deleted.len(),
+            reports.len()
+        );
+        *STATE.bulk_delete_running.lock().unwrap() = false;
+    });
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Looks for cleanup temp folders left behind by a crash between moving and recycling
+/// Must run before auto-cleanup so its fresh temp folder isn't picked up
+pub fn check_stranded_cleanup_on_load() {
+    let log_dir = Settings::get().log_directory.clone();
+    let stranded = find_stranded_cleanup_folders(&log_dir);
+
+    if !stranded.is_empty() {
+        log::warn!(
+            "Found {} stranded cleanup folder(s) from an interrupted cleanup",
+            stranded.len()
+        );
+    }
+    *STATE.stranded_cleanup_folders.lock().unwrap() = stranded;
+}
+
+/// Returns the cleanup temp folders directly inside the log directory
+pub fn find_stranded_cleanup_folders(log_directory: &str) -> Vec<PathBuf> {
+    if log_directory.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(entries) = std::fs::read_dir(log_directory) else {
+        return Vec::new();
+    };
+
+    let mut folders: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.metadata().map(|m| m.is_dir()).unwrap_or(false))
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(CLEANUP_FOLDER_PREFIX)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    folders.sort();
+    folders
+}
+
+/// Counts the log files inside a stranded cleanup folder
+pub fn count_stranded_logs(folder: &Path) -> usize {
+    std::fs::read_dir(folder)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| crate::logfile::is_log_file(&e.path()))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Finishes an interrupted cleanup by sending the stranded folder to the Recycle Bin
+pub fn recycle_stranded_folder(folder: &Path) -> Result<usize, String> {
+    let count = count_stranded_logs(folder);
+    send_to_recycle_bin(folder)?;
+    log::info!(
+        "Recycled stranded cleanup folder {:?} ({} files)",
+        folder,
+        count
+    );
+    Ok(count)
+}
+
+/// Moves the logs of a stranded cleanup folder back to where they came from
+/// Files without a manifest entry (or whose folder is gone) go to the log directory root
+/// The folder is removed once it is empty
+pub fn restore_stranded_folder(folder: &Path, log_directory: &str) -> Result<usize, String> {
+    let manifest = std::fs::read_to_string(folder.join(RESTORE_MANIFEST)).unwrap_or_default();
+    let original_paths: std::collections::HashMap<&str, PathBuf> = manifest
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, original)| (name, PathBuf::from(original)))
+        .collect();
+
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read cleanup folder: {}", e))?;
+
+    let mut restored = 0;
+    let mut failed = 0;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !crate::logfile::is_log_file(&path) {
+            continue;
+        }
+
+        let file_name = entry.file_name();
+        let target = original_paths
+            .get(&*file_name.to_string_lossy())
+            .filter(|original| original.parent().is_some_and(|p| p.exists()))
+            .cloned()
+            .unwrap_or_else(|| Path::new(log_directory).join(&file_name));
+
+        if target.exists() {
+            log::warn!("Not restoring {:?}, {:?} already exists", path, target);
+            failed += 1;
+            continue;
+        }
+
+        let move_result = std::fs::rename(&path, &target).or_else(|_| {
+            std::fs::copy(&path, &target).and_then(|_| std::fs::remove_file(&path))
+        });
+
+        match move_result {
+            Ok(()) => restored += 1,
+            Err(e) => {
+                log::warn!("Failed to restore {:?}: {}", path, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "Restored {} files, {} could not be restored and remain in {}",
+            restored,
+            failed,
+            folder.display()
+        ));
+    }
+
+    let _ = std::fs::remove_file(folder.join(RESTORE_MANIFEST));
+    if let Err(e) = std::fs::remove_dir(folder) {
+        log::warn!("Restored files but could not remove {:?}: {}", folder, e);
+    }
+
+    log::info!("Restored {} files from stranded cleanup folder {:?}", restored, folder);
+    Ok(restored)
+}
+
 /// Moves old log files to the Recycle Bin
 pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u64), String> {
     if log_directory.is_empty() {
@@ -81,7 +470,7 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
         - std::time::Duration::from_secs(days_old as u64 * 24 * 60 * 60);
 
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-    let temp_folder_name = format!("WvW_Insights_Cleanup_{}", timestamp);
+    let temp_folder_name = format!("{}_{}", CLEANUP_FOLDER_PREFIX, timestamp);
     let temp_folder_path = log_dir.join(&temp_folder_name);
 
     if let Err(e) = std::fs::create_dir(&temp_folder_path) {
@@ -90,6 +479,7 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
 
     let mut files_to_move = Vec::new();
     let mut total_size = 0u64;
+    let exclude = Settings::get().scan_exclude_patterns.clone();
 
     collect_old_logs_recursive(
         &log_dir,
@@ -97,6 +487,7 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
         &mut files_to_move,
         &mut total_size,
         &temp_folder_path,
+        &exclude,
     )
     .map_err(|e| format!("Failed to scan directory: {}", e))?;
 
@@ -108,7 +499,24 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
     let mut moved_count = 0;
     let mut moved_size = 0u64;
 
+    // Remember where every file came from so a stranded folder can be restored after a crash
+    // Written line by line as files move, so a crash halfway still leaves a usable manifest
+    let manifest_path = temp_folder_path.join(RESTORE_MANIFEST);
+    let mut manifest = match std::fs::File::options().create(true).append(true).open(&manifest_path) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            log::warn!("Failed to create cleanup restore manifest: {}", e);
+            None
+        }
+    };
+
     for file in files_to_move.iter() {
+        // On unload, recycle what was moved so far and leave the rest for next time
+        if crate::cancel::is_shutting_down() {
+            log::info!("Cleanup interrupted by unload");
+            break;
+        }
+
         let file_name = match file.file_name() {
             Some(name) => name,
             None => continue,
@@ -117,15 +525,11 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
         let mut dest_path = temp_folder_path.join(file_name);
         let mut counter = 1;
         while dest_path.exists() {
-            let stem = file
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("file");
-            let ext = file
-                .extension()
-                .and_then(|s| s.to_str())
-                .unwrap_or("zevtc");
-            dest_path = temp_folder_path.join(format!("{}_{}.{}", stem, counter, ext));
+            // Numbered before the log ending so "x.evtc.zip" becomes "x_1.evtc.zip"
+            let name = file_name.to_string_lossy();
+            let stem = crate::logfile::log_stem(&name);
+            let ext = &name[stem.len()..];
+            dest_path = temp_folder_path.join(format!("{}_{}{}", stem, counter, ext));
             counter += 1;
         }
 
@@ -141,10 +545,20 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
                 moved_size += metadata.len();
             }
             moved_count += 1;
+
+            if let (Some(manifest), Some(dest_name)) = (manifest.as_mut(), dest_path.file_name()) {
+                use std::io::Write;
+                let line = format!("{}\t{}\n", dest_name.to_string_lossy(), file.display());
+                if let Err(e) = manifest.write_all(line.as_bytes()).and_then(|_| manifest.flush()) {
+                    log::warn!("Failed to write cleanup restore manifest: {}", e);
+                }
+            }
         }
     }
+    drop(manifest);
 
     if moved_count == 0 {
+        let _ = std::fs::remove_file(&manifest_path);
         let _ = std::fs::remove_dir(&temp_folder_path);
         return Err("Failed to move any files".to_string());
     }
@@ -154,6 +568,208 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
         moved_count
     );
 
+    send_to_recycle_bin(&temp_folder_path)?;
+
+    log::info!(
+        "Cleanup: {} files ({:.2} MB) moved to Recycle Bin",
+        moved_count,
+        moved_size as f64 / 1024.0 / 1024.0
+    );
+    Ok((moved_count, moved_size))
+}
+
+/// Logs written this recently may still be open in arcdps and are left where they are
+const ORGANIZE_MIN_AGE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// What organizing the log directory did
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrganizeResult {
+    pub moved: usize,
+    /// Queued for upload, or a file of the same name already in the target folder
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Checks an organize pattern: a date format that works and stays inside the log directory
+pub fn validate_organize_pattern(pattern: &str) -> Result<(), String> {
+    use chrono::format::{Item, StrftimeItems};
+
+    if pattern.trim().is_empty() {
+        return Err("Folder pattern cannot be empty".to_string());
+    }
+    if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+        return Err("Folder pattern has an unknown % code".to_string());
+    }
+    if pattern.starts_with(['/', '\\'])
+        || pattern.contains(':')
+        || pattern.split(['/', '\\']).any(|part| part.trim() == "..")
+    {
+        return Err("Folder pattern must stay inside the log directory".to_string());
+    }
+    Ok(())
+}
+
+/// Filenames of logs that must not move: live mode's queue and the logs of a running upload
+fn queued_filenames() -> std::collections::HashSet<String> {
+    let mut queued: std::collections::HashSet<String> = STATE
+        .live_queue
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|log| log.status != crate::watcher::LiveStatus::Uploaded)
+        .map(|log| log.filename.clone())
+        .collect();
+
+    if *STATE.processing_state.lock().unwrap() == crate::state::ProcessingState::Uploading {
+        queued.extend(
+            STATE
+                .logs
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|log| log.selected)
+                .map(|log| log.filename.clone()),
+        );
+    }
+    queued
+}
+
+/// Moves every log into a subfolder of the log directory named after its date, e.g. 2025/10-10
+/// Filenames stay the same, so the upload history still knows every moved log
+pub fn organize_logs(log_directory: &str, pattern: &str) -> Result<OrganizeResult, String> {
+    validate_organize_pattern(pattern)?;
+
+    if log_directory.is_empty() {
+        return Err("No log directory configured".to_string());
+    }
+
+    let log_dir = PathBuf::from(log_directory);
+    let canonical = log_dir
+        .canonicalize()
+        .map_err(|e| format!("Invalid directory path: {}", e))?;
+    let canonical_str = canonical.to_string_lossy().to_lowercase();
+    if canonical_str.ends_with(":\\")
+        || canonical_str.ends_with(":/")
+        || canonical_str.contains("\\windows\\")
+        || canonical_str.contains("\\program files")
+    {
+        return Err("Cannot organize system directories or drive roots".to_string());
+    }
+
+    let mut files = Vec::new();
+    let mut total_size = 0u64;
+    let exclude = Settings::get().scan_exclude_patterns.clone();
+    collect_old_logs_recursive(
+        &log_dir,
+        std::time::SystemTime::now() - ORGANIZE_MIN_AGE,
+        &mut files,
+        &mut total_size,
+        Path::new(""),
+        &exclude,
+    )?;
+
+    let queued = queued_filenames();
+    let mut result = OrganizeResult::default();
+    let mut moves = Vec::new();
+
+    for file in files {
+        if crate::cancel::is_shutting_down() {
+            log::info!("Organizing logs interrupted by unload");
+            break;
+        }
+
+        let Some(file_name) = file.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if queued.contains(&file_name) {
+            result.skipped += 1;
+            continue;
+        }
+
+        let date = crate::formatting::parse_log_timestamp(&file_name)
+            .or_else(|| {
+                std::fs::metadata(&file)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .map(|t| chrono::DateTime::<chrono::Local>::from(t).naive_local())
+            });
+        let Some(date) = date else {
+            result.failed += 1;
+            continue;
+        };
+
+        let target_dir = log_dir.join(date.format(pattern).to_string());
+        if file.parent() == Some(target_dir.as_path()) {
+            continue;
+        }
+        let target = target_dir.join(&file_name);
+        if target.exists() {
+            log::warn!("Not moving {:?}, {:?} already exists", file, target);
+            result.skipped += 1;
+            continue;
+        }
+
+        let move_result = std::fs::create_dir_all(&target_dir).and_then(|_| {
+            std::fs::rename(&file, &target)
+                .or_else(|_| std::fs::copy(&file, &target).and_then(|_| std::fs::remove_file(&file)))
+        });
+        match move_result {
+            Ok(()) => {
+                result.moved += 1;
+                moves.push((file, target));
+            }
+            Err(e) => {
+                log::warn!("Failed to move {:?}: {}", file, e);
+                result.failed += 1;
+            }
+        }
+    }
+
+    crate::scanning::relocate_logs(&moves);
+
+    log::info!(
+        "Organized logs: {} moved, {} skipped, {} failed",
+        result.moved,
+        result.skipped,
+        result.failed
+    );
+    Ok(result)
+}
+
+/// Organizes the log directory in the background with the saved folder pattern
+pub fn start_organize_logs() {
+    let settings = Settings::get();
+    let log_directory = settings.log_directory.clone();
+    let pattern = settings.organize_pattern.clone();
+    drop(settings);
+
+    *STATE.organize_running.lock().unwrap() = true;
+    STATE.organize_summary.lock().unwrap().clear();
+
+    crate::threads::spawn_tracked("organize-logs", move || {
+        let _activity = crate::threads::activity("Organizing logs");
+        crate::throttle::apply_background_priority();
+
+        let summary = match organize_logs(&log_directory, &pattern) {
+            Ok(result) if result.skipped + result.failed == 0 => {
+                format!("Moved {}", crate::formatting::plural(result.moved as u64, "log", "logs"))
+            }
+            Ok(result) => format!(
+                "Moved {}, {} skipped, {} failed",
+                crate::formatting::plural(result.moved as u64, "log", "logs"),
+                result.skipped,
+                result.failed
+            ),
+            Err(e) => e,
+        };
+        *STATE.organize_summary.lock().unwrap() = summary;
+        *STATE.organize_running.lock().unwrap() = false;
+    });
+}
+
+/// Sends a cleanup temp folder to the Recycle Bin
+/// The folder is left in place if the shell operation fails
+fn send_to_recycle_bin(temp_folder_path: &Path) -> Result<(), String> {
     // Check if temp folder actually exists before attempting recycle
     if !temp_folder_path.exists() {
         log::error!(
@@ -222,12 +838,7 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
     );
 
     if result == 0 && file_op.fAnyOperationsAborted != TRUE {
-        log::info!(
-            "Cleanup: {} files ({:.2} MB) moved to Recycle Bin",
-            moved_count,
-            moved_size as f64 / 1024.0 / 1024.0
-        );
-        Ok((moved_count, moved_size))
+        Ok(())
     } else {
         log::error!(
             "SHFileOperationW failed with code: {}, aborted: {}",
@@ -250,13 +861,14 @@ pub fn cleanup_old_logs(log_directory: &str, days_old: u32) -> Result<(usize, u6
     }
 }
 
-/// Recursively collects old log files from a directory
+/// Recursively collects old log files from a directory, skipping the scan exclude patterns
 fn collect_old_logs_recursive(
     dir: &std::path::Path,
     cutoff: std::time::SystemTime,
     files: &mut Vec<PathBuf>,
     size: &mut u64,
     exclude_folder: &std::path::Path,
+    exclude_patterns: &[String],
 ) -> Result<(), String> {
     let entries = match std::fs::read_dir(dir) {
         Ok(e) => e,
@@ -269,7 +881,7 @@ fn collect_old_logs_recursive(
         let entry_path = entry.path();
 
         // Skip the temp folder we just created
-        if entry_path == exclude_folder {
+        if entry_path == exclude_folder || crate::scanning::is_excluded(&entry_path, exclude_patterns) {
             continue;
         }
 
@@ -283,22 +895,22 @@ fn collect_old_logs_recursive(
             if let Some(dir_name) = entry_path.file_name() {
                 if dir_name
                     .to_string_lossy()
-                    .contains("WvW_Insights_Cleanup")
+                    .contains(CLEANUP_FOLDER_PREFIX)
                 {
                     log::info!("Skipping cleanup temp folder: {:?}", entry_path);
                     continue;
                 }
             }
-            collect_old_logs_recursive(&entry_path, cutoff, files, size, exclude_folder)?;
-        } else if metadata.is_file() {
-            if let Some(ext) = entry_path.extension() {
-                if ext == "zevtc" {
-                    if let Ok(modified) = metadata.modified() {
-                        if modified < cutoff {
-                            files.push(entry_path);
-                            *size += metadata.len();
-                        }
-                    }
+            // Archived logs stay where they were archived to
+            if crate::archive::is_archive_root(&entry_path) {
+                continue;
+            }
+            collect_old_logs_recursive(&entry_path, cutoff, files, size, exclude_folder, exclude_patterns)?;
+        } else if metadata.is_file() && crate::logfile::is_log_file(&entry_path) {
+            if let Ok(modified) = metadata.modified() {
+                if modified < cutoff {
+                    files.push(entry_path);
+                    *size += metadata.len();
                 }
             }
         }
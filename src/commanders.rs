@@ -0,0 +1,131 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, File};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::state::STATE;
+
+/// What the user keeps about a commander tag seen in logs, e.g. their guild or a build link
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommanderNote {
+    /// Character name as it appears in logs
+    pub name: String,
+    #[serde(default)]
+    pub note: String,
+    /// Wiki page or build template, opened from the note popup
+    #[serde(default)]
+    pub link: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommanderNotes {
+    /// Lists the notes of the session's commanders under report posts
+    #[serde(default)]
+    pub include_in_posts: bool,
+    #[serde(default)]
+    pub notes: Vec<CommanderNote>,
+}
+
+static COMMANDER_NOTES: Mutex<CommanderNotes> = Mutex::new(CommanderNotes {
+    include_in_posts: false,
+    notes: Vec::new(),
+});
+
+impl CommanderNotes {
+    pub fn get() -> MutexGuard<'static, Self> {
+        COMMANDER_NOTES.lock().unwrap()
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            let notes: Self = serde_json::from_str(&contents)?;
+            log::info!("Loaded notes for {} commander(s)", notes.notes.len());
+            *COMMANDER_NOTES.lock().unwrap() = notes;
+        }
+        Ok(())
+    }
+
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let prefix = path.parent().unwrap();
+        create_dir_all(prefix)?;
+        let mut file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        Ok(())
+    }
+
+    pub fn find(&self, name: &str) -> Option<&CommanderNote> {
+        self.notes.iter().find(|n| n.name == name)
+    }
+
+    /// Saves the note for a commander, an empty note and link removes it
+    pub fn set(&mut self, name: &str, note: &str, link: &str) {
+        self.notes.retain(|n| n.name != name);
+        if note.trim().is_empty() && link.trim().is_empty() {
+            return;
+        }
+        self.notes.push(CommanderNote {
+            name: name.to_string(),
+            note: note.to_string(),
+            link: link.to_string(),
+        });
+        self.notes.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+impl CommanderNote {
+    /// "Name: note (link)", leaving out what is empty
+    pub fn line(&self) -> String {
+        let mut line = self.name.clone();
+        if !self.note.trim().is_empty() {
+            line.push_str(&format!(": {}", self.note.trim()));
+        }
+        if !self.link.trim().is_empty() {
+            line.push_str(&format!(" (<{}>)", self.link.trim()));
+        }
+        line
+    }
+}
+
+/// Commanders tagged in the current session's logs, most logs first
+pub fn session_commanders() -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for commander in STATE
+        .uploaded_files
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|f| f.metadata.as_ref().and_then(|m| m.commander.clone()))
+    {
+        match counts.iter_mut().find(|(name, _)| *name == commander) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((commander, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1));
+    counts.into_iter().map(|(name, _)| name).collect()
+}
+
+/// Adds the notes of the given commanders below a report post, when that is turned on
+pub fn append_notes(message: &str, commanders: &[String]) -> String {
+    let notes = CommanderNotes::get();
+    if !notes.include_in_posts {
+        return message.to_string();
+    }
+    let lines: Vec<String> = commanders
+        .iter()
+        .filter_map(|name| notes.find(name))
+        .map(|note| format!("- {}", note.line()))
+        .collect();
+    if lines.is_empty() {
+        return message.to_string();
+    }
+    format!("{}\n\nCommanders:\n{}", message, lines.join("\n"))
+}
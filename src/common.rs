@@ -9,6 +9,10 @@ pub struct WorkerMessage {
 #[derive(Debug)]
 pub enum WorkerType {
     UploadResult(Result<String>),
+    /// Bytes of the file sent so far and the total size of the request
+    UploadProgress { sent: u64, total: u64 },
+    /// Sent just before the result of a successful upload
+    UploadTiming(crate::upload_stats::UploadTiming),
 }
 
 impl WorkerMessage {
@@ -18,4 +22,18 @@ impl WorkerMessage {
             payload: WorkerType::UploadResult(result),
         }
     }
+
+    pub fn upload_progress(index: usize, sent: u64, total: u64) -> Self {
+        Self {
+            index,
+            payload: WorkerType::UploadProgress { sent, total },
+        }
+    }
+
+    pub fn upload_timing(index: usize, timing: crate::upload_stats::UploadTiming) -> Self {
+        Self {
+            index,
+            payload: WorkerType::UploadTiming(timing),
+        }
+    }
 }
\ No newline at end of file
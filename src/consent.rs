@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::settings::Settings;
+
+/// Cached result of the consent check, so network code never has to take the settings lock
+static GRANTED: AtomicBool = AtomicBool::new(false);
+
+/// A server the addon talks to and what it sends there
+pub struct NetworkUse {
+    pub name: &'static str,
+    pub endpoint: String,
+    pub when: &'static str,
+    pub data: &'static [&'static str],
+}
+
+/// Everything the addon may contact, shown before the first network call
+pub fn network_uses() -> Vec<NetworkUse> {
+    let api_endpoint = Settings::get().api_endpoint.clone();
+
+    vec![
+        NetworkUse {
+            name: "WvW Insights parser",
+            endpoint: api_endpoint,
+            when: "Uploading and processing logs, token checks, cancelled sessions, report history cleanup",
+            data: &[
+                "Selected arcdps log files",
+                "History token and session tokens",
+                "Guild name",
+                "dps.report token, if one is set",
+            ],
+        },
+        NetworkUse {
+            name: "Session endpoint override",
+            endpoint: "Addresses you enter yourself".to_string(),
+            when: "Only for a session you point at another parser server, e.g. for testing",
+            data: &["The same as the WvW Insights parser, for that session only"],
+        },
+        NetworkUse {
+            name: "Token generator",
+            endpoint: "https://parser.rethl.net".to_string(),
+            when: "Only when you generate a new history token",
+            data: &["Nothing besides the request itself"],
+        },
+        NetworkUse {
+            name: "dps.report",
+            endpoint: "https://dps.report".to_string(),
+            when: "Only for sessions uploaded to dps.report",
+            data: &["Selected arcdps log files", "dps.report token, if one is set"],
+        },
+        NetworkUse {
+            name: "Post-processing hooks",
+            endpoint: "Addresses you enter yourself".to_string(),
+            when: "Only for POST hooks you add under Settings > General > Advanced",
+            data: &["Report links, session id, guild name, fight count and maps"],
+        },
+        NetworkUse {
+            name: "Custom webhook endpoints",
+            endpoint: "Addresses you enter yourself".to_string(),
+            when: "Only for custom endpoints saved under Settings > Webhooks",
+            data: &["Report links, session id, guild name and the auth header you set"],
+        },
+        NetworkUse {
+            name: "Discord",
+            endpoint: "https://discord.com/api/webhooks".to_string(),
+            when: "Only when you send reports or enable announcements",
+            data: &["Report links", "Announcement messages with your guild name"],
+        },
+    ]
+}
+
+/// Whether the user allowed network access for the current API endpoint
+/// Changing the endpoint asks again, since the data would go somewhere else
+pub fn is_granted() -> bool {
+    GRANTED.load(Ordering::Relaxed)
+}
+
+/// Re-checks the consent against the settings, called every frame and after loading
+pub fn update() {
+    let settings = Settings::get();
+    let granted = !settings.network_consent.is_empty() && settings.network_consent == settings.api_endpoint;
+    drop(settings);
+    GRANTED.store(granted, Ordering::Relaxed);
+}
+
+/// Fails network calls made before the user allowed them
+pub fn require() -> Result<()> {
+    if is_granted() {
+        Ok(())
+    } else {
+        Err(anyhow!("Network access has not been allowed yet"))
+    }
+}
+
+/// Allows network access for the current API endpoint and saves the choice
+pub fn grant(config_path: &std::path::Path) {
+    let mut settings = Settings::get();
+    settings.network_consent = settings.api_endpoint.clone();
+    log::info!("Network access allowed for {}", settings.network_consent);
+    if let Err(e) = settings.store(config_path) {
+        log::error!("Failed to save settings: {}", e);
+    }
+    drop(settings);
+
+    update();
+
+    // Skipped on load while access wasn't allowed yet
+    crate::failures::refresh_remote_patterns();
+    crate::tokens::check_saved_token();
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::cancel::CancelToken;
+use crate::state::STATE;
+
+/// How often the state snapshot is refreshed on disk
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// API responses kept for the snapshot, older ones are dropped
+const MAX_RECENT_RESPONSES: usize = 10;
+
+/// Lists crash reports the user hasn't been shown yet, one filename per line
+const PENDING_FILE: &str = "pending.txt";
+
+static RECENT_RESPONSES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// What the addon was doing when it crashed or was last seen running
+/// Tokens, session ids and file names are left out on purpose, the file is meant to be shared
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub version: String,
+    pub time: String,
+    pub reason: String,
+    pub thread: Option<String>,
+    pub message: Option<String>,
+    pub location: Option<String>,
+    pub screen: Option<String>,
+    pub processing_state: Option<String>,
+    pub processing_phase: Option<String>,
+    pub processing_progress: Option<f32>,
+    pub upload_backend: Option<String>,
+    pub logs_listed: Option<usize>,
+    pub logs_selected: Option<usize>,
+    pub session_files: Option<usize>,
+    pub split_sessions: Option<usize>,
+    pub live_queue: Option<usize>,
+    pub thread_panics: Option<usize>,
+    pub recent_responses: Vec<String>,
+}
+
+/// Reads a piece of state without blocking
+/// A panic can happen while its own thread holds a lock, waiting on it would hang the hook
+fn peek<T, R>(lock: &Mutex<T>, f: impl FnOnce(&T) -> R) -> Option<R> {
+    lock.try_lock().ok().map(|guard| f(&guard))
+}
+
+impl StateSnapshot {
+    fn capture(reason: &str) -> Self {
+        let screen = (|| {
+            let screens = [
+                (peek(&STATE.show_settings, |s| *s)?, "Settings"),
+                (peek(&STATE.show_token_input, |s| *s)?, "Token input"),
+                (peek(&STATE.show_log_selection, |s| *s)?, "Log selection"),
+                (peek(&STATE.show_upload_progress, |s| *s)?, "Upload progress"),
+                (peek(&STATE.show_upload_review, |s| *s)?, "Upload review"),
+                (peek(&STATE.show_results, |s| *s)?, "Results"),
+            ];
+            let open = peek(&STATE.show_main_window, |open| *open)?;
+            let screen = screens.iter().find(|(shown, _)| *shown).map_or("None", |(_, name)| name);
+            Some(format!("{}{}", screen, if open { "" } else { " (window closed)" }))
+        })();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            time: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            reason: reason.to_string(),
+            screen,
+            processing_state: peek(&STATE.processing_state, |s| format!("{:?}", s)),
+            processing_phase: peek(&STATE.processing_phase, |p| p.clone()),
+            processing_progress: peek(&STATE.processing_progress, |p| *p),
+            upload_backend: peek(&STATE.upload_backend, |b| b.label().to_string()),
+            logs_listed: peek(&STATE.logs, |logs| logs.len()),
+            logs_selected: peek(&STATE.logs, |logs| logs.iter().filter(|l| l.selected).count()),
+            session_files: peek(&STATE.uploaded_files, |files| files.len()),
+            split_sessions: peek(&STATE.split_sessions, |splits| splits.len()),
+            live_queue: peek(&STATE.live_queue, |queue| queue.len()),
+            thread_panics: peek(&STATE.thread_panics, |panics| panics.len()),
+            recent_responses: peek(&RECENT_RESPONSES, |r| r.iter().cloned().collect()).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn store(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Not the usual path helpers, those panic without an addon dir and this runs inside the panic hook
+fn addon_dir() -> Option<PathBuf> {
+    nexus::paths::get_addon_dir("wvw-insights")
+}
+
+fn crash_dir() -> Option<PathBuf> {
+    addon_dir().map(|dir| dir.join("crash_reports"))
+}
+
+fn snapshot_path() -> Option<PathBuf> {
+    addon_dir().map(|dir| dir.join("state_snapshot.json"))
+}
+
+/// Keeps a short summary of an API response for crash reports
+pub fn record_response(endpoint: &str, summary: &str) {
+    let mut responses = RECENT_RESPONSES.lock().unwrap();
+    if responses.len() >= MAX_RECENT_RESPONSES {
+        responses.pop_front();
+    }
+    responses.push_back(format!(
+        "{} {}: {}",
+        chrono::Local::now().format("%H:%M:%S"),
+        endpoint,
+        summary
+    ));
+}
+
+/// Writes a crash report and marks it to be shown on the next load
+fn write_report(snapshot: &StateSnapshot) -> Option<PathBuf> {
+    let dir = crash_dir()?;
+    let path = dir.join(format!("crash-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    snapshot.store(&path).ok()?;
+
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let pending = dir.join(PENDING_FILE);
+    let mut contents = std::fs::read_to_string(&pending).unwrap_or_default();
+    contents.push_str(&name);
+    contents.push('\n');
+    std::fs::write(&pending, contents).ok()?;
+
+    Some(path)
+}
+
+/// Called from the panic hook, must never panic itself
+pub fn write_panic_report(thread: &str, message: &str, location: &str) {
+    let mut snapshot = StateSnapshot::capture("Panic");
+    snapshot.thread = Some(thread.to_string());
+    snapshot.message = Some(message.to_string());
+    snapshot.location = Some(location.to_string());
+
+    if let Some(path) = write_report(&snapshot) {
+        log::error!("Crash report written to {:?}", path);
+    }
+}
+
+/// Picks up what the last run left behind, called on load
+/// A snapshot still on disk means the last run never unloaded cleanly
+pub fn check_previous_run() {
+    if let Some(path) = snapshot_path().filter(|p| p.exists()) {
+        let snapshot = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<StateSnapshot>(&contents).ok());
+        if let Some(mut snapshot) = snapshot {
+            log::warn!("WvW Insights did not shut down cleanly last time (last seen {})", snapshot.time);
+            snapshot.reason = format!("Closed unexpectedly, last seen running at {}", snapshot.time);
+            write_report(&snapshot);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    let Some(dir) = crash_dir() else {
+        return;
+    };
+    let pending = std::fs::read_to_string(dir.join(PENDING_FILE)).unwrap_or_default();
+    let reports: Vec<PathBuf> = pending
+        .lines()
+        .map(|name| dir.join(name.trim()))
+        .filter(|path| path.is_file())
+        .collect();
+    *STATE.crash_reports.lock().unwrap() = reports;
+}
+
+/// Forgets the reports shown in the prompt, the files stay in the crash_reports folder
+pub fn dismiss() {
+    STATE.crash_reports.lock().unwrap().clear();
+    if let Some(dir) = crash_dir() {
+        let _ = std::fs::remove_file(dir.join(PENDING_FILE));
+    }
+}
+
+pub fn open_folder() {
+    if let Some(dir) = crash_dir() {
+        if let Err(e) = open::that_detached(&dir) {
+            log::error!("Failed to open crash reports folder: {}", e);
+        }
+    }
+}
+
+/// Starts the thread keeping the state snapshot up to date
+pub fn start() {
+    crate::threads::spawn_tracked("state-snapshot", || {
+        // Never cancelled itself, only stops on unload
+        let idle = CancelToken::new();
+        loop {
+            if let Some(path) = snapshot_path() {
+                if let Err(e) = StateSnapshot::capture("Running").store(&path) {
+                    log::warn!("Failed to write state snapshot: {}", e);
+                }
+            }
+            if !idle.sleep(SNAPSHOT_INTERVAL) {
+                break;
+            }
+        }
+    });
+}
+
+/// Removes the snapshot on a clean unload, after the snapshot thread has stopped
+pub fn clean_shutdown() {
+    if let Some(path) = snapshot_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::report_history::ReportHistory;
+use crate::state::STATE;
+
+/// Name of the screen the main window currently shows, in render order
+fn active_screen() -> &'static str {
+    if STATE.settings_safe_mode.lock().unwrap().is_some() {
+        "Safe Mode"
+    } else if *STATE.show_settings.lock().unwrap() {
+        "Settings"
+    } else if *STATE.show_token_input.lock().unwrap() {
+        "Token Input"
+    } else if *STATE.show_log_selection.lock().unwrap() {
+        "Log Selection"
+    } else if *STATE.show_upload_progress.lock().unwrap() {
+        "Upload Progress"
+    } else if *STATE.show_upload_review.lock().unwrap() {
+        "Upload Review"
+    } else if *STATE.show_results.lock().unwrap() {
+        "Results"
+    } else {
+        "None"
+    }
+}
+
+/// Describes the current UI state for bug reports and for comparing screens after refactors
+/// Tokens, session ids and URLs are left out, only counts and flags are written
+fn describe_ui_state() -> String {
+    let mut out = String::new();
+
+    let (log_count, selected, uploaded, invalid) = {
+        let logs = STATE.logs.lock().unwrap();
+        (
+            logs.len(),
+            logs.iter().filter(|l| l.selected).count(),
+            logs.iter().filter(|l| l.uploaded).count(),
+            logs.iter().filter(|l| l.invalid.is_some()).count(),
+        )
+    };
+
+    let _ = writeln!(out, "WvW Insights UI state");
+    let _ = writeln!(out, "Captured: {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "[Window]");
+    let _ = writeln!(out, "main_window_open = {}", *STATE.show_main_window.lock().unwrap());
+    let _ = writeln!(out, "active_screen = {}", active_screen());
+    let _ = writeln!(out, "webhook_modal_open = {}", *STATE.show_webhook_modal.lock().unwrap());
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "[Log Selection]");
+    let _ = writeln!(out, "time_filter = {:?}", *STATE.selected_time_filter.lock().unwrap());
+    let _ = writeln!(out, "logs = {}", log_count);
+    let _ = writeln!(out, "selected = {}", selected);
+    let _ = writeln!(out, "uploaded = {}", uploaded);
+    let _ = writeln!(out, "invalid = {}", invalid);
+    let _ = writeln!(out, "scan_in_progress = {}", *STATE.scan_in_progress.lock().unwrap());
+    let _ = writeln!(out, "list_interaction_active = {}", *STATE.list_interaction_active.lock().unwrap());
+    let _ = writeln!(out, "scan_refresh_queued = {}", *STATE.scan_refresh_queued.lock().unwrap());
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "[Upload & Processing]");
+    let _ = writeln!(out, "processing_state = {:?}", *STATE.processing_state.lock().unwrap());
+    let _ = writeln!(out, "has_session = {}", !STATE.session_id.lock().unwrap().is_empty());
+    let _ = writeln!(out, "uploaded_files = {}", STATE.uploaded_files.lock().unwrap().len());
+    let _ = writeln!(out, "split_sessions = {}", STATE.split_sessions.lock().unwrap().len());
+    let _ = writeln!(out, "report_urls = {}", STATE.report_urls.lock().unwrap().len());
+    let _ = writeln!(out, "processing_progress = {:.1}", *STATE.processing_progress.lock().unwrap());
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "[Background]");
+    let _ = writeln!(out, "tracked_threads = {}", STATE.threads.lock().unwrap().len());
+    let _ = writeln!(out, "thread_panics = {}", STATE.thread_panics.lock().unwrap().len());
+    let _ = writeln!(out, "audit_running = {}", *STATE.audit_running.lock().unwrap());
+    let _ = writeln!(out, "reparse_busy = {}", *STATE.reparse_busy.lock().unwrap());
+    let _ = writeln!(out, "throttled = {}", crate::throttle::is_throttled());
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "[Report History]");
+    let _ = writeln!(out, "reports = {}", ReportHistory::get().reports.len());
+
+    out
+}
+
+/// Writes the current UI state to a timestamped file in the addon folder
+pub fn dump_ui_state() -> Result<PathBuf> {
+    let path = crate::config_path().with_file_name(format!(
+        "ui_state_{}.txt",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    std::fs::write(&path, describe_ui_state())?;
+    log::info!("Dumped UI state to {:?}", path);
+    Ok(path)
+}
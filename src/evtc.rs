@@ -0,0 +1,307 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use flate2::read::DeflateDecoder;
+use serde::{Deserialize, Serialize};
+
+// Layout of the EVTC format as written by arcdps
+const HEADER_SIZE: usize = 16;
+const AGENT_SIZE: usize = 96;
+const SKILL_SIZE: usize = 68;
+const EVENT_SIZE: usize = 64;
+
+/// Agents with this elite value are NPCs or gadgets, everything else is a player
+const NON_PLAYER_ELITE: u32 = 0xFFFF_FFFF;
+
+/// The fixed header at the start of every EVTC file
+#[derive(Debug, Clone)]
+pub struct EvtcHeader {
+    /// arcdps build the log was recorded with, e.g. "20240612"
+    pub build: String,
+    pub revision: u8,
+}
+
+/// An entry of the agent table
+#[derive(Debug, Clone)]
+pub struct Agent {
+    pub profession: u32,
+    pub is_elite: u32,
+    pub account: String,
+}
+
+impl Agent {
+    pub fn is_player(&self) -> bool {
+        self.is_elite != NON_PLAYER_ELITE && self.profession != 0
+    }
+
+    /// Players recorded with their account name are allies, enemy players are anonymous
+    pub fn is_squad_member(&self) -> bool {
+        self.is_player() && !self.account.is_empty()
+    }
+}
+
+/// A parsed EVTC file without the combat events themselves
+#[derive(Debug, Clone)]
+pub struct Evtc {
+    pub header: EvtcHeader,
+    pub agents: Vec<Agent>,
+    /// Skill ids and names, in table order
+    pub skills: Vec<(i32, String)>,
+    /// Time of the first and last combat event in milliseconds
+    pub event_span: Option<(u64, u64)>,
+}
+
+/// Fight details shown in the log list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FightSummary {
+    pub duration_secs: u64,
+    pub squad_size: usize,
+    pub enemy_count: usize,
+    pub arcdps_build: String,
+    pub revision: u8,
+    pub agent_count: usize,
+    pub skill_count: usize,
+}
+
+impl Evtc {
+    /// Account names of everyone in the squad, sorted and without duplicates
+    pub fn squad_accounts(&self) -> Vec<String> {
+        let mut accounts: Vec<String> = self
+            .agents
+            .iter()
+            .filter(|a| a.is_squad_member())
+            .map(|a| a.account.clone())
+            .collect();
+        accounts.sort();
+        accounts.dedup();
+        accounts
+    }
+
+    pub fn summary(&self) -> FightSummary {
+        let players = self.agents.iter().filter(|a| a.is_player());
+        let (squad, enemies): (Vec<&Agent>, Vec<&Agent>) = players.partition(|a| a.is_squad_member());
+
+        FightSummary {
+            duration_secs: self
+                .event_span
+                .map(|(first, last)| last.saturating_sub(first) / 1000)
+                .unwrap_or(0),
+            squad_size: squad.len(),
+            enemy_count: enemies.len(),
+            arcdps_build: self.header.build.clone(),
+            revision: self.header.revision,
+            agent_count: self.agents.len(),
+            skill_count: self.skills.len(),
+        }
+    }
+}
+
+/// Reads and parses a .zevtc archive or a plain .evtc file
+pub fn parse_file(path: &Path) -> Option<Evtc> {
+    parse(&read_evtc_bytes(path)?)
+}
+
+/// Parses the header, agent and skill tables and scans the event times
+pub fn parse(data: &[u8]) -> Option<Evtc> {
+    if data.len() < HEADER_SIZE || !data.starts_with(b"EVTC") {
+        return None;
+    }
+
+    let header = EvtcHeader {
+        build: String::from_utf8_lossy(&data[4..12]).trim_end_matches('\0').to_string(),
+        revision: data[12],
+    };
+
+    let mut pos = HEADER_SIZE;
+
+    let agent_count = read_u32(data, pos)? as usize;
+    pos += 4;
+    let mut agents = Vec::with_capacity(agent_count.min(10_000));
+    for _ in 0..agent_count {
+        agents.push(parse_agent(data.get(pos..pos + AGENT_SIZE)?)?);
+        pos += AGENT_SIZE;
+    }
+
+    let skill_count = read_u32(data, pos)? as usize;
+    pos += 4;
+    let mut skills = Vec::with_capacity(skill_count.min(10_000));
+    for _ in 0..skill_count {
+        let raw = data.get(pos..pos + SKILL_SIZE)?;
+        skills.push((i32::from_le_bytes(raw[0..4].try_into().ok()?), read_name(&raw[4..68])));
+        pos += SKILL_SIZE;
+    }
+
+    // Events are written in time order, apart from a few state changes
+    let event_span = data[pos..]
+        .chunks_exact(EVENT_SIZE)
+        .filter_map(|event| read_u64(event, 0))
+        .filter(|&time| time > 0)
+        .fold(None, |span: Option<(u64, u64)>, time| match span {
+            Some((first, last)) => Some((first.min(time), last.max(time))),
+            None => Some((time, time)),
+        });
+
+    Some(Evtc {
+        header,
+        agents,
+        skills,
+        event_span,
+    })
+}
+
+fn parse_agent(raw: &[u8]) -> Option<Agent> {
+    // The name field holds "character\0:account\0subgroup\0" for players, only the account is needed
+    let parts: Vec<String> = raw[28..92]
+        .split(|&b| b == 0)
+        .map(|p| String::from_utf8_lossy(p).trim().to_string())
+        .collect();
+
+    let part = |i: usize| parts.get(i).cloned().unwrap_or_default();
+
+    Some(Agent {
+        profession: read_u32(raw, 8)?,
+        is_elite: read_u32(raw, 12)?,
+        account: part(1).trim_start_matches(':').to_string(),
+    })
+}
+
+/// Decompresses a .zevtc archive, plain .evtc files are read as is
+fn read_evtc_bytes(path: &Path) -> Option<Vec<u8>> {
+    let mut buffer = Vec::new();
+    File::open(path).ok()?.read_to_end(&mut buffer).ok()?;
+
+    if !buffer.starts_with(b"PK") {
+        return Some(buffer);
+    }
+
+    // Skip the zip local file header to reach the deflate stream
+    let name_len = read_u16(&buffer, 26)? as usize;
+    let extra_len = read_u16(&buffer, 28)? as usize;
+    let start = 30 + name_len + extra_len;
+
+    let mut data = Vec::new();
+    DeflateDecoder::new(buffer.get(start..)?)
+        .read_to_end(&mut data)
+        .ok()?;
+    Some(data)
+}
+
+fn read_name(raw: &[u8]) -> String {
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+    String::from_utf8_lossy(&raw[..end]).to_string()
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn agent(profession: u32, is_elite: u32, name: &[u8]) -> Vec<u8> {
+        let mut raw = vec![0u8; AGENT_SIZE];
+        raw[8..12].copy_from_slice(&profession.to_le_bytes());
+        raw[12..16].copy_from_slice(&is_elite.to_le_bytes());
+        raw[28..28 + name.len()].copy_from_slice(name);
+        raw
+    }
+
+    fn event(time: u64) -> Vec<u8> {
+        let mut raw = vec![0u8; EVENT_SIZE];
+        raw[0..8].copy_from_slice(&time.to_le_bytes());
+        raw
+    }
+
+    /// A log with two squad members, an enemy player, an NPC, one skill and a 90 second fight
+    fn sample_log() -> Vec<u8> {
+        let mut data = b"EVTC20240612".to_vec();
+        data.push(1);
+        data.extend([0u8; 3]);
+
+        let agents = [
+            agent(1, 0, b"Char One\0:Account.1234\x001\0"),
+            agent(2, 0, b"Char Two\0:Other.5678\x002\0"),
+            agent(3, 0, b"Enemy\0\0\0"),
+            agent(0x1234, NON_PLAYER_ELITE, b"Guard\0"),
+        ];
+        data.extend((agents.len() as u32).to_le_bytes());
+        for raw in agents {
+            data.extend(raw);
+        }
+
+        data.extend(1u32.to_le_bytes());
+        data.extend(5i32.to_le_bytes());
+        let mut skill_name = [0u8; 64];
+        skill_name[..5].copy_from_slice(b"Dodge");
+        data.extend(skill_name);
+
+        data.extend(event(10_000));
+        data.extend(event(100_000));
+        data
+    }
+
+    #[test]
+    fn parses_a_valid_log() {
+        let evtc = parse(&sample_log()).expect("Sample log to parse");
+
+        assert_eq!(evtc.header.build, "20240612");
+        assert_eq!(evtc.header.revision, 1);
+        assert_eq!(evtc.agents.len(), 4);
+        assert_eq!(evtc.skills, vec![(5, "Dodge".to_string())]);
+        assert_eq!(evtc.squad_accounts(), vec!["Account.1234", "Other.5678"]);
+
+        let summary = evtc.summary();
+        assert_eq!(summary.duration_secs, 90);
+        assert_eq!(summary.squad_size, 2);
+        assert_eq!(summary.enemy_count, 1);
+    }
+
+    #[test]
+    fn rejects_truncated_logs() {
+        let data = sample_log();
+
+        assert!(parse(&data[..HEADER_SIZE - 1]).is_none());
+        // Cut in the middle of the agent table
+        assert!(parse(&data[..HEADER_SIZE + 4 + AGENT_SIZE + 10]).is_none());
+        assert!(parse(b"NOTEVTC_________").is_none());
+    }
+
+    #[test]
+    fn reads_zipped_logs() {
+        let log = sample_log();
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&log).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        // A zip local file header is 30 bytes, the name and extra field lengths are at 26 and 28
+        let name = b"20240612-200000";
+        let mut zip = b"PK\x03\x04".to_vec();
+        zip.extend([0u8; 22]);
+        zip.extend((name.len() as u16).to_le_bytes());
+        zip.extend(0u16.to_le_bytes());
+        zip.extend(name);
+        zip.extend(compressed);
+
+        let path = std::env::temp_dir().join(format!("wvw-insights-evtc-test-{}.zevtc", std::process::id()));
+        std::fs::write(&path, &zip).unwrap();
+        let evtc = parse_file(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let evtc = evtc.expect("Zipped log to parse");
+        assert_eq!(evtc.header.build, "20240612");
+        assert_eq!(evtc.squad_accounts(), vec!["Account.1234", "Other.5678"]);
+    }
+}
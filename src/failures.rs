@@ -0,0 +1,100 @@
+use serde::Deserialize;
+use std::sync::RwLock;
+
+use crate::settings::Settings;
+
+/// A known kind of server failure with a friendly explanation
+#[derive(Debug, Clone, Deserialize)]
+pub struct FailurePattern {
+    /// Lowercase snippets, the pattern applies when the message contains any of them
+    pub patterns: Vec<String>,
+    pub title: String,
+    pub explanation: String,
+    pub suggestion: String,
+}
+
+/// Patterns shipped with the addon: snippets, title, explanation, suggested action
+const BUILTIN_PATTERNS: &[(&[&str], &str, &str, &str)] = &[
+    (
+        &["corrupt", "invalid evtc", "not a valid", "unexpected end of file", "bad zip"],
+        "Damaged log file",
+        "The server could not read one of the logs. This happens when the game closed or crashed while arcdps was still writing it.",
+        "Remove the damaged log from the session and process the rest.",
+    ),
+    (
+        &["unsupported build", "unsupported version", "build not supported", "outdated arcdps"],
+        "Unsupported arcdps build",
+        "The log was recorded with an arcdps version the parser doesn't support.",
+        "Update arcdps, or wait for the parser to support the new build and retry.",
+    ),
+    (
+        &["quota", "too many requests", "rate limit", "limit exceeded"],
+        "Upload limit reached",
+        "You have processed more logs than the server allows in a short time.",
+        "Wait a while before retrying, or process fewer logs per session.",
+    ),
+    (
+        &["maintenance", "service unavailable", "temporarily unavailable", "bad gateway", "status code 502", "status code 503"],
+        "Server maintenance",
+        "The parser server is down for maintenance or restarting.",
+        "Try again in a few minutes, your uploads are kept until then.",
+    ),
+    (
+        &["timed out", "timeout", "connection refused", "failed to connect", "dns"],
+        "Connection problem",
+        "The server could not be reached.",
+        "Check your internet connection and retry.",
+    ),
+];
+
+/// Patterns fetched from the server, checked before the built-in ones
+static REMOTE_PATTERNS: RwLock<Vec<FailurePattern>> = RwLock::new(Vec::new());
+
+/// Finds the explanation for a failure message, None for unknown failures
+pub fn classify(message: &str) -> Option<FailurePattern> {
+    let message = message.to_lowercase();
+
+    if let Some(remote) = REMOTE_PATTERNS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|p| p.patterns.iter().any(|s| message.contains(&s.to_lowercase())))
+    {
+        return Some(remote.clone());
+    }
+
+    BUILTIN_PATTERNS
+        .iter()
+        .find(|(snippets, ..)| snippets.iter().any(|s| message.contains(s)))
+        .map(|(snippets, title, explanation, suggestion)| FailurePattern {
+            patterns: snippets.iter().map(|s| s.to_string()).collect(),
+            title: title.to_string(),
+            explanation: explanation.to_string(),
+            suggestion: suggestion.to_string(),
+        })
+}
+
+/// Fetches the latest failure patterns in the background
+/// New error types can be explained without an addon release, the built-in table stays as fallback
+pub fn refresh_remote_patterns() {
+    let api_endpoint = Settings::get().api_endpoint.clone();
+    if api_endpoint.is_empty() || !crate::consent::is_granted() {
+        return;
+    }
+
+    crate::threads::spawn("failure-patterns", move || {
+        match crate::upload::fetch_failure_patterns(&api_endpoint) {
+            Ok(mut patterns) => {
+                // An empty snippet is contained in every message and would explain all failures
+                for pattern in patterns.iter_mut() {
+                    pattern.patterns.retain(|s| !s.trim().is_empty());
+                }
+                patterns.retain(|p| !p.patterns.is_empty());
+
+                log::info!("Fetched {} failure pattern(s)", patterns.len());
+                *REMOTE_PATTERNS.write().unwrap() = patterns;
+            }
+            Err(e) => log::debug!("Failure patterns not available, using built-in ones: {}", e),
+        }
+    });
+}
@@ -1,32 +1,75 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Formats a count with the matching singular or plural word (e.g., "1 minute", "2 minutes")
+/// Both forms are passed in so they can be swapped for translated strings later
+pub fn plural(count: u64, one: &str, other: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { other })
+}
+
+/// Formats a span of time in its largest whole unit (e.g., "3 days", "1 hour", "45 seconds")
+pub fn format_elapsed(seconds: u64) -> String {
+    if seconds >= 86400 {
+        plural(seconds / 86400, "day", "days")
+    } else if seconds >= 3600 {
+        plural(seconds / 3600, "hour", "hours")
+    } else if seconds >= 60 {
+        plural(seconds / 60, "minute", "minutes")
+    } else {
+        plural(seconds, "second", "seconds")
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats a file size in MB, or GB once it gets that large (e.g., "512.0 MB", "60.3 GB")
+pub fn format_size(bytes: u64) -> String {
+    let mb = bytes as f64 / 1024.0 / 1024.0;
+    if mb >= 1024.0 {
+        format!("{:.1} GB", mb / 1024.0)
+    } else {
+        format!("{:.1} MB", mb)
+    }
+}
+
+/// Formats a countdown or overdue time (e.g., "45 seconds", "2 min 30 sec", "3 minutes")
+pub fn format_duration(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    let rest = seconds % 60;
+    if minutes == 0 {
+        plural(seconds, "second", "seconds")
+    } else if rest == 0 {
+        plural(minutes, "minute", "minutes")
+    } else {
+        format!("{} min {} sec", minutes, rest)
+    }
+}
+
 /// Formats a Unix timestamp into a relative time string (e.g., "2 hours ago")
 pub fn format_report_timestamp(timestamp: u64) -> String {
     let datetime = UNIX_EPOCH + std::time::Duration::from_secs(timestamp);
     let now = SystemTime::now();
     
     // Calculate time difference
-    if let Ok(duration) = now.duration_since(datetime) {
-        let days = duration.as_secs() / 86400;
-        let hours = (duration.as_secs() % 86400) / 3600;
-        let minutes = (duration.as_secs() % 3600) / 60;
-        
-        let relative = if days > 0 {
-            format!("{} day{} ago", days, if days == 1 { "" } else { "s" })
-        } else if hours > 0 {
-            format!("{} hour{} ago", hours, if hours == 1 { "" } else { "s" })
-        } else if minutes > 0 {
-            format!("{} minute{} ago", minutes, if minutes == 1 { "" } else { "s" })
-        } else {
-            "Just now".to_string()
-        };
-        
-        relative
-    } else {
-        "Unknown".to_string()
+    match now.duration_since(datetime) {
+        Ok(duration) if duration.as_secs() < 60 => "Just now".to_string(),
+        Ok(duration) => format!("{} ago", format_elapsed(duration.as_secs())),
+        Err(_) => "Unknown".to_string(),
     }
 }
 
+/// Parses the timestamp of a log filename (e.g., "20251010-222255.zevtc")
+pub fn parse_log_timestamp(filename: &str) -> Option<chrono::NaiveDateTime> {
+    let stem = filename.split('.').next()?;
+    chrono::NaiveDateTime::parse_from_str(stem, "%Y%m%d-%H%M%S").ok()
+}
+
 /// Formats a log filename timestamp (e.g., "20251010-222255.zevtc") into a readable format
 pub fn format_timestamp(filename: &str) -> Option<String> {
     // Extract timestamp from filename like "20251010-222255.zevtc"
@@ -63,4 +106,43 @@ pub fn format_timestamp(filename: &str) -> Option<String> {
         "{} {}, {} - {:02}:{:02}",
         month_name, day, year, hour, minute
     ))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plural_picks_the_form_by_count() {
+        assert_eq!(plural(0, "log", "logs"), "0 logs");
+        assert_eq!(plural(1, "log", "logs"), "1 log");
+        assert_eq!(plural(2, "log", "logs"), "2 logs");
+    }
+
+    #[test]
+    fn format_elapsed_uses_the_largest_whole_unit() {
+        assert_eq!(format_elapsed(0), "0 seconds");
+        assert_eq!(format_elapsed(1), "1 second");
+        assert_eq!(format_elapsed(59), "59 seconds");
+        assert_eq!(format_elapsed(60), "1 minute");
+        assert_eq!(format_elapsed(119), "1 minute");
+        assert_eq!(format_elapsed(3599), "59 minutes");
+        assert_eq!(format_elapsed(3600), "1 hour");
+        assert_eq!(format_elapsed(7200), "2 hours");
+        assert_eq!(format_elapsed(86399), "23 hours");
+        assert_eq!(format_elapsed(86400), "1 day");
+        assert_eq!(format_elapsed(3 * 86400 + 5), "3 days");
+    }
+
+    #[test]
+    fn format_duration_splits_minutes_and_seconds() {
+        assert_eq!(format_duration(0), "0 seconds");
+        assert_eq!(format_duration(1), "1 second");
+        assert_eq!(format_duration(59), "59 seconds");
+        assert_eq!(format_duration(60), "1 minute");
+        assert_eq!(format_duration(61), "1 min 1 sec");
+        assert_eq!(format_duration(150), "2 min 30 sec");
+        assert_eq!(format_duration(180), "3 minutes");
+        assert_eq!(format_duration(3600), "60 minutes");
+    }
+}
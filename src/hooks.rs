@@ -0,0 +1,167 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::settings::Settings;
+use crate::state::STATE;
+use crate::webhooks::ReportSummary;
+
+/// Longest a custom URL gets to answer a hook's POST
+const POST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Placeholders filled in when a hook template is used
+pub const TEMPLATE_PLACEHOLDERS: &str = "{name}, {urls}, {first_url}, {guild}, {fights}, {date}";
+
+pub fn default_template() -> String {
+    "{name}\n{urls}".to_string()
+}
+
+/// Text waiting to be put on the clipboard, which can only be done from the render thread
+static PENDING_CLIPBOARD: Mutex<Option<String>> = Mutex::new(None);
+
+/// What a post-processing hook does with the finished reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HookAction {
+    /// Opens every report in the browser
+    OpenUrls,
+    /// Copies the filled-in template to the clipboard
+    CopyToClipboard { template: String },
+    /// POSTs the session as JSON to a custom URL, e.g. a guild bot
+    PostJson { url: String },
+    /// Sends the filled-in template to a saved webhook
+    Webhook { webhook: String, template: String },
+}
+
+impl HookAction {
+    /// One of each kind with empty fields, offered when adding a hook
+    pub fn kinds() -> Vec<HookAction> {
+        vec![
+            HookAction::OpenUrls,
+            HookAction::CopyToClipboard { template: default_template() },
+            HookAction::PostJson { url: String::new() },
+            HookAction::Webhook {
+                webhook: String::new(),
+                template: default_template(),
+            },
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HookAction::OpenUrls => "Open Reports in Browser",
+            HookAction::CopyToClipboard { .. } => "Copy to Clipboard",
+            HookAction::PostJson { .. } => "POST JSON to URL",
+            HookAction::Webhook { .. } => "Send to Webhook",
+        }
+    }
+}
+
+/// An action run after every completed report, in the order of the list in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostProcessHook {
+    pub enabled: bool,
+    pub action: HookAction,
+}
+
+/// The finished session the hooks run for
+#[derive(Debug, Clone, Serialize)]
+struct HookContext {
+    session_id: String,
+    report_name: String,
+    report_urls: Vec<String>,
+    guild: String,
+    #[serde(flatten)]
+    summary: ReportSummary,
+    timestamp: String,
+}
+
+impl HookContext {
+    fn fill(&self, template: &str) -> String {
+        template
+            .replace("{name}", &self.report_name)
+            .replace("{urls}", &self.report_urls.join("\n"))
+            .replace("{first_url}", self.report_urls.first().map(String::as_str).unwrap_or(""))
+            .replace("{guild}", &self.guild)
+            .replace("{fights}", &self.summary.fights.to_string())
+            .replace("{date}", &chrono::Local::now().format("%d.%m.%y").to_string())
+    }
+}
+
+/// Runs the enabled hooks for the session that just completed, one after another
+/// A failing hook is logged and the next one still runs
+pub fn run_after_report() {
+    let settings = Settings::get();
+    let hooks: Vec<PostProcessHook> = settings.post_process_hooks.iter().filter(|h| h.enabled).cloned().collect();
+    let guild = settings.guild_name.clone();
+    drop(settings);
+
+    if hooks.is_empty() {
+        return;
+    }
+
+    let report_urls = STATE.report_urls.lock().unwrap().clone();
+    if report_urls.is_empty() {
+        return;
+    }
+
+    let context = HookContext {
+        session_id: STATE.session_id.lock().unwrap().clone(),
+        report_name: crate::tokens::current_report_name(),
+        report_urls,
+        guild,
+        summary: ReportSummary::current_session(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    crate::threads::spawn("post-process-hooks", move || {
+        let _activity = crate::threads::activity("Running post-processing hooks");
+        for (index, hook) in hooks.iter().enumerate() {
+            match run_hook(&hook.action, &context) {
+                Ok(()) => log::info!("Hook {} ({}) done", index + 1, hook.action.label()),
+                Err(e) => log::warn!("Hook {} ({}) failed: {}", index + 1, hook.action.label(), e),
+            }
+        }
+    });
+}
+
+fn run_hook(action: &HookAction, context: &HookContext) -> Result<()> {
+    match action {
+        HookAction::OpenUrls => {
+            for url in context.report_urls.iter() {
+                open::that_detached(url)?;
+            }
+            Ok(())
+        }
+        HookAction::CopyToClipboard { template } => {
+            *PENDING_CLIPBOARD.lock().unwrap() = Some(context.fill(template));
+            Ok(())
+        }
+        HookAction::PostJson { url } => {
+            crate::consent::require()?;
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err(anyhow!("Not a web address: {}", url));
+            }
+            ureq::post(url).timeout(POST_TIMEOUT).send_json(context)?;
+            Ok(())
+        }
+        HookAction::Webhook { webhook, template } => {
+            let url = crate::webhooks::WebhookSettings::get()
+                .saved_webhooks
+                .iter()
+                .find(|w| w.name == *webhook)
+                .map(|w| w.url.clone())
+                .ok_or_else(|| anyhow!("Webhook '{}' is not saved anymore", webhook))?;
+            let cancel = crate::cancel::upload_token();
+            crate::pending_posts::send_or_queue(&url, &context.fill(template), None, &cancel, |_| {})
+        }
+    }
+}
+
+/// Puts text from a clipboard hook on the clipboard, called every frame
+pub fn apply_clipboard(ui: &nexus::imgui::Ui) {
+    if let Some(text) = PENDING_CLIPBOARD.lock().unwrap().take() {
+        ui.set_clipboard_text(text);
+    }
+}
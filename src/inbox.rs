@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cancel::CancelToken;
+use crate::logfile::LogFile;
+use crate::settings::Settings;
+use crate::state::{ProcessingState, STATE};
+
+/// How often the inbox folder is checked for new commands
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Commands changed more recently than this may still be being written
+const SETTLE_TIME: Duration = Duration::from_secs(1);
+
+/// A command dropped into the inbox by an external tool, e.g.
+/// `{ "files": ["20250101-200000.zevtc"], "process": true }`
+#[derive(Debug, Deserialize)]
+struct InboxCommand {
+    /// Logs to upload, relative to the log directory or absolute paths inside it
+    #[serde(default)]
+    files: Vec<PathBuf>,
+    /// Start processing once the files are uploaded
+    #[serde(default)]
+    process: bool,
+}
+
+/// Written next to the command as `<name>.result.json` once it has run
+#[derive(Debug, Default, Serialize)]
+struct InboxResult {
+    uploaded: Vec<String>,
+    failed: Vec<FailedFile>,
+    processing_started: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct FailedFile {
+    file: String,
+    error: String,
+}
+
+/// Starts the thread that runs commands dropped into the inbox folder
+pub fn start() {
+    crate::threads::spawn_tracked("inbox", || {
+        // Never cancelled itself, only stops on unload
+        let idle = CancelToken::new();
+
+        while idle.sleep(POLL_INTERVAL) {
+            if !Settings::get().inbox_enabled || !crate::consent::is_granted() {
+                continue;
+            }
+            if crate::mumble::should_defer_work() || !accepts_uploads() {
+                continue;
+            }
+
+            for command in pending_commands() {
+                if idle.is_cancelled() {
+                    break;
+                }
+                run_command(&command);
+            }
+        }
+    });
+}
+
+/// Command files waiting in the inbox, oldest name first
+fn pending_commands() -> Vec<PathBuf> {
+    let inbox = crate::inbox_path();
+    if let Err(e) = std::fs::create_dir_all(&inbox) {
+        log::error!("Failed to create inbox folder: {}", e);
+        return Vec::new();
+    }
+
+    let Ok(entries) = std::fs::read_dir(&inbox) else {
+        return Vec::new();
+    };
+
+    let mut commands: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && is_command_file(p) && is_settled(p))
+        .collect();
+    commands.sort();
+    commands
+}
+
+fn is_settled(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age >= SETTLE_TIME)
+}
+
+fn is_command_file(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    name.ends_with(".json") && !name.ends_with(".result.json")
+}
+
+/// Commands wait while a session is being processed or its results are showing
+fn accepts_uploads() -> bool {
+    let state = *STATE.processing_state.lock().unwrap();
+    matches!(state, ProcessingState::Idle | ProcessingState::Uploading)
+        && !*STATE.show_results.lock().unwrap()
+        && STATE.resumable_session.lock().unwrap().is_none()
+}
+
+fn run_command(path: &Path) {
+    log::info!("Running inbox command {:?}", path);
+
+    let result = match read_command(path) {
+        Ok(command) => execute(command),
+        Err(e) => InboxResult {
+            error: Some(format!("Invalid command: {}", e)),
+            ..Default::default()
+        },
+    };
+
+    if let Some(error) = &result.error {
+        log::warn!("Inbox command {:?} failed: {}", path, error);
+    }
+
+    // The result replaces the command so it only runs once
+    let result_path = path.with_extension("result.json");
+    match serde_json::to_string_pretty(&result) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&result_path, json) {
+                log::error!("Failed to write inbox result: {}", e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize inbox result: {}", e),
+    }
+    if let Err(e) = std::fs::remove_file(path) {
+        log::error!("Failed to remove inbox command {:?}: {}", path, e);
+    }
+}
+
+fn read_command(path: &Path) -> anyhow::Result<InboxCommand> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn execute(command: InboxCommand) -> InboxResult {
+    let mut result = InboxResult::default();
+
+    if !command.files.is_empty() {
+        let Some(session_id) = crate::watcher::active_session() else {
+            result.error = Some("No session could be created, check your history token".to_string());
+            return result;
+        };
+
+        let log_directory = PathBuf::from(Settings::get().log_directory.clone());
+        for file in command.files {
+            let name = file.display().to_string();
+
+            match resolve(&log_directory, &file).and_then(|path| upload(&path, &session_id)) {
+                Ok(_) => result.uploaded.push(name),
+                Err(error) => result.failed.push(FailedFile { file: name, error }),
+            }
+        }
+
+        log::info!(
+            "Inbox uploaded {} log(s), {} failed",
+            result.uploaded.len(),
+            result.failed.len()
+        );
+    }
+
+    if command.process {
+        if STATE.uploaded_files.lock().unwrap().is_empty() {
+            result.error = Some("Nothing to process, no logs were uploaded".to_string());
+        } else if *STATE.processing_state.lock().unwrap() != ProcessingState::Idle {
+            result.error = Some("Uploads are still running, processing was not started".to_string());
+        } else {
+            result.processing_started = crate::watcher::start_unattended_processing();
+            if !result.processing_started {
+                result.error = Some("Processing could not be started".to_string());
+            }
+        }
+    }
+
+    result
+}
+
+/// Finds a commanded file inside the log directory
+/// Anything outside it or not an ArcDps log is refused, so a command can't send other files to the server
+fn resolve(log_directory: &Path, file: &Path) -> Result<PathBuf, String> {
+    if !crate::logfile::is_log_file(file) {
+        return Err("Not an ArcDps log".to_string());
+    }
+
+    let log_directory = log_directory
+        .canonicalize()
+        .map_err(|_| "The log directory is not set or doesn't exist".to_string())?;
+    let path = log_directory
+        .join(file)
+        .canonicalize()
+        .map_err(|_| "File not found".to_string())?;
+
+    if !path.starts_with(&log_directory) {
+        return Err("File is outside the log directory".to_string());
+    }
+    Ok(path)
+}
+
+fn upload(path: &Path, session_id: &str) -> Result<(), String> {
+    let log = LogFile::new_fast(path.to_path_buf()).map_err(|e| e.to_string())?;
+    if let Some(reason) = &log.invalid {
+        return Err(reason.clone());
+    }
+
+    if STATE.uploaded_files.lock().unwrap().iter().any(|f| f.filename == log.filename) {
+        return Ok(());
+    }
+
+    crate::watcher::upload_to_session(&log, session_id, &crate::cancel::upload_token()).map_err(|e| e.to_string())
+}
@@ -12,25 +12,51 @@ use nexus::{
 };
 
 mod arcdps;
+mod archive;
+mod attendance;
+mod audit;
+mod cancel;
+mod capabilities;
 mod cleanup;
+mod commanders;
 mod common;
+mod consent;
+mod crash;
+mod debug;
+mod evtc;
+mod failures;
 mod formatting;
+mod hooks;
+mod inbox;
+mod lock;
 mod logfile;
+mod maps;
+mod metadata_cache;
+mod mumble;
+mod pending_posts;
+mod profiles;
 mod scanning;
+mod session;
+mod speech;
 mod settings;
 mod state;
 mod qol;
+mod reparse;
+mod threads;
+mod throttle;
 mod tokens;
 mod ui;
 mod upload;
+mod upload_stats;
 mod uploaded_logs;
+mod watcher;
 use uploaded_logs::UploadedLogs;
 mod webhooks;
 use webhooks::WebhookSettings;
 mod report_history;
-use report_history::ReportHistory;
+use report_history::{ReportEntry, ReportHistory};
 
-use cleanup::check_auto_cleanup_on_load;
+use cleanup::{check_auto_cleanup_on_load, check_server_cleanup_on_load, check_stranded_cleanup_on_load};
 use common::{WorkerMessage, WorkerType};
 use scanning::{check_auto_scan, update_scan_display};
 use settings::Settings;
@@ -47,6 +73,12 @@ fn config_path() -> PathBuf {
         .join("settings.json")
 }
 
+fn metadata_cache_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("metadata_cache.json")
+}
+
 fn uploaded_logs_path() -> PathBuf {
     get_addon_dir("wvw-insights")
         .expect("Addon dir to exist")
@@ -59,6 +91,54 @@ fn webhooks_path() -> PathBuf {
         .join("webhooks.json")
 }
 
+fn maps_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("maps.json")
+}
+
+fn active_session_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("active_session.json")
+}
+
+fn inbox_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("inbox")
+}
+
+fn pending_posts_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("pending_posts.json")
+}
+
+fn attendance_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("attendance.json")
+}
+
+fn commanders_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("commanders.json")
+}
+
+fn upload_stats_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("upload_stats.json")
+}
+
+fn abandoned_sessions_path() -> PathBuf {
+    get_addon_dir("wvw-insights")
+        .expect("Addon dir to exist")
+        .join("abandoned_sessions.json")
+}
+
 fn report_history_path() -> PathBuf {
     get_addon_dir("wvw-insights")
         .expect("Addon dir to exist")
@@ -101,15 +181,20 @@ fn render_simple_shortcut(ui: &Ui) {
 
 /// Updates the log list with results from upload workers
 fn update_logs() {
+    let mut any_uploaded = false;
+    let mut any_timed = false;
+
     while let Some(WorkerMessage { index, payload }) = STATE.try_next_producer() {
         match payload {
             WorkerType::UploadResult(result) => {
                 let mut logs = STATE.logs.lock().unwrap();
                 if index < logs.len() {
+                    logs[index].upload_progress = None;
                     match result {
                         Ok(status) => {
                             logs[index].status = status;
                             logs[index].uploaded = true;
+                            any_uploaded = true;
                         }
                         Err(e) => {
                             logs[index].status = format!("Failed: {}", e);
@@ -117,8 +202,46 @@ fn update_logs() {
                     }
                 }
             }
+            WorkerType::UploadProgress { sent, total } => {
+                let mut logs = STATE.logs.lock().unwrap();
+                if let Some(log) = logs.get_mut(index) {
+                    log.status = "Uploading".to_string();
+                    log.upload_progress = Some((sent, total));
+                }
+            }
+            WorkerType::UploadTiming(timing) => {
+                let mut logs = STATE.logs.lock().unwrap();
+                let Some(log) = logs.get_mut(index) else {
+                    continue;
+                };
+                log.upload_timing = Some(timing);
+                let filename = log.filename.clone();
+                drop(logs);
+
+                if let Some(file) = STATE
+                    .uploaded_files
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .find(|f| f.filename == filename)
+                {
+                    file.upload_timing = Some(timing);
+                }
+                upload_stats::UploadStats::get().record(timing);
+                any_timed = true;
+            }
         }
     }
+
+    if any_timed {
+        if let Err(e) = upload_stats::UploadStats::get().store(upload_stats_path()) {
+            log::error!("Failed to save upload statistics: {}", e);
+        }
+    }
+
+    if any_uploaded {
+        crate::webhooks::announce_session_start();
+    }
 }
 
 /// Checks the upload and processing progress
@@ -131,11 +254,17 @@ fn check_upload_progress() {
         let total = selected_logs.len();
         let uploaded = selected_logs
             .iter()
-            .filter(|l| l.uploaded || l.status.starts_with("Failed"))
+            .filter(|l| l.upload_finished())
             .count();
         drop(logs);
 
         if uploaded >= total && total > 0 {
+            if *STATE.upload_backend.lock().unwrap() == upload::UploadBackend::DpsReport {
+                log::info!("All dps.report uploads complete ({}/{})", uploaded, total);
+                finish_dps_report_uploads();
+                return;
+            }
+
             log::info!("All uploads complete ({}/{}), showing review screen", uploaded, total);
             
             // Transition to review screen instead of idle
@@ -153,15 +282,26 @@ fn check_upload_progress() {
             *last_check = Some(std::time::Instant::now());
             drop(last_check);
 
-            std::thread::spawn(|| {
-                let settings = Settings::get();
-                let api_endpoint = settings.api_endpoint.clone();
-                drop(settings);
+            let cancel = cancel::upload_token();
+            crate::threads::spawn("status-check", move || {
+                let _activity = crate::threads::activity("Polling status");
+                let api_endpoint = upload::session_endpoint();
+
+                if !STATE.split_sessions.lock().unwrap().is_empty() {
+                    check_split_sessions(&api_endpoint, &cancel);
+                    return;
+                }
 
                 let session_id = STATE.session_id.lock().unwrap().clone();
 
-                match upload::check_status(&api_endpoint, &session_id) {
-                    Ok((status, report_urls, progress, phase)) => {
+                let result = upload::check_status(&api_endpoint, &session_id);
+                // The session was cancelled or reset while the request was in flight
+                if cancel.is_cancelled() {
+                    return;
+                }
+
+                match result {
+                    Ok((status, report_urls, progress, phase, artifacts)) => {
                         // Update progress and phase
                         *STATE.processing_progress.lock().unwrap() = progress;
                         if let Some(phase_msg) = phase {
@@ -171,32 +311,19 @@ fn check_upload_progress() {
                             log::info!("Processing complete!");
                             if let Some(urls) = report_urls {
                                 *STATE.report_urls.lock().unwrap() = urls.clone();
+                                *STATE.report_artifacts.lock().unwrap() = artifacts.clone();
 
                                 // Save to new report history system
                                 let session_id = STATE.session_id.lock().unwrap().clone();
-                                let timestamp = std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                                    .as_secs();
-
-                                let mut history = ReportHistory::get();
-                                
-                                // First URL is always the main report
-                                let main_url = urls[0].clone();
-                                // Second URL (if exists) is the legacy report
-                                let legacy_url = urls.get(1).cloned();
-                                
-                                history.add_report(session_id, timestamp, main_url, legacy_url);
-                                
-                                if let Err(e) = history.store(report_history_path()) {
-                                    log::error!("Failed to save report history: {}", e);
-                                } else {
-                                    log::info!("Saved report to history");
-                                }
+                                save_report_history(session_id, &urls, artifacts);
                             }
                             *STATE.processing_state.lock().unwrap() = ProcessingState::Complete;
                             *STATE.show_upload_progress.lock().unwrap() = false;
                             *STATE.show_results.lock().unwrap() = true;
+                            watcher::on_processing_complete();
+                            webhooks::auto_post_reports();
+                            hooks::run_after_report();
+                            archive::archive_after_report();
                         } else if status == "failed" {
                             log::error!("Processing failed");
                             *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
@@ -211,13 +338,259 @@ fn check_upload_progress() {
     }
 }
 
+/// Polls every unfinished part when the session is split by map or time
+/// Finishes once all parts are done, failing if any part failed
+fn check_split_sessions(api_endpoint: &str, cancel: &cancel::CancelToken) {
+    let pending: Vec<(String, String)> = STATE
+        .split_sessions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|s| !s.complete && !s.failed)
+        .map(|s| (s.label.clone(), s.session_id.clone()))
+        .collect();
+
+    for (label, session_id) in pending {
+        let result = upload::check_status(api_endpoint, &session_id);
+        if cancel.is_cancelled() {
+            return;
+        }
+
+        match result {
+            Ok((status, report_urls, progress, phase, artifacts)) => {
+                if let Some(phase_msg) = phase {
+                    *STATE.processing_phase.lock().unwrap() = format!("{}: {}", label, phase_msg);
+                }
+
+                let mut split_sessions = STATE.split_sessions.lock().unwrap();
+                let Some(split_session) = split_sessions.iter_mut().find(|s| s.session_id == session_id) else {
+                    return;
+                };
+                split_session.progress = progress;
+
+                if status == "complete" {
+                    log::info!("Processing complete for {}", label);
+                    split_session.complete = true;
+                    split_session.progress = 100.0;
+                    if let Some(urls) = report_urls {
+                        split_session.report_urls = urls.clone();
+                        drop(split_sessions);
+                        STATE.report_artifacts.lock().unwrap().extend(artifacts.iter().cloned());
+                        save_report_history(session_id, &urls, artifacts);
+                    }
+                } else if status == "failed" {
+                    log::error!("Processing failed for {}", label);
+                    split_session.failed = true;
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to check status for {}: {}", label, e);
+            }
+        }
+    }
+
+    let split_sessions = STATE.split_sessions.lock().unwrap().clone();
+    let total = split_sessions.len().max(1) as f32;
+    *STATE.processing_progress.lock().unwrap() =
+        split_sessions.iter().map(|s| s.progress).sum::<f32>() / total;
+
+    if split_sessions.iter().any(|s| !s.complete && !s.failed) {
+        return;
+    }
+
+    if split_sessions.iter().any(|s| s.failed) {
+        let failed: Vec<&str> = split_sessions
+            .iter()
+            .filter(|s| s.failed)
+            .map(|s| s.label.as_str())
+            .collect();
+        log::error!("Processing failed for: {}", failed.join(", "));
+        *STATE.report_urls.lock().unwrap() =
+            vec![format!("Processing failed for: {}", failed.join(", "))];
+        *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
+        return;
+    }
+
+    log::info!("Processing complete for all {} parts", split_sessions.len());
+    *STATE.report_urls.lock().unwrap() = split_sessions
+        .iter()
+        .flat_map(|s| s.report_urls.iter().cloned())
+        .collect();
+    *STATE.processing_state.lock().unwrap() = ProcessingState::Complete;
+    *STATE.show_upload_progress.lock().unwrap() = false;
+    *STATE.show_results.lock().unwrap() = true;
+    watcher::on_processing_complete();
+    webhooks::auto_post_reports();
+    hooks::run_after_report();
+    archive::archive_after_report();
+}
+
+/// Shows the dps.report permalinks as the session's reports, there is nothing to process
+fn finish_dps_report_uploads() {
+    let uploads = STATE.dps_report_uploads.lock().unwrap().clone();
+    *STATE.show_upload_progress.lock().unwrap() = false;
+
+    if uploads.is_empty() {
+        log::error!("No log could be uploaded to dps.report");
+        *STATE.report_urls.lock().unwrap() = vec!["dps.report: no log could be uploaded".to_string()];
+        *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
+        *STATE.show_upload_progress.lock().unwrap() = true;
+        return;
+    }
+
+    let urls: Vec<String> = uploads.iter().map(|u| u.permalink.clone()).collect();
+    *STATE.report_urls.lock().unwrap() = urls.clone();
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let log_files: Vec<String> = uploads.iter().map(|u| u.filename.clone()).collect();
+    let details = report_history::SessionDetails::collect(&log_files, "");
+
+    let entry = ReportEntry {
+        // Only used to tell entries apart, dps.report uploads have no server session
+        session_id: format!("dps.report-{}", timestamp),
+        timestamp,
+        main_report_url: urls[0].clone(),
+        legacy_report_url: None,
+        token_key: String::new(),
+        log_files,
+        ownership_token: String::new(),
+        pinned: false,
+        favorite: false,
+        dps_report_urls: urls,
+        label: String::new(),
+        details: Some(details),
+        artifacts: Vec::new(),
+    };
+    attendance::record_report(&entry);
+
+    let mut history = ReportHistory::get();
+    history.add_report(entry);
+    if let Err(e) = history.store(report_history_path()) {
+        log::error!("Failed to save report history: {}", e);
+    }
+    drop(history);
+
+    *STATE.processing_state.lock().unwrap() = ProcessingState::Complete;
+    *STATE.show_results.lock().unwrap() = true;
+}
+
+/// Saves a finished session to the report history
+fn save_report_history(session_id: String, urls: &[String], artifacts: Vec<report_history::ReportArtifact>) {
+    let Some(main_url) = urls.first().cloned() else {
+        return;
+    };
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let history_token = Settings::get().history_token.clone();
+
+    // Split parts each have their own ownership token
+    let ownership_token = STATE
+        .split_sessions
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|s| s.session_id == session_id)
+        .map(|s| s.ownership_token.clone())
+        .unwrap_or_else(|| STATE.ownership_token.lock().unwrap().clone());
+
+    // Second URL (if exists) is the legacy report
+    let legacy_url = urls.get(1).cloned();
+
+    // Kept so the session can be re-parsed later
+    let log_files: Vec<String> = STATE
+        .uploaded_files
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|f| f.session_id == session_id)
+        .map(|f| f.filename.clone())
+        .collect();
+    let details = report_history::SessionDetails::collect(&log_files, &history_token);
+
+    let entry = ReportEntry {
+        session_id,
+        timestamp,
+        main_report_url: main_url,
+        legacy_report_url: legacy_url,
+        token_key: tokens::token_key(&history_token),
+        log_files,
+        ownership_token,
+        pinned: false,
+        favorite: false,
+        dps_report_urls: Vec::new(),
+        label: String::new(),
+        details: Some(details),
+        artifacts,
+    };
+    attendance::record_report(&entry);
+
+    let mut history = ReportHistory::get();
+    history.add_report(entry);
+
+    if let Err(e) = history.store(report_history_path()) {
+        log::error!("Failed to save report history: {}", e);
+    } else {
+        log::info!("Saved report to history");
+    }
+}
+
+/// Keybind handler for the internal UI state dump
+fn handle_debug_dump_keybind(id: &str, is_release: bool) {
+    if id == "KB_WVW_INSIGHTS_DEBUG_DUMP" && !is_release {
+        if let Err(e) = debug::dump_ui_state() {
+            log::error!("Failed to dump UI state: {}", e);
+        }
+    }
+}
+
+/// Short status for the window title and the title bar color matching it
+fn window_status() -> (Option<String>, Option<[f32; 4]>) {
+    match *STATE.processing_state.lock().unwrap() {
+        ProcessingState::Uploading => {
+            let logs = STATE.logs.lock().unwrap();
+            let selected = logs.iter().filter(|l| l.selected);
+            let total = selected.clone().count();
+            let done = selected
+                .filter(|l| l.upload_finished())
+                .count();
+            (
+                Some(format!("Uploading {}/{}", done, total)),
+                Some([0.2, 0.4, 0.8, 1.0]),
+            )
+        }
+        ProcessingState::Processing => {
+            let progress = *STATE.processing_progress.lock().unwrap();
+            (
+                Some(format!("Processing {:.0}%", progress)),
+                Some([0.7, 0.45, 0.1, 1.0]),
+            )
+        }
+        ProcessingState::Complete => (Some("Complete".to_string()), Some([0.15, 0.55, 0.2, 1.0])),
+        ProcessingState::Failed => (Some("Failed".to_string()), Some([0.7, 0.15, 0.15, 1.0])),
+        ProcessingState::Idle => (None, None),
+    }
+}
+
 /// Main render function
 fn render_fn(ui: &Ui) {
+    consent::update();
     update_logs();
     check_upload_progress();
+    speech::check_status();
+    hooks::apply_clipboard(ui);
+    session::save_if_changed();
     check_auto_scan();
     update_scan_display();
     qol::update_mouse_lock();
+    mumble::update();
+    throttle::record_frame(ui.io().delta_time);
 
     let show_window = *STATE.show_main_window.lock().unwrap();
     if !show_window {
@@ -226,11 +599,26 @@ fn render_fn(ui: &Ui) {
 
     let mut is_open = true;
 
-    if let Some(_w) = Window::new("WvW Insights")
+    // Live status in the title stays readable while the window is collapsed, the ### keeps its ID stable
+    let (status, title_color) = window_status();
+    let title = match status {
+        Some(status) => format!("WvW Insights - {}###WvW Insights", status),
+        None => "WvW Insights###WvW Insights".to_string(),
+    };
+    let title_styles = title_color.map(|color| {
+        (
+            ui.push_style_color(nexus::imgui::StyleColor::TitleBg, color),
+            ui.push_style_color(nexus::imgui::StyleColor::TitleBgActive, color),
+            ui.push_style_color(nexus::imgui::StyleColor::TitleBgCollapsed, color),
+        )
+    });
+
+    let window = Window::new(&title)
         .size([500.0, 600.0], nexus::imgui::Condition::FirstUseEver)
         .opened(&mut is_open)
-        .begin(ui)
-    {
+        .begin(ui);
+
+    if let Some(_w) = window {
         if ui.is_window_focused() && ui.is_key_pressed(nexus::imgui::Key::Escape) {
             *STATE.show_main_window.lock().unwrap() = false;
             log::info!("Window closed with ESC key");
@@ -246,8 +634,18 @@ fn render_fn(ui: &Ui) {
 
         let cfg_path = config_path();
 
-        if show_settings {
+        ui::settings::cleanup::render_stranded_cleanup_prompt(ui);
+        ui::resume_session::render_resume_prompt(ui);
+        ui::crash_report::render_crash_prompt(ui);
+        ui::thread_errors::render_thread_errors(ui);
+        ui::live_queue::render_live_queue(ui);
+
+        if STATE.settings_safe_mode.lock().unwrap().is_some() {
+            ui::safe_mode::render_safe_mode(ui, &cfg_path);
+        } else if show_settings {
             ui::render_settings(ui, &cfg_path);
+        } else if !consent::is_granted() {
+            ui::consent::render_consent(ui, &cfg_path);
         } else if show_token {
             ui::render_token_input(ui, &cfg_path);
         } else if show_logs {
@@ -259,7 +657,12 @@ fn render_fn(ui: &Ui) {
         } else if show_results {
             ui::render_results(ui);
         }
+
+        ui::activity::render_activity_line(ui);
     }
+
+    // Only popped once the window has ended, imgui checks the style stack against Begin
+    drop(title_styles);
     
     if !is_open {
         *STATE.show_main_window.lock().unwrap() = false;
@@ -270,6 +673,9 @@ fn render_fn(ui: &Ui) {
 fn load() {
     log::info!("WvW Insights: Starting load");
 
+    threads::install_panic_hook();
+    cancel::reset_shutdown();
+
     // Capture the addon load time
     *STATE.addon_load_time.lock().unwrap() = Some(std::time::Instant::now());
     
@@ -279,13 +685,30 @@ fn load() {
     if let Err(e) = Settings::from_path(&cfg_path) {
         log::error!("Failed to load settings: {e}");
         let mut settings = Settings::get();
-        settings.init();
-        if let Err(e) = settings.store(&cfg_path) {
-            log::error!("Failed to save initialized settings: {e}");
+        if cfg_path.exists() {
+            // Run on defaults without touching the damaged file, the safe mode screen takes it from here
+            settings.init();
+            drop(settings);
+            *STATE.settings_safe_mode.lock().unwrap() = Some(e.to_string());
+            *STATE.show_main_window.lock().unwrap() = true;
+            log::warn!("Settings could not be parsed, entering safe mode");
+        } else {
+            settings.init();
+            if let Err(e) = settings.store(&cfg_path) {
+                log::error!("Failed to save initialized settings: {e}");
+            }
+            log::info!("Settings initialized with defaults and saved");
+            // First launch, size up the default log folder for the settings suggestions
+            scanning::start_directory_estimate(settings.log_directory.clone());
         }
-        log::info!("Settings initialized with defaults and saved");
     }
     log::info!("Settings loaded - log_directory: {}", Settings::get().log_directory);
+    consent::update();
+
+    // Extra or renamed WvW maps, on top of the built-in table
+    if let Err(e) = maps::from_path(maps_path()) {
+        log::warn!("Failed to load map definitions: {e}");
+    }
 
     // Load uploaded logs history
     let uploaded_path = uploaded_logs_path();
@@ -293,10 +716,11 @@ fn load() {
         log::warn!("Failed to load uploaded logs history: {e}");
     }
     
-    // Clean up uploaded logs older than 72 hours
+    // Archive uploaded logs older than 72 hours and forget old archived ones
     {
+        let archive_days = Settings::get().uploaded_archive_days;
         let mut uploaded = UploadedLogs::get();
-        let removed = uploaded.cleanup_old_entries();
+        let removed = uploaded.cleanup_old_entries(archive_days);
         
         // Save after cleanup if anything was removed
         if removed > 0 {
@@ -308,6 +732,12 @@ fn load() {
         }
     }
 
+    // Parsed log metadata from earlier runs, the rest is filled in the background
+    if let Err(e) = metadata_cache::from_path(metadata_cache_path()) {
+        log::warn!("Failed to load the metadata cache: {e}");
+    }
+    scanning::start_metadata_warmup();
+
     // Load webhook settings at startup
     let webhooks_path = webhooks_path();
     if let Err(e) = WebhookSettings::from_path(&webhooks_path) {
@@ -325,13 +755,63 @@ fn load() {
         }
     }
 
+    // Discord posts that failed before the last unload
+    if let Err(e) = pending_posts::PendingPosts::from_path(pending_posts_path()) {
+        log::warn!("Failed to load pending Discord posts: {e}");
+    }
+
+    if let Err(e) = attendance::AttendanceLedger::from_path(attendance_path()) {
+        log::warn!("Failed to load attendance: {e}");
+    }
+
+    if let Err(e) = commanders::CommanderNotes::from_path(commanders_path()) {
+        log::warn!("Failed to load commander notes: {e}");
+    }
+
+    if let Err(e) = upload_stats::UploadStats::from_path(upload_stats_path()) {
+        log::warn!("Failed to load upload statistics: {e}");
+    }
+
     // Load report history at startup
     let history_path = report_history_path();
     if let Err(e) = ReportHistory::from_path(&history_path) {
         log::warn!("Failed to load report history: {e}");
     }
 
+    // Move report history left in settings.json by older versions
+    {
+        let mut history = ReportHistory::get();
+        match history.migrate_from_settings(&cfg_path) {
+            Ok(0) => {}
+            Ok(_) => match history.store(&history_path) {
+                Ok(()) => {
+                    drop(history);
+                    // Re-saving settings drops the old report_history key
+                    if let Err(e) = Settings::get().store(&cfg_path) {
+                        log::error!("Failed to save settings after history migration: {e}");
+                    }
+                }
+                Err(e) => log::error!("Failed to save migrated report history: {e}"),
+            },
+            Err(e) => log::warn!("Failed to migrate legacy report history: {e}"),
+        }
+    }
+
+    // Offer to continue a session cut short by a crash or reload
+    session::load_previous();
+    crash::check_previous_run();
+
+    check_stranded_cleanup_on_load();
     check_auto_cleanup_on_load();
+    check_server_cleanup_on_load();
+    failures::refresh_remote_patterns();
+    upload::cancel_queued_sessions(abandoned_sessions_path());
+    tokens::check_saved_token();
+    watcher::start();
+    pending_posts::start();
+    inbox::start();
+    speech::start();
+    crash::start();
     
     // Enable mouse lock if it was enabled last time
     let settings = Settings::get();
@@ -341,10 +821,10 @@ fn load() {
     drop(settings);
     
     let producer_tx = STATE.init_producer();
-    let upload_rx = STATE.init_upload_worker();
 
-    let handle = upload::run(upload_rx, producer_tx);
-    STATE.append_thread(handle);
+    for handle in upload::run(producer_tx) {
+        STATE.append_thread(handle);
+    }
 
     register_render(RenderType::Render, render!(render_fn)).revert_on_unload();
 
@@ -370,6 +850,14 @@ fn load() {
     )
     .revert_on_unload();
 
+    // Unbound by default, only needed when reviewing UI changes or reporting bugs
+    register_keybind_with_string(
+        "KB_WVW_INSIGHTS_DEBUG_DUMP",
+        keybind_handler!(handle_debug_dump_keybind),
+        "(null)",
+    )
+    .revert_on_unload();
+
     // Add context menu shortcut (right-click menu on Nexus icon)
     add_quick_access_context_menu(
         "QAS_WVW_INSIGHTS",
@@ -394,6 +882,12 @@ fn load() {
 fn unload() {
     log::info!("WvW Insights: Starting unload");
 
+    // Free the server storage of a session that never got anything uploaded, on the next start
+    upload::cancel_abandoned_session(abandoned_sessions_path());
+
+    // Tell every background task to stop before waiting on any of them
+    cancel::shutdown();
+
     qol::disable_mouse_lock();
 
     let settings = Settings::get();
@@ -402,6 +896,10 @@ fn unload() {
     }
     drop(settings);
 
+    if let Err(e) = metadata_cache::store_if_changed(metadata_cache_path()) {
+        log::error!("Failed to store the metadata cache: {e}");
+    }
+
     // Save uploaded logs history
     let uploaded = UploadedLogs::get();
     if let Err(e) = uploaded.store(uploaded_logs_path()) {
@@ -410,7 +908,7 @@ fn unload() {
     drop(uploaded);
 
     drop(STATE.producer_rx.lock().unwrap().take());
-    drop(STATE.upload_worker.lock().unwrap().take());
+    upload::close_queue();
 
     for t in STATE.threads.lock().unwrap().drain(..) {
         let threadname = t
@@ -424,6 +922,9 @@ fn unload() {
         }
     }
 
+    crash::clean_shutdown();
+    threads::remove_panic_hook();
+
     log::info!("WvW Insights: Unload complete");
 }
 
@@ -0,0 +1,162 @@
+use crate::settings::Settings;
+use crate::state::STATE;
+
+/// Minimum number of characters required for a settings lock PIN
+pub const MIN_PIN_LENGTH: usize = 4;
+
+/// Marks a PBKDF2 PIN hash, saved as `pbkdf2:<rounds>:<salt>:<hash>` in hex
+const HASH_PREFIX: &str = "pbkdf2";
+
+/// PBKDF2-HMAC-SHA256 rounds, slow enough that short PINs can't be tried through quickly
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+const SALT_LEN: usize = 16;
+
+/// Hashes a PIN with a new random salt so it isn't stored as plain text in settings.json
+pub fn hash_pin(pin: &str) -> anyhow::Result<String> {
+    let salt = random_salt().ok_or_else(|| anyhow::anyhow!("Could not generate a random salt"))?;
+    let hash = derive(pin, &salt, PBKDF2_ROUNDS);
+    Ok(format!(
+        "{}:{}:{}:{}",
+        HASH_PREFIX,
+        PBKDF2_ROUNDS,
+        to_hex(&salt),
+        to_hex(&hash)
+    ))
+}
+
+/// Whether the PIN matches a saved hash
+/// Hashes saved by older versions are unsalted FNV-1a and are replaced once the PIN is entered
+fn verify_pin(pin: &str, saved: &str) -> bool {
+    let mut parts = saved.split(':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(HASH_PREFIX), Some(rounds), Some(salt), Some(hash)) => {
+            let (Ok(rounds), Some(salt)) = (rounds.parse::<u32>(), from_hex(salt)) else {
+                return false;
+            };
+            let derived = derive(pin, &salt, rounds);
+            // Compares every byte so the time taken doesn't tell how much matched
+            from_hex(hash).is_some_and(|hash| {
+                hash.len() == derived.len()
+                    && hash
+                        .iter()
+                        .zip(derived.iter())
+                        .fold(0, |acc, (a, b)| acc | (a ^ b))
+                        == 0
+            })
+        }
+        _ => saved == legacy_hash(pin),
+    }
+}
+
+fn derive(pin: &str, salt: &[u8], rounds: u32) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(pin.trim().as_bytes(), salt, rounds, &mut hash);
+    hash
+}
+
+/// The unsalted FNV-1a hash older versions saved
+fn legacy_hash(pin: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in pin.trim().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Random bytes from the Windows crypto provider
+fn random_salt() -> Option<[u8; SALT_LEN]> {
+    use winapi::um::wincrypt::{
+        CryptAcquireContextW, CryptGenRandom, CryptReleaseContext, CRYPT_VERIFYCONTEXT,
+        PROV_RSA_FULL,
+    };
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut provider = 0;
+    // SAFETY: the provider is only used between acquiring and releasing it, salt outlives the call
+    unsafe {
+        if CryptAcquireContextW(
+            &mut provider,
+            std::ptr::null(),
+            std::ptr::null(),
+            PROV_RSA_FULL,
+            CRYPT_VERIFYCONTEXT,
+        ) == 0
+        {
+            return None;
+        }
+        let ok = CryptGenRandom(provider, SALT_LEN as u32, salt.as_mut_ptr());
+        CryptReleaseContext(provider, 0);
+        (ok != 0).then_some(salt)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect()
+}
+
+/// Returns true if a settings lock PIN has been configured
+pub fn has_pin() -> bool {
+    !Settings::get().lock_pin_hash.is_empty()
+}
+
+/// Returns true if a PIN is configured and hasn't been entered this session
+/// Must not be called while holding the Settings lock
+pub fn is_locked() -> bool {
+    has_pin() && !*STATE.settings_unlocked.lock().unwrap()
+}
+
+/// Attempts to unlock protected settings with the given PIN
+pub fn try_unlock(pin: &str) -> bool {
+    // Hashing is slow on purpose, the settings aren't held locked meanwhile
+    let saved = Settings::get().lock_pin_hash.clone();
+    let matches = !saved.is_empty() && verify_pin(pin, &saved);
+
+    if matches {
+        *STATE.settings_unlocked.lock().unwrap() = true;
+        log::info!("Protected settings unlocked");
+
+        if !saved.starts_with(HASH_PREFIX) {
+            upgrade_legacy_hash(pin);
+        }
+    } else {
+        log::warn!("Incorrect settings lock PIN entered");
+    }
+    matches
+}
+
+/// Replaces an unsalted hash from an older version with a salted one
+fn upgrade_legacy_hash(pin: &str) {
+    let hash = match hash_pin(pin) {
+        Ok(hash) => hash,
+        Err(e) => {
+            log::warn!("Could not upgrade the settings lock PIN hash: {}", e);
+            return;
+        }
+    };
+
+    let mut settings = Settings::get();
+    settings.lock_pin_hash = hash;
+    if let Err(e) = settings.store(crate::config_path()) {
+        log::error!("Failed to save the upgraded settings lock: {}", e);
+    } else {
+        log::info!("Settings lock PIN hash upgraded");
+    }
+}
+
+/// Re-locks protected settings until the PIN is entered again
+pub fn lock() {
+    *STATE.settings_unlocked.lock().unwrap() = false;
+    log::info!("Protected settings locked");
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
@@ -8,6 +9,15 @@ const CBTS_POINTOFVIEW: u8 = 13;
 const MARKER_STATECHANGE: u8 = 37;
 const COMMANDER_MARKER_VALUE: u8 = 1;
 
+// Local file header signature every .zevtc archive starts with
+const ZIP_MAGIC: [u8; 4] = [0x50, 0x4B, 0x03, 0x04];
+// Start of an uncompressed log
+const EVTC_MAGIC: [u8; 4] = *b"EVTC";
+
+/// Log file endings picked up by scans, longest first so ".evtc.zip" isn't read as ".zip"
+/// Older arcdps versions and other tools write uncompressed .evtc or zipped .evtc.zip logs
+pub const LOG_EXTENSIONS: &[&str] = &[".evtc.zip", ".zevtc", ".evtc"];
+
 // Profession and specialization names to filter out from commander detection
 const PROF_OR_SPEC_NAMES: &[&str] = &[
     "Guardian", "Warrior", "Revenant", "Engineer", "Ranger", "Thief", "Elementalist", "Mesmer", "Necromancer",
@@ -22,48 +32,57 @@ const PROF_OR_SPEC_NAMES: &[&str] = &[
     "Reaper", "Scourge", "Harbinger",
 ];
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MapType {
-    EternalBattlegrounds,
-    GreenAlpineBorderlands,
-    BlueAlpineBorderlands,
-    RedDesertBorderlands,
-    EdgeOfTheMists,
-    ObsidianSanctum,
+    /// A map from the map definitions, holds the map id
+    WvW(u16),
     PvE,
     Unknown,
 }
 
 impl MapType {
     pub fn from_map_id(map_id: u16) -> Self {
-        match map_id {
-            38 => MapType::EternalBattlegrounds,
-            95 => MapType::GreenAlpineBorderlands,
-            96 => MapType::BlueAlpineBorderlands,
-            1099 => MapType::RedDesertBorderlands,
-            968 => MapType::EdgeOfTheMists,
-            899 => MapType::ObsidianSanctum,
-            _ => {
-                if map_id > 0 { MapType::PvE } else { MapType::Unknown }
-            }
+        if crate::maps::find(map_id).is_some() {
+            MapType::WvW(map_id)
+        } else if map_id > 0 {
+            MapType::PvE
+        } else {
+            MapType::Unknown
         }
     }
-    
-    pub fn display_name(&self) -> &'static str {
+
+    /// Short name shown in map badges, e.g. "EBG"
+    pub fn display_name(&self) -> String {
         match self {
-            MapType::EternalBattlegrounds => "EBG",
-            MapType::GreenAlpineBorderlands => "GBL",
-            MapType::BlueAlpineBorderlands => "BBL",
-            MapType::RedDesertBorderlands => "RBL",
-            MapType::EdgeOfTheMists => "EotM", 
-            MapType::ObsidianSanctum => "OS",
-            MapType::PvE => "PvE",
-            MapType::Unknown => "Unknown",
+            MapType::WvW(id) => crate::maps::find(*id)
+                .map(|m| m.abbreviation)
+                .unwrap_or_else(|| id.to_string()),
+            MapType::PvE => "PvE".to_string(),
+            MapType::Unknown => "Unknown".to_string(),
         }
     }
-    
+
+    /// Full map name, e.g. "Eternal Battlegrounds"
+    pub fn full_name(&self) -> String {
+        match self {
+            MapType::WvW(id) => crate::maps::find(*id)
+                .map(|m| m.name)
+                .unwrap_or_else(|| format!("Map {}", id)),
+            _ => self.display_name(),
+        }
+    }
+
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            MapType::WvW(id) => crate::maps::find(*id)
+                .map(|m| m.color)
+                .unwrap_or(crate::maps::FALLBACK_COLOR),
+            _ => crate::maps::FALLBACK_COLOR,
+        }
+    }
+
     pub fn is_wvw(&self) -> bool {
-        !matches!(self, MapType::PvE | MapType::Unknown)
+        matches!(self, MapType::WvW(_))
     }
 }
 
@@ -159,6 +178,16 @@ pub struct LogFile {
     pub map_type: MapType,
     pub recorder: Option<String>,
     pub commander: Option<String>,
+    /// Why the server would reject the file, None if it passed the preflight check
+    pub invalid: Option<String>,
+    /// Bytes sent and total while the file is being uploaded
+    pub upload_progress: Option<(u64, u64)>,
+    /// Duration and player counts from the local parse, None if the log couldn't be parsed
+    pub fight: Option<crate::evtc::FightSummary>,
+    /// Hash of the file contents, recognizes renamed or copied logs, None if the file couldn't be read
+    pub content_hash: Option<String>,
+    /// How long the upload took, set once it succeeded
+    pub upload_timing: Option<crate::upload_stats::UploadTiming>,
 }
 
 /// Parse agents from EVTC data
@@ -277,6 +306,57 @@ fn read_evtc_info_from_bytes(data: &[u8]) -> Option<(u16, MapType, Option<String
     Some((map_id, map_type, recorder, commander))
 }
 
+/// Whether the file has one of the log endings, ignoring case
+pub fn is_log_file(path: &std::path::Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| log_extension(&name.to_string_lossy()).is_some())
+}
+
+/// Whether the log is an uncompressed .evtc
+pub fn is_uncompressed(path: &std::path::Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| log_extension(&name.to_string_lossy()) == Some(".evtc"))
+}
+
+/// The log ending of a filename, e.g. ".zevtc"
+fn log_extension(filename: &str) -> Option<&'static str> {
+    let lower = filename.to_ascii_lowercase();
+    LOG_EXTENSIONS.iter().copied().find(|ext| lower.ends_with(ext))
+}
+
+/// The filename without its log ending
+/// Example: "20241105-143022.evtc.zip" -> "20241105-143022"
+pub fn log_stem(filename: &str) -> &str {
+    match log_extension(filename) {
+        Some(ext) => &filename[..filename.len() - ext.len()],
+        None => filename,
+    }
+}
+
+/// Checks a log is a readable, non-empty zevtc archive (or raw evtc log) before it is uploaded
+/// Returns the reason it isn't, so the log can be flagged instead of failing mid-upload
+pub fn preflight_check(path: &std::path::Path) -> Option<String> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return Some(format!("Can't open file: {}", e)),
+    };
+
+    if file.metadata().map(|m| m.len()).unwrap_or(0) == 0 {
+        return Some("Empty file".to_string());
+    }
+
+    let mut magic = [0u8; 4];
+    if is_uncompressed(path) {
+        if file.read_exact(&mut magic).is_err() || magic != EVTC_MAGIC {
+            return Some("Not a valid evtc log".to_string());
+        }
+    } else if file.read_exact(&mut magic).is_err() || magic != ZIP_MAGIC {
+        return Some("Not a valid zevtc archive".to_string());
+    }
+
+    None
+}
+
 /// Read partial EVTC data (up to max_bytes)
 fn read_evtc_info_partial(file_path: &std::path::Path, max_bytes: usize) -> Option<(u16, MapType, Option<String>, Option<String>)> {
     let mut file = File::open(file_path).ok()?;
@@ -379,7 +459,38 @@ fn read_evtc_info_full(file_path: &std::path::Path) -> Option<(u16, MapType, Opt
     read_evtc_info_from_bytes(&data)
 }
 
+/// Bytes read from each end of a log for its content hash
+const HASH_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Hashes the size and the first and last chunk of the file with xxh3, so scans don't read every log completely
+/// Enough to tell logs apart: the start holds the arcdps build and agents, and the zip header of a .zevtc has
+/// the CRC of the whole log
+pub fn content_hash(path: &std::path::Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let size = file.metadata().ok()?.len();
+
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    hasher.update(&size.to_le_bytes());
+
+    let mut buffer = Vec::with_capacity(HASH_CHUNK_SIZE as usize);
+    file.by_ref().take(HASH_CHUNK_SIZE).read_to_end(&mut buffer).ok()?;
+    hasher.update(&buffer);
+
+    if size > HASH_CHUNK_SIZE {
+        buffer.clear();
+        file.seek(SeekFrom::Start(size.saturating_sub(HASH_CHUNK_SIZE).max(HASH_CHUNK_SIZE))).ok()?;
+        file.read_to_end(&mut buffer).ok()?;
+        hasher.update(&buffer);
+    }
+    Some(format!("{:032x}", hasher.digest128()))
+}
+
 impl LogFile {
+    /// Whether the log's upload is over, uploaded, failed or taken out of the queue
+    pub fn upload_finished(&self) -> bool {
+        self.uploaded || self.status.starts_with("Failed") || self.status == crate::upload::CANCELLED_STATUS
+    }
+
     /// Create LogFile with optimized two-step metadata parsing
     pub fn new_fast(path: PathBuf) -> anyhow::Result<Self> {
         let metadata = std::fs::metadata(&path)?;
@@ -394,6 +505,28 @@ impl LogFile {
             .duration_since(std::time::UNIX_EPOCH)?
             .as_secs();
 
+        // Broken files are kept so they can be flagged in the list, there is nothing to parse
+        if let Some(reason) = preflight_check(&path) {
+            log::warn!("Log failed preflight check {:?}: {}", path, reason);
+            return Ok(Self {
+                path,
+                filename,
+                size: metadata.len(),
+                modified,
+                selected: false,
+                uploaded: false,
+                status: "Ready".to_string(),
+                map_type: MapType::Unknown,
+                recorder: None,
+                commander: None,
+                invalid: Some(reason),
+                upload_progress: None,
+                fight: None,
+                content_hash: None,
+                upload_timing: None,
+            });
+        }
+
         // OPTIMIZATION: Two-step scan like PHP
         // Step 1: Try fast scan (500KB) - gets most metadata quickly
         let (map_type, recorder, commander) = match read_evtc_info_partial(&path, 500_000) {
@@ -432,6 +565,15 @@ impl LogFile {
             }
         };
 
+        // Only WvW logs are listed, parsing anything else would be wasted work
+        let fight = if map_type.is_wvw() {
+            crate::evtc::parse_file(&path).map(|evtc| evtc.summary())
+        } else {
+            None
+        };
+
+        let hash = content_hash(&path);
+
         Ok(Self {
             path,
             filename,
@@ -443,6 +585,11 @@ impl LogFile {
             map_type,
             recorder,
             commander,
+            invalid: None,
+            upload_progress: None,
+            fight,
+            content_hash: hash,
+            upload_timing: None,
         })
     }
 }
\ No newline at end of file
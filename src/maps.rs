@@ -0,0 +1,118 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Badge color for maps without a definition
+pub const FALLBACK_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+
+/// A WvW map the addon recognizes in logs
+#[derive(Debug, Clone, Deserialize)]
+pub struct MapDefinition {
+    pub id: u16,
+    pub name: String,
+    pub abbreviation: String,
+    #[serde(default = "default_color")]
+    pub color: [f32; 4],
+}
+
+fn default_color() -> [f32; 4] {
+    FALLBACK_COLOR
+}
+
+/// Maps known out of the box: id, name, abbreviation, badge color
+const BUILTIN_MAPS: &[(u16, &str, &str, [f32; 4])] = &[
+    (38, "Eternal Battlegrounds", "EBG", [0.8, 0.6, 0.2, 1.0]),
+    (95, "Green Alpine Borderlands", "GBL", [0.2, 0.8, 0.3, 1.0]),
+    (96, "Blue Alpine Borderlands", "BBL", [0.3, 0.5, 1.0, 1.0]),
+    (1099, "Red Desert Borderlands", "RBL", [1.0, 0.3, 0.3, 1.0]),
+    (968, "Edge of the Mists", "EotM", [0.6, 0.3, 0.8, 1.0]),
+    (899, "Obsidian Sanctum", "OS", [0.4, 0.4, 0.4, 1.0]),
+];
+
+/// Definitions read from maps.json, these win over the built-in ones with the same id
+static CUSTOM_MAPS: RwLock<Vec<MapDefinition>> = RwLock::new(Vec::new());
+
+/// Looks up the definition of a map id
+pub fn find(map_id: u16) -> Option<MapDefinition> {
+    if let Some(custom) = CUSTOM_MAPS.read().unwrap().iter().find(|m| m.id == map_id) {
+        return Some(custom.clone());
+    }
+
+    BUILTIN_MAPS
+        .iter()
+        .find(|(id, ..)| *id == map_id)
+        .map(|(id, name, abbreviation, color)| MapDefinition {
+            id: *id,
+            name: name.to_string(),
+            abbreviation: abbreviation.to_string(),
+            color: *color,
+        })
+}
+
+/// Every known map, custom definitions first and built-ins they don't replace after
+pub fn all() -> Vec<MapDefinition> {
+    let mut maps = CUSTOM_MAPS.read().unwrap().clone();
+    for (id, ..) in BUILTIN_MAPS {
+        if !maps.iter().any(|m| m.id == *id) {
+            maps.extend(find(*id));
+        }
+    }
+    maps
+}
+
+/// Loads extra or renamed maps from a JSON list next to the settings
+/// A missing file is fine, the built-in table is used on its own then
+pub fn from_path(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(path)?;
+    let maps: Vec<MapDefinition> = serde_json::from_str(&contents)?;
+    log::info!("Loaded {} map definition(s) from {:?}", maps.len(), path);
+    *CUSTOM_MAPS.write().unwrap() = maps;
+    Ok(())
+}
+
+/// Server region, WvW matchups reset at a different time in each
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WvwRegion {
+    Na,
+    Eu,
+}
+
+impl WvwRegion {
+    pub const ALL: [WvwRegion; 2] = [WvwRegion::Na, WvwRegion::Eu];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WvwRegion::Na => "NA",
+            WvwRegion::Eu => "EU",
+        }
+    }
+
+    /// When the weekly reset happens, e.g. "Friday 18:00 UTC"
+    pub fn reset_label(self) -> &'static str {
+        match self {
+            WvwRegion::Na => "Saturday 02:00 UTC",
+            WvwRegion::Eu => "Friday 18:00 UTC",
+        }
+    }
+
+    /// Unix time of the last matchup reset at or before `now`
+    pub fn last_reset(self, now: u64) -> u64 {
+        const WEEK: u64 = 7 * 24 * 60 * 60;
+        // Seconds from a Thursday midnight UTC to the reset, the unix epoch was a Thursday
+        let offset = match self {
+            WvwRegion::Na => 2 * 24 * 60 * 60 + 2 * 60 * 60,
+            WvwRegion::Eu => 24 * 60 * 60 + 18 * 60 * 60,
+        };
+        if now < offset {
+            return 0;
+        }
+        now - (now - offset) % WEEK
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{create_dir_all, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use crate::evtc::FightSummary;
+use crate::logfile::{LogFile, MapType};
+
+/// Logs kept in the cache, the oldest logs are dropped first once it is full
+const MAX_CACHED_LOGS: usize = 20_000;
+
+/// What parsing a log found, reused while the file's size and modified time are unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedLog {
+    size: u64,
+    modified: u64,
+    map_type: MapType,
+    recorder: Option<String>,
+    commander: Option<String>,
+    invalid: Option<String>,
+    fight: Option<FightSummary>,
+    content_hash: Option<String>,
+}
+
+/// Parsed logs by path, kept on disk so scans after a restart don't parse everything again
+static CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedLog>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+/// Set when the cache changed since it was last written
+static DIRTY: AtomicBool = AtomicBool::new(false);
+
+/// The cached log for the file, None if it was never parsed or changed since
+pub fn lookup(path: &Path, size: u64, modified: u64) -> Option<LogFile> {
+    let cache = CACHE.lock().unwrap();
+    let cached = cache.get(path).filter(|c| c.size == size && c.modified == modified)?;
+    let filename = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    Some(LogFile {
+        path: path.to_path_buf(),
+        filename,
+        size,
+        modified,
+        selected: false,
+        uploaded: false,
+        status: "Ready".to_string(),
+        map_type: cached.map_type.clone(),
+        recorder: cached.recorder.clone(),
+        commander: cached.commander.clone(),
+        invalid: cached.invalid.clone(),
+        upload_progress: None,
+        fight: cached.fight.clone(),
+        content_hash: cached.content_hash.clone(),
+        upload_timing: None,
+    })
+}
+
+pub fn contains(path: &Path, size: u64, modified: u64) -> bool {
+    CACHE
+        .lock()
+        .unwrap()
+        .get(path)
+        .is_some_and(|c| c.size == size && c.modified == modified)
+}
+
+pub fn insert(log: &LogFile) {
+    let mut cache = CACHE.lock().unwrap();
+    cache.insert(
+        log.path.clone(),
+        CachedLog {
+            size: log.size,
+            modified: log.modified,
+            map_type: log.map_type.clone(),
+            recorder: log.recorder.clone(),
+            commander: log.commander.clone(),
+            invalid: log.invalid.clone(),
+            fight: log.fight.clone(),
+            content_hash: log.content_hash.clone(),
+        },
+    );
+
+    if cache.len() > MAX_CACHED_LOGS {
+        // Drop a tenth at once so a full cache isn't sorted on every insert
+        let mut by_age: Vec<(u64, PathBuf)> = cache.iter().map(|(p, c)| (c.modified, p.clone())).collect();
+        by_age.sort();
+        for (_, path) in by_age.into_iter().take(MAX_CACHED_LOGS / 10) {
+            cache.remove(&path);
+        }
+    }
+    DIRTY.store(true, Ordering::Relaxed);
+}
+
+/// Moves cached entries along with files that were moved on disk
+pub fn relocate(moves: &[(PathBuf, PathBuf)]) {
+    let mut cache = CACHE.lock().unwrap();
+    for (from, to) in moves {
+        if let Some(cached) = cache.remove(from) {
+            cache.insert(to.clone(), cached);
+        }
+    }
+    DIRTY.store(true, Ordering::Relaxed);
+}
+
+pub fn from_path(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if path.exists() {
+        let contents = std::fs::read_to_string(path)?;
+        let cached: HashMap<PathBuf, CachedLog> = serde_json::from_str(&contents)?;
+        log::info!("Loaded cached metadata for {} logs", cached.len());
+        *CACHE.lock().unwrap() = cached;
+    }
+    Ok(())
+}
+
+/// Writes the cache to disk when it changed since the last write
+pub fn store_if_changed(path: impl AsRef<Path>) -> Result<()> {
+    if !DIRTY.swap(false, Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    let cache = CACHE.lock().unwrap().clone();
+    let result = write(path.as_ref(), &cache);
+    if result.is_err() {
+        // Tried again with the next write
+        DIRTY.store(true, Ordering::Relaxed);
+    } else {
+        log::debug!("Saved cached metadata for {} logs", cache.len());
+    }
+    result
+}
+
+fn write(path: &Path, cache: &HashMap<PathBuf, CachedLog>) -> Result<()> {
+    let prefix = path.parent().unwrap();
+    create_dir_all(prefix)?;
+    let mut file = File::options()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    serde_json::to_writer(&mut file, cache)?;
+    Ok(())
+}
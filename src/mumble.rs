@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use nexus::data_link::{get_mumble_link, mumble::UiState};
+
+use crate::settings::Settings;
+use crate::state::STATE;
+
+static IN_COMBAT: AtomicBool = AtomicBool::new(false);
+static IN_WVW: AtomicBool = AtomicBool::new(false);
+
+/// Mumble map types belonging to World vs. World
+/// (Eternal Battlegrounds, the three borderlands, Obsidian Sanctum, Edge of the Mists, Armistice Bastion)
+const WVW_MAP_TYPES: [u32; 7] = [9, 10, 11, 12, 14, 15, 18];
+
+/// Refreshes the cached combat and map state from the Mumble link
+/// This should be called every frame from the render function
+pub fn update() {
+    let Some(link) = get_mumble_link() else {
+        return;
+    };
+
+    let in_combat = link.context.ui_state.contains(UiState::IS_IN_COMBAT);
+    let in_wvw = WVW_MAP_TYPES.contains(&link.context.map_type);
+
+    if IN_COMBAT.swap(in_combat, Ordering::Relaxed) != in_combat {
+        log::debug!("Combat state changed: {}", in_combat);
+    }
+    IN_WVW.store(in_wvw, Ordering::Relaxed);
+}
+
+/// Returns true if the player is currently in combat
+pub fn is_in_combat() -> bool {
+    IN_COMBAT.load(Ordering::Relaxed)
+}
+
+/// Returns true if the player is currently on a WvW map
+pub fn is_in_wvw() -> bool {
+    IN_WVW.load(Ordering::Relaxed)
+}
+
+/// Returns true if heavy background work (scans, uploads) should wait for combat to end
+pub fn should_defer_work() -> bool {
+    is_in_combat() && Settings::get().defer_work_in_combat
+}
+
+/// Blocks the calling worker thread until the player leaves combat
+/// Returns early if the addon is unloading so the thread can be joined
+pub fn wait_until_out_of_combat(cancel: &crate::cancel::CancelToken) {
+    if !should_defer_work() {
+        return;
+    }
+
+    log::info!("In combat - deferring background work");
+    *STATE.work_deferred.lock().unwrap() = true;
+
+    while should_defer_work() {
+        if !cancel.sleep(Duration::from_millis(500)) {
+            break;
+        }
+    }
+
+    *STATE.work_deferred.lock().unwrap() = false;
+    log::info!("Out of combat - resuming background work");
+}
@@ -0,0 +1,203 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, File};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::cancel::CancelToken;
+use crate::webhooks::ReportEmbed;
+
+/// How often queued posts are retried in the background
+const RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Background retries stop after this many attempts, the retry button still works
+pub const MAX_AUTO_ATTEMPTS: u32 = 10;
+
+/// A Discord post that could not be delivered yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingPost {
+    pub webhook_url: String,
+    pub message: String,
+    pub created: u64, // Unix timestamp
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub last_error: String,
+    #[serde(default)]
+    pub embed: Option<ReportEmbed>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingPosts {
+    #[serde(default)]
+    pub posts: Vec<PendingPost>,
+}
+
+static PENDING_POSTS: Mutex<PendingPosts> = Mutex::new(PendingPosts { posts: Vec::new() });
+
+/// Set while a retry pass runs, so the button and the background thread don't post twice
+static RETRYING: AtomicBool = AtomicBool::new(false);
+
+impl PendingPosts {
+    pub fn get() -> MutexGuard<'static, Self> {
+        PENDING_POSTS.lock().unwrap()
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            let pending: Self = serde_json::from_str(&contents)?;
+            if !pending.posts.is_empty() {
+                log::info!("Loaded {} pending Discord post(s)", pending.posts.len());
+            }
+            *PENDING_POSTS.lock().unwrap() = pending;
+        }
+        Ok(())
+    }
+
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let prefix = path.parent().unwrap();
+        create_dir_all(prefix)?;
+        let mut file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, post: &PendingPost) {
+        self.posts.retain(|p| p != post);
+    }
+}
+
+/// Adds a failed post to the queue and saves it
+fn queue(webhook_url: &str, message: &str, embed: Option<ReportEmbed>, error: &str) {
+    let mut pending = PendingPosts::get();
+    pending.posts.push(PendingPost {
+        webhook_url: webhook_url.to_string(),
+        message: message.to_string(),
+        created: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        attempts: 1,
+        last_error: error.to_string(),
+        embed,
+    });
+    log::info!("Queued failed Discord post for retry ({} pending)", pending.posts.len());
+    save(&pending);
+}
+
+fn save(pending: &PendingPosts) {
+    if let Err(e) = pending.store(crate::pending_posts_path()) {
+        log::error!("Failed to save pending Discord posts: {}", e);
+    }
+}
+
+/// Sends a message to Discord, queueing it for a later retry when it can't be delivered
+/// Invalid webhooks and posts cancelled by the user are not queued
+pub fn send_or_queue(
+    webhook_url: &str,
+    message: &str,
+    embed: Option<ReportEmbed>,
+    cancel: &CancelToken,
+    on_rate_limited: impl Fn(Duration),
+) -> Result<()> {
+    crate::consent::require()?;
+    crate::webhooks::validate_target(webhook_url)?;
+
+    let result = crate::webhooks::send_message(webhook_url, message, embed.as_ref(), cancel, on_rate_limited);
+    if let Err(e) = &result {
+        // Unloading also cancels, those posts are picked up again next time
+        let cancelled_by_user = cancel.is_cancelled() && !crate::cancel::is_shutting_down();
+        if !cancelled_by_user && !message.trim().is_empty() {
+            queue(webhook_url, message, embed, &e.to_string());
+        }
+    }
+    result
+}
+
+/// Whether a retry pass is running right now
+pub fn is_retrying() -> bool {
+    RETRYING.load(Ordering::Relaxed)
+}
+
+/// Tries every queued post once
+/// `only_auto` skips posts that used up their background attempts
+fn retry_all(cancel: &CancelToken, only_auto: bool) {
+    if RETRYING.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    let posts = PendingPosts::get().posts.clone();
+    let mut sent = 0;
+
+    for post in posts {
+        if cancel.is_cancelled() {
+            break;
+        }
+        if only_auto && post.attempts >= MAX_AUTO_ATTEMPTS {
+            continue;
+        }
+
+        let result =
+            crate::webhooks::send_message(&post.webhook_url, &post.message, post.embed.as_ref(), cancel, |_| {});
+
+        let mut pending = PendingPosts::get();
+        match result {
+            Ok(_) => {
+                sent += 1;
+                pending.remove(&post);
+            }
+            Err(e) => {
+                log::warn!("Retrying Discord post failed: {}", e);
+                if let Some(entry) = pending.posts.iter_mut().find(|p| **p == post) {
+                    entry.attempts += 1;
+                    entry.last_error = e.to_string();
+                }
+            }
+        }
+        save(&pending);
+    }
+
+    RETRYING.store(false, Ordering::Relaxed);
+
+    if sent > 0 {
+        log::info!(
+            "Sent {} queued Discord post(s), {} still pending",
+            sent,
+            PendingPosts::get().posts.len()
+        );
+    }
+}
+
+/// Retries every queued post now, including those out of background attempts
+pub fn retry_now() {
+    crate::threads::spawn("webhook-retry", || {
+        let _activity = crate::threads::activity("Retrying webhook posts");
+        retry_all(&CancelToken::new(), false);
+    });
+}
+
+/// Starts the thread that retries queued posts every few minutes
+pub fn start() {
+    crate::threads::spawn_tracked("webhook-retry-timer", || {
+        // Never cancelled itself, only stops on unload
+        let idle = CancelToken::new();
+        while idle.sleep(RETRY_INTERVAL) {
+            if PendingPosts::get().posts.is_empty()
+                || crate::throttle::is_throttled()
+                || !crate::consent::is_granted()
+            {
+                continue;
+            }
+            retry_all(&idle, true);
+        }
+    });
+}
@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+use crate::state::STATE;
+use crate::webhooks::WebhookSettings;
+
+/// Everything that changes when logging for a different guild, switched in one go
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default, serialize_with = "crate::settings::protected::serialize", deserialize_with = "crate::settings::protected::deserialize")]
+    pub history_token: String,
+    #[serde(default, serialize_with = "crate::settings::protected::serialize", deserialize_with = "crate::settings::protected::deserialize")]
+    pub dps_report_token: String,
+    #[serde(default)]
+    pub guild_name: String,
+    /// Name of the saved webhook reports and announcements go to, empty leaves it as it is
+    #[serde(default)]
+    pub webhook: String,
+    #[serde(default)]
+    pub min_fight_seconds: u32,
+    #[serde(default)]
+    pub min_squad_size: u32,
+    #[serde(default)]
+    pub min_log_size_kb: u32,
+    /// Map ids unticked in the map filter
+    #[serde(default)]
+    pub hidden_maps: Vec<u16>,
+}
+
+impl Profile {
+    /// The current tokens, guild, webhook and filters under the given name
+    fn capture(name: &str) -> Self {
+        let settings = Settings::get();
+        let mut profile = Profile {
+            name: name.to_string(),
+            history_token: settings.history_token.clone(),
+            dps_report_token: settings.dps_report_token.clone(),
+            guild_name: settings.guild_name.clone(),
+            webhook: String::new(),
+            min_fight_seconds: settings.min_fight_seconds,
+            min_squad_size: settings.min_squad_size,
+            min_log_size_kb: settings.min_log_size_kb,
+            hidden_maps: STATE.hidden_maps.lock().unwrap().clone(),
+        };
+        drop(settings);
+
+        let webhooks = WebhookSettings::get();
+        profile.webhook = if !webhooks.announce_webhook.is_empty() {
+            webhooks.announce_webhook.clone()
+        } else {
+            webhooks
+                .saved_webhooks
+                .iter()
+                .find(|w| w.url == webhooks.last_webhook_url)
+                .map(|w| w.name.clone())
+                .unwrap_or_default()
+        };
+        profile
+    }
+}
+
+/// Saves the current setup as a profile, replacing one with the same name
+pub fn save_current(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+
+    let profile = Profile::capture(name);
+    let mut settings = Settings::get();
+    match settings.profiles.iter_mut().find(|p| p.name == name) {
+        Some(existing) => *existing = profile,
+        None => settings.profiles.push(profile),
+    }
+    settings.active_profile = name.to_string();
+    settings.store(crate::config_path()).map_err(|e| e.to_string())?;
+    log::info!("Saved profile '{}'", name);
+    Ok(())
+}
+
+/// Switches to a saved profile and rechecks its token
+pub fn apply(name: &str) -> Result<()> {
+    let mut settings = Settings::get();
+    let profile = settings
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .cloned()
+        .ok_or_else(|| anyhow!("Profile '{}' doesn't exist", name))?;
+
+    settings.history_token = profile.history_token.clone();
+    settings.dps_report_token = profile.dps_report_token.clone();
+    settings.guild_name = profile.guild_name.clone();
+    settings.min_fight_seconds = profile.min_fight_seconds;
+    settings.min_squad_size = profile.min_squad_size;
+    settings.min_log_size_kb = profile.min_log_size_kb;
+    settings.active_profile = profile.name.clone();
+    settings.store(crate::config_path())?;
+    drop(settings);
+
+    *STATE.hidden_maps.lock().unwrap() = profile.hidden_maps.clone();
+
+    if !profile.webhook.is_empty() {
+        let mut webhooks = WebhookSettings::get();
+        if webhooks.select_default(&profile.webhook) {
+            webhooks.store(crate::webhooks_path())?;
+        } else {
+            log::warn!("Webhook '{}' of profile '{}' is not saved anymore", profile.webhook, name);
+        }
+    }
+
+    log::info!("Switched to profile '{}'", name);
+    crate::tokens::check_saved_token();
+    Ok(())
+}
+
+/// Removes a saved profile, the current tokens and guild stay as they are
+pub fn delete(name: &str) -> Result<()> {
+    let mut settings = Settings::get();
+    settings.profiles.retain(|p| p.name != name);
+    if settings.active_profile == name {
+        settings.active_profile.clear();
+    }
+    settings.store(crate::config_path())?;
+    log::info!("Deleted profile '{}'", name);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::logfile::LogFile;
+use crate::settings::Settings;
+use crate::state::{ProcessingState, STATE};
+
+/// Uploads the logs of an earlier session again as a new session
+/// Used to get updated reports after the parser backend improved
+pub fn start_reparse(session_id: String, log_files: Vec<String>) {
+    if *STATE.processing_state.lock().unwrap() != ProcessingState::Idle {
+        *STATE.reparse_message.lock().unwrap() =
+            "Finish or cancel the current upload first".to_string();
+        return;
+    }
+
+    let log_directory = Settings::get().log_directory.clone();
+
+    *STATE.reparse_busy.lock().unwrap() = true;
+    STATE.reparse_message.lock().unwrap().clear();
+
+    crate::threads::spawn("reparse", move || {
+        let _activity = crate::threads::activity("Reparsing session");
+        crate::throttle::apply_background_priority();
+
+        let wanted: HashSet<&str> = log_files.iter().map(String::as_str).collect();
+        let mut paths = Vec::new();
+        find_log_paths(Path::new(&log_directory), &wanted, &mut paths);
+
+        let mut logs: Vec<LogFile> = paths
+            .into_iter()
+            .filter_map(|path| match LogFile::new_fast(path.clone()) {
+                Ok(log) => Some(log),
+                Err(e) => {
+                    log::warn!("Failed to read {:?} for re-parse: {}", path, e);
+                    None
+                }
+            })
+            .collect();
+
+        *STATE.reparse_busy.lock().unwrap() = false;
+
+        if logs.is_empty() {
+            log::warn!("None of the logs of session {} were found", session_id);
+            *STATE.reparse_message.lock().unwrap() =
+                "None of the original logs were found in the log directory".to_string();
+            return;
+        }
+
+        let missing = log_files.len().saturating_sub(logs.len());
+        if missing > 0 {
+            *STATE.reparse_message.lock().unwrap() = format!(
+                "{} not found, re-parsing the rest",
+                crate::formatting::plural(missing as u64, "log", "logs")
+            );
+        }
+
+        log::info!(
+            "Re-parsing {} log(s) from session {}",
+            logs.len(),
+            session_id
+        );
+
+        logs.sort_by(|a, b| a.filename.cmp(&b.filename));
+        for log in logs.iter_mut() {
+            log.selected = true;
+        }
+
+        // Start from a clean session so nothing of the current one is mixed in
+        crate::ui::upload_progress::reset_upload_state();
+        *STATE.logs.lock().unwrap() = logs;
+
+        *STATE.show_settings.lock().unwrap() = false;
+        *STATE.show_token_input.lock().unwrap() = false;
+        *STATE.show_log_selection.lock().unwrap() = false;
+        *STATE.show_upload_progress.lock().unwrap() = true;
+
+        crate::ui::log_selection::start_upload_process();
+    });
+}
+
+/// Recursively collects the paths of the wanted log files below a directory
+pub fn find_log_paths(dir: &Path, wanted: &HashSet<&str>, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            find_log_paths(&path, wanted, paths);
+        } else if let Some(name) = path.file_name() {
+            // A log copied into several folders is only uploaded once
+            let already_found = paths.iter().any(|p| p.file_name() == Some(name));
+            if wanted.contains(name.to_string_lossy().as_ref()) && !already_found {
+                paths.push(path);
+            }
+        }
+    }
+}
@@ -10,11 +10,175 @@ pub struct ReportEntry {
     pub timestamp: u64,
     pub main_report_url: String,
     pub legacy_report_url: Option<String>,
+    /// Short hash of the history token the session was created with, see `tokens::token_key`
+    /// Empty for entries saved before this was tracked
+    #[serde(default)]
+    pub token_key: String,
+    /// Log filenames uploaded in the session (empty for entries saved before this was tracked)
+    #[serde(default)]
+    pub log_files: Vec<String>,
+    /// Proves the session is ours when asking the server to delete it (empty for older entries)
+    #[serde(default, serialize_with = "crate::settings::protected::serialize", deserialize_with = "crate::settings::protected::deserialize")]
+    pub ownership_token: String,
+    /// Shown above all other sessions in the history tab
+    #[serde(default)]
+    pub pinned: bool,
+    /// Kept when the history is cleared
+    #[serde(default)]
+    pub favorite: bool,
+    /// One permalink per log for sessions uploaded to dps.report, empty for parser sessions
+    #[serde(default)]
+    pub dps_report_urls: Vec<String>,
+    /// Name the user gave the session, empty until it is renamed
+    #[serde(default)]
+    pub label: String,
+    /// What the session covered, None for entries saved before this was tracked
+    #[serde(default)]
+    pub details: Option<SessionDetails>,
+    /// JSON and CSV files the server made alongside the HTML reports
+    #[serde(default)]
+    pub artifacts: Vec<ReportArtifact>,
+}
+
+/// A file the server produced besides the HTML reports, e.g. the parsed fights as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportArtifact {
+    pub name: String,
+    pub url: String,
+}
+
+/// Logs on one map in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapCount {
+    pub map: String,
+    pub logs: usize,
+}
+
+/// Context about a session shown in the history tab
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionDetails {
+    pub log_count: usize,
+    pub total_bytes: u64,
+    /// Most logs first
+    #[serde(default)]
+    pub maps: Vec<MapCount>,
+    #[serde(default)]
+    pub guild: String,
+    /// Name the history token was saved under, empty for unsaved tokens
+    #[serde(default)]
+    pub token_name: String,
+    /// Largest squad across the session's fights, 0 when none of the logs could be parsed
+    #[serde(default)]
+    pub squad_size: usize,
+    /// Endpoint the session was processed on when it was overridden for this session, empty otherwise
+    #[serde(default)]
+    pub endpoint_override: String,
+}
+
+impl SessionDetails {
+    /// Gathers the details of a session from the listed logs and the current settings
+    pub fn collect(log_files: &[String], history_token: &str) -> Self {
+        let mut total_bytes = 0;
+        let mut squad_size = 0;
+        let mut maps: Vec<MapCount> = Vec::new();
+        for log in crate::state::STATE.logs.lock().unwrap().iter() {
+            if !log_files.contains(&log.filename) {
+                continue;
+            }
+            total_bytes += log.size;
+            if let Some(ref fight) = log.fight {
+                squad_size = squad_size.max(fight.squad_size);
+            }
+            let map = log.map_type.display_name();
+            match maps.iter_mut().find(|m| m.map == map) {
+                Some(count) => count.logs += 1,
+                None => maps.push(MapCount { map, logs: 1 }),
+            }
+        }
+        maps.sort_by(|a, b| b.logs.cmp(&a.logs));
+
+        let settings = crate::settings::Settings::get();
+        let token_name = settings
+            .saved_tokens
+            .iter()
+            .find(|t| !history_token.is_empty() && t.token == history_token)
+            .map(|t| t.name.clone())
+            .unwrap_or_default();
+
+        Self {
+            log_count: log_files.len(),
+            total_bytes,
+            maps,
+            guild: settings.guild_name.clone(),
+            token_name,
+            squad_size,
+            endpoint_override: crate::state::STATE.endpoint_override.lock().unwrap().clone(),
+        }
+    }
+
+    /// One line summary, e.g. "5 logs, 42.0 MB - EBG 3, RBL 2"
+    pub fn summary(&self) -> String {
+        let mut summary = crate::formatting::plural(self.log_count as u64, "log", "logs");
+        if self.total_bytes > 0 {
+            summary.push_str(&format!(", {}", crate::formatting::format_size(self.total_bytes)));
+        }
+        if !self.maps.is_empty() {
+            let maps: Vec<String> = self.maps.iter().map(|m| format!("{} {}", m.map, m.logs)).collect();
+            summary.push_str(&format!(" - {}", maps.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Number of history changes kept in the changelog
+const MAX_CHANGELOG_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HistoryAction {
+    Added,
+    Deleted,
+    Cleared,
+    Migrated,
+    Renamed,
+}
+
+impl HistoryAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryAction::Added => "Added",
+            HistoryAction::Deleted => "Deleted",
+            HistoryAction::Cleared => "Cleared",
+            HistoryAction::Migrated => "Migrated",
+            HistoryAction::Renamed => "Renamed",
+        }
+    }
+}
+
+/// A single modification of the report history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryChange {
+    pub timestamp: u64,
+    pub action: HistoryAction,
+    /// Session the change applies to (empty for changes covering several sessions)
+    pub session_id: String,
+    pub detail: String,
+}
+
+/// A report history entry as older versions stored it in settings.json
+#[derive(Deserialize)]
+struct LegacyReportEntry {
+    session_id: String,
+    timestamp: u64,
+    main_report_url: String,
+    legacy_report_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ReportHistory {
     pub reports: Vec<ReportEntry>,
+    /// Recent modifications, oldest first
+    #[serde(default)]
+    pub changelog: Vec<HistoryChange>,
 }
 
 impl ReportHistory {
@@ -23,31 +187,218 @@ impl ReportHistory {
     }
 
     /// Add a new report session with main and optional legacy URLs
-    pub fn add_report(
-        &mut self,
-        session_id: String,
-        timestamp: u64,
-        main_url: String,
-        legacy_url: Option<String>,
-    ) {
-        self.reports.push(ReportEntry {
-            session_id,
-            timestamp,
-            main_report_url: main_url,
-            legacy_report_url: legacy_url,
-        });
+    pub fn add_report(&mut self, entry: ReportEntry) {
+        let mut detail = if entry.legacy_report_url.is_some() {
+            "Main and legacy report".to_string()
+        } else {
+            "Main report".to_string()
+        };
+        if let Some(ref details) = entry.details {
+            detail.push_str(&format!(", {}", details.summary()));
+        }
+        self.record_change(HistoryAction::Added, entry.session_id.clone(), detail);
+
+        self.reports.push(entry);
     }
 
     /// Remove a report by index
     pub fn remove_report(&mut self, index: usize) {
         if index < self.reports.len() {
-            self.reports.remove(index);
+            let removed = self.reports.remove(index);
+            let detail = format!(
+                "Parsed {}",
+                crate::formatting::format_report_timestamp(removed.timestamp)
+            );
+            self.record_change(HistoryAction::Deleted, removed.session_id, detail);
         }
     }
 
-    /// Clear all reports
+    /// Clear all reports except favorites
     pub fn clear(&mut self) {
-        self.reports.clear();
+        let count = self.reports.len();
+        self.reports.retain(|r| r.favorite);
+        let mut detail = crate::formatting::plural(
+            (count - self.reports.len()) as u64,
+            "session",
+            "sessions",
+        );
+        if !self.reports.is_empty() {
+            detail.push_str(&format!(", kept {} favorite(s)", self.reports.len()));
+        }
+        self.record_change(HistoryAction::Cleared, String::new(), detail);
+    }
+
+    /// Pins or unpins a session, returns false if it isn't in the history
+    pub fn set_pinned(&mut self, session_id: &str, pinned: bool) -> bool {
+        match self.reports.iter_mut().find(|r| r.session_id == session_id) {
+            Some(entry) => {
+                entry.pinned = pinned;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Marks or unmarks a session as favorite, returns false if it isn't in the history
+    pub fn set_favorite(&mut self, session_id: &str, favorite: bool) -> bool {
+        match self.reports.iter_mut().find(|r| r.session_id == session_id) {
+            Some(entry) => {
+                entry.favorite = favorite;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gives a session a name, an empty label removes it, returns false if it isn't in the history
+    pub fn set_label(&mut self, session_id: &str, label: &str) -> bool {
+        let Some(entry) = self.reports.iter_mut().find(|r| r.session_id == session_id) else {
+            return false;
+        };
+        let label = label.trim().to_string();
+        if entry.label == label {
+            return true;
+        }
+        entry.label = label.clone();
+
+        let detail = if label.is_empty() {
+            "Name removed".to_string()
+        } else {
+            format!("Named \"{}\"", label)
+        };
+        self.record_change(HistoryAction::Renamed, session_id.to_string(), detail);
+        true
+    }
+
+    /// Every session as CSV, oldest first
+    pub fn to_csv(&self) -> String {
+        use crate::formatting::csv_field;
+
+        let mut csv = String::from(
+            "date,session_id,name,main_report_url,legacy_report_url,dps_report_urls,logs,size_mb,maps,guild,token,pinned,favorite\n",
+        );
+        let mut reports: Vec<&ReportEntry> = self.reports.iter().collect();
+        reports.sort_by_key(|r| r.timestamp);
+
+        for report in reports {
+            let date = chrono::DateTime::from_timestamp(report.timestamp as i64, 0)
+                .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            let details = report.details.clone().unwrap_or_default();
+            let log_count = if report.details.is_some() { details.log_count } else { report.log_files.len() };
+            let maps: Vec<String> = details.maps.iter().map(|m| format!("{} {}", m.map, m.logs)).collect();
+
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.2},{},{},{},{},{}\n",
+                date,
+                csv_field(&report.session_id),
+                csv_field(&report.label),
+                csv_field(&report.main_report_url),
+                csv_field(report.legacy_report_url.as_deref().unwrap_or_default()),
+                csv_field(&report.dps_report_urls.join(" ")),
+                log_count,
+                details.total_bytes as f64 / 1024.0 / 1024.0,
+                csv_field(&maps.join("; ")),
+                csv_field(&details.guild),
+                csv_field(&details.token_name),
+                report.pinned,
+                report.favorite
+            ));
+        }
+        csv
+    }
+
+    /// Appends an entry to the changelog, dropping the oldest once it is full
+    fn record_change(&mut self, action: HistoryAction, session_id: String, detail: String) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        self.changelog.push(HistoryChange {
+            timestamp,
+            action,
+            session_id,
+            detail,
+        });
+
+        if self.changelog.len() > MAX_CHANGELOG_ENTRIES {
+            let excess = self.changelog.len() - MAX_CHANGELOG_ENTRIES;
+            self.changelog.drain(..excess);
+        }
+    }
+
+    /// Moves report history stored inside settings.json by older versions into this history
+    /// Entries already present (same session and main URL) are skipped
+    /// Returns the number of entries migrated, the caller re-saves settings to drop the old key
+    pub fn migrate_from_settings(&mut self, settings_path: impl AsRef<Path>) -> Result<usize> {
+        let settings_path = settings_path.as_ref();
+        if !settings_path.exists() {
+            return Ok(0);
+        }
+
+        let contents = std::fs::read_to_string(settings_path)?;
+        let value: serde_json::Value = serde_json::from_str(&contents)?;
+        let Some(legacy_entries) = value.get("report_history").and_then(|v| v.as_array()) else {
+            return Ok(0);
+        };
+
+        let mut migrated = 0;
+        for legacy in legacy_entries.iter() {
+            let Some(entry) = Self::parse_legacy_entry(legacy) else {
+                log::warn!("Skipping unreadable legacy report history entry: {}", legacy);
+                continue;
+            };
+
+            let exists = self.reports.iter().any(|r| {
+                r.session_id == entry.session_id && r.main_report_url == entry.main_report_url
+            });
+            if !exists {
+                self.reports.push(entry);
+                migrated += 1;
+            }
+        }
+
+        if migrated > 0 {
+            // Migrated entries are older than most of the history, keep it in upload order
+            self.reports.sort_by_key(|r| r.timestamp);
+            self.record_change(
+                HistoryAction::Migrated,
+                String::new(),
+                format!(
+                    "{} from settings.json",
+                    crate::formatting::plural(migrated as u64, "session", "sessions")
+                ),
+            );
+        }
+
+        log::info!(
+            "Migrated {} of {} legacy report history entries from settings",
+            migrated,
+            legacy_entries.len()
+        );
+        Ok(migrated)
+    }
+
+    /// Reads a single entry of the old settings.json history format
+    fn parse_legacy_entry(legacy: &serde_json::Value) -> Option<ReportEntry> {
+        let legacy: LegacyReportEntry = serde_json::from_value(legacy.clone()).ok()?;
+
+        Some(ReportEntry {
+            session_id: legacy.session_id,
+            timestamp: legacy.timestamp,
+            main_report_url: legacy.main_report_url,
+            legacy_report_url: legacy.legacy_report_url,
+            token_key: String::new(),
+            log_files: Vec::new(),
+            ownership_token: String::new(),
+            pinned: false,
+            favorite: false,
+            dps_report_urls: Vec::new(),
+            label: String::new(),
+            details: None,
+            artifacts: Vec::new(),
+        })
     }
 
     /// Load from file
@@ -55,7 +406,9 @@ impl ReportHistory {
         let path = path.as_ref();
         if path.exists() {
             let contents = std::fs::read_to_string(path)?;
-            let history: Self = serde_json::from_str(&contents)?;
+            let mut value: serde_json::Value = serde_json::from_str(&contents)?;
+            migrate_token_keys(&mut value);
+            let history: Self = serde_json::from_value(value)?;
             let count = history.reports.len();
             *REPORT_HISTORY.lock().unwrap() = history;
             log::info!("Loaded {} reports from history", count);
@@ -81,6 +434,108 @@ impl ReportHistory {
     }
 }
 
+/// Older files kept each session's whole history token, only a key of it is kept now
+fn migrate_token_keys(history: &mut serde_json::Value) {
+    let Some(reports) = history.get_mut("reports").and_then(|r| r.as_array_mut()) else {
+        return;
+    };
+    for report in reports.iter_mut().filter_map(|r| r.as_object_mut()) {
+        let Some(token) = report.remove("history_token") else {
+            continue;
+        };
+        if report.contains_key("token_key") {
+            continue;
+        }
+        let token = crate::settings::protected::deserialize(token).unwrap_or_default();
+        report.insert("token_key".to_string(), crate::tokens::token_key(&token).into());
+    }
+}
+
 static REPORT_HISTORY: Mutex<ReportHistory> = Mutex::new(ReportHistory {
     reports: Vec::new(),
-});
\ No newline at end of file
+    changelog: Vec::new(),
+});
+
+/// File formats the history can be exported as
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// A history entry as written to a JSON export, without the tokens
+/// Exports are meant to be kept or shared, the ownership token would let anyone delete the report
+#[derive(Serialize)]
+struct ExportedReport<'a> {
+    session_id: &'a str,
+    timestamp: u64,
+    main_report_url: &'a str,
+    legacy_report_url: Option<&'a str>,
+    log_files: &'a [String],
+    pinned: bool,
+    favorite: bool,
+    dps_report_urls: &'a [String],
+    label: &'a str,
+    details: Option<&'a SessionDetails>,
+    artifacts: &'a [ReportArtifact],
+}
+
+impl<'a> From<&'a ReportEntry> for ExportedReport<'a> {
+    fn from(entry: &'a ReportEntry) -> Self {
+        ExportedReport {
+            session_id: &entry.session_id,
+            timestamp: entry.timestamp,
+            main_report_url: &entry.main_report_url,
+            legacy_report_url: entry.legacy_report_url.as_deref(),
+            log_files: &entry.log_files,
+            pinned: entry.pinned,
+            favorite: entry.favorite,
+            dps_report_urls: &entry.dps_report_urls,
+            label: &entry.label,
+            details: entry.details.as_ref(),
+            artifacts: &entry.artifacts,
+        }
+    }
+}
+
+/// Writes the whole history to the exports folder in the addon directory, returns where it went
+/// `file_name` is used without an extension, anything that would leave the folder is refused
+pub fn export(file_name: &str, format: ExportFormat) -> Result<std::path::PathBuf> {
+    let file_name = file_name.trim();
+    if file_name.is_empty()
+        || file_name.contains(['/', '\\', ':'])
+        || file_name.starts_with('.')
+    {
+        return Err(anyhow::anyhow!("Enter a file name without folders"));
+    }
+
+    let history = ReportHistory::get();
+    let contents = match format {
+        ExportFormat::Csv => history.to_csv(),
+        ExportFormat::Json => {
+            let reports: Vec<ExportedReport> = history.reports.iter().map(ExportedReport::from).collect();
+            serde_json::to_string_pretty(&reports)?
+        }
+    };
+    drop(history);
+
+    let dir = crate::report_history_path()
+        .parent()
+        .map(|p| p.join("exports"))
+        .ok_or_else(|| anyhow::anyhow!("Addon directory not found"))?;
+    create_dir_all(&dir)?;
+
+    let path = dir.join(format!("{}.{}", file_name, format.extension()));
+    std::fs::write(&path, contents)?;
+    log::info!("Exported report history to {:?}", path);
+    Ok(path)
+}
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
-use crate::logfile::LogFile;
+use crate::formatting::format_elapsed;
+use crate::logfile::{LogFile, MapType};
 use crate::settings::Settings;
 use crate::state::{TimeFilter, STATE};
 
@@ -17,6 +21,31 @@ pub fn check_auto_scan() {
         return;
     }
 
+    // Don't hit the disk mid-fight, the next check after combat picks it up
+    if crate::mumble::should_defer_work() {
+        return;
+    }
+
+    // Replacing the list under an open popup or drag selection loses the interaction,
+    // so refreshes wait until it is over
+    if *STATE.list_interaction_active.lock().unwrap() {
+        if current_filter == TimeFilter::SincePluginStart {
+            let mut last_scan = STATE.last_auto_scan.lock().unwrap();
+            if last_scan.as_ref().map_or(true, |t| t.elapsed() >= Duration::from_secs(20)) {
+                *last_scan = Some(std::time::Instant::now());
+                *STATE.scan_refresh_queued.lock().unwrap() = true;
+            }
+        }
+        return;
+    }
+
+    if std::mem::take(&mut *STATE.scan_refresh_queued.lock().unwrap()) {
+        log::info!("Running log refresh queued during list interaction");
+        *STATE.last_auto_scan.lock().unwrap() = Some(std::time::Instant::now());
+        scan_for_logs();
+        return;
+    }
+
     if current_filter == TimeFilter::SincePluginStart {
         let mut last_scan = STATE.last_auto_scan.lock().unwrap();
         let should_scan = last_scan
@@ -37,69 +66,543 @@ pub fn update_scan_display() {
     let last_scan = STATE.last_auto_scan.lock().unwrap();
     if let Some(scan_time) = *last_scan {
         let elapsed = scan_time.elapsed().as_secs();
-        let display = if elapsed < 60 {
-            format!(
-                "Last refreshed: {} second{} ago",
-                elapsed,
-                if elapsed == 1 { "" } else { "s" }
-            )
-        } else {
-            let minutes = elapsed / 60;
-            format!(
-                "Last refreshed: {} minute{} ago",
-                minutes,
-                if minutes == 1 { "" } else { "s" }
-            )
-        };
+        let display = format!("Last refreshed: {} ago", format_elapsed(elapsed));
         *STATE.last_scan_display.lock().unwrap() = display;
     } else {
         *STATE.last_scan_display.lock().unwrap() = "Not yet refreshed".to_string();
     }
 }
 
-/// Recursively scans a directory for log files
+/// Most threads parsing logs at the same time during a scan
+const MAX_SCAN_WORKERS: usize = 4;
+
+/// Logs parsed between updates of the list while a scan is running
+const PUBLISH_EVERY: usize = 25;
+
+/// Threads filling the metadata cache in the background, kept low next to the game
+const WARMUP_WORKERS: usize = 2;
+
+/// Logs the background warm-up parses between writes of the cache file
+const WARMUP_SAVE_EVERY: usize = 200;
+
+/// How long the warm-up waits while a scan runs or work is deferred
+const WARMUP_IDLE_POLL: Duration = Duration::from_secs(2);
+
+/// Limits below which a log counts as junk and is left out of the list
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct JunkThresholds {
+    min_duration_secs: u64,
+    min_squad_size: usize,
+    min_size_bytes: u64,
+}
+
+impl JunkThresholds {
+    fn from_settings(settings: &Settings) -> Self {
+        Self {
+            min_duration_secs: settings.min_fight_seconds as u64,
+            min_squad_size: settings.min_squad_size as usize,
+            min_size_bytes: settings.min_log_size_kb as u64 * 1024,
+        }
+    }
+
+    /// Fights are only judged when the local parse produced a summary
+    fn is_junk(&self, log: &LogFile) -> bool {
+        log.fight.as_ref().is_some_and(|fight| {
+            fight.duration_secs < self.min_duration_secs || fight.squad_size < self.min_squad_size
+        })
+    }
+}
+
+/// A log file found on disk that still has to be parsed
+#[derive(Clone)]
+struct Candidate {
+    path: PathBuf,
+    size: u64,
+    modified: u64,
+}
+
+/// Directories are walked again regardless of their fingerprint at least this often,
+/// so a change deep below an unchanged folder is never missed for long
+const FULL_WALK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Folders that held a log this recently are always walked, that's where arcdps is writing
+const RECENT_DIRECTORY_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// How a directory looked when it was last walked
+struct DirectorySnapshot {
+    /// Number of entries and newest modified time among them
+    fingerprint: (usize, u64),
+    /// Newest log anywhere below the directory
+    newest_log: u64,
+    logs: Vec<Candidate>,
+    subdirectories: Vec<PathBuf>,
+}
+
+/// Directories seen by earlier scans, unchanged ones are answered from here instead of the disk
+static DIRECTORY_CACHE: LazyLock<Mutex<HashMap<PathBuf, DirectorySnapshot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static LAST_FULL_WALK: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// What a scan's result depends on, a scan with the same inputs as the last one leaves the list alone
+#[derive(PartialEq)]
+struct ScanInputs {
+    log_dir: PathBuf,
+    time_filter: TimeFilter,
+    thresholds: JunkThresholds,
+    /// Path, size and modified time of every log found, sorted by path
+    files: Vec<(PathBuf, u64, u64)>,
+}
+
+/// Inputs of the last published scan and the paths it listed
+static LAST_SCAN: Mutex<Option<(ScanInputs, Vec<PathBuf>)>> = Mutex::new(None);
+
+/// Makes the next scan walk every directory and rebuild the list, used by the Refresh button
+pub fn forget_directory_fingerprints() {
+    *LAST_FULL_WALK.lock().unwrap() = None;
+    *LAST_SCAN.lock().unwrap() = None;
+}
+
+/// Paths of the listed logs, sorted
+fn listed_paths(logs: &[LogFile]) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = logs.iter().map(|l| l.path.clone()).collect();
+    paths.sort();
+    paths
+}
+
+/// Whether the last scan saw the same files and its list is still the one shown
+fn unchanged_since_last_scan(inputs: &ScanInputs) -> bool {
+    // Reparsing or moving logs replaces the list without a scan
+    let shown = listed_paths(&STATE.logs.lock().unwrap());
+    let last_scan = LAST_SCAN.lock().unwrap();
+    let Some((last_inputs, listed)) = last_scan.as_ref() else {
+        return false;
+    };
+    *last_inputs == *inputs && *listed == shown
+}
+
+fn unix_secs(time: std::time::SystemTime) -> u64 {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Whether a folder or log matches one of the exclude patterns
+/// Patterns without a slash match a single folder or file name, e.g. "Archive" or "*.evtc"
+/// Patterns with one match the end of the path, e.g. "Archive/2023*"
+/// `*` and `?` are wildcards and case is ignored
+pub fn is_excluded(path: &std::path::Path, patterns: &[String]) -> bool {
+    if patterns.is_empty() {
+        return false;
+    }
+    let full = path.to_string_lossy().replace('\\', "/").to_lowercase();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim().replace('\\', "/").to_lowercase();
+        let pattern = pattern.trim_matches('/');
+        if pattern.is_empty() {
+            false
+        } else if pattern.contains('/') {
+            glob_match(&format!("*/{}", pattern), &full)
+        } else {
+            glob_match(pattern, &name)
+        }
+    })
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    // Position after the last `*` and the text position it was tried at, to backtrack to
+    let mut star: Option<(usize, usize)> = None;
+    let (mut p, mut t) = (0, 0);
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, t));
+            p += 1;
+        } else if let Some((after_star, tried)) = star {
+            p = after_star;
+            t = tried + 1;
+            star = Some((after_star, tried + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Recursively collects the log files in a directory that pass the time filter
+/// Only cheap filesystem metadata is read here, parsing happens afterwards
 fn scan_dir_recursive(
     dir: &std::path::Path,
-    logs: &mut Vec<LogFile>,
+    candidates: &mut Vec<Candidate>,
     cutoff_time: Option<std::time::SystemTime>,
 ) {
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_dir() {
-                    scan_dir_recursive(&entry.path(), logs, cutoff_time);
-                } else if metadata.is_file() {
-                    if let Some(ext) = entry.path().extension() {
-                        if ext == "zevtc" {
-                            // OPTIMIZATION: Check time filter BEFORE parsing
-                            // This uses cheap filesystem metadata instead of expensive EVTC parsing
-                            if let Some(cutoff) = cutoff_time {
-                                if let Ok(modified) = metadata.modified() {
-                                    if modified < cutoff {
-                                        continue; // Skip - file too old, don't even parse it
-                                    }
+    let trust_cache = {
+        let mut last_full_walk = LAST_FULL_WALK.lock().unwrap();
+        let trusted = last_full_walk.is_some_and(|at| at.elapsed() < FULL_WALK_INTERVAL);
+        if !trusted {
+            *last_full_walk = Some(Instant::now());
+        }
+        trusted
+    };
+
+    let cutoff = cutoff_time.map(unix_secs);
+    let exclude = Settings::get().scan_exclude_patterns.clone();
+    let mut found = Vec::new();
+    walk_directory(dir, trust_cache, &exclude, &mut found);
+    candidates.extend(found.into_iter().filter(|c| cutoff.map_or(true, |cutoff| c.modified >= cutoff)));
+}
+
+/// Walks one directory, skipping the subtree when its fingerprint matches the last walk
+/// Returns the newest log found below the directory
+fn walk_directory(dir: &std::path::Path, trust_cache: bool, exclude: &[String], found: &mut Vec<Candidate>) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let entries: Vec<(PathBuf, std::fs::Metadata)> = entries
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok().map(|metadata| (entry.path(), metadata)))
+        .collect();
+    let fingerprint = (
+        entries.len(),
+        entries
+            .iter()
+            .filter_map(|(_, metadata)| metadata.modified().ok())
+            .map(unix_secs)
+            .max()
+            .unwrap_or(0),
+    );
+
+    if trust_cache {
+        let recent = unix_secs(std::time::SystemTime::now()).saturating_sub(RECENT_DIRECTORY_SECS);
+        let cache = DIRECTORY_CACHE.lock().unwrap();
+        if let Some(snapshot) = cache.get(dir) {
+            if snapshot.fingerprint == fingerprint && snapshot.newest_log < recent {
+                let before = found.len();
+                if collect_cached(&cache, dir, found) {
+                    return snapshot.newest_log;
+                }
+                found.truncate(before);
+            }
+        }
+    }
+
+    let mut snapshot = DirectorySnapshot {
+        fingerprint,
+        newest_log: 0,
+        logs: Vec::new(),
+        subdirectories: Vec::new(),
+    };
+    for (path, metadata) in entries {
+        if crate::cancel::is_shutting_down() {
+            return snapshot.newest_log;
+        }
+        if is_excluded(&path, exclude) {
+            continue;
+        }
+        if metadata.is_dir() {
+            if crate::archive::is_archive_root(&path) {
+                continue;
+            }
+            let newest = walk_directory(&path, trust_cache, exclude, found);
+            snapshot.newest_log = snapshot.newest_log.max(newest);
+            snapshot.subdirectories.push(path);
+        } else if metadata.is_file() && crate::logfile::is_log_file(&path) {
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            let candidate = Candidate {
+                path,
+                size: metadata.len(),
+                modified: unix_secs(modified),
+            };
+            snapshot.newest_log = snapshot.newest_log.max(candidate.modified);
+            snapshot.logs.push(candidate.clone());
+            found.push(candidate);
+        }
+    }
+
+    let newest_log = snapshot.newest_log;
+    DIRECTORY_CACHE.lock().unwrap().insert(dir.to_path_buf(), snapshot);
+    newest_log
+}
+
+/// Gathers the logs of a cached subtree, false if part of it was never walked
+fn collect_cached(
+    cache: &HashMap<PathBuf, DirectorySnapshot>,
+    dir: &std::path::Path,
+    found: &mut Vec<Candidate>,
+) -> bool {
+    let Some(snapshot) = cache.get(dir) else {
+        return false;
+    };
+    found.extend(snapshot.logs.iter().cloned());
+    snapshot
+        .subdirectories
+        .iter()
+        .all(|subdirectory| collect_cached(cache, subdirectory, found))
+}
+
+/// Points the listed and cached logs at the files' new places after they were moved
+pub fn relocate_logs(moves: &[(PathBuf, PathBuf)]) {
+    if moves.is_empty() {
+        return;
+    }
+
+    let mut logs = STATE.logs.lock().unwrap();
+    for log in logs.iter_mut() {
+        if let Some((_, to)) = moves.iter().find(|(from, _)| *from == log.path) {
+            log.path = to.clone();
+        }
+    }
+    drop(logs);
+    crate::metadata_cache::relocate(moves);
+}
+
+fn is_current_scan(scan_id: u64) -> bool {
+    *STATE.current_scan_id.lock().unwrap() == scan_id
+}
+
+/// Parses the found logs on a small pool of threads
+/// The list is updated every few logs so large folders fill in while the scan runs
+fn parse_candidates(candidates: &[Candidate], scan_id: u64, thresholds: JunkThresholds) -> Vec<LogFile> {
+    let total = candidates.len();
+    *STATE.scan_progress.lock().unwrap() = (0, total);
+
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .clamp(1, MAX_SCAN_WORKERS)
+        .min(total.max(1));
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let found: Mutex<Vec<LogFile>> = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for worker in 0..workers {
+            let spawned = std::thread::Builder::new()
+                .name(format!("wvw-insights-log-scan-{}", worker + 1))
+                .spawn_scoped(scope, || {
+                    crate::throttle::apply_background_priority();
+
+                    loop {
+                        // Stop early when unloading or when a newer scan replaced this one
+                        if crate::cancel::is_shutting_down() || !is_current_scan(scan_id) {
+                            return;
+                        }
+
+                        let Some(candidate) = candidates.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                            return;
+                        };
+
+                        let log = crate::metadata_cache::lookup(&candidate.path, candidate.size, candidate.modified)
+                            .or_else(|| {
+                                crate::throttle::pace();
+                                let log = LogFile::new_fast(candidate.path.clone()).ok()?;
+                                crate::metadata_cache::insert(&log);
+                                Some(log)
+                            });
+
+                        // Only include WvW logs (filters out PvE/Unknown), broken files are shown flagged
+                        let log = log
+                            .filter(|l| l.map_type.is_wvw() || l.invalid.is_some())
+                            .filter(|l| !thresholds.is_junk(l));
+                        if let Some(log) = log {
+                            found.lock().unwrap().push(log);
+                        }
+
+                        let count = done.fetch_add(1, Ordering::Relaxed) + 1;
+                        *STATE.scan_progress.lock().unwrap() = (count, total);
+
+                        if count % PUBLISH_EVERY == 0 && count < total {
+                            let partial = found.lock().unwrap().clone();
+                            publish_partial(partial, candidates, scan_id);
+                        }
+                    }
+                });
+
+            if let Err(e) = spawned {
+                log::error!("Could not create log scan worker: {}", e);
+            }
+        }
+    });
+
+    found.into_inner().unwrap()
+}
+
+/// Parses every log in the log directory that isn't cached yet, newest first, on a small pool of threads
+/// Runs after load so later scans, even over long time ranges, mostly read the cache
+pub fn start_metadata_warmup() {
+    let log_dir = Settings::get().log_directory.clone();
+    if log_dir.is_empty() {
+        return;
+    }
+
+    crate::threads::spawn("metadata-warmup", move || {
+        crate::throttle::apply_background_priority();
+
+        let mut candidates = Vec::new();
+        scan_dir_recursive(std::path::Path::new(&log_dir), &mut candidates, None);
+        candidates.retain(|c| !crate::metadata_cache::contains(&c.path, c.size, c.modified));
+        if candidates.is_empty() {
+            return;
+        }
+        candidates.sort_by(|a, b| b.modified.cmp(&a.modified));
+        log::info!("Caching metadata of {} logs in the background", candidates.len());
+        let _activity = crate::threads::activity("Indexing logs");
+
+        let next = AtomicUsize::new(0);
+        let done = AtomicUsize::new(0);
+        let candidates = &candidates;
+
+        std::thread::scope(|scope| {
+            for worker in 0..WARMUP_WORKERS {
+                let spawned = std::thread::Builder::new()
+                    .name(format!("wvw-insights-metadata-warmup-{}", worker + 1))
+                    .spawn_scoped(scope, || {
+                        crate::throttle::apply_background_priority();
+
+                        loop {
+                            if crate::cancel::is_shutting_down() {
+                                return;
+                            }
+                            // Scans take priority, and nothing is read from disk mid-fight
+                            if *STATE.scan_in_progress.lock().unwrap() || crate::mumble::should_defer_work() {
+                                std::thread::sleep(WARMUP_IDLE_POLL);
+                                continue;
+                            }
+
+                            let Some(candidate) = candidates.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                                return;
+                            };
+                            // A scan may have parsed it in the meantime
+                            if !crate::metadata_cache::contains(&candidate.path, candidate.size, candidate.modified) {
+                                crate::throttle::pace();
+                                if let Ok(log) = LogFile::new_fast(candidate.path.clone()) {
+                                    crate::metadata_cache::insert(&log);
                                 }
                             }
-                            
-                            // File is recent enough, now parse it to determine map type
-                            if let Ok(log) = LogFile::new_fast(entry.path()) {
-                                // Only include WvW logs (filters out PvE/Unknown)
-                                if log.map_type.is_wvw() {
-                                    logs.push(log);
+
+                            let count = done.fetch_add(1, Ordering::Relaxed) + 1;
+                            if count % WARMUP_SAVE_EVERY == 0 {
+                                if let Err(e) = crate::metadata_cache::store_if_changed(crate::metadata_cache_path()) {
+                                    log::warn!("Failed to save the metadata cache: {}", e);
                                 }
                             }
                         }
-                    }
+                    });
+
+                if let Err(e) = spawned {
+                    log::error!("Could not create metadata warm-up worker: {}", e);
                 }
             }
+        });
+
+        if let Err(e) = crate::metadata_cache::store_if_changed(crate::metadata_cache_path()) {
+            log::warn!("Failed to save the metadata cache: {}", e);
+        }
+        log::info!("Cached metadata of {} logs", done.load(Ordering::Relaxed));
+    });
+}
+
+/// Shows the logs parsed so far, keeping listed logs that haven't been parsed again yet
+fn publish_partial(mut found: Vec<LogFile>, candidates: &[Candidate], scan_id: u64) {
+    if !is_current_scan(scan_id) || *STATE.list_interaction_active.lock().unwrap() {
+        return;
+    }
+
+    let mut logs = STATE.logs.lock().unwrap();
+    for log in logs.iter() {
+        let pending = candidates.iter().any(|c| c.path == log.path)
+            && !found.iter().any(|f| f.path == log.path);
+        if pending {
+            found.push(log.clone());
+        }
+    }
+
+    apply_selections(&logs, &mut found);
+    found.sort_by(|a, b| b.modified.cmp(&a.modified));
+    *logs = found;
+    *STATE.log_continuations.lock().unwrap() = find_continuations(&logs);
+}
+
+/// Carries the selection of logs already in the list over to the new scan results
+fn apply_selections(current: &[LogFile], found: &mut [LogFile]) {
+    let selections: std::collections::HashMap<&str, bool> = current
+        .iter()
+        .map(|log| (log.filename.as_str(), log.selected))
+        .collect();
+
+    for log in found.iter_mut() {
+        if let Some(&was_selected) = selections.get(log.filename.as_str()) {
+            log.selected = was_selected;
+        }
+    }
+}
+
+/// A log starting this soon after the previous one ended on the same map is likely the same fight
+/// split in two, e.g. by a map change or an arcdps restart
+pub const CONTINUATION_GAP_SECS: i64 = 30;
+
+/// Links each log to the log it likely continues, as filename to previous filename
+pub fn find_continuations(logs: &[LogFile]) -> Vec<(String, String)> {
+    let mut ordered: Vec<&LogFile> = logs.iter().filter(|l| matches!(l.map_type, MapType::WvW(_))).collect();
+    ordered.sort_by_key(|l| l.modified);
+
+    let mut continuations = Vec::new();
+    for pair in ordered.windows(2) {
+        let (previous, next) = (pair[0], pair[1]);
+        if previous.map_type != next.map_type {
+            continue;
+        }
+        let Some(start) = log_start(next) else {
+            continue;
+        };
+        // arcdps writes the log when the fight ends, so the previous file's time is its end
+        let gap = start - previous.modified as i64;
+        if (-CONTINUATION_GAP_SECS..CONTINUATION_GAP_SECS).contains(&gap) {
+            continuations.push((next.filename.clone(), previous.filename.clone()));
         }
     }
+    continuations
+}
+
+/// When the fight in a log started, from the filename or else its end minus the parsed duration
+fn log_start(log: &LogFile) -> Option<i64> {
+    use chrono::TimeZone;
+
+    crate::formatting::parse_log_timestamp(&log.filename)
+        .and_then(|t| chrono::Local.from_local_datetime(&t).single())
+        .map(|t| t.timestamp())
+        .or_else(|| log.fight.as_ref().map(|f| log.modified as i64 - f.duration_secs as i64))
+}
+
+/// The log an upload continues, looked up by file stem so compressed copies still match
+pub fn continued_log(location: &std::path::Path) -> Option<String> {
+    let stem = location.file_stem()?.to_string_lossy().to_string();
+    STATE
+        .log_continuations
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(filename, _)| filename.split('.').next() == Some(stem.as_str()))
+        .map(|(_, previous)| previous.clone())
 }
 
 /// Scans for log files based on the current time filter
 pub fn scan_for_logs() {
     // Set scanning flag to true at the start
     *STATE.scan_in_progress.lock().unwrap() = true;
+    *STATE.scan_progress.lock().unwrap() = (0, 0);
     
     // Increment scan ID to invalidate any in-progress scans
     let scan_id = {
@@ -111,12 +614,15 @@ pub fn scan_for_logs() {
     // Capture settings and time filter BEFORE spawning the thread
     let settings = Settings::get();
     let log_dir_string = settings.log_directory.clone();
+    let thresholds = JunkThresholds::from_settings(&settings);
     drop(settings);
     
     let time_filter = *STATE.selected_time_filter.lock().unwrap();
     
-    std::thread::spawn(move || {
+    crate::threads::spawn("log-scan", move || {
+        let _activity = crate::threads::activity("Scanning logs");
         log::info!("Starting background log scan (ID: {})", scan_id);
+        crate::throttle::apply_background_priority();
         
         if log_dir_string.is_empty() {
             log::error!("Log directory is not configured");
@@ -136,7 +642,7 @@ pub fn scan_for_logs() {
             return;
         }
 
-        let mut found_logs = Vec::new();
+        let mut candidates = Vec::new();
 
         let cutoff_time = match time_filter {
             TimeFilter::SincePluginStart => STATE.addon_load_time.lock().unwrap().map(|load_time| {
@@ -153,7 +659,30 @@ pub fn scan_for_logs() {
             ),
         };
 
-        scan_dir_recursive(&log_dir, &mut found_logs, cutoff_time);
+        scan_dir_recursive(&log_dir, &mut candidates, cutoff_time);
+        // Tiny files are dropped before they are parsed at all
+        candidates.retain(|c| c.size >= thresholds.min_size_bytes);
+
+        let mut files: Vec<(PathBuf, u64, u64)> = candidates
+            .iter()
+            .map(|c| (c.path.clone(), c.size, c.modified))
+            .collect();
+        files.sort();
+        let inputs = ScanInputs {
+            log_dir: log_dir.clone(),
+            time_filter,
+            thresholds,
+            files,
+        };
+        if unchanged_since_last_scan(&inputs) {
+            log::debug!("Scan {} found no changed logs, keeping the list", scan_id);
+            if is_current_scan(scan_id) {
+                *STATE.scan_in_progress.lock().unwrap() = false;
+            }
+            return;
+        }
+
+        let mut found_logs = parse_candidates(&candidates, scan_id, thresholds);
         found_logs.sort_by(|a, b| b.modified.cmp(&a.modified));
 
         // CHECK: Is this scan still the current one?
@@ -171,24 +700,150 @@ pub fn scan_for_logs() {
             TimeFilter::Last72Hours => "72-hour",
         };
 
+        // Don't swap the list out from under an open popup or drag, scan again once it ends
+        if *STATE.list_interaction_active.lock().unwrap() {
+            log::info!("Scan {} deferred until list interaction ends", scan_id);
+            *STATE.scan_refresh_queued.lock().unwrap() = true;
+            *STATE.scan_in_progress.lock().unwrap() = false;
+            return;
+        }
+
         let mut logs = STATE.logs.lock().unwrap();
         // Preserve existing selections by filename
-        let selections: std::collections::HashMap<String, bool> = logs
-            .iter()
-            .map(|log| (log.filename.clone(), log.selected))
-            .collect();
-
-        // Apply preserved selections to new logs
-        for log in found_logs.iter_mut() {
-            if let Some(&was_selected) = selections.get(&log.filename) {
-                log.selected = was_selected;
-            }
-        }
+        apply_selections(&logs, &mut found_logs);
 
         *logs = found_logs;
+        *STATE.log_continuations.lock().unwrap() = find_continuations(&logs);
+        *LAST_SCAN.lock().unwrap() = Some((inputs, listed_paths(&logs)));
         log::info!("Scan {} completed: Found {} log files ({} filter)", scan_id, logs.len(), filter_name);
         
         // NEW: Clear scanning flag when scan is complete and current
         *STATE.scan_in_progress.lock().unwrap() = false;
+        drop(logs);
+
+        if let Err(e) = crate::metadata_cache::store_if_changed(crate::metadata_cache_path()) {
+            log::warn!("Failed to save the metadata cache: {}", e);
+        }
+    });
+}
+
+/// Logs parsed by the directory estimate to judge how many fights are short
+const ESTIMATE_SAMPLE_SIZE: usize = 40;
+
+/// Sampled fights shorter than this count as short
+pub const SHORT_FIGHT_SECS: u32 = 60;
+
+/// Age the auto-cleanup recommendation is based on
+pub const ESTIMATE_CLEANUP_DAYS: u32 = 90;
+
+/// Directories with more logs or bytes than this get an auto-cleanup recommendation
+const LARGE_DIRECTORY_LOGS: usize = 5000;
+const LARGE_DIRECTORY_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// What a quick pass over a log directory found, to suggest filter and cleanup settings
+#[derive(Debug, Clone)]
+pub struct DirectoryEstimate {
+    pub directory: String,
+    pub log_count: usize,
+    pub total_bytes: u64,
+    /// Logs older than ESTIMATE_CLEANUP_DAYS
+    pub old_count: usize,
+    pub old_bytes: u64,
+    /// WvW fights among the parsed sample, and how many of them were short
+    pub sampled_fights: usize,
+    pub short_fights: usize,
+}
+
+impl DirectoryEstimate {
+    pub fn recommends_cleanup(&self) -> bool {
+        self.old_count > 0
+            && (self.log_count >= LARGE_DIRECTORY_LOGS || self.total_bytes >= LARGE_DIRECTORY_BYTES)
+    }
+
+    /// A quarter or more of the sampled fights being short suggests hiding them
+    pub fn recommends_short_fight_filter(&self) -> bool {
+        self.sampled_fights > 0 && self.short_fights * 4 >= self.sampled_fights
+    }
+}
+
+/// Counts the logs in a directory and parses an even sample of them
+/// Only file metadata is read for the totals, so even large folders are quick
+pub fn estimate_directory(directory: &str) -> DirectoryEstimate {
+    let mut candidates = Vec::new();
+    scan_dir_recursive(std::path::Path::new(directory), &mut candidates, None);
+
+    let old_cutoff = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        .saturating_sub(ESTIMATE_CLEANUP_DAYS as u64 * 24 * 60 * 60);
+
+    let mut estimate = DirectoryEstimate {
+        directory: directory.to_string(),
+        log_count: candidates.len(),
+        total_bytes: candidates.iter().map(|c| c.size).sum(),
+        old_count: 0,
+        old_bytes: 0,
+        sampled_fights: 0,
+        short_fights: 0,
+    };
+    for candidate in candidates.iter().filter(|c| c.modified < old_cutoff) {
+        estimate.old_count += 1;
+        estimate.old_bytes += candidate.size;
+    }
+
+    let step = (candidates.len() / ESTIMATE_SAMPLE_SIZE).max(1);
+    for candidate in candidates.iter().step_by(step).take(ESTIMATE_SAMPLE_SIZE) {
+        if crate::cancel::is_shutting_down() {
+            break;
+        }
+        crate::throttle::pace();
+
+        let Some(fight) = LogFile::new_fast(candidate.path.clone())
+            .ok()
+            .filter(|log| log.map_type.is_wvw())
+            .and_then(|log| log.fight)
+        else {
+            continue;
+        };
+        estimate.sampled_fights += 1;
+        if fight.duration_secs < SHORT_FIGHT_SECS as u64 {
+            estimate.short_fights += 1;
+        }
+    }
+
+    estimate
+}
+
+/// Estimates a newly configured log directory in the background
+pub fn start_directory_estimate(directory: String) {
+    if directory.trim().is_empty() {
+        return;
+    }
+    {
+        let mut pending = STATE.dir_estimate_pending.lock().unwrap();
+        if *pending {
+            return;
+        }
+        *pending = true;
+    }
+    *STATE.dir_estimate.lock().unwrap() = None;
+
+    crate::threads::spawn("dir-estimate", move || {
+        crate::throttle::apply_background_priority();
+
+        let estimate = estimate_directory(&directory);
+        log::info!(
+            "Log directory estimate: {} logs, {} bytes, {} older than {} days, {}/{} sampled fights short",
+            estimate.log_count,
+            estimate.total_bytes,
+            estimate.old_count,
+            ESTIMATE_CLEANUP_DAYS,
+            estimate.short_fights,
+            estimate.sampled_fights
+        );
+
+        *STATE.dir_estimate.lock().unwrap() = Some(estimate);
+        *STATE.dir_estimate_pending.lock().unwrap() = false;
     });
-}
\ No newline at end of file
+}
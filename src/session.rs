@@ -0,0 +1,167 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, File};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::state::{ProcessingState, STATE};
+use crate::upload_review::{SplitSession, UploadedFileInfo};
+
+/// How often the active session is compared against the saved copy
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The active upload session as kept on disk, so it survives a crash or reload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSession {
+    pub session_id: String,
+    #[serde(serialize_with = "crate::settings::protected::serialize", deserialize_with = "crate::settings::protected::deserialize")]
+    pub ownership_token: String,
+    #[serde(default)]
+    pub files: Vec<UploadedFileInfo>,
+    #[serde(default)]
+    pub split_sessions: Vec<SplitSession>,
+    /// Endpoint the session was created on when it didn't use the configured one
+    #[serde(default)]
+    pub endpoint_override: String,
+}
+
+/// What was last written to disk, None once the file is removed
+static LAST_SAVED: Mutex<Option<SavedSession>> = Mutex::new(None);
+static LAST_CHECK: Mutex<Option<Instant>> = Mutex::new(None);
+
+impl SavedSession {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(path)?;
+        let saved: Self = serde_json::from_str(&contents)?;
+        Ok(Some(saved))
+    }
+
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let prefix = path.parent().unwrap();
+        create_dir_all(prefix)?;
+        let mut file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        Ok(())
+    }
+}
+
+/// Takes the session left behind by the last run, if it still has uploaded files
+pub fn load_previous() {
+    match SavedSession::from_path(crate::active_session_path()) {
+        Ok(Some(saved)) if !saved.files.is_empty() => {
+            log::info!(
+                "Found previous session {} with {} uploaded file(s)",
+                saved.session_id,
+                saved.files.len()
+            );
+            *LAST_SAVED.lock().unwrap() = Some(saved.clone());
+            *STATE.resumable_session.lock().unwrap() = Some(saved);
+        }
+        Ok(_) => remove_file(),
+        Err(e) => {
+            log::warn!("Failed to read previous session: {}", e);
+            remove_file();
+        }
+    }
+}
+
+/// The session worth resuming right now, None when there is nothing uploaded or it is finished
+fn current_session() -> Option<SavedSession> {
+    if *STATE.processing_state.lock().unwrap() == ProcessingState::Complete {
+        return None;
+    }
+
+    let session_id = STATE.session_id.lock().unwrap().clone();
+    let files = STATE.uploaded_files.lock().unwrap().clone();
+    if session_id.is_empty() || files.is_empty() {
+        return None;
+    }
+
+    Some(SavedSession {
+        session_id,
+        ownership_token: STATE.ownership_token.lock().unwrap().clone(),
+        files,
+        split_sessions: STATE.split_sessions.lock().unwrap().clone(),
+        endpoint_override: STATE.endpoint_override.lock().unwrap().clone(),
+    })
+}
+
+/// Writes the active session to disk when it changed, removing the file once it is done
+/// Called every frame, only looks at the session once a second
+pub fn save_if_changed() {
+    {
+        let mut last_check = LAST_CHECK.lock().unwrap();
+        if last_check.is_some_and(|t| t.elapsed() < SAVE_INTERVAL) {
+            return;
+        }
+        *last_check = Some(Instant::now());
+    }
+
+    // The previous session stays on disk until the user decides what to do with it
+    if STATE.resumable_session.lock().unwrap().is_some() {
+        return;
+    }
+
+    let current = current_session();
+    let mut last_saved = LAST_SAVED.lock().unwrap();
+    if *last_saved == current {
+        return;
+    }
+
+    match &current {
+        Some(session) => {
+            if let Err(e) = session.store(crate::active_session_path()) {
+                log::error!("Failed to save active session: {}", e);
+                return;
+            }
+        }
+        None => remove_file(),
+    }
+    *last_saved = current;
+}
+
+/// Makes the previous session the active one and opens the review screen
+pub fn resume(saved: SavedSession) {
+    log::info!("Resuming previous session {}", saved.session_id);
+
+    *STATE.session_id.lock().unwrap() = saved.session_id.clone();
+    *STATE.ownership_token.lock().unwrap() = saved.ownership_token;
+    *STATE.uploaded_files.lock().unwrap() = saved.files;
+    *STATE.split_sessions.lock().unwrap() = saved.split_sessions;
+    *STATE.endpoint_override.lock().unwrap() = saved.endpoint_override;
+    *STATE.processing_state.lock().unwrap() = ProcessingState::Idle;
+    // It was announced before the crash already
+    *STATE.announced_session.lock().unwrap() = saved.session_id;
+
+    *STATE.resumable_session.lock().unwrap() = None;
+    *STATE.show_token_input.lock().unwrap() = false;
+    *STATE.show_log_selection.lock().unwrap() = false;
+    *STATE.show_upload_review.lock().unwrap() = true;
+}
+
+/// Forgets the previous session, the files stay on the server until it is cleaned up
+pub fn discard() {
+    log::info!("Discarding previous session");
+    *STATE.resumable_session.lock().unwrap() = None;
+    *LAST_SAVED.lock().unwrap() = None;
+    remove_file();
+}
+
+fn remove_file() {
+    let path = crate::active_session_path();
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::error!("Failed to remove saved session: {}", e);
+        }
+    }
+}
@@ -1,21 +1,186 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use dirs_next::document_dir;
 use serde::{Deserialize, Serialize};
 use std::fs::{create_dir_all, File};
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, MutexGuard};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::hooks::PostProcessHook;
+use crate::maps::WvwRegion;
+use crate::profiles::Profile;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SavedToken {
     pub name: String,
+    #[serde(serialize_with = "protected::serialize", deserialize_with = "protected::deserialize")]
     pub token: String,
+    /// Saved webhook selected when switching to this token, empty leaves it as it is
+    #[serde(default)]
+    pub webhook: String,
+    /// Name reports of this token are posted under, {date} is the parse date, empty uses "WvW: {date}"
+    #[serde(default)]
+    pub report_name: String,
+}
+
+/// Tokens are encrypted for the current Windows user (DPAPI) before they are written to disk
+/// Plain values from older files are read as they are and get encrypted on the next save
+/// A value encrypted by another Windows user or PC stays encrypted in memory and is written back unchanged
+pub(crate) mod protected {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use winapi::um::dpapi::{CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN};
+    use winapi::um::winbase::LocalFree;
+    use winapi::um::wincrypt::DATA_BLOB;
+
+    /// Marks an encrypted value, anything without it is an older plain value
+    const PREFIX: &str = "dpapi:";
+
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&protect(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(unprotect(&value))
+    }
+
+    /// Whether a token was encrypted by another Windows user or PC and can't be used here
+    pub fn is_unreadable(value: &str) -> bool {
+        value.starts_with(PREFIX)
+    }
+
+    /// Encrypts a token, empty values stay empty
+    /// Falls back to the plain value when DPAPI fails so the token isn't lost
+    fn protect(value: &str) -> String {
+        if value.is_empty() {
+            return String::new();
+        }
+        if is_unreadable(value) {
+            return value.to_string();
+        }
+        match crypt(value.as_bytes(), true) {
+            Some(encrypted) => {
+                let hex: String = encrypted.iter().map(|b| format!("{:02x}", b)).collect();
+                format!("{}{}", PREFIX, hex)
+            }
+            None => {
+                log::warn!("Could not encrypt a token, saving it unencrypted");
+                value.to_string()
+            }
+        }
+    }
+
+    /// Decrypts a saved token, one encrypted by another Windows user or PC is kept as it was saved
+    fn unprotect(value: &str) -> String {
+        let Some(hex) = value.strip_prefix(PREFIX) else {
+            return value.to_string();
+        };
+
+        let data: Option<Vec<u8>> = (0..hex.len())
+            .step_by(2)
+            .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+            .collect();
+
+        data.and_then(|data| crypt(&data, false))
+            .and_then(|plain| String::from_utf8(plain).ok())
+            .unwrap_or_else(|| {
+                log::warn!("Could not decrypt a saved token, it was saved by another Windows user or PC");
+                value.to_string()
+            })
+    }
+
+    fn crypt(data: &[u8], encrypt: bool) -> Option<Vec<u8>> {
+        let mut input = DATA_BLOB {
+            cbData: data.len() as u32,
+            pbData: data.as_ptr() as *mut u8,
+        };
+        let mut output = DATA_BLOB {
+            cbData: 0,
+            pbData: std::ptr::null_mut(),
+        };
+
+        // SAFETY: input points at `data` for the whole call, output is allocated by DPAPI
+        // and freed below once it has been copied
+        let ok = unsafe {
+            if encrypt {
+                CryptProtectData(
+                    &mut input,
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    CRYPTPROTECT_UI_FORBIDDEN,
+                    &mut output,
+                )
+            } else {
+                CryptUnprotectData(
+                    &mut input,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    CRYPTPROTECT_UI_FORBIDDEN,
+                    &mut output,
+                )
+            }
+        };
+        if ok == 0 || output.pbData.is_null() {
+            return None;
+        }
+
+        let result = unsafe { std::slice::from_raw_parts(output.pbData, output.cbData as usize) }.to_vec();
+        unsafe {
+            LocalFree(output.pbData as _);
+        }
+        Some(result)
+    }
+}
+
+/// A button on the results screen that can be shown, hidden and reordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultsAction {
+    CopyUrl,
+    OpenInBrowser,
+    CopyAllUrls,
+    SendToDiscord,
+    UploadMore,
+}
+
+impl ResultsAction {
+    /// Every action, in the default order
+    pub const ALL: [ResultsAction; 5] = [
+        ResultsAction::CopyUrl,
+        ResultsAction::OpenInBrowser,
+        ResultsAction::CopyAllUrls,
+        ResultsAction::SendToDiscord,
+        ResultsAction::UploadMore,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResultsAction::CopyUrl => "Copy URL",
+            ResultsAction::OpenInBrowser => "Open in Browser",
+            ResultsAction::CopyAllUrls => "Copy All URLs",
+            ResultsAction::SendToDiscord => "Send to Discord",
+            ResultsAction::UploadMore => "Upload More Logs",
+        }
+    }
+
+    /// Whether the button is repeated under every report URL
+    pub fn is_per_url(self) -> bool {
+        matches!(self, ResultsAction::CopyUrl | ResultsAction::OpenInBrowser)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
+    #[serde(serialize_with = "protected::serialize", deserialize_with = "protected::deserialize")]
     pub history_token: String,
     pub api_endpoint: String,
     pub log_directory: String,
+    /// Folder names or globs below the log directory left out of scans and cleanup, e.g. "Archive"
+    #[serde(default)]
+    pub scan_exclude_patterns: Vec<String>,
     #[serde(default = "default_show_formatted_timestamps")]
     pub show_formatted_timestamps: bool,
     #[serde(default)]
@@ -30,12 +195,104 @@ pub struct Settings {
     pub guild_name: String,
     #[serde(default)]
     pub enable_legacy_parser: bool,
-    #[serde(default)]
+    #[serde(default, serialize_with = "protected::serialize", deserialize_with = "protected::deserialize")]
     pub dps_report_token: String,
     #[serde(default)]
-    pub saved_dps_tokens: Vec<SavedToken>,    
+    pub saved_dps_tokens: Vec<SavedToken>,
+    #[serde(default)]
+    pub lock_pin_hash: String,
+    #[serde(default)]
+    pub streamer_mode: bool,
+    #[serde(default)]
+    pub defer_work_in_combat: bool,
+    #[serde(default)]
+    pub adaptive_throttling: bool,
+    #[serde(default)]
+    pub split_by_map: bool,
+    #[serde(default)]
+    pub show_advanced_settings: bool,
+    #[serde(default = "default_low_priority_background")]
+    pub low_priority_background: bool,
+    #[serde(default)]
+    pub check_report_urls: bool,
+    #[serde(default = "default_results_actions")]
+    pub results_actions: Vec<ResultsAction>,
+    #[serde(default)]
+    pub server_cleanup_enabled: bool,
+    #[serde(default = "default_server_cleanup_months")]
+    pub server_cleanup_months: u32,
+    #[serde(default)]
+    pub last_server_cleanup: u64,
+    /// Days uploaded logs stay searchable after they stop being tracked, 0 forgets them right away
+    #[serde(default = "default_uploaded_archive_days")]
+    pub uploaded_archive_days: u32,
+    #[serde(default = "default_upload_concurrency")]
+    pub upload_concurrency: u32,
+    #[serde(default)]
+    pub live_mode: bool,
+    #[serde(default)]
+    pub auto_process_enabled: bool,
+    #[serde(default = "default_auto_process_minutes")]
+    pub auto_process_minutes: u32,
+    #[serde(default)]
+    pub inbox_enabled: bool,
+    /// API endpoint the user allowed network access for, empty until they did
+    #[serde(default)]
+    pub network_consent: String,
+    #[serde(default)]
+    pub speak_status_changes: bool,
+    #[serde(default = "default_select_all_threshold")]
+    pub select_all_threshold: u32,
+    /// Count of the custom "Select newest" quick action above the log list
+    #[serde(default = "default_select_newest_count")]
+    pub select_newest_count: u32,
+    #[serde(default)]
+    pub min_fight_seconds: u32,
+    #[serde(default)]
+    pub min_squad_size: u32,
+    #[serde(default)]
+    pub min_log_size_kb: u32,
+    #[serde(default)]
+    pub low_bandwidth_mode: bool,
+    /// Upload speed limit in Mbps shared by all uploads, 0 for unlimited
+    #[serde(default)]
+    pub upload_limit_mbps: u32,
+    /// Uploads the smallest queued logs first so early fights are ready on the server quickly
+    #[serde(default = "default_smallest_uploads_first")]
+    pub smallest_uploads_first: bool,
+    /// Zips uncompressed .evtc logs into .zevtc before they are uploaded, low bandwidth mode always does
+    #[serde(default = "default_compress_evtc_uploads")]
+    pub compress_evtc_uploads: bool,
+    #[serde(default)]
+    pub post_process_hooks: Vec<PostProcessHook>,
+    /// Subfolder the log organizer moves each log to, as a date format of the log's start
+    #[serde(default = "default_organize_pattern")]
+    pub organize_pattern: String,
+    /// Moves a session's logs into a dated archive folder once its report is ready
+    #[serde(default)]
+    pub archive_uploaded_logs: bool,
+    /// Where archived logs go, empty uses an "Uploaded" folder in the log directory
+    #[serde(default)]
+    pub archive_directory: String,
+    /// Where JSON and CSV report files are downloaded to, empty uses the Downloads folder
+    #[serde(default)]
+    pub artifact_download_dir: String,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    /// Name of the profile last switched to, empty when none is in use
+    #[serde(default)]
+    pub active_profile: String,
+    /// Decides when the matchup week starts, for the warning about last week's logs
+    #[serde(default = "default_wvw_region")]
+    pub wvw_region: WvwRegion,
 }
 
+/// Most uploads that run at the same time, also the size of the upload worker pool
+pub const MAX_UPLOAD_CONCURRENCY: usize = 6;
+
+/// Highest upload speed limit that can be set, in Mbps
+pub const MAX_UPLOAD_LIMIT_MBPS: u32 = 50;
+
 fn default_cleanup_days() -> u32 {
     30
 }
@@ -44,12 +301,135 @@ fn default_show_formatted_timestamps() -> bool {
     true // Default to the prettier format
 }
 
+fn default_low_priority_background() -> bool {
+    true
+}
+
+fn default_server_cleanup_months() -> u32 {
+    6
+}
+
+fn default_uploaded_archive_days() -> u32 {
+    30
+}
+
+fn default_upload_concurrency() -> u32 {
+    3
+}
+
+fn default_wvw_region() -> WvwRegion {
+    WvwRegion::Eu
+}
+
+fn default_smallest_uploads_first() -> bool {
+    true
+}
+
+fn default_compress_evtc_uploads() -> bool {
+    true
+}
+
+fn default_auto_process_minutes() -> u32 {
+    15
+}
+
+fn default_select_all_threshold() -> u32 {
+    50
+}
+
+fn default_select_newest_count() -> u32 {
+    30
+}
+
+fn default_results_actions() -> Vec<ResultsAction> {
+    ResultsAction::ALL.to_vec()
+}
+
+pub fn default_organize_pattern() -> String {
+    "%Y/%m-%d".to_string()
+}
+
+/// Groups of settings that can be reset on their own when settings.json is damaged
+pub const SETTINGS_SECTIONS: &[(&str, &[&str])] = &[
+    (
+        "Tokens",
+        &[
+            "history_token",
+            "saved_tokens",
+            "dps_report_token",
+            "saved_dps_tokens",
+            "profiles",
+            "active_profile",
+        ],
+    ),
+    (
+        "Server & Processing",
+        &[
+            "api_endpoint",
+            "guild_name",
+            "wvw_region",
+            "enable_legacy_parser",
+            "split_by_map",
+            "live_mode",
+            "auto_process_enabled",
+            "auto_process_minutes",
+            "inbox_enabled",
+            "post_process_hooks",
+            "network_consent",
+        ],
+    ),
+    (
+        "Logs & Cleanup",
+        &[
+            "log_directory",
+            "scan_exclude_patterns",
+            "show_formatted_timestamps",
+            "select_all_threshold",
+            "select_newest_count",
+            "min_fight_seconds",
+            "min_squad_size",
+            "min_log_size_kb",
+            "results_actions",
+            "auto_cleanup_enabled",
+            "auto_cleanup_days",
+            "server_cleanup_enabled",
+            "server_cleanup_months",
+            "last_server_cleanup",
+            "uploaded_archive_days",
+            "organize_pattern",
+            "archive_uploaded_logs",
+            "archive_directory",
+            "artifact_download_dir",
+        ],
+    ),
+    (
+        "Quality of Life",
+        &[
+            "mouse_lock_enabled",
+            "streamer_mode",
+            "defer_work_in_combat",
+            "adaptive_throttling",
+            "low_priority_background",
+            "upload_concurrency",
+            "low_bandwidth_mode",
+            "upload_limit_mbps",
+            "smallest_uploads_first",
+            "compress_evtc_uploads",
+            "check_report_urls",
+            "speak_status_changes",
+            "lock_pin_hash",
+            "show_advanced_settings",
+        ],
+    ),
+];
+
 impl Settings {
     const fn default() -> Self {
         Self {
             history_token: String::new(),
             api_endpoint: String::new(),
             log_directory: String::new(),
+            scan_exclude_patterns: Vec::new(),
             show_formatted_timestamps: true,
             saved_tokens: Vec::new(),
             saved_dps_tokens: Vec::new(),
@@ -59,6 +439,66 @@ impl Settings {
             guild_name: String::new(),
             enable_legacy_parser: false,
             dps_report_token: String::new(),
+            lock_pin_hash: String::new(),
+            streamer_mode: false,
+            defer_work_in_combat: false,
+            adaptive_throttling: false,
+            split_by_map: false,
+            show_advanced_settings: false,
+            low_priority_background: true,
+            check_report_urls: false,
+            results_actions: Vec::new(),
+            server_cleanup_enabled: false,
+            server_cleanup_months: 6,
+            last_server_cleanup: 0,
+            uploaded_archive_days: 30,
+            upload_concurrency: 3,
+            live_mode: false,
+            auto_process_enabled: false,
+            auto_process_minutes: 15,
+            inbox_enabled: false,
+            network_consent: String::new(),
+            speak_status_changes: false,
+            select_all_threshold: 50,
+            select_newest_count: 30,
+            min_fight_seconds: 0,
+            min_squad_size: 0,
+            min_log_size_kb: 0,
+            low_bandwidth_mode: false,
+            upload_limit_mbps: 0,
+            smallest_uploads_first: true,
+            compress_evtc_uploads: true,
+            post_process_hooks: Vec::new(),
+            organize_pattern: String::new(),
+            archive_uploaded_logs: false,
+            archive_directory: String::new(),
+            artifact_download_dir: String::new(),
+            profiles: Vec::new(),
+            active_profile: String::new(),
+            wvw_region: WvwRegion::Eu,
+        }
+    }
+
+    /// Number of uploads to run at once, kept within the size of the worker pool
+    /// Low bandwidth mode uploads one log at a time
+    pub fn upload_concurrency(&self) -> usize {
+        if self.low_bandwidth_mode {
+            return 1;
+        }
+        (self.upload_concurrency as usize).clamp(1, MAX_UPLOAD_CONCURRENCY)
+    }
+
+    /// Whether processing runs the legacy parser, always off in low bandwidth mode
+    pub fn legacy_parser_enabled(&self) -> bool {
+        self.enable_legacy_parser && !self.low_bandwidth_mode
+    }
+
+    /// dps.report token sent along for processing, empty in low bandwidth mode so nothing is forwarded
+    pub fn forwarded_dps_report_token(&self) -> &str {
+        if self.low_bandwidth_mode {
+            ""
+        } else {
+            &self.dps_report_token
         }
     }
 
@@ -73,12 +513,75 @@ impl Settings {
         self.guild_name = String::new();
         self.enable_legacy_parser = false;
         self.dps_report_token = String::new();
+        self.results_actions = default_results_actions();
+        self.organize_pattern = default_organize_pattern();
     }
 
     pub fn get() -> MutexGuard<'static, Self> {
         SETTINGS.lock().unwrap()
     }
 
+    /// Copy of the last settings file that loaded successfully
+    pub fn backup_path(path: &Path) -> PathBuf {
+        path.with_file_name("settings.backup.json")
+    }
+
+    /// Fresh defaults, as used on first launch
+    fn initialized() -> Self {
+        let mut settings = Self::default();
+        settings.init();
+        settings
+    }
+
+    /// Returns the sections of a settings file whose values can't be read
+    /// None means the file isn't a JSON object at all, so no single section can be blamed
+    pub fn invalid_sections(contents: &str) -> Option<Vec<&'static str>> {
+        let value: serde_json::Value = serde_json::from_str(contents).ok()?;
+        if !value.is_object() {
+            return None;
+        }
+        let defaults = serde_json::to_value(Self::initialized()).ok()?;
+
+        let mut invalid = Vec::new();
+        for (name, keys) in SETTINGS_SECTIONS.iter() {
+            // Check the section on top of known-good defaults so other sections can't fail it
+            let mut probe = defaults.clone();
+            for key in keys.iter() {
+                if let Some(v) = value.get(*key) {
+                    probe[*key] = v.clone();
+                }
+            }
+            if serde_json::from_value::<Self>(probe).is_err() {
+                invalid.push(*name);
+            }
+        }
+        Some(invalid)
+    }
+
+    /// Replaces one section of the settings file with defaults, leaving the rest untouched
+    pub fn reset_section(path: impl AsRef<Path>, section: &str) -> Result<()> {
+        let path = path.as_ref();
+        let (_, keys) = SETTINGS_SECTIONS
+            .iter()
+            .find(|(name, _)| *name == section)
+            .ok_or_else(|| anyhow!("Unknown settings section: {}", section))?;
+
+        let contents = std::fs::read_to_string(path)?;
+        let mut value: serde_json::Value = serde_json::from_str(&contents)?;
+        let defaults = serde_json::to_value(Self::initialized())?;
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("settings.json is not a JSON object"))?;
+
+        for key in keys.iter() {
+            object.insert(key.to_string(), defaults[*key].clone());
+        }
+
+        std::fs::write(path, serde_json::to_string_pretty(&value)?)?;
+        log::info!("Reset settings section '{}'", section);
+        Ok(())
+    }
+
     pub fn default_log_dir() -> PathBuf {
         let mut base = document_dir().unwrap_or_default();
         base.push("Guild Wars 2");
@@ -120,6 +623,11 @@ impl Settings {
             
             log::info!("Parsed settings - log_directory: '{}'", settings.log_directory);
             *SETTINGS.lock().unwrap() = settings;
+
+            // Keep the last file that loaded fine around for safe mode
+            if let Err(e) = std::fs::copy(path, Self::backup_path(path)) {
+                log::warn!("Failed to back up settings: {}", e);
+            }
         } else {
             log::info!("Settings file doesn't exist, initializing defaults");
             let mut settings = SETTINGS.lock().unwrap();
@@ -152,6 +660,12 @@ impl Settings {
 
     pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
         let path = path.as_ref();
+
+        // A damaged settings.json stays on disk until the user decides what to do with it
+        if crate::state::STATE.settings_safe_mode.lock().unwrap().is_some() {
+            return Err(anyhow!("Settings are in safe mode, not overwriting settings.json"));
+        }
+
         let prefix = path.parent().unwrap();
         create_dir_all(prefix)?;
         
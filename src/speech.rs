@@ -0,0 +1,128 @@
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Media::Speech::{ISpVoice, SpVoice, SPF_ASYNC, SPF_IS_NOT_XML, SPF_PURGEBEFORESPEAK};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_MULTITHREADED,
+};
+
+use crate::settings::Settings;
+use crate::state::{ProcessingState, STATE};
+
+/// How often the speech thread checks for unload while nothing is queued
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+static SENDER: Mutex<Option<Sender<String>>> = Mutex::new(None);
+
+/// Status seen last frame, announcements are made on changes
+static LAST_STATE: Mutex<Option<ProcessingState>> = Mutex::new(None);
+static LAST_FAILED_UPLOADS: Mutex<usize> = Mutex::new(0);
+
+/// Starts the thread owning the Windows text-to-speech voice
+pub fn start() {
+    let (tx, rx) = mpsc::channel();
+    *SENDER.lock().unwrap() = Some(tx);
+    crate::threads::spawn_tracked("speech", move || run(rx));
+}
+
+fn run(rx: Receiver<String>) {
+    // The voice is a COM object, it's created and used on this thread only
+    if let Err(e) = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.ok() {
+        log::warn!("Text-to-speech unavailable, COM failed to start: {}", e);
+        return;
+    }
+
+    let voice: Option<ISpVoice> = match unsafe { CoCreateInstance(&SpVoice, None, CLSCTX_ALL) } {
+        Ok(voice) => Some(voice),
+        Err(e) => {
+            log::warn!("Text-to-speech unavailable: {}", e);
+            None
+        }
+    };
+
+    loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(text) => {
+                if let Some(voice) = &voice {
+                    let flags = (SPF_ASYNC.0 | SPF_IS_NOT_XML.0) as u32;
+                    if let Err(e) = unsafe { voice.Speak(&HSTRING::from(text), flags, None) } {
+                        log::warn!("Failed to speak status: {}", e);
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if crate::cancel::is_shutting_down() {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // Cut off anything still being spoken, the addon is going away
+    if let Some(voice) = voice {
+        let _ = unsafe { voice.Speak(PCWSTR::null(), SPF_PURGEBEFORESPEAK.0 as u32, None) };
+        drop(voice);
+    }
+    unsafe { CoUninitialize() };
+}
+
+/// Reads a message aloud, whether or not announcements are turned on
+pub fn speak(text: &str) {
+    log::debug!("Speaking: {}", text);
+    if let Some(sender) = SENDER.lock().unwrap().as_ref() {
+        let _ = sender.send(text.to_string());
+    }
+}
+
+/// Announces upload and processing changes, called every frame
+pub fn check_status() {
+    let state = *STATE.processing_state.lock().unwrap();
+    let failed_uploads = if state == ProcessingState::Uploading {
+        STATE
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|l| l.selected && l.status.starts_with("Failed"))
+            .count()
+    } else {
+        0
+    };
+
+    let previous = LAST_STATE.lock().unwrap().replace(state);
+    let previous_failed = std::mem::replace(&mut *LAST_FAILED_UPLOADS.lock().unwrap(), failed_uploads);
+
+    // Nothing to compare against on the first frame
+    let Some(previous) = previous else {
+        return;
+    };
+    if !Settings::get().speak_status_changes {
+        return;
+    }
+
+    if failed_uploads > previous_failed {
+        speak(&format!(
+            "{} failed to upload",
+            crate::formatting::plural((failed_uploads - previous_failed) as u64, "log", "logs")
+        ));
+    }
+
+    if state == previous {
+        return;
+    }
+
+    match (previous, state) {
+        (ProcessingState::Uploading, ProcessingState::Idle) => {
+            speak("Uploads complete, ready to process");
+        }
+        (ProcessingState::Uploading, ProcessingState::Complete) => {
+            speak("Uploads to dps report complete");
+        }
+        (_, ProcessingState::Complete) => speak("Processing complete, reports are ready"),
+        (_, ProcessingState::Failed) => speak("Processing failed"),
+        _ => {}
+    }
+}
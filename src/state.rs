@@ -4,8 +4,9 @@ use std::sync::{
 };
 use std::thread;
 
+use crate::cancel::CancelToken;
 use crate::common::WorkerMessage;
-use crate::upload_review::UploadedFileInfo;
+use crate::upload_review::{SplitSession, UploadedFileInfo};
 use crate::logfile::LogFile;
 use crate::upload;
 
@@ -30,19 +31,32 @@ pub struct State {
     // ============================================
     // Worker Threads & Communication
     // ============================================
-    pub upload_worker: Mutex<Option<Sender<upload::UploadJob>>>,
     pub producer_rx: Mutex<Option<Receiver<WorkerMessage>>>,
     pub threads: Mutex<Vec<thread::JoinHandle<()>>>,
+    pub thread_panics: Mutex<Vec<crate::threads::ThreadPanic>>,
+    /// Crash reports from earlier runs the user hasn't looked at yet
+    pub crash_reports: Mutex<Vec<std::path::PathBuf>>,
+    pub upload_cancel: Mutex<Option<CancelToken>>,
 
     // ============================================
     // Log Management
     // ============================================
     pub logs: Mutex<Vec<LogFile>>,
     pub selected_time_filter: Mutex<TimeFilter>,
+    /// Map ids unticked in the map filter, logs on unknown maps always show
+    pub hidden_maps: Mutex<Vec<u16>>,
+    /// Text the commander or recorder name has to contain, empty shows every log
+    pub log_name_filter: Mutex<String>,
     pub last_auto_scan: Mutex<Option<std::time::Instant>>,
     pub last_scan_display: Mutex<String>,
     pub current_scan_id: Mutex<u64>,
     pub scan_in_progress: Mutex<bool>,
+    /// Logs parsed and found by the running scan
+    pub scan_progress: Mutex<(usize, usize)>,
+    pub list_interaction_active: Mutex<bool>,
+    pub scan_refresh_queued: Mutex<bool>,
+    /// Logs that likely continue a fight split across files, as filename to previous filename
+    pub log_continuations: Mutex<Vec<(String, String)>>,
 
     // ============================================
     // Upload & Processing State
@@ -50,6 +64,11 @@ pub struct State {
     pub session_id: Mutex<String>,
     pub ownership_token: Mutex<String>,
     pub report_urls: Mutex<Vec<String>>,
+    pub report_url_health: Mutex<Vec<(String, upload::UrlHealth)>>,
+    /// JSON and CSV files of the finished session, listed on the results screen
+    pub report_artifacts: Mutex<Vec<crate::report_history::ReportArtifact>>,
+    /// Download state of each artifact by URL, "Downloading", "Saved to ..." or the error
+    pub artifact_downloads: Mutex<Vec<(String, String)>>,
     pub processing_state: Mutex<ProcessingState>,
     pub last_status_check: Mutex<Option<std::time::Instant>>,
     pub processing_progress: Mutex<f32>,
@@ -57,6 +76,17 @@ pub struct State {
     pub uploaded_files: Mutex<Vec<UploadedFileInfo>>,
     pub processing_time_estimate: Mutex<Option<u32>>,
     pub processing_time_estimate_start: Mutex<Option<std::time::Instant>>,
+    pub split_sessions: Mutex<Vec<SplitSession>>,
+    pub time_split_busy: Mutex<bool>,
+    pub time_split_message: Mutex<String>,
+    /// Where the logs of the current session are uploaded to
+    pub upload_backend: Mutex<upload::UploadBackend>,
+    /// Server the current session talks to instead of the configured endpoint, empty for the setting
+    pub endpoint_override: Mutex<String>,
+    /// Permalinks of the logs uploaded to dps.report in the current session
+    pub dps_report_uploads: Mutex<Vec<upload::DpsReportUpload>>,
+    /// Session saved by the last run, waiting for the user to resume or discard it
+    pub resumable_session: Mutex<Option<crate::session::SavedSession>>,
 
     // ============================================
     // UI Window Visibility
@@ -70,6 +100,8 @@ pub struct State {
     #[allow(dead_code)]
     pub show_recent_logs: Mutex<bool>,
     pub show_uploaded_logs: Mutex<bool>,
+    /// Uploads logs whose contents were already uploaded under another name
+    pub force_duplicate_uploads: Mutex<bool>,
     pub show_upload_review: Mutex<bool>,
     pub token_modal_should_close: Mutex<bool>,
 
@@ -87,6 +119,8 @@ pub struct State {
     pub token_validation_message: Mutex<String>,
     pub token_validation_message_until: Mutex<Option<std::time::Instant>>,
     pub token_validation_is_error: Mutex<bool>,
+    /// Last token validated and whether it was valid, for the warning badge
+    pub token_check: Mutex<Option<(String, bool)>>,
 
     // ============================================
     // Token Manager (Settings Page)
@@ -106,6 +140,8 @@ pub struct State {
     pub sync_arcdps_message: Mutex<String>,
     pub sync_arcdps_message_until: Mutex<Option<std::time::Instant>>,
     pub sync_arcdps_message_is_error: Mutex<bool>,
+    pub dir_estimate: Mutex<Option<crate::scanning::DirectoryEstimate>>,
+    pub dir_estimate_pending: Mutex<bool>,
 
     // ============================================
     // Cleanup Operations
@@ -114,6 +150,44 @@ pub struct State {
     pub cleanup_result: Mutex<Option<Result<(usize, u64), String>>>,
     pub cleanup_message_until: Mutex<Option<std::time::Instant>>,
     pub auto_cleanup_done: Mutex<bool>,
+    pub stranded_cleanup_folders: Mutex<Vec<std::path::PathBuf>>,
+    pub stranded_cleanup_busy: Mutex<bool>,
+    pub stranded_cleanup_message: Mutex<String>,
+    pub server_cleanup_running: Mutex<bool>,
+    pub server_cleanup_summary: Mutex<String>,
+    pub bulk_delete_running: Mutex<bool>,
+    pub bulk_delete_results: Mutex<Vec<crate::cleanup::BulkDeleteResult>>,
+    pub organize_running: Mutex<bool>,
+    pub organize_summary: Mutex<String>,
+
+    // ============================================
+    // Unprocessed Server Sessions
+    // ============================================
+    pub pending_sessions: Mutex<Vec<upload::PendingSession>>,
+    pub pending_sessions_loading: Mutex<bool>,
+    pub pending_sessions_message: Mutex<String>,
+
+    // ============================================
+    // Upload Audit
+    // ============================================
+    pub audit_running: Mutex<bool>,
+    pub audit_findings: Mutex<Option<Vec<crate::audit::AuditFinding>>>,
+    pub audit_message: Mutex<String>,
+
+    // ============================================
+    // Re-parse
+    // ============================================
+    pub reparse_busy: Mutex<bool>,
+    pub reparse_message: Mutex<String>,
+
+    // ============================================
+    // Live Mode
+    // ============================================
+    pub live_queue: Mutex<Vec<crate::watcher::LiveLog>>,
+    /// When live mode last uploaded a log, auto-processing waits for this to go quiet
+    pub live_last_upload: Mutex<Option<std::time::Instant>>,
+    /// The running processing was started by auto-processing
+    pub auto_processing: Mutex<bool>,
 
     // ============================================
     // UI Resources & Misc
@@ -129,10 +203,33 @@ pub struct State {
     pub webhook_url_input: Mutex<String>,
     pub webhook_remember: Mutex<bool>,
     pub webhook_sending: Mutex<bool>,
+    pub webhook_cancel: Mutex<Option<CancelToken>>,
     pub webhook_status_message: Mutex<String>,
     pub webhook_status_until: Mutex<Option<std::time::Instant>>,
     pub webhook_status_is_error: Mutex<bool>,
-    pub webhook_selected_name: Mutex<String>,
+    /// Saved webhooks ticked in the send modal
+    pub webhook_selected_names: Mutex<Vec<String>>,
+    /// Status of the last send per webhook name
+    pub webhook_deliveries: Mutex<Vec<(String, crate::webhooks::WebhookDelivery)>>,
+    pub webhook_report_urls: Mutex<Vec<String>>,
+    pub webhook_report_summary: Mutex<crate::webhooks::ReportSummary>,
+    /// Session the raid start announcement was posted for
+    pub announced_session: Mutex<String>,
+
+    // ============================================
+    // Settings Lock
+    // ============================================
+    pub settings_unlocked: Mutex<bool>,
+
+    // ============================================
+    // Combat Deferral
+    // ============================================
+    pub work_deferred: Mutex<bool>,
+
+    // ============================================
+    // Settings Safe Mode
+    // ============================================
+    pub settings_safe_mode: Mutex<Option<String>>,
 }
 
 impl State {
@@ -147,14 +244,11 @@ impl State {
         tx
     }
 
-    pub fn init_upload_worker(&self) -> Receiver<upload::UploadJob> {
-        let (tx, rx) = mpsc::channel();
-        *self.upload_worker.lock().unwrap() = Some(tx);
-        rx
-    }
-
     pub fn append_thread(&self, handle: thread::JoinHandle<()>) {
-        self.threads.lock().unwrap().push(handle);
+        let mut threads = self.threads.lock().unwrap();
+        // Finished threads have nothing left to wait for on unload
+        threads.retain(|t| !t.is_finished());
+        threads.push(handle);
     }
 }
 
@@ -162,19 +256,27 @@ pub static STATE: State = State {
     // ============================================
     // Worker Threads & Communication
     // ============================================
-    upload_worker: Mutex::new(None),
     producer_rx: Mutex::new(None),
     threads: Mutex::new(Vec::new()),
+    thread_panics: Mutex::new(Vec::new()),
+    crash_reports: Mutex::new(Vec::new()),
+    upload_cancel: Mutex::new(None),
 
     // ============================================
     // Log Management
     // ============================================
     logs: Mutex::new(Vec::new()),
     selected_time_filter: Mutex::new(TimeFilter::SincePluginStart),
+    hidden_maps: Mutex::new(Vec::new()),
+    log_name_filter: Mutex::new(String::new()),
     last_auto_scan: Mutex::new(None),
     last_scan_display: Mutex::new(String::new()),
     current_scan_id: Mutex::new(0),
     scan_in_progress: Mutex::new(false), 
+    scan_progress: Mutex::new((0, 0)),
+    list_interaction_active: Mutex::new(false),
+    scan_refresh_queued: Mutex::new(false),
+    log_continuations: Mutex::new(Vec::new()),
 
     // ============================================
     // Upload & Processing State
@@ -182,6 +284,9 @@ pub static STATE: State = State {
     session_id: Mutex::new(String::new()),
     ownership_token: Mutex::new(String::new()),
     report_urls: Mutex::new(Vec::new()),
+    report_url_health: Mutex::new(Vec::new()),
+    report_artifacts: Mutex::new(Vec::new()),
+    artifact_downloads: Mutex::new(Vec::new()),
     processing_state: Mutex::new(ProcessingState::Idle),
     last_status_check: Mutex::new(None),
     processing_progress: Mutex::new(0.0),
@@ -189,6 +294,13 @@ pub static STATE: State = State {
     uploaded_files: Mutex::new(Vec::new()),
     processing_time_estimate: Mutex::new(None),
     processing_time_estimate_start: Mutex::new(None),
+    split_sessions: Mutex::new(Vec::new()),
+    time_split_busy: Mutex::new(false),
+    time_split_message: Mutex::new(String::new()),
+    upload_backend: Mutex::new(upload::UploadBackend::Parser),
+    endpoint_override: Mutex::new(String::new()),
+    dps_report_uploads: Mutex::new(Vec::new()),
+    resumable_session: Mutex::new(None),
 
     // ============================================
     // UI Window Visibility
@@ -201,6 +313,7 @@ pub static STATE: State = State {
     show_settings: Mutex::new(false),
     show_recent_logs: Mutex::new(true),
     show_uploaded_logs: Mutex::new(true),
+    force_duplicate_uploads: Mutex::new(false),
     show_upload_review: Mutex::new(false),
     token_modal_should_close: Mutex::new(false),
 
@@ -218,6 +331,7 @@ pub static STATE: State = State {
     token_validation_message: Mutex::new(String::new()),
     token_validation_message_until: Mutex::new(None),
     token_validation_is_error: Mutex::new(false),
+    token_check: Mutex::new(None),
 
     // ============================================
     // Token Manager (Settings Page)
@@ -237,6 +351,8 @@ pub static STATE: State = State {
     sync_arcdps_message: Mutex::new(String::new()),
     sync_arcdps_message_until: Mutex::new(None),
     sync_arcdps_message_is_error: Mutex::new(false),
+    dir_estimate: Mutex::new(None),
+    dir_estimate_pending: Mutex::new(false),
 
     // ============================================
     // Cleanup Operations
@@ -245,6 +361,42 @@ pub static STATE: State = State {
     cleanup_result: Mutex::new(None),
     cleanup_message_until: Mutex::new(None),
     auto_cleanup_done: Mutex::new(false),
+    stranded_cleanup_folders: Mutex::new(Vec::new()),
+    stranded_cleanup_busy: Mutex::new(false),
+    stranded_cleanup_message: Mutex::new(String::new()),
+    server_cleanup_running: Mutex::new(false),
+    server_cleanup_summary: Mutex::new(String::new()),
+    bulk_delete_running: Mutex::new(false),
+    bulk_delete_results: Mutex::new(Vec::new()),
+    organize_running: Mutex::new(false),
+    organize_summary: Mutex::new(String::new()),
+
+    // ============================================
+    // Unprocessed Server Sessions
+    // ============================================
+    pending_sessions: Mutex::new(Vec::new()),
+    pending_sessions_loading: Mutex::new(false),
+    pending_sessions_message: Mutex::new(String::new()),
+
+    // ============================================
+    // Upload Audit
+    // ============================================
+    audit_running: Mutex::new(false),
+    audit_findings: Mutex::new(None),
+    audit_message: Mutex::new(String::new()),
+
+    // ============================================
+    // Re-parse
+    // ============================================
+    reparse_busy: Mutex::new(false),
+    reparse_message: Mutex::new(String::new()),
+
+    // ============================================
+    // Live Mode
+    // ============================================
+    live_queue: Mutex::new(Vec::new()),
+    live_last_upload: Mutex::new(None),
+    auto_processing: Mutex::new(false),
 
     // ============================================
     // UI Resources & Misc
@@ -260,8 +412,33 @@ pub static STATE: State = State {
     webhook_url_input: Mutex::new(String::new()),
     webhook_remember: Mutex::new(false),
     webhook_sending: Mutex::new(false),
+    webhook_cancel: Mutex::new(None),
     webhook_status_message: Mutex::new(String::new()),
     webhook_status_until: Mutex::new(None),
     webhook_status_is_error: Mutex::new(false),
-    webhook_selected_name: Mutex::new(String::new()),    
+    webhook_selected_names: Mutex::new(Vec::new()),
+    webhook_deliveries: Mutex::new(Vec::new()),
+    webhook_report_urls: Mutex::new(Vec::new()),
+    webhook_report_summary: Mutex::new(crate::webhooks::ReportSummary {
+        fights: 0,
+        duration_secs: None,
+        maps: Vec::new(),
+        commanders: Vec::new(),
+    }),
+    announced_session: Mutex::new(String::new()),
+
+    // ============================================
+    // Settings Lock
+    // ============================================
+    settings_unlocked: Mutex::new(false),
+
+    // ============================================
+    // Combat Deferral
+    // ============================================
+    work_deferred: Mutex::new(false),
+
+    // ============================================
+    // Settings Safe Mode
+    // ============================================
+    settings_safe_mode: Mutex::new(None),
 };
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+
+use crate::state::STATE;
+
+/// Panics kept for the error panel, older ones are dropped
+const MAX_THREAD_PANICS: usize = 20;
+
+/// A panic caught by the panic hook
+#[derive(Debug, Clone)]
+pub struct ThreadPanic {
+    pub thread: String,
+    pub message: String,
+    pub location: String,
+    pub time: String,
+}
+
+/// Spawns a named background thread
+/// The name shows up in the log and in panic reports
+pub fn spawn<F, T>(name: &str, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::Builder::new()
+        .name(format!("wvw-insights-{}", name))
+        .spawn(f)
+        .unwrap_or_else(|e| panic!("Could not create {} thread: {}", name, e))
+}
+
+/// Spawns a named background thread that unload waits for
+/// For work that must not be cut off halfway, like moving files
+pub fn spawn_tracked<F>(name: &str, f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    STATE.append_thread(spawn(name, f));
+}
+
+/// Background work running right now, by id, shown in the activity line of the main window
+static ACTIVITIES: Mutex<Vec<(u64, &'static str)>> = Mutex::new(Vec::new());
+static NEXT_ACTIVITY_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Keeps a piece of background work listed as running until it is dropped
+pub struct Activity {
+    id: u64,
+}
+
+impl Drop for Activity {
+    fn drop(&mut self) {
+        ACTIVITIES.lock().unwrap().retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// Lists work as running while the returned guard lives, e.g. "Scanning logs"
+pub fn activity(label: &'static str) -> Activity {
+    let id = NEXT_ACTIVITY_ID.fetch_add(1, Ordering::Relaxed);
+    ACTIVITIES.lock().unwrap().push((id, label));
+    Activity { id }
+}
+
+/// What is running in the background, e.g. "Scanning logs, Uploading (3)", None when nothing is
+pub fn activity_summary() -> Option<String> {
+    let activities = ACTIVITIES.lock().unwrap();
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for (_, label) in activities.iter() {
+        match counts.iter_mut().find(|(l, _)| l == label) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((label, 1)),
+        }
+    }
+    if counts.is_empty() {
+        return None;
+    }
+
+    let parts: Vec<String> = counts
+        .into_iter()
+        .map(|(label, count)| if count > 1 { format!("{} ({})", label, count) } else { label.to_string() })
+        .collect();
+    Some(parts.join(", "))
+}
+
+/// Logs panics with the thread they happened on and keeps them for the error panel
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let thread = thread::current()
+            .name()
+            .map(String::from)
+            .unwrap_or_else(|| format!("{:?}", thread::current().id()));
+
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic".to_string()
+        };
+
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_default();
+
+        log::error!("Thread '{}' panicked at {}: {}", thread, location, message);
+
+        crate::crash::write_panic_report(&thread, &message, &location);
+
+        // Never panic inside the hook, a poisoned lock just loses the entry
+        if let Ok(mut panics) = STATE.thread_panics.lock() {
+            if panics.len() >= MAX_THREAD_PANICS {
+                panics.remove(0);
+            }
+            panics.push(ThreadPanic {
+                thread,
+                message,
+                location,
+                time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            });
+        }
+    }));
+}
+
+/// Restores the default panic hook before the addon is unloaded
+pub fn remove_panic_hook() {
+    let _ = std::panic::take_hook();
+}
@@ -0,0 +1,115 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::settings::Settings;
+
+static THROTTLED: AtomicBool = AtomicBool::new(false);
+
+/// Throttled once frames take this much longer than the player's usual frame time
+const THROTTLE_ABOVE_RATIO: f32 = 1.5;
+/// Resumed once frames are back within this much of the usual frame time
+const RESUME_BELOW_RATIO: f32 = 1.2;
+/// Weight of the newest frame in the moving average
+const SMOOTHING: f32 = 0.05;
+/// Weight of the newest frame in the usual frame time, a few minutes of play
+const BASELINE_SMOOTHING: f32 = 0.0005;
+/// Longest a worker waits for the game to recover before it carries on anyway
+const MAX_PAUSE: Duration = Duration::from_secs(30);
+/// Frames longer than this are loading screens or alt-tabs, not fights
+const IGNORE_ABOVE_MS: f32 = 1000.0;
+
+/// Delay added per parsed file while scanning under load
+const SCAN_DELAY: Duration = Duration::from_millis(50);
+
+thread_local! {
+    static AVERAGE_FRAME_MS: Cell<f32> = const { Cell::new(0.0) };
+    static BASELINE_FRAME_MS: Cell<f32> = const { Cell::new(0.0) };
+}
+
+/// Feeds the latest frame time into the moving average
+/// This should be called every frame from the render function
+pub fn record_frame(delta_seconds: f32) {
+    let frame_ms = delta_seconds * 1000.0;
+    if frame_ms <= 0.0 || frame_ms > IGNORE_ABOVE_MS {
+        return;
+    }
+
+    let average = AVERAGE_FRAME_MS.get();
+    let average = if average == 0.0 {
+        frame_ms
+    } else {
+        average + (frame_ms - average) * SMOOTHING
+    };
+    AVERAGE_FRAME_MS.set(average);
+
+    // The player's usual frame time, so a 30 fps cap or a slow PC isn't treated as a struggling game
+    let baseline = BASELINE_FRAME_MS.get();
+    let baseline = if baseline == 0.0 {
+        frame_ms
+    } else {
+        baseline + (frame_ms - baseline) * BASELINE_SMOOTHING
+    };
+    BASELINE_FRAME_MS.set(baseline);
+
+    let was_throttled = THROTTLED.load(Ordering::Relaxed);
+    let throttled = if was_throttled {
+        average > baseline * RESUME_BELOW_RATIO
+    } else {
+        average > baseline * THROTTLE_ABOVE_RATIO
+    };
+
+    if throttled != was_throttled {
+        THROTTLED.store(throttled, Ordering::Relaxed);
+        if throttled {
+            log::info!("Frame time {:.1}ms (usually {:.1}ms) - throttling background work", average, baseline);
+        } else {
+            log::info!("Frame time {:.1}ms (usually {:.1}ms) - background work back to full speed", average, baseline);
+        }
+    }
+}
+
+/// Returns true if background work is currently throttled because the game is struggling
+pub fn is_throttled() -> bool {
+    THROTTLED.load(Ordering::Relaxed) && Settings::get().adaptive_throttling
+}
+
+/// Blocks the calling worker thread while the game is under heavy load, for at most MAX_PAUSE
+/// Returns early if the addon is unloading so the thread can be joined
+pub fn wait_while_throttled(cancel: &crate::cancel::CancelToken) {
+    let started = Instant::now();
+    while is_throttled() {
+        if started.elapsed() >= MAX_PAUSE {
+            log::info!("Game still under load after {}s, continuing background work", MAX_PAUSE.as_secs());
+            break;
+        }
+        if !cancel.sleep(Duration::from_millis(500)) {
+            break;
+        }
+    }
+}
+
+/// Sets the calling thread's priority for background work
+/// Below normal unless disabled in settings, so uploads and scans never compete with the game's render threads
+pub fn apply_background_priority() {
+    use windows::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL, THREAD_PRIORITY_NORMAL,
+    };
+
+    let priority = if Settings::get().low_priority_background {
+        THREAD_PRIORITY_BELOW_NORMAL
+    } else {
+        THREAD_PRIORITY_NORMAL
+    };
+
+    if let Err(e) = unsafe { SetThreadPriority(GetCurrentThread(), priority) } {
+        log::warn!("Failed to set background thread priority: {}", e);
+    }
+}
+
+/// Slows down a scan loop while the game is under heavy load
+pub fn pace() {
+    if is_throttled() {
+        std::thread::sleep(SCAN_DELAY);
+    }
+}
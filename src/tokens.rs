@@ -15,6 +15,7 @@ struct ValidationResponse {
 
 /// Generates a new history token from the server
 pub fn generate_token() -> Result<String> {
+    crate::consent::require()?;
     let url = "https://parser.rethl.net/api.php?endpoint=generate-token";
     
     let response = ureq::get(url).call()?;
@@ -32,6 +33,7 @@ pub fn generate_token() -> Result<String> {
 
 /// Validates a history token with the server
 pub fn validate_token(api_endpoint: &str, token: &str) -> Result<bool> {
+    crate::consent::require()?;
     let url = format!("{}?endpoint=nexus-validate-token", api_endpoint);
     
     let response = ureq::post(&url)
@@ -40,4 +42,116 @@ pub fn validate_token(api_endpoint: &str, token: &str) -> Result<bool> {
     let validation_resp: ValidationResponse = response.into_json()?;
     
     Ok(validation_resp.valid)
-}
\ No newline at end of file
+}
+
+/// Checks the saved history token in the background, so a token deleted on the server
+/// is flagged on the token screen instead of failing once logs are uploaded
+pub fn check_saved_token() {
+    let settings = crate::settings::Settings::get();
+    let api_endpoint = settings.api_endpoint.clone();
+    let token = settings.history_token.clone();
+    drop(settings);
+
+    if token.is_empty() || api_endpoint.is_empty() || !crate::consent::is_granted() {
+        return;
+    }
+    if crate::settings::protected::is_unreadable(&token) {
+        log::warn!("The saved history token was saved by another Windows user or PC, enter it again to use it here");
+        return;
+    }
+
+    crate::threads::spawn("token-check", move || match validate_token(&api_endpoint, &token) {
+        Ok(valid) => {
+            if !valid {
+                log::warn!("Saved history token is no longer valid");
+            }
+            record_check(&token, valid);
+        }
+        // Offline or server trouble says nothing about the token
+        Err(e) => log::debug!("Could not check the saved history token: {}", e),
+    });
+}
+
+/// Selects the default webhook of a saved token after switching to it
+/// Tokens that aren't saved or have no default leave the webhook as it is
+pub fn apply_token_defaults(token: &str) {
+    let webhook = crate::settings::Settings::get()
+        .saved_tokens
+        .iter()
+        .find(|t| t.token == token)
+        .map(|t| t.webhook.clone())
+        .unwrap_or_default();
+    if webhook.is_empty() {
+        return;
+    }
+
+    let mut webhooks = crate::webhooks::WebhookSettings::get();
+    if !webhooks.select_default(&webhook) {
+        log::warn!("Default webhook '{}' of the token is not saved anymore", webhook);
+        return;
+    }
+    if let Err(e) = webhooks.store(crate::webhooks_path()) {
+        log::error!("Failed to save webhook settings: {}", e);
+    }
+    log::info!("Switched reports to webhook '{}' for this token", webhook);
+}
+
+/// Name reports made with the token are posted under, from the saved token's template when it has one
+pub fn report_name(token: &str, date: &str) -> String {
+    let template = crate::settings::Settings::get()
+        .saved_tokens
+        .iter()
+        .find(|t| t.token == token)
+        .map(|t| t.report_name.trim().to_string())
+        .unwrap_or_default();
+    if template.is_empty() {
+        format!("WvW: {}", date)
+    } else {
+        template.replace("{date}", date)
+    }
+}
+
+/// Short hash a token is remembered by in the report history, so the token itself isn't stored there
+/// Empty for an empty token
+pub fn token_key(token: &str) -> String {
+    if token.is_empty() {
+        return String::new();
+    }
+    let hash = xxhash_rust::xxh3::xxh3_64(token.as_bytes());
+    format!("{:012x}", hash >> 16)
+}
+
+/// The token a history key belongs to, looked up among the saved tokens, profiles and the current token
+/// None when the token isn't kept anywhere anymore
+pub fn resolve_token_key(key: &str) -> Option<String> {
+    if key.is_empty() {
+        return None;
+    }
+    let settings = crate::settings::Settings::get();
+    std::iter::once(&settings.history_token)
+        .chain(settings.saved_tokens.iter().map(|t| &t.token))
+        .chain(settings.profiles.iter().map(|p| &p.history_token))
+        .find(|token| token_key(token) == key)
+        .cloned()
+}
+
+/// Name for today's reports of the current token
+pub fn current_report_name() -> String {
+    let token = crate::settings::Settings::get().history_token.clone();
+    report_name(&token, &chrono::Local::now().format("%d.%m.%y").to_string())
+}
+
+/// Remembers the outcome of a token validation for the token screen badge
+pub fn record_check(token: &str, valid: bool) {
+    *crate::state::STATE.token_check.lock().unwrap() = Some((token.to_string(), valid));
+}
+
+/// Whether the given token was found invalid by the last check
+pub fn is_known_invalid(token: &str) -> bool {
+    crate::state::STATE
+        .token_check
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(checked, valid)| checked == token && !valid)
+}
@@ -0,0 +1,24 @@
+use nexus::imgui::Ui;
+
+/// Frames of the spinner, the default font only has ASCII
+const SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// Renders a status line at the bottom of the main window while background work runs
+/// Sits below the content instead when that already reaches the bottom
+pub fn render_activity_line(ui: &Ui) {
+    let Some(summary) = crate::threads::activity_summary() else {
+        return;
+    };
+
+    let padding = ui.clone_style().window_padding[1];
+    let bottom = ui.window_size()[1] - ui.text_line_height_with_spacing() - padding + ui.scroll_y();
+    let [x, y] = ui.cursor_pos();
+    ui.set_cursor_pos([x, y.max(bottom)]);
+
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let frame = SPINNER[(millis / 150) as usize % SPINNER.len()];
+    ui.text_colored([0.6, 0.6, 0.6, 1.0], &format!("{} {}...", frame, summary));
+}
@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use nexus::imgui::Ui;
+
+use crate::state::STATE;
+
+/// Renders the screen listing every server the addon talks to, shown until the user allows it
+pub fn render_consent(ui: &Ui, config_path: &Path) {
+    ui.text_colored([1.0, 0.8, 0.2, 1.0], "Before WvW Insights goes online");
+    ui.spacing();
+    ui.text_wrapped(
+        "WvW Insights needs to contact the servers below to upload and process your logs. \
+         Nothing is sent anywhere until you allow it.",
+    );
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    for network_use in crate::consent::network_uses() {
+        ui.text(network_use.name);
+        ui.same_line();
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], &network_use.endpoint);
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], network_use.when);
+        for data in network_use.data {
+            ui.bullet_text(data);
+        }
+        ui.spacing();
+    }
+
+    ui.separator();
+    ui.spacing();
+
+    if ui.button("Allow") {
+        crate::consent::grant(config_path);
+    }
+
+    ui.same_line();
+
+    if ui.button("Open Settings") {
+        *STATE.show_settings.lock().unwrap() = true;
+    }
+
+    ui.spacing();
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Changing the API endpoint in the settings asks again before contacting the new server",
+    );
+}
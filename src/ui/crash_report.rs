@@ -0,0 +1,50 @@
+use nexus::imgui::Ui;
+
+use crate::state::STATE;
+
+/// Renders the prompt pointing at crash reports written by the last run
+pub fn render_crash_prompt(ui: &Ui) {
+    // One prompt at a time, the resume prompt goes first
+    if STATE.resumable_session.lock().unwrap().is_some() {
+        return;
+    }
+
+    let reports = STATE.crash_reports.lock().unwrap().clone();
+    let Some(latest) = reports.last() else {
+        return;
+    };
+
+    ui.open_popup("crash_report");
+
+    ui.popup_modal("crash_report")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text_colored([1.0, 0.5, 0.3, 1.0], "WvW Insights Ran Into a Problem");
+            ui.spacing();
+            ui.text_wrapped(&format!(
+                "{} written last time. They describe what the addon was doing, \
+                 without tokens or log names, and can be attached to a bug report.",
+                crate::formatting::plural(reports.len() as u64, "crash report was", "crash reports were")
+            ));
+            ui.spacing();
+
+            if ui.button("Open Latest Report") {
+                if let Err(e) = open::that_detached(latest) {
+                    log::error!("Failed to open crash report: {}", e);
+                }
+            }
+
+            ui.same_line();
+
+            if ui.button("Open Folder") {
+                crate::crash::open_folder();
+            }
+
+            ui.same_line();
+
+            if ui.button("Dismiss") {
+                crate::crash::dismiss();
+                ui.close_current_popup();
+            }
+        });
+}
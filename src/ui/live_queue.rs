@@ -0,0 +1,57 @@
+use nexus::imgui::{TreeNodeFlags, Ui};
+
+use crate::settings::Settings;
+use crate::state::STATE;
+use crate::watcher::LiveStatus;
+
+/// Renders the logs picked up by live mode while it is on
+pub fn render_live_queue(ui: &Ui) {
+    if !Settings::get().live_mode {
+        return;
+    }
+
+    let queue = STATE.live_queue.lock().unwrap().clone();
+    let pending = queue
+        .iter()
+        .filter(|l| matches!(l.status, LiveStatus::Writing | LiveStatus::Waiting | LiveStatus::Uploading))
+        .count();
+
+    let header = format!("Live Mode ({} pending)###live_queue", pending);
+    if ui.collapsing_header(&header, TreeNodeFlags::empty()) {
+        if queue.is_empty() {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], "Watching for new logs...");
+        }
+
+        for log in queue.iter() {
+            let color = match log.status {
+                LiveStatus::Writing | LiveStatus::Waiting => [0.7, 0.7, 0.7, 1.0],
+                LiveStatus::Uploading => [0.7, 0.9, 1.0, 1.0],
+                LiveStatus::Uploaded => [0.0, 1.0, 0.0, 1.0],
+                LiveStatus::Failed(_) => [1.0, 0.5, 0.5, 1.0],
+            };
+            ui.text(&log.filename);
+            ui.same_line();
+            ui.text_colored(color, &format!("- {}", log.status.label()));
+        }
+
+        if crate::mumble::should_defer_work() {
+            ui.text_colored([1.0, 0.6, 0.0, 1.0], "Uploads paused until you leave combat");
+        }
+
+        let has_failed = queue.iter().any(|l| matches!(l.status, LiveStatus::Failed(_)));
+        if has_failed && ui.small_button("Retry Failed") {
+            crate::watcher::retry_failed();
+        }
+
+        if queue.len() > pending {
+            if has_failed {
+                ui.same_line();
+            }
+            if ui.small_button("Clear Finished") {
+                crate::watcher::clear_finished();
+            }
+        }
+    }
+
+    ui.separator();
+}
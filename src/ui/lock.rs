@@ -0,0 +1,47 @@
+use nexus::imgui::Ui;
+
+thread_local! {
+    static UNLOCK_PIN_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static UNLOCK_ERROR: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+}
+
+/// Renders the "settings locked" notice with a PIN field to unlock
+pub fn render_lock_notice(ui: &Ui, what: &str) {
+    ui.text_colored([1.0, 0.6, 0.2, 1.0], "Locked");
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        &format!("{} can't be changed until the settings lock PIN is entered.", what),
+    );
+    ui.spacing();
+
+    ui.set_next_item_width(120.0);
+    let mut submitted = false;
+    UNLOCK_PIN_BUFFER.with_borrow_mut(|pin| {
+        if ui
+            .input_text("##unlock_pin", pin)
+            .password(true)
+            .enter_returns_true(true)
+            .hint("PIN")
+            .build()
+        {
+            submitted = true;
+        }
+    });
+
+    ui.same_line();
+
+    if ui.button("Unlock") || submitted {
+        let pin = UNLOCK_PIN_BUFFER.with_borrow(|pin| pin.clone());
+        if crate::lock::try_unlock(&pin) {
+            UNLOCK_ERROR.set(String::new());
+        } else {
+            UNLOCK_ERROR.set("Incorrect PIN".to_string());
+        }
+        UNLOCK_PIN_BUFFER.set(String::new());
+    }
+
+    let error = UNLOCK_ERROR.with_borrow(|e| e.clone());
+    if !error.is_empty() {
+        ui.text_colored([1.0, 0.3, 0.0, 1.0], &error);
+    }
+}
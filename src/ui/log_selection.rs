@@ -1,11 +1,42 @@
-use nexus::imgui::{ChildWindow, Ui};
+use nexus::imgui::{ChildWindow, Key, TreeNodeFlags, Ui};
 
 use crate::formatting::{format_timestamp};
+use crate::logfile::{LogFile, MapType};
 use crate::scanning::scan_for_logs;
 use crate::settings::Settings;
 use crate::state::{ProcessingState, TimeFilter, STATE};
+use crate::upload::UploadBackend;
+use crate::upload_review::SplitSession;
 use crate::uploaded_logs::UploadedLogs;
 
+thread_local! {
+    static IMPORT_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static IMPORT_RESULT: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    // Filename Shift+Click and Shift+arrow ranges start from
+    static SELECTION_ANCHOR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+    // Filename of the row moved with the arrow keys
+    static SELECTION_CURSOR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+    static SCROLL_TO_CURSOR: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static NEWEST_COUNT: std::cell::Cell<Option<i32>> = const { std::cell::Cell::new(None) };
+}
+
+/// Fixed counts offered next to the custom one by the "Select newest" quick action
+const NEWEST_PRESETS: [usize; 2] = [10, 20];
+
+/// Logs further apart than this belong to different raid sessions in the list
+const SESSION_GAP_SECS: u64 = 45 * 60;
+
+/// How a row was clicked, decided by the modifier keys held
+#[derive(Clone, Copy)]
+enum RowClick {
+    /// Plain click on the row, selects only that log
+    Only,
+    /// Ctrl+Click on the row, or any click on its checkbox
+    Toggle,
+    /// Shift+Click, selects everything from the anchor to the row
+    Range,
+}
+
 /// Renders the log selection screen
 pub fn render_log_selection(ui: &Ui) {
     let logs = STATE.logs.lock().unwrap();
@@ -52,12 +83,15 @@ pub fn render_log_selection(ui: &Ui) {
         *STATE.show_uploaded_logs.lock().unwrap() = show_uploaded;
     }
 
+    let filters_changed = render_map_filter(ui) | render_name_filter(ui);
+
     ui.spacing();
 
     // Refresh button
     if ui.button("Refresh") {
         drop(logs);
         *STATE.last_auto_scan.lock().unwrap() = Some(std::time::Instant::now());
+        crate::scanning::forget_directory_fingerprints();
         scan_for_logs();
         return;
     }
@@ -80,14 +114,24 @@ pub fn render_log_selection(ui: &Ui) {
 
     let mut logs = STATE.logs.lock().unwrap();
 
+    // Filtered out logs can't stay selected, they would be uploaded unseen
+    if filters_changed {
+        for log in logs.iter_mut().filter(|log| !passes_filters(log)) {
+            log.selected = false;
+        }
+    }
+
     ui.separator();
 
     // Show scanning indicator or empty message
     if scan_in_progress && logs.is_empty() {
         ui.text_colored(
             [0.7, 0.9, 1.0, 1.0],
-            "Scanning for logs...",
+            &scan_status_text("Scanning for logs"),
         );
+        if crate::throttle::is_throttled() {
+            ui.text_colored([1.0, 0.6, 0.0, 1.0], "Throttled due to combat load");
+        }
         ui.spacing();
 
         if ui.button("Open Settings") {
@@ -99,7 +143,7 @@ pub fn render_log_selection(ui: &Ui) {
         ui.same_line();
 
         if ui.button("Back") {
-            std::thread::spawn(|| {
+            crate::threads::spawn("back-navigation", || {
                 handle_back_navigation();
             });
             return;
@@ -124,7 +168,7 @@ pub fn render_log_selection(ui: &Ui) {
         ui.same_line();
 
         if ui.button("Back") {
-            std::thread::spawn(|| {
+            crate::threads::spawn("back-navigation", || {
                 handle_back_navigation();
             });
             return;
@@ -136,42 +180,33 @@ pub fn render_log_selection(ui: &Ui) {
     if scan_in_progress && !logs.is_empty() {
         ui.text_colored(
             [0.7, 0.9, 1.0, 1.0],
-            "Scanning for new logs...",
+            &scan_status_text("Scanning for new logs"),
         );
+        if crate::throttle::is_throttled() {
+            ui.text_colored([1.0, 0.6, 0.0, 1.0], "Throttled due to combat load");
+        }
         ui.spacing();
     }
 
     // Selection buttons
-    let show_select_all = matches!(
+    // Longer time ranges can hold a lot of logs, large selections there are confirmed first
+    let short_range = matches!(
         current_filter,
         TimeFilter::SincePluginStart | TimeFilter::Last24Hours
     );
 
-    if show_select_all {
-        if ui.button("Select All") {
-            let uploaded = UploadedLogs::get();
-            let show_uploaded = *STATE.show_uploaded_logs.lock().unwrap();
-            
-            for log in logs.iter_mut() {
-                if show_uploaded || !uploaded.is_uploaded(&log.filename) {
-                    log.selected = true;
-                }
+    if ui.button("Select All") {
+        let candidates = select_all_candidates(&logs);
+        let threshold = Settings::get().select_all_threshold as usize;
+        if short_range || candidates.len() <= threshold {
+            for index in candidates {
+                logs[index].selected = true;
             }
-            drop(uploaded);
-        }
-        ui.same_line();
-    } else {
-        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
-        let _style2 =
-            ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
-        let _style3 =
-            ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
-        ui.button("Select All");
-        if ui.is_item_hovered() {
-            ui.tooltip_text("Only available for 'This session' and 'Last 24 hours' filters");
+        } else {
+            ui.open_popup("confirm_select_all");
         }
-        ui.same_line();
     }
+    ui.same_line();
 
     if ui.button("Deselect All") {
         for log in logs.iter_mut() {
@@ -179,6 +214,26 @@ pub fn render_log_selection(ui: &Ui) {
         }
     }
 
+    ui.same_line();
+
+    render_select_newest(ui, &mut logs);
+
+    ui.same_line();
+
+    if ui.button("Import Selection") {
+        IMPORT_RESULT.set(String::new());
+        ui.open_popup("import_selection");
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Select logs from a pasted list of timestamps or filenames");
+    }
+
+    render_import_selection_popup(ui, &mut logs);
+    render_confirm_select_all_popup(ui, &mut logs);
+    let popup_open = ui.is_popup_open("import_selection")
+        || ui.is_popup_open("confirm_select_all")
+        || ui.is_popup_open("stranded_cleanup");
+
     ui.spacing();
 
     // Compact log list with better styling
@@ -213,6 +268,9 @@ pub fn render_log_selection(ui: &Ui) {
             let left_down = ui.is_mouse_down(MouseButton::Left);
             let right_down = ui.is_mouse_down(MouseButton::Right);
 
+            // Where a left click that didn't turn into a drag started
+            let mut click_pos: Option<[f32; 2]> = None;
+
             unsafe {
                 if left_clicked && ui.is_window_hovered() {
                     START_POS = Some(mouse_pos);
@@ -256,6 +314,10 @@ pub fn render_log_selection(ui: &Ui) {
                     }
                 }
 
+                if left_released && !DRAG_STARTED {
+                    click_pos = START_POS;
+                }
+
                 if left_released || right_released {
                     IS_DRAGGING = false;
                     IS_DESELECT_DRAG = false;
@@ -263,6 +325,10 @@ pub fn render_log_selection(ui: &Ui) {
                     START_POS = None;
                 }
 
+                // Pauses log refreshes while a popup is open or a drag is in progress
+                let pressed_in_list = START_POS;
+                *STATE.list_interaction_active.lock().unwrap() = popup_open || pressed_in_list.is_some();
+
                 // Draw selection box
                 if IS_DRAGGING && DRAG_STARTED {
                     if let Some(start) = START_POS {
@@ -301,145 +367,274 @@ pub fn render_log_selection(ui: &Ui) {
             let use_formatted = settings.show_formatted_timestamps;
             drop(settings);
 
+            // Logs left after the filters, computed before the uploaded logs are locked below
+            let shown = shown_logs(&logs);
+
             let uploaded = UploadedLogs::get();
-            let show_uploaded = *STATE.show_uploaded_logs.lock().unwrap();
 
-            for log in logs.iter_mut() {
-                let is_uploaded = uploaded.is_uploaded(&log.filename);
-                if is_uploaded && !show_uploaded {
-                    continue;
-                }
-                
-                // NEW: Skip logs already in current session
-                let in_current_session = {
-                    let uploaded_files = STATE.uploaded_files.lock().unwrap();
-                    uploaded_files.iter().any(|f| f.filename == log.filename)
-                };
-                if in_current_session {
+            let io = ui.io();
+            let (shift_down, ctrl_down) = (io.key_shift, io.key_ctrl);
+            let cursor = SELECTION_CURSOR.with_borrow(|c| c.clone());
+            let continuations = STATE.log_continuations.lock().unwrap().clone();
+            let mut select_split: Option<String> = None;
+
+            // Rows in display order and the one clicked, applied once every row is known
+            let mut visible: Vec<usize> = Vec::new();
+            let mut clicked: Option<(usize, RowClick)> = None;
+
+            for group in group_by_session(&logs, &shown) {
+                if !render_group_header(ui, &mut logs, &group) {
                     continue;
                 }
+                ui.indent();
 
-                // Compact item height - single line with info on same line
-                let line_height = ui.text_line_height_with_spacing();
-                let item_height = line_height * 1.8; // Reduced from 2.5
+                for &index in &group {
+                    let log = &mut logs[index];
+                    let is_uploaded = uploaded.is_uploaded(&log.filename);
+                    visible.push(index);
+
+                    // Compact item height - single line with info on same line
+                    let line_height = ui.text_line_height_with_spacing();
+                    let item_height = line_height * 1.8; // Reduced from 2.5
                 
-                let item_screen_pos = ui.cursor_screen_pos();
-                let content_width = ui.content_region_avail()[0];
-
-                // Better background for uploaded logs - more visible
-                if is_uploaded {
-                    draw_list
-                        .add_rect(
-                            item_screen_pos,
-                            [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height],
-                            [0.1, 0.4, 0.1, 0.3] // Increased alpha from 0.15 to 0.3
-                        )
-                        .filled(true)
-                        .rounding(2.0)
-                        .build();
-                }
+                    let item_screen_pos = ui.cursor_screen_pos();
+                    let content_width = ui.content_region_avail()[0];
 
-                // Draw selection highlight when selected
-                if log.selected {
-                    draw_list
-                        .add_rect(
-                            item_screen_pos,
-                            [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height],
-                            [0.2, 0.5, 1.0, 0.2]
-                        )
-                        .filled(true)
-                        .rounding(2.0)
-                        .build();
-                }
+                    // Better background for uploaded logs - more visible
+                    if is_uploaded {
+                        draw_list
+                            .add_rect(
+                                item_screen_pos,
+                                [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height],
+                                [0.1, 0.4, 0.1, 0.3] // Increased alpha from 0.15 to 0.3
+                            )
+                            .filled(true)
+                            .rounding(2.0)
+                            .build();
+                    }
+
+                    // Draw selection highlight when selected
+                    if log.selected {
+                        draw_list
+                            .add_rect(
+                                item_screen_pos,
+                                [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height],
+                                [0.2, 0.5, 1.0, 0.2]
+                            )
+                            .filled(true)
+                            .rounding(2.0)
+                            .build();
+                    }
+
+                    // Outline the row the arrow keys move
+                    if cursor.as_deref() == Some(log.filename.as_str()) {
+                        draw_list
+                            .add_rect(
+                                item_screen_pos,
+                                [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height],
+                                [0.6, 0.8, 1.0, 0.8]
+                            )
+                            .rounding(2.0)
+                            .build();
+
+                        if SCROLL_TO_CURSOR.take() {
+                            ui.set_scroll_here_y();
+                        }
+                    }
 
-                // Check intersection with drag selection box
-                unsafe {
-                    if IS_DRAGGING && DRAG_STARTED {
-                        if let Some(start) = START_POS {
-                            let raw_rect_min = [start[0].min(mouse_pos[0]), start[1].min(mouse_pos[1])];
-                            let raw_rect_max = [start[0].max(mouse_pos[0]), start[1].max(mouse_pos[1])];
-
-                            let rect_min = [
-                                raw_rect_min[0].max(window_min[0]),
-                                raw_rect_min[1].max(window_min[1])
-                            ];
-                            let rect_max = [
-                                raw_rect_max[0].min(window_max[0]),
-                                raw_rect_max[1].min(window_max[1])
-                            ];
-
-                            let item_min = item_screen_pos;
-                            let item_max = [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height];
-
-                            let overlaps = !(item_max[0] < rect_min[0]
-                                || item_min[0] > rect_max[0]
-                                || item_max[1] < rect_min[1]
-                                || item_min[1] > rect_max[1]);
-
-                            if overlaps {
-                                log.selected = !IS_DESELECT_DRAG;
+                    // Check intersection with drag selection box
+                    unsafe {
+                        if IS_DRAGGING && DRAG_STARTED {
+                            if let Some(start) = START_POS {
+                                let raw_rect_min = [start[0].min(mouse_pos[0]), start[1].min(mouse_pos[1])];
+                                let raw_rect_max = [start[0].max(mouse_pos[0]), start[1].max(mouse_pos[1])];
+
+                                let rect_min = [
+                                    raw_rect_min[0].max(window_min[0]),
+                                    raw_rect_min[1].max(window_min[1])
+                                ];
+                                let rect_max = [
+                                    raw_rect_max[0].min(window_max[0]),
+                                    raw_rect_max[1].min(window_max[1])
+                                ];
+
+                                let item_min = item_screen_pos;
+                                let item_max = [item_screen_pos[0] + content_width, item_screen_pos[1] + item_height];
+
+                                let overlaps = !(item_max[0] < rect_min[0]
+                                    || item_min[0] > rect_max[0]
+                                    || item_max[1] < rect_min[1]
+                                    || item_min[1] > rect_max[1]);
+
+                                if overlaps {
+                                    log.selected = !IS_DESELECT_DRAG;
+                                }
                             }
                         }
                     }
-                }
 
-                // Checkbox
-                ui.checkbox(&format!("##checkbox_{}", log.filename), &mut log.selected);
-                ui.same_line();
+                    // Checkbox
+                    let was_selected = log.selected;
+                    let checkbox_clicked = ui.checkbox(&format!("##checkbox_{}", log.filename), &mut log.selected);
+                    let checkbox_hovered = ui.is_item_hovered();
+                    if checkbox_clicked {
+                        // Shift ranges follow the checkbox, applied below
+                        log.selected = was_selected;
+                        clicked = Some((index, if shift_down { RowClick::Range } else { RowClick::Toggle }));
+                    } else if let Some(pos) = click_pos {
+                        let inside = |p: [f32; 2]| {
+                            p[0] >= item_screen_pos[0]
+                                && p[0] <= item_screen_pos[0] + content_width
+                                && p[1] >= item_screen_pos[1]
+                                && p[1] < item_screen_pos[1] + item_height
+                        };
+                        if !checkbox_hovered && inside(pos) && inside(mouse_pos) {
+                            let kind = if shift_down {
+                                RowClick::Range
+                            } else if ctrl_down {
+                                RowClick::Toggle
+                            } else {
+                                RowClick::Only
+                            };
+                            clicked = Some((index, kind));
+                        }
+                    }
+                    if log.invalid.is_some() {
+                        // Broken files can't be uploaded, whichever way they got selected
+                        log.selected = false;
+                    }
+                    ui.same_line();
 
-                // Single line layout - Date/Time
-                if use_formatted {
-                    if let Some(formatted) = format_timestamp(&log.filename) {
-                        ui.text(&formatted);
+                    // Single line layout - Date/Time
+                    if use_formatted {
+                        if let Some(formatted) = format_timestamp(&log.filename) {
+                            ui.text(&formatted);
+                        } else {
+                            ui.text(&log.filename);
+                        }
                     } else {
                         ui.text(&log.filename);
                     }
-                } else {
-                    ui.text(&log.filename);
-                }
                 
-                ui.same_line();
+                    ui.same_line();
                 
-                // Map badge with color coding
-                let map_name = log.map_type.display_name();
-                let map_color = match log.map_type {
-                    crate::logfile::MapType::EternalBattlegrounds => [0.8, 0.6, 0.2, 1.0],
-                    crate::logfile::MapType::GreenAlpineBorderlands => [0.2, 0.8, 0.3, 1.0],
-                    crate::logfile::MapType::BlueAlpineBorderlands => [0.3, 0.5, 1.0, 1.0],
-                    crate::logfile::MapType::RedDesertBorderlands => [1.0, 0.3, 0.3, 1.0],
-                    crate::logfile::MapType::EdgeOfTheMists => [0.6, 0.3, 0.8, 1.0],
-                    crate::logfile::MapType::ObsidianSanctum => [0.4, 0.4, 0.4, 1.0],
-                    _ => [0.5, 0.5, 0.5, 1.0],
-                };
+                    // Map badge with color coding
+                    let map_name = log.map_type.display_name();
+                    ui.text_colored(log.map_type.color(), &format!("[{}]", map_name));
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text(log.map_type.full_name());
+                    }
                 
-                ui.text_colored(map_color, &format!("[{}]", map_name));
+                    ui.same_line();
+
+                    // Fights arcdps split across files
+                    let continues = continuations.iter().find(|(f, _)| *f == log.filename).map(|(_, p)| p);
+                    let continued = continuations.iter().any(|(_, p)| *p == log.filename);
+                    if continues.is_some() || continued {
+                        ui.text_colored([0.9, 0.6, 1.0, 1.0], "[Split]");
+                        if ui.is_item_hovered() {
+                            let note = match continues {
+                                Some(previous) => format!("Likely continues {}", previous),
+                                None => "Likely continued in the next log".to_string(),
+                            };
+                            ui.tooltip_text(format!(
+                                "{}\nUploaded together, the server is told they are one fight",
+                                note
+                            ));
+                        }
+                        if continues.is_some() {
+                            ui.same_line();
+                            if ui.small_button(&format!("Select Split Fight##split_{}", log.filename)) {
+                                select_split = Some(log.filename.clone());
+                            }
+                        }
+                        ui.same_line();
+                    }
                 
-                ui.same_line();
+                    if let Some(original) = uploaded.duplicate_of(log) {
+                        ui.text_colored([1.0, 0.6, 0.0, 1.0], "[Duplicate]");
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(format!(
+                                "Same contents as {}, which was already uploaded\nSkipped unless duplicates are uploaded anyway",
+                                original
+                            ));
+                        }
+                        ui.same_line();
+                    }
+
+                    // Recorder (only show if present)
+                    if let Some(ref recorder) = log.recorder {
+                        ui.text_colored([0.7, 0.9, 1.0, 1.0], "Char:");
+                        ui.same_line();
+                        ui.text_colored([0.8, 0.8, 0.8, 1.0], recorder);
+                        ui.same_line();
+                    }
                 
-                // Recorder (only show if present)
-                if let Some(ref recorder) = log.recorder {
-                    ui.text_colored([0.7, 0.9, 1.0, 1.0], "Char:");
-                    ui.same_line();
-                    ui.text_colored([0.8, 0.8, 0.8, 1.0], recorder);
-                    ui.same_line();
-                }
+                    // Commander (only show if present)
+                    if let Some(ref commander) = log.commander {
+                        ui.text_colored([1.0, 0.8, 0.2, 1.0], "Cmd:");
+                        ui.same_line();
+                        ui.text_colored([1.0, 0.9, 0.6, 1.0], commander);
+                        if ui.is_item_hovered() {
+                            if let Some(note) = crate::commanders::CommanderNotes::get().find(commander) {
+                                let lines: Vec<&str> =
+                                    [note.note.trim(), note.link.trim()].into_iter().filter(|l| !l.is_empty()).collect();
+                                ui.tooltip_text(lines.join("\n"));
+                            }
+                        }
+                        ui.same_line();
+                    }
                 
-                // Commander (only show if present)
-                if let Some(ref commander) = log.commander {
-                    ui.text_colored([1.0, 0.8, 0.2, 1.0], "Cmd:");
-                    ui.same_line();
-                    ui.text_colored([1.0, 0.9, 0.6, 1.0], commander);
-                    ui.same_line();
+                    // Fight details from the local parse
+                    if let Some(ref fight) = log.fight {
+                        ui.text_colored(
+                            [0.8, 0.8, 0.8, 1.0],
+                            &format!(
+                                "{}:{:02} | {} squad | {} enemies",
+                                fight.duration_secs / 60,
+                                fight.duration_secs % 60,
+                                fight.squad_size,
+                                fight.enemy_count
+                            ),
+                        );
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(format!(
+                                "arcdps {} (revision {}), {} agents, {} skills",
+                                fight.arcdps_build, fight.revision, fight.agent_count, fight.skill_count
+                            ));
+                        }
+                        ui.same_line();
+                    }
+
+                    // File size at the end
+                    ui.text_colored([0.6, 0.6, 0.6, 1.0], &format!("{:.1}MB", log.size as f64 / 1024.0 / 1024.0));
+
+                    if let Some(ref reason) = log.invalid {
+                        ui.same_line();
+                        ui.text_colored([1.0, 0.3, 0.3, 1.0], "[Invalid]");
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(format!("{} - this file can't be uploaded", reason));
+                        }
+                    }
+
+                    // Add minimal spacing between items
+                    ui.dummy([0.0, 2.0]);
                 }
-                
-                // File size at the end
-                ui.text_colored([0.6, 0.6, 0.6, 1.0], &format!("{:.1}MB", log.size as f64 / 1024.0 / 1024.0));
 
-                // Add minimal spacing between items
-                ui.dummy([0.0, 2.0]);
+                ui.unindent();
             }
 
             drop(uploaded);
+
+            if let Some((index, kind)) = clicked {
+                apply_row_click(&mut logs, &visible, index, kind);
+            }
+            if let Some(filename) = select_split {
+                select_split_fight(&mut logs, &continuations, &filename);
+            }
+            if ui.is_window_focused() && !popup_open {
+                handle_list_keys(ui, &mut logs, &visible, shift_down);
+            }
         });
                 
     ui.separator();
@@ -451,9 +646,45 @@ pub fn render_log_selection(ui: &Ui) {
         let is_uploaded = uploaded.is_uploaded(&l.filename);
         l.selected && (show_uploaded || !is_uploaded)
     }).count();
+    let duplicate_count = logs
+        .iter()
+        .filter(|l| l.selected && uploaded.duplicate_of(l).is_some())
+        .count();
     drop(uploaded);
     
     ui.text(format!("Selected: {} files", selected_count));
+    if duplicate_count > 0 {
+        ui.text_colored(
+            [1.0, 0.6, 0.0, 1.0],
+            &format!(
+                "{} already uploaded under another name",
+                crate::formatting::plural(duplicate_count as u64, "selected log was", "selected logs were")
+            ),
+        );
+        let mut force = *STATE.force_duplicate_uploads.lock().unwrap();
+        if ui.checkbox("Upload duplicates anyway", &mut force) {
+            *STATE.force_duplicate_uploads.lock().unwrap() = force;
+        }
+    }
+    render_old_matchup_warning(ui, &mut logs);
+
+    let backend = render_backend_choice(ui);
+
+    if backend == UploadBackend::Parser {
+        let mut split_by_map = Settings::get().split_by_map;
+        if ui.checkbox("One report per map", &mut split_by_map) {
+            let mut settings = Settings::get();
+            settings.split_by_map = split_by_map;
+            if let Err(e) = settings.store(crate::config_path()) {
+                log::error!("Failed to save split by map setting: {}", e);
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Process the logs of each WvW map into its own report");
+        }
+    }
+
+    let endpoint_valid = backend != UploadBackend::Parser || render_endpoint_override(ui);
 
     let state = *STATE.processing_state.lock().unwrap();
 
@@ -462,13 +693,13 @@ pub fn render_log_selection(ui: &Ui) {
         return;
     }
 
-    if ui.button("Upload Selected") && selected_count > 0 {
+    if ui.button("Upload Selected") && selected_count > 0 && endpoint_valid {
         log::info!("Starting upload for {} files", selected_count);
 
         *STATE.show_log_selection.lock().unwrap() = false;
         *STATE.show_upload_progress.lock().unwrap() = true;
 
-        std::thread::spawn(|| {
+        crate::threads::spawn("upload-start", || {
             start_upload_process();
         });
     }
@@ -476,7 +707,7 @@ pub fn render_log_selection(ui: &Ui) {
     ui.same_line();
 
     if ui.button("Back") {
-        std::thread::spawn(|| {
+        crate::threads::spawn("back-navigation", || {
             log::info!("Back button clicked from log selection");
             handle_back_navigation();
         });
@@ -500,6 +731,592 @@ pub fn render_log_selection(ui: &Ui) {
     }    
 }
 
+/// Warns when selected logs are from before the last matchup reset, mixing weeks confuses the stats
+fn render_old_matchup_warning(ui: &Ui, logs: &mut [LogFile]) {
+    let region = Settings::get().wvw_region;
+    let reset = region.last_reset(chrono::Utc::now().timestamp().max(0) as u64);
+    let old = logs.iter().filter(|l| l.selected && l.modified < reset).count();
+    if old == 0 {
+        return;
+    }
+
+    ui.text_colored(
+        [1.0, 0.7, 0.3, 1.0],
+        &format!(
+            "{} from before the matchup reset ({})",
+            crate::formatting::plural(old as u64, "selected log is", "selected logs are"),
+            region.reset_label()
+        ),
+    );
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Last week's fights in this week's report mix up the stats\nThe region can be changed in Settings > General");
+    }
+    ui.same_line();
+    if ui.small_button("Deselect Old Logs") {
+        for log in logs.iter_mut().filter(|l| l.modified < reset) {
+            log.selected = false;
+        }
+    }
+}
+
+/// Renders the buttons choosing where the session's logs are uploaded to, returns the current choice
+/// The choice is fixed once the session has files
+fn render_backend_choice(ui: &Ui) -> UploadBackend {
+    let current = *STATE.upload_backend.lock().unwrap();
+    let locked = !STATE.uploaded_files.lock().unwrap().is_empty();
+
+    ui.text("Upload to:");
+    for backend in [UploadBackend::Parser, UploadBackend::DpsReport] {
+        ui.same_line();
+        let style = (backend == current)
+            .then(|| ui.push_style_color(nexus::imgui::StyleColor::Button, [0.2, 0.5, 0.2, 1.0]));
+        let clicked = ui.small_button(&format!("{}##backend", backend.label()));
+        drop(style);
+
+        if clicked && !locked && backend != current {
+            log::info!("Uploading this session to {}", backend.label());
+            *STATE.upload_backend.lock().unwrap() = backend;
+        }
+        if locked && ui.is_item_hovered() {
+            ui.tooltip_text("Start a new session to change where logs are uploaded");
+        }
+    }
+
+    if current == UploadBackend::DpsReport {
+        ui.text_colored(
+            [0.6, 0.6, 0.6, 1.0],
+            "Every log gets its own dps.report link, no combined report is made",
+        );
+    }
+
+    current
+}
+
+/// Renders the endpoint override for this session only, returns false while the typed endpoint is invalid
+/// Shown with the advanced settings, and fixed once the session has files
+fn render_endpoint_override(ui: &Ui) -> bool {
+    let mut endpoint = STATE.endpoint_override.lock().unwrap().clone();
+    let locked = !STATE.uploaded_files.lock().unwrap().is_empty();
+
+    if locked || !Settings::get().show_advanced_settings {
+        if !endpoint.is_empty() {
+            ui.text_colored([1.0, 0.7, 0.3, 1.0], &format!("Session endpoint: {}", endpoint));
+        }
+        return true;
+    }
+
+    ui.text("Endpoint for this session:");
+    ui.same_line();
+    ui.set_next_item_width(250.0);
+    if ui
+        .input_text("##endpoint_override", &mut endpoint)
+        .hint("Endpoint from settings")
+        .build()
+    {
+        endpoint = endpoint.trim().to_string();
+        *STATE.endpoint_override.lock().unwrap() = endpoint.clone();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Only for testing, e.g. a staging server\nLeave empty to use the endpoint from settings");
+    }
+
+    if endpoint.is_empty() {
+        return true;
+    }
+    match crate::upload::validate_endpoint_override(&endpoint) {
+        Ok(()) => true,
+        Err(e) => {
+            ui.text_colored([1.0, 0.3, 0.0, 1.0], &e.to_string());
+            false
+        }
+    }
+}
+
+/// Indices of the logs shown in the list, in display order
+/// Logs already in the current session are on the review screen instead
+fn shown_logs(logs: &[LogFile]) -> Vec<usize> {
+    let uploaded = UploadedLogs::get();
+    let show_uploaded = *STATE.show_uploaded_logs.lock().unwrap();
+    let uploaded_files = STATE.uploaded_files.lock().unwrap();
+
+    logs.iter()
+        .enumerate()
+        .filter(|(_, log)| {
+            (show_uploaded || !uploaded.is_uploaded(&log.filename))
+                && passes_filters(log)
+                && !uploaded_files.iter().any(|f| f.filename == log.filename)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Logs Select All would pick: everything shown in the list that can be uploaded
+/// Duplicates of uploaded logs are left out unless they are uploaded anyway
+fn select_all_candidates(logs: &[LogFile]) -> Vec<usize> {
+    let shown = shown_logs(logs);
+    let force_duplicates = *STATE.force_duplicate_uploads.lock().unwrap();
+    let uploaded = UploadedLogs::get();
+    shown
+        .into_iter()
+        .filter(|&index| logs[index].invalid.is_none())
+        .filter(|&index| force_duplicates || uploaded.duplicate_of(&logs[index]).is_none())
+        .collect()
+}
+
+/// Renders the "Select newest" quick action: preset counts and a custom count kept in the settings
+fn render_select_newest(ui: &Ui, logs: &mut [LogFile]) {
+    ui.text("Newest:");
+    for count in NEWEST_PRESETS {
+        ui.same_line();
+        if ui.button(format!("{}##newest_{}", count, count)) {
+            select_newest(logs, count);
+        }
+    }
+
+    ui.same_line();
+    let mut count = NEWEST_COUNT.get().unwrap_or_else(|| Settings::get().select_newest_count as i32);
+    ui.set_next_item_width(50.0);
+    if ui.input_int("##newest_count", &mut count).build() {
+        count = count.clamp(1, 9999);
+    }
+    // Saved once the field is left, not on every keystroke
+    if ui.is_item_deactivated_after_edit() {
+        let mut settings = Settings::get();
+        settings.select_newest_count = count as u32;
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+    NEWEST_COUNT.set(Some(count));
+
+    ui.same_line();
+    if ui.button("Select##newest_custom") {
+        select_newest(logs, count as usize);
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Replaces the selection with the newest logs shown in the list");
+    }
+}
+
+/// Selects only the newest logs Select All would pick, by modified time
+fn select_newest(logs: &mut [LogFile], count: usize) {
+    let mut candidates = select_all_candidates(logs);
+    candidates.sort_by(|&a, &b| logs[b].modified.cmp(&logs[a].modified));
+    candidates.truncate(count);
+
+    for log in logs.iter_mut() {
+        log.selected = false;
+    }
+    for index in candidates {
+        logs[index].selected = true;
+    }
+}
+
+/// Splits the shown logs into raid sessions wherever two logs are further apart than SESSION_GAP_SECS
+fn group_by_session(logs: &[LogFile], shown: &[usize]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+
+    for &index in shown {
+        let starts_group = match groups.last().and_then(|g| g.last()) {
+            Some(&previous) => logs[previous].modified.abs_diff(logs[index].modified) > SESSION_GAP_SECS,
+            None => true,
+        };
+        if starts_group {
+            groups.push(vec![index]);
+        } else if let Some(group) = groups.last_mut() {
+            group.push(index);
+        }
+    }
+
+    groups
+}
+
+/// Renders the collapsible header of a raid session, returns true when it is open
+fn render_group_header(ui: &Ui, logs: &mut [LogFile], group: &[usize]) -> bool {
+    let (Some(&newest), Some(&oldest)) = (group.first(), group.last()) else {
+        return false;
+    };
+
+    let local_time = |timestamp: u64| {
+        chrono::DateTime::from_timestamp(timestamp as i64, 0)
+            .map(|t| t.with_timezone(&chrono::Local))
+    };
+    let range = match (local_time(logs[oldest].modified), local_time(logs[newest].modified)) {
+        (Some(start), Some(end)) => format!(
+            "{} {}-{}",
+            start.format("%d.%m.%Y"),
+            start.format("%H:%M"),
+            end.format("%H:%M")
+        ),
+        _ => "Unknown time".to_string(),
+    };
+    let total_size: u64 = group.iter().map(|&i| logs[i].size).sum();
+
+    let header = format!(
+        "{} ({}, {:.1} MB)##log_group_{}",
+        range,
+        crate::formatting::plural(group.len() as u64, "fight", "fights"),
+        total_size as f64 / 1024.0 / 1024.0,
+        logs[newest].filename
+    );
+
+    if !ui.collapsing_header(&header, TreeNodeFlags::DEFAULT_OPEN) {
+        return false;
+    }
+
+    if ui.small_button(&format!("Select group##select_group_{}", logs[newest].filename)) {
+        for &index in group {
+            if logs[index].invalid.is_none() {
+                logs[index].selected = true;
+            }
+        }
+    }
+
+    true
+}
+
+/// Selects every part of the split fight a log belongs to
+fn select_split_fight(logs: &mut [LogFile], continuations: &[(String, String)], filename: &str) {
+    let mut parts = vec![filename.to_string()];
+    let mut index = 0;
+    while index < parts.len() {
+        let part = parts[index].clone();
+        for (next, previous) in continuations {
+            for linked in [next, previous] {
+                if (*next == part || *previous == part) && !parts.contains(linked) {
+                    parts.push(linked.clone());
+                }
+            }
+        }
+        index += 1;
+    }
+
+    for log in logs.iter_mut() {
+        if parts.contains(&log.filename) && log.invalid.is_none() {
+            log.selected = true;
+        }
+    }
+}
+
+/// Asks before selecting more logs than the configured threshold
+fn render_confirm_select_all_popup(ui: &Ui, logs: &mut [LogFile]) {
+    ui.popup_modal("confirm_select_all")
+        .always_auto_resize(true)
+        .build(ui, || {
+            let candidates = select_all_candidates(logs);
+            let total_size: u64 = candidates.iter().map(|&i| logs[i].size).sum();
+
+            ui.text_colored([1.0, 0.8, 0.2, 1.0], "Select All Logs?");
+            ui.spacing();
+            ui.text(format!(
+                "This selects {} ({:.1} MB in total).",
+                crate::formatting::plural(candidates.len() as u64, "log", "logs"),
+                total_size as f64 / 1024.0 / 1024.0
+            ));
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                "Uploading this many logs can take a while and makes one very large report.",
+            );
+            ui.spacing();
+
+            if ui.button("Select All") {
+                for index in candidates {
+                    logs[index].selected = true;
+                }
+                ui.close_current_popup();
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+        });
+}
+
+/// Renders a checkbox per known map, returns true when one was toggled
+fn render_map_filter(ui: &Ui) -> bool {
+    let mut changed = false;
+    let mut hidden = STATE.hidden_maps.lock().unwrap();
+
+    ui.text("Maps:");
+    for map in crate::maps::all() {
+        ui.same_line();
+        let mut shown = !hidden.contains(&map.id);
+        let color = ui.push_style_color(nexus::imgui::StyleColor::Text, map.color);
+        if ui.checkbox(&format!("{}##map_filter_{}", map.abbreviation, map.id), &mut shown) {
+            if shown {
+                hidden.retain(|id| *id != map.id);
+            } else {
+                hidden.push(map.id);
+            }
+            changed = true;
+        }
+        color.pop();
+        if ui.is_item_hovered() {
+            ui.tooltip_text(&map.name);
+        }
+    }
+
+    changed
+}
+
+/// Renders the commander/recorder name filter, returns true when the text changed
+fn render_name_filter(ui: &Ui) -> bool {
+    let mut filter = STATE.log_name_filter.lock().unwrap();
+
+    ui.text("Character:");
+    ui.same_line();
+    ui.set_next_item_width(200.0);
+    let changed = ui
+        .input_text("##log_name_filter", &mut filter)
+        .hint("Commander or recorder name")
+        .build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Only shows logs whose commander or recording character contains this text");
+    }
+
+    if !filter.is_empty() {
+        ui.same_line();
+        if ui.button("Clear##log_name_filter") {
+            filter.clear();
+            return true;
+        }
+    }
+
+    changed
+}
+
+/// Whether the map and name filters let a log through
+/// Logs without a known map pass the map filter
+fn passes_filters(log: &LogFile) -> bool {
+    let map_shown = match log.map_type {
+        MapType::WvW(id) => !STATE.hidden_maps.lock().unwrap().contains(&id),
+        _ => true,
+    };
+    if !map_shown {
+        return false;
+    }
+
+    let filter = STATE.log_name_filter.lock().unwrap().trim().to_lowercase();
+    if filter.is_empty() {
+        return true;
+    }
+    [&log.commander, &log.recorder]
+        .into_iter()
+        .flatten()
+        .any(|name| name.to_lowercase().contains(&filter))
+}
+
+/// Applies a click on a row, moving the anchor and cursor to it
+fn apply_row_click(logs: &mut [LogFile], visible: &[usize], index: usize, kind: RowClick) {
+    match kind {
+        RowClick::Only => {
+            for log in logs.iter_mut() {
+                log.selected = false;
+            }
+            logs[index].selected = logs[index].invalid.is_none();
+        }
+        RowClick::Toggle => {
+            logs[index].selected = !logs[index].selected && logs[index].invalid.is_none();
+        }
+        RowClick::Range => {
+            select_range(logs, visible, index);
+            // The anchor stays put so the range can be adjusted with another Shift+Click
+            SELECTION_CURSOR.set(Some(logs[index].filename.clone()));
+            return;
+        }
+    }
+
+    SELECTION_ANCHOR.set(Some(logs[index].filename.clone()));
+    SELECTION_CURSOR.set(Some(logs[index].filename.clone()));
+}
+
+/// Selects every visible log between the anchor and `index`, only `index` without an anchor
+fn select_range(logs: &mut [LogFile], visible: &[usize], index: usize) {
+    let anchor = SELECTION_ANCHOR.with_borrow(|a| a.clone());
+    let Some(end) = visible.iter().position(|&i| i == index) else {
+        return;
+    };
+    let start = anchor
+        .and_then(|name| visible.iter().position(|&i| logs[i].filename == name))
+        .unwrap_or_else(|| {
+            SELECTION_ANCHOR.set(Some(logs[index].filename.clone()));
+            end
+        });
+
+    for &i in &visible[start.min(end)..=start.max(end)] {
+        if logs[i].invalid.is_none() {
+            logs[i].selected = true;
+        }
+    }
+}
+
+/// Up/Down move the cursor, Shift extends the selection while moving, Space toggles the cursor row
+fn handle_list_keys(ui: &Ui, logs: &mut [LogFile], visible: &[usize], shift_down: bool) {
+    if visible.is_empty() {
+        return;
+    }
+
+    let cursor = SELECTION_CURSOR
+        .with_borrow(|c| c.clone())
+        .and_then(|name| visible.iter().position(|&i| logs[i].filename == name));
+
+    let step: isize = if ui.is_key_pressed(Key::DownArrow) {
+        1
+    } else if ui.is_key_pressed(Key::UpArrow) {
+        -1
+    } else {
+        0
+    };
+
+    if step != 0 {
+        let next = match cursor {
+            Some(position) => position.saturating_add_signed(step).min(visible.len() - 1),
+            None => 0,
+        };
+        let index = visible[next];
+        if shift_down {
+            select_range(logs, visible, index);
+        } else {
+            SELECTION_ANCHOR.set(Some(logs[index].filename.clone()));
+        }
+        SELECTION_CURSOR.set(Some(logs[index].filename.clone()));
+        SCROLL_TO_CURSOR.set(true);
+    }
+
+    if ui.is_key_pressed(Key::Space) {
+        if let Some(position) = cursor {
+            apply_row_click(logs, visible, visible[position], RowClick::Toggle);
+        }
+    }
+}
+
+/// Renders the popup for selecting logs from a pasted list of timestamps or filenames
+fn render_import_selection_popup(ui: &Ui, logs: &mut [LogFile]) {
+    ui.popup_modal("import_selection")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text("Paste timestamps or filenames (one per line):");
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                "e.g. 20251010-222255.zevtc, 2025-10-10 22:22 or just 22:22",
+            );
+            ui.spacing();
+
+            IMPORT_BUFFER.with_borrow_mut(|buffer| {
+                ui.input_text_multiline("##import_selection", buffer, [400.0, 150.0])
+                    .build();
+            });
+
+            let result = IMPORT_RESULT.with_borrow(|r| r.clone());
+            if !result.is_empty() {
+                ui.spacing();
+                ui.text_colored([0.7, 0.9, 1.0, 1.0], &result);
+            }
+
+            ui.spacing();
+
+            if ui.button("Select Matching") {
+                let entries: Vec<String> = IMPORT_BUFFER.with_borrow(|buffer| {
+                    buffer
+                        .split(['\n', ';'])
+                        .map(|entry| entry.trim().to_string())
+                        .filter(|entry| !entry.is_empty())
+                        .collect()
+                });
+
+                let uploaded = UploadedLogs::get();
+                let show_uploaded = *STATE.show_uploaded_logs.lock().unwrap();
+
+                let mut newly_selected = 0;
+                let mut unmatched = 0;
+
+                for entry in entries.iter() {
+                    let mut matched = false;
+                    for log in logs.iter_mut() {
+                        if (!show_uploaded && uploaded.is_uploaded(&log.filename)) || !passes_filters(log) {
+                            continue;
+                        }
+                        if import_entry_matches(&log.filename, entry) {
+                            matched = true;
+                            if !log.selected {
+                                log.selected = true;
+                                newly_selected += 1;
+                            }
+                        }
+                    }
+                    if !matched {
+                        unmatched += 1;
+                    }
+                }
+                drop(uploaded);
+
+                log::info!(
+                    "Import selection: {} logs selected, {} of {} entries unmatched",
+                    newly_selected,
+                    unmatched,
+                    entries.len()
+                );
+                IMPORT_RESULT.set(format!(
+                    "Selected {} log(s) - {} of {} entries had no match",
+                    newly_selected,
+                    unmatched,
+                    entries.len()
+                ));
+            }
+
+            ui.same_line();
+
+            if ui.button("Clear") {
+                IMPORT_BUFFER.set(String::new());
+                IMPORT_RESULT.set(String::new());
+            }
+
+            ui.same_line();
+
+            if ui.button("Close") {
+                ui.close_current_popup();
+            }
+        });
+}
+
+/// Checks whether a pasted import entry refers to the given log file
+/// Accepts filenames, raw timestamps (YYYYMMDD-HHMMSS), formatted timestamps,
+/// dates with minute precision and bare times of day (HH:MM or HH:MM:SS)
+fn import_entry_matches(filename: &str, entry: &str) -> bool {
+    let stem = crate::logfile::log_stem(filename);
+    let entry_stem = crate::logfile::log_stem(entry);
+
+    if stem.eq_ignore_ascii_case(entry_stem) {
+        return true;
+    }
+
+    if let Some(formatted) = format_timestamp(filename) {
+        if formatted.eq_ignore_ascii_case(entry) {
+            return true;
+        }
+    }
+
+    // Compare digits only so "2025-10-10 22:22" matches "20251010-222255"
+    let entry_digits: String = entry.chars().filter(|c| c.is_ascii_digit()).collect();
+    let file_digits: String = stem
+        .chars()
+        .take(15)
+        .filter(|c| c.is_ascii_digit())
+        .collect();
+
+    if file_digits.len() != 14 {
+        return false;
+    }
+
+    match entry_digits.len() {
+        4 | 6 if entry.contains(':') => file_digits[8..].starts_with(&entry_digits),
+        12 | 14 => file_digits.starts_with(&entry_digits),
+        _ => false,
+    }
+}
+
 /// Handles back navigation logic based on session state
 fn handle_back_navigation() {
     // Check if we have an active session with uploads
@@ -515,82 +1332,143 @@ fn handle_back_navigation() {
     } else {
         // No active session or no uploads - clear everything and go to token input
         log::info!("No uploads in session, clearing session and returning to token input");
+        let sessions = crate::upload::active_sessions();
+        if !sessions.is_empty() {
+            let api_endpoint = crate::upload::session_endpoint();
+            crate::threads::spawn("session-cancel", move || {
+                crate::upload::cancel_sessions(&api_endpoint, &sessions);
+            });
+        }
         STATE.session_id.lock().unwrap().clear();
         STATE.ownership_token.lock().unwrap().clear();
+        STATE.split_sessions.lock().unwrap().clear();
+        STATE.endpoint_override.lock().unwrap().clear();
         *STATE.show_token_input.lock().unwrap() = true;
     }
 }
 
+/// Scan message with the number of logs parsed so far, once the total is known
+fn scan_status_text(message: &str) -> String {
+    let (done, total) = *STATE.scan_progress.lock().unwrap();
+    if total > 0 {
+        format!("{}... ({}/{})", message, done, total)
+    } else {
+        format!("{}...", message)
+    }
+}
+
 /// Starts the upload process for selected logs
-fn start_upload_process() {
+pub fn start_upload_process() {
     log::info!("Starting upload process");
 
     *STATE.processing_state.lock().unwrap() = ProcessingState::Uploading;
 
+    let api_endpoint = crate::upload::session_endpoint();
     let settings = Settings::get();
-    let api_endpoint = settings.api_endpoint.clone();
     let history_token = settings.history_token.clone();
+    let split_by_map = settings.split_by_map;
     drop(settings);
+    let backend = *STATE.upload_backend.lock().unwrap();
 
-    // Check if we have an existing session or need to create one
-    let session_id = {
-        let existing_session = STATE.session_id.lock().unwrap().clone();
-        if !existing_session.is_empty() {
-            log::info!("Using existing session: {}", existing_session);
-            existing_session
+    // A session that already has plain uploads or a time split keeps its layout
+    let split_by_map = split_by_map
+        && if STATE.uploaded_files.lock().unwrap().is_empty() {
+            true
         } else {
-            // Create new session WITHOUT the token
-            log::info!("Creating new session");
-            match crate::upload::create_session(&api_endpoint, &history_token) { 
-                Ok((sid, ot)) => {
-                    log::info!("Session created: {}", sid);
-                    *STATE.session_id.lock().unwrap() = sid.clone();
-                    *STATE.ownership_token.lock().unwrap() = ot.clone();
-                    sid
-                }
-                Err(e) => {
-                    log::error!("Failed to create session: {}", e);
-                    *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
-                    return;
+            let split_sessions = STATE.split_sessions.lock().unwrap();
+            let all_by_map = !split_sessions.is_empty() && split_sessions.iter().all(|s| s.by_map);
+            all_by_map
+        };
+
+    // Get selected logs, re-checking them since the files may have changed after the scan
+    let selected_logs: Vec<(usize, crate::logfile::LogFile)> = {
+        let mut logs = STATE.logs.lock().unwrap();
+        let force_duplicates = *STATE.force_duplicate_uploads.lock().unwrap();
+        let uploaded = UploadedLogs::get();
+        for log in logs.iter_mut().filter(|log| log.selected) {
+            if let Some(reason) = crate::logfile::preflight_check(&log.path) {
+                log::warn!("Skipping {}: {}", log.filename, reason);
+                log.invalid = Some(reason);
+                log.selected = false;
+            } else if !force_duplicates {
+                if let Some(original) = uploaded.duplicate_of(log) {
+                    log::info!("Skipping {}: same contents as the uploaded {}", log.filename, original);
+                    log.selected = false;
                 }
             }
         }
-    };
-    
-    // Get selected logs
-    let selected_logs: Vec<(usize, crate::logfile::LogFile)> = {
-        let logs = STATE.logs.lock().unwrap();
+        drop(uploaded);
         logs.iter()
             .enumerate()
             .filter(|(_, log)| log.selected)
             .map(|(i, log)| (i, log.clone()))
             .collect()
     };
+
+    if selected_logs.is_empty() {
+        log::error!("No valid logs left to upload");
+        *STATE.processing_state.lock().unwrap() = ProcessingState::Idle;
+        *STATE.show_upload_progress.lock().unwrap() = false;
+        *STATE.show_log_selection.lock().unwrap() = true;
+        return;
+    }
+
+    // Resolve the session each log is uploaded to
+    let mut log_sessions: Vec<String> = Vec::with_capacity(selected_logs.len());
+    if backend == UploadBackend::DpsReport {
+        // dps.report has no sessions, every log stands on its own
+        log_sessions.resize(selected_logs.len(), String::new());
+    } else if split_by_map {
+        for (_, log) in selected_logs.iter() {
+            let map = log.map_type.display_name();
+            match map_session_for(&map, &api_endpoint, &history_token) {
+                Ok(sid) => log_sessions.push(sid),
+                Err(e) => {
+                    log::error!("Failed to create session for {}: {}", map, e);
+                    *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
+                    return;
+                }
+            }
+        }
+    } else {
+        // Check if we have an existing session or need to create one
+        let session_id = {
+            let existing_session = STATE.session_id.lock().unwrap().clone();
+            if !existing_session.is_empty() {
+                log::info!("Using existing session: {}", existing_session);
+                existing_session
+            } else {
+                // Create new session WITHOUT the token
+                log::info!("Creating new session");
+                match crate::upload::create_session(&api_endpoint, &history_token) { 
+                    Ok((sid, ot)) => {
+                        log::info!("Session created: {}", sid);
+                        *STATE.session_id.lock().unwrap() = sid.clone();
+                        *STATE.ownership_token.lock().unwrap() = ot.clone();
+                        sid
+                    }
+                    Err(e) => {
+                        log::error!("Failed to create session: {}", e);
+                        *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
+                        return;
+                    }
+                }
+            }
+        };
+        log_sessions.resize(selected_logs.len(), session_id);
+    }
     
     // APPEND to uploaded_files (don't clear if session already exists)
     {
         let mut uploaded_files = STATE.uploaded_files.lock().unwrap();
         
-        for (_, log) in selected_logs.iter() {
-            use crate::upload_review::{UploadedFileInfo, FileMetadata};
-            use crate::formatting::format_timestamp;
-            
+        for ((_, log), session_id) in selected_logs.iter().zip(log_sessions.iter()) {
             // Check if already in list
             if uploaded_files.iter().any(|f| f.filename == log.filename) {
                 continue;
             }
-                        
-            uploaded_files.push(UploadedFileInfo {
-                filename: log.filename.clone(),
-                size: format!("{:.2} MB", log.size as f64 / 1024.0 / 1024.0),
-                metadata: Some(FileMetadata {
-                    map_abbr: log.map_type.display_name().to_string(),
-                    map_color: get_map_color(&log.map_type),
-                    recorder: log.recorder.clone(),
-                    commander: log.commander.clone(),
-                    timestamp: format_timestamp(&log.filename),
-                }),
-            });
+
+            uploaded_files.push(crate::upload_review::UploadedFileInfo::from_log(log, session_id.clone()));
         }
         
         log::info!("uploaded_files now has {} entries", uploaded_files.len());
@@ -599,34 +1477,49 @@ fn start_upload_process() {
     log::info!("Queueing {} logs for upload", selected_logs.len());
 
     // Queue uploads
-    let upload_tx = STATE.upload_worker.lock().unwrap();
-    if let Some(tx) = upload_tx.as_ref() {
-        for (index, log) in selected_logs.iter() {
-            log::info!("Queuing: {}", log.filename);
-            if let Err(e) = tx.send((
-                *index,
-                log.path.clone(),
-                api_endpoint.clone(),
-                session_id.clone(),
-                history_token.clone(),
-            )) {
-                log::error!("Failed to queue upload: {}", e);
-            }
-        }
+    let cancel = crate::cancel::upload_token();
+    for ((index, log), session_id) in selected_logs.iter().zip(log_sessions.iter()) {
+        log::info!("Queuing: {}", log.filename);
+        crate::upload::enqueue((
+            *index,
+            log.path.clone(),
+            api_endpoint.clone(),
+            session_id.clone(),
+            history_token.clone(),
+            cancel.clone(),
+            backend,
+        ));
     }
     log::info!("All uploads queued");
 }
 
-// Helper function to get map colors
-fn get_map_color(map_type: &crate::logfile::MapType) -> [f32; 4] {
-    use crate::logfile::MapType;
-    match map_type {
-        MapType::EternalBattlegrounds => [0.8, 0.6, 0.2, 1.0],
-        MapType::GreenAlpineBorderlands => [0.2, 0.8, 0.3, 1.0],
-        MapType::BlueAlpineBorderlands => [0.3, 0.5, 1.0, 1.0],
-        MapType::RedDesertBorderlands => [1.0, 0.3, 0.3, 1.0],
-        MapType::EdgeOfTheMists => [0.6, 0.3, 0.8, 1.0],
-        MapType::ObsidianSanctum => [0.4, 0.4, 0.4, 1.0],
-        _ => [0.5, 0.5, 0.5, 1.0],
-    }
-}
\ No newline at end of file
+/// Returns the session for the given map, creating it on first use
+/// The first map session also becomes the active session so the rest of the flow sees one
+fn map_session_for(map: &str, api_endpoint: &str, history_token: &str) -> anyhow::Result<String> {
+    if let Some(existing) = STATE.split_sessions.lock().unwrap().iter().find(|s| s.label == map) {
+        return Ok(existing.session_id.clone());
+    }
+
+    log::info!("Creating new session for {}", map);
+    let (sid, ot) = crate::upload::create_session(api_endpoint, history_token)?;
+    log::info!("Session created for {}: {}", map, sid);
+
+    if STATE.session_id.lock().unwrap().is_empty() {
+        *STATE.session_id.lock().unwrap() = sid.clone();
+        *STATE.ownership_token.lock().unwrap() = ot.clone();
+    }
+
+    STATE.split_sessions.lock().unwrap().push(SplitSession {
+        label: map.to_string(),
+        by_map: true,
+        session_id: sid.clone(),
+        ownership_token: ot,
+        report_urls: Vec::new(),
+        progress: 0.0,
+        complete: false,
+        failed: false,
+        started: false,
+    });
+
+    Ok(sid)
+}
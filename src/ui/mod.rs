@@ -1,6 +1,15 @@
+pub mod activity;
+pub mod consent;
+pub mod crash_report;
+pub mod live_queue;
+pub mod lock;
 pub mod log_selection;
 pub mod results;
+pub mod resume_session;
+pub mod safe_mode;
 pub mod settings;
+pub mod streamer;
+pub mod thread_errors;
 pub mod token_input;
 pub mod upload_progress;
 
@@ -1,13 +1,17 @@
 use nexus::imgui::Ui;
 
 use crate::scanning::scan_for_logs;
+use crate::settings::{ResultsAction, Settings};
 use crate::state::STATE;
 use crate::ui::upload_progress::reset_upload_state;
+use crate::upload::{check_report_url, UrlHealth};
+use crate::upload_review::split_label_for_report_url;
 use crate::uploaded_logs::UploadedLogs;
-use crate::webhooks::{send_to_discord, WebhookSettings};
+use crate::webhooks::{WebhookDelivery, WebhookSettings};
 
 thread_local! {
     static REPORT_NAME_BUFFER: std::cell::RefCell<String> = std::cell::RefCell::new(String::new());
+    static DOWNLOAD_DIR_BUFFER: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
 }
 
 /// Renders the results screen after processing is complete
@@ -15,12 +19,17 @@ pub fn render_results(ui: &Ui) {
     ui.text("Processing Complete!");
     ui.spacing();
 
-    let report_urls = STATE.report_urls.lock().unwrap();
+    let report_urls = STATE.report_urls.lock().unwrap().clone();
+    let actions = Settings::get().results_actions.clone();
     
     if report_urls.is_empty() {
         ui.text_colored([1.0, 1.0, 0.0, 1.0], "No report URLs available");
     } else {
         ui.text("Your reports are ready:");
+
+        if Settings::get().check_report_urls {
+            start_url_checks(&report_urls);
+        }
         
         for url in report_urls.iter() {
             let label = if url.contains("Legacy") || url.to_lowercase().contains("legacy") {
@@ -29,67 +38,90 @@ pub fn render_results(ui: &Ui) {
                 "Report:"
             };
             
-            ui.text(label);
-            ui.text_wrapped(url);
-            
-            // Use the URL itself as the unique ID for buttons
-            let copy_id = format!("Copy URL##{}", url);
-            if ui.button(&copy_id) {
-                ui.set_clipboard_text(url);
+            match split_label_for_report_url(url) {
+                Some(map) => ui.text(format!("{} {}", map, label)),
+                None => ui.text(label),
             }
+            render_url_health(ui, url);
+            crate::ui::streamer::sensitive_text(
+                ui,
+                &format!("result_url_{}", url),
+                url,
+                [1.0, 1.0, 1.0, 1.0],
+                true,
+            );
             
-            ui.same_line();
+            // Per-URL buttons in the configured order, the URL itself is the unique ID
+            let mut first_button = true;
+            for action in actions.iter().filter(|a| a.is_per_url()) {
+                if !first_button {
+                    ui.same_line();
+                }
+                first_button = false;
 
-            let open_id = format!("Open in Browser##{}", url);
-            if ui.button(&open_id) {
-                if let Err(e) = open::that_detached(url.as_str()) {
-                    log::error!("Failed to open browser: {}", e);
+                let button_id = format!("{}##{}", action.label(), url);
+                if ui.button(&button_id) {
+                    match action {
+                        ResultsAction::CopyUrl => ui.set_clipboard_text(url),
+                        ResultsAction::OpenInBrowser => {
+                            if let Err(e) = open::that_detached(url.as_str()) {
+                                log::error!("Failed to open browser: {}", e);
+                            }
+                        }
+                        _ => {}
+                    }
                 }
             }
             
             ui.spacing();
         }
 
-        // Copy Both URLs button (only show if multiple reports)
-        if report_urls.len() > 1 {
-            if ui.button("Copy Both URLs") {
-                let combined_urls = report_urls.join("\n-\n");
-                ui.set_clipboard_text(&combined_urls);
-            }
-            ui.spacing();
-        }
+        // Report-wide buttons in the configured order
+        let mut first_button = true;
+        for action in actions.iter() {
+            match action {
+                // Copy Both URLs button (only show if multiple reports)
+                ResultsAction::CopyAllUrls if report_urls.len() > 1 => {
+                    if !first_button {
+                        ui.same_line();
+                    }
+                    first_button = false;
 
-        // Send to Discord button
-        if ui.button("Send to Discord") {
-            *STATE.show_webhook_modal.lock().unwrap() = true;
-            
-            // Load remembered webhook if available
-            let webhook_settings = WebhookSettings::get();
-            if webhook_settings.remember_last_webhook && !webhook_settings.last_webhook_url.is_empty() {
-                *STATE.webhook_url_input.lock().unwrap() = webhook_settings.last_webhook_url.clone();
-                *STATE.webhook_remember.lock().unwrap() = true;
-            } else {
-                STATE.webhook_url_input.lock().unwrap().clear();
-                *STATE.webhook_remember.lock().unwrap() = false;
+                    let copy_label = if report_urls.len() > 2 { "Copy All URLs" } else { "Copy Both URLs" };
+                    if ui.button(copy_label) {
+                        let combined_urls = report_urls.join("\n-\n");
+                        ui.set_clipboard_text(&combined_urls);
+                    }
+                }
+                ResultsAction::SendToDiscord => {
+                    if !first_button {
+                        ui.same_line();
+                    }
+                    first_button = false;
+
+                    if ui.button("Send to Discord") {
+                        open_webhook_modal(
+                            report_urls.clone(),
+                            crate::tokens::current_report_name(),
+                            crate::webhooks::ReportSummary::current_session(),
+                        );
+                    }
+                }
+                _ => {}
             }
-            drop(webhook_settings);
-            
-            // Initialize report name with default pattern
-            REPORT_NAME_BUFFER.with(|buffer| {
-                let current_date = chrono::Local::now().format("%d.%m.%y").to_string();
-                *buffer.borrow_mut() = format!("WvW: {}", current_date);
-            });
         }
     }
 
     drop(report_urls);
 
+    render_artifacts(ui);
+
     ui.spacing();
     ui.separator();
 
-    if ui.button("Upload More Logs") {
+    if actions.contains(&ResultsAction::UploadMore) && ui.button("Upload More Logs") {
         log::info!("Upload More Logs button clicked");
-        std::thread::spawn(|| {
+        crate::threads::spawn("upload-more", || {
             log::info!("Resetting upload state and clearing session");
             
             // Mark uploaded logs BEFORE resetting state
@@ -110,10 +142,12 @@ pub fn render_results(ui: &Ui) {
         });
     }
 
-    ui.same_line();
+    if actions.contains(&ResultsAction::UploadMore) {
+        ui.same_line();
+    }
 
     if ui.button("Back to Start") {
-        std::thread::spawn(|| {
+        crate::threads::spawn("back-to-start", || {
             log::info!("Back to Start button clicked");
             
             // Mark uploaded logs BEFORE resetting state
@@ -144,8 +178,53 @@ pub fn render_results(ui: &Ui) {
 }
 
 
+/// Builds the Discord message linking every report of a session
+pub fn build_report_message(report_urls: &[String], report_name: &str) -> String {
+    let mut message_parts = Vec::new();
+
+    for report_url in report_urls.iter() {
+        let is_legacy = report_url.contains("Legacy") || report_url.to_lowercase().contains("legacy");
+
+        // Format each report link
+        let base_name = if is_legacy { "Legacy Report" } else { report_name };
+        let link = match split_label_for_report_url(report_url) {
+            Some(map) => format!("[{} - {}]({})", base_name, map, report_url),
+            None => format!("[{}]({})", base_name, report_url),
+        };
+
+        message_parts.push(link);
+    }
+
+    // Join all parts with newline and dash separator
+    message_parts.join("   \n-\n")
+}
+
+/// Opens the Discord webhook modal for the given report URLs
+/// Used by the results screen and by the report history tab, `summary` fills the embed fields
+pub fn open_webhook_modal(report_urls: Vec<String>, report_name: String, summary: crate::webhooks::ReportSummary) {
+    *STATE.webhook_report_urls.lock().unwrap() = report_urls;
+    *STATE.webhook_report_summary.lock().unwrap() = summary;
+    STATE.webhook_deliveries.lock().unwrap().clear();
+    *STATE.show_webhook_modal.lock().unwrap() = true;
+    
+    // Load remembered webhook if available
+    let webhook_settings = WebhookSettings::get();
+    if webhook_settings.remember_last_webhook && !webhook_settings.last_webhook_url.is_empty() {
+        *STATE.webhook_url_input.lock().unwrap() = webhook_settings.last_webhook_url.clone();
+        *STATE.webhook_remember.lock().unwrap() = true;
+    } else {
+        STATE.webhook_url_input.lock().unwrap().clear();
+        *STATE.webhook_remember.lock().unwrap() = false;
+    }
+    drop(webhook_settings);
+    
+    REPORT_NAME_BUFFER.with(|buffer| {
+        *buffer.borrow_mut() = report_name;
+    });
+}
+
 /// Renders the Discord webhook modal
-fn render_webhook_modal(ui: &Ui) {
+pub fn render_webhook_modal(ui: &Ui) {
     ui.open_popup("Send to Discord");
     
     ui.popup_modal("Send to Discord")
@@ -171,7 +250,7 @@ fn render_webhook_modal(ui: &Ui) {
                     [0.0, 1.0, 0.0, 1.0]
                 };
                 
-                ui.text_colored(color, &message);
+                ui.text_colored(color, &crate::ui::streamer::redact(&message));
                 ui.spacing();
             }
 
@@ -181,18 +260,24 @@ fn render_webhook_modal(ui: &Ui) {
             let webhook_settings = WebhookSettings::get();
             let webhooks = webhook_settings.get_webhooks_sorted();
             
+            drop(webhook_settings);
+
             if webhooks.is_empty() {
                 ui.text_colored([0.7, 0.7, 0.7, 1.0], "No saved webhooks. Add one in Settings.");
             } else {
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Tick every server the reports should go to");
                 for webhook in webhooks.iter() {
-                    let button_label = format!("{}##{}", webhook.name, webhook.name);
-                    if ui.button(&button_label) {
-                        *STATE.webhook_url_input.lock().unwrap() = webhook.url.clone();
-                        *STATE.webhook_selected_name.lock().unwrap() = webhook.name.clone();
+                    let mut selected = STATE.webhook_selected_names.lock().unwrap().contains(&webhook.name);
+                    if ui.checkbox(&format!("{}##send_to_{}", webhook.name, webhook.name), &mut selected) {
+                        let mut names = STATE.webhook_selected_names.lock().unwrap();
+                        names.retain(|n| *n != webhook.name);
+                        if selected {
+                            names.push(webhook.name.clone());
+                        }
                     }
+                    render_delivery(ui, &webhook.name);
                 }
             }
-            drop(webhook_settings);
             
             ui.spacing();
             ui.separator();
@@ -201,10 +286,13 @@ fn render_webhook_modal(ui: &Ui) {
             // Webhook URL input
             ui.text("Webhook URL:");
             let mut url = STATE.webhook_url_input.lock().unwrap();
+            let streamer_mode = Settings::get().streamer_mode;
             ui.input_text("##webhook_url", &mut *url)
                 .hint("https://discord.com/api/webhooks/...")
+                .password(streamer_mode)
                 .build();
             drop(url);
+            render_delivery(ui, MANUAL_WEBHOOK_NAME);
 
             let mut remember = *STATE.webhook_remember.lock().unwrap();
             if ui.checkbox("Remember this webhook", &mut remember) {
@@ -232,7 +320,7 @@ fn render_webhook_modal(ui: &Ui) {
             ui.spacing();
 
             // Preview section - show all reports
-            let report_urls = STATE.report_urls.lock().unwrap();
+            let report_urls = STATE.webhook_report_urls.lock().unwrap();
             let num_reports = report_urls.len();
             
             // Dynamic preview header based on number of reports
@@ -255,13 +343,21 @@ fn render_webhook_modal(ui: &Ui) {
             for url in report_urls.iter() {
                 let is_legacy = url.contains("Legacy") || url.to_lowercase().contains("legacy");
                 
-                if is_legacy {
-                    ui.text_colored([0.3, 0.7, 1.0, 1.0], "Legacy Report:");
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], &format!("Link: {}", url));
-                } else {
-                    ui.text_colored([0.3, 0.7, 1.0, 1.0], &report_name);
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], &format!("Link: {}", url));
+                let base_name = if is_legacy { "Legacy Report" } else { report_name.as_str() };
+                match split_label_for_report_url(url) {
+                    Some(map) => ui.text_colored([0.3, 0.7, 1.0, 1.0], &format!("{} - {}", base_name, map)),
+                    None => ui.text_colored([0.3, 0.7, 1.0, 1.0], base_name),
                 }
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], "Link:");
+                ui.same_line();
+                crate::ui::streamer::sensitive_text(
+                    ui,
+                    &format!("preview_url_{}", url),
+                    url,
+                    [0.5, 0.5, 0.5, 1.0],
+                    false,
+                );
+                render_url_health(ui, url);
                 ui.spacing();
             }
             ui.unindent();
@@ -277,91 +373,133 @@ fn render_webhook_modal(ui: &Ui) {
             
             if is_sending {
                 ui.text("Sending...");
+                ui.same_line();
+
+                // Only stops a send that is waiting out a rate limit
+                if ui.button("Cancel##cancel_sending") {
+                    if let Some(cancel) = STATE.webhook_cancel.lock().unwrap().take() {
+                        cancel.cancel();
+                    }
+                }
             } else {
                 if ui.button("Send now!") {
-                    let webhook_url = STATE.webhook_url_input.lock().unwrap().clone();
+                    let webhook_url = STATE.webhook_url_input.lock().unwrap().trim().to_string();
                     let remember = *STATE.webhook_remember.lock().unwrap();
-                    
+                    let targets = send_targets(&webhook_url);
+
                     // Validate URL on main thread
-                    if webhook_url.trim().is_empty() {
-                        show_webhook_message("Please enter a webhook URL", true);
-                    } else if !webhook_url.starts_with("https://discord.com/api/webhooks/") 
+                    if targets.is_empty() {
+                        show_webhook_message("Select a saved webhook or enter a webhook URL", true);
+                    } else if !webhook_url.is_empty()
+                        && !webhook_url.starts_with("https://discord.com/api/webhooks/")
                         && !webhook_url.starts_with("https://discordapp.com/api/webhooks/") {
                         show_webhook_message("Invalid Discord webhook URL", true);
                     } else {
                         // Clone all data we need BEFORE spawning thread
-                        let report_urls = STATE.report_urls.lock().unwrap().clone();
+                        let report_urls = STATE.webhook_report_urls.lock().unwrap().clone();
+                        let summary = STATE.webhook_report_summary.lock().unwrap().clone();
                         let report_name = REPORT_NAME_BUFFER.with(|buffer| {
                             let name = buffer.borrow().clone();
                             let current_date = chrono::Local::now().format("%d.%m.%y").to_string();
                             name.replace("(*DATE)", &current_date)
                         });
-                        
+
                         // Set sending state
                         *STATE.webhook_sending.lock().unwrap() = true;
-                        
+                        *STATE.webhook_deliveries.lock().unwrap() = targets
+                            .iter()
+                            .map(|(name, _)| (name.clone(), WebhookDelivery::Waiting))
+                            .collect();
+                        let cancel = crate::cancel::CancelToken::new();
+                        *STATE.webhook_cancel.lock().unwrap() = Some(cancel.clone());
+
                         // Spawn thread with all cloned data
-                        std::thread::spawn(move || {
+                        crate::threads::spawn("webhook", move || {
+                            let _activity = crate::threads::activity("Posting to Discord");
                             log::info!("Discord webhook thread started");
-                            
+
                             // Build a single message with all reports
-                            let mut message_parts = Vec::new();
-                            
-                            for report_url in report_urls.iter() {
-                                let is_legacy = report_url.contains("Legacy") || report_url.to_lowercase().contains("legacy");
-                                
-                                // Format each report link
-                                let link = if is_legacy {
-                                    format!("[Legacy Report]({})", report_url)
-                                } else {
-                                    format!("[{}]({})", report_name, report_url)
-                                };
-                                
-                                message_parts.push(link);
-                            }
-                            
-                            // Join all parts with newline and dash separator
-                            let full_message = message_parts.join("   \n-\n");
-                            
-                            log::info!("Sending Discord message");
-                            
-                            // Send single message with all reports
-                            match send_to_discord(&webhook_url, &full_message) {
-                                Ok(_) => {
-                                    log::info!("All reports sent to Discord successfully");
-                                    
-                                    // Update webhook usage
-                                    let mut webhook_settings = WebhookSettings::get();
-                                    webhook_settings.update_webhook_usage(&webhook_url);
-                                    
-                                    // Save remembered webhook if needed
-                                    if remember {
-                                        webhook_settings.remember_last_webhook = true;
-                                        webhook_settings.last_webhook_url = webhook_url.clone();
-                                    } else {
-                                        webhook_settings.remember_last_webhook = false;
-                                        webhook_settings.last_webhook_url.clear();
+                            let full_message = crate::commanders::append_notes(
+                                &build_report_message(&report_urls, &report_name),
+                                &summary.commanders,
+                            );
+                            let embed = crate::webhooks::report_embed(&report_name, summary);
+
+                            // Stays up until the retry is sent
+                            let on_rate_limited = |delay: std::time::Duration| {
+                                show_webhook_message_for(
+                                    &format!("Rate limited, retrying in {}s", delay.as_secs_f32().ceil()),
+                                    true,
+                                    delay,
+                                );
+                            };
+
+                            // One webhook after another, so a rate limit on one doesn't hit the others
+                            let mut failed = 0;
+                            for (name, url) in targets.iter() {
+                                if cancel.is_cancelled() {
+                                    break;
+                                }
+                                set_delivery(name, WebhookDelivery::Sending);
+                                log::info!("Sending Discord message to {}", name);
+
+                                let result = crate::pending_posts::send_or_queue(
+                                    url,
+                                    &full_message,
+                                    embed.clone(),
+                                    &cancel,
+                                    on_rate_limited,
+                                );
+                                match result {
+                                    Ok(_) => {
+                                        set_delivery(name, WebhookDelivery::Sent);
+                                        WebhookSettings::get().update_webhook_usage(url);
                                     }
-                                    
-                                    if let Err(e) = webhook_settings.store(crate::webhooks_path()) {
-                                        log::error!("Failed to save webhook settings: {}", e);
+                                    Err(e) => {
+                                        log::error!("Failed to send reports to {}: {}", name, e);
+                                        failed += 1;
+                                        set_delivery(name, WebhookDelivery::Failed(e.to_string()));
                                     }
-                                    
-                                    drop(webhook_settings);
-                                    
-                                    // Update status on main thread
-                                    show_webhook_message("All reports sent successfully!", false);
-                                    
-                                    // Close modal after a delay
-                                    std::thread::sleep(std::time::Duration::from_secs(1));
-                                    *STATE.show_webhook_modal.lock().unwrap() = false;
-                                }
-                                Err(e) => {
-                                    log::error!("Failed to send reports to Discord: {}", e);
-                                    show_webhook_message(&format!("Failed to send: {}", e), true);
                                 }
                             }
-                            
+
+                            let mut webhook_settings = WebhookSettings::get();
+
+                            // Save remembered webhook if needed
+                            if remember && !webhook_url.is_empty() {
+                                webhook_settings.remember_last_webhook = true;
+                                webhook_settings.last_webhook_url = webhook_url.clone();
+                            } else if !remember {
+                                webhook_settings.remember_last_webhook = false;
+                                webhook_settings.last_webhook_url.clear();
+                            }
+
+                            if let Err(e) = webhook_settings.store(crate::webhooks_path()) {
+                                log::error!("Failed to save webhook settings: {}", e);
+                            }
+
+                            drop(webhook_settings);
+
+                            if cancel.is_cancelled() {
+                                show_webhook_message("Sending was cancelled", true);
+                            } else if failed == 0 {
+                                log::info!("All reports sent to Discord successfully");
+                                show_webhook_message("All reports sent successfully!", false);
+
+                                // Close modal after a delay
+                                std::thread::sleep(std::time::Duration::from_secs(1));
+                                *STATE.show_webhook_modal.lock().unwrap() = false;
+                            } else {
+                                show_webhook_message(
+                                    &format!(
+                                        "{} of {} failed - queued for retry in the Webhooks tab",
+                                        failed,
+                                        targets.len()
+                                    ),
+                                    true,
+                                );
+                            }
+
                             *STATE.webhook_sending.lock().unwrap() = false;
                             log::info!("Discord webhook thread finished");
                         });
@@ -377,10 +515,227 @@ fn render_webhook_modal(ui: &Ui) {
         });
 }
 
+/// Label of the webhook typed into the URL field of the send modal
+const MANUAL_WEBHOOK_NAME: &str = "Webhook URL";
+
+/// The ticked saved webhooks and the typed URL as (name, url), each URL once
+fn send_targets(typed_url: &str) -> Vec<(String, String)> {
+    let selected = STATE.webhook_selected_names.lock().unwrap().clone();
+    let webhooks = WebhookSettings::get().get_webhooks_sorted();
+
+    let mut targets: Vec<(String, String)> = webhooks
+        .into_iter()
+        .filter(|w| selected.contains(&w.name))
+        .map(|w| (w.name, w.url))
+        .collect();
+
+    if !typed_url.is_empty() && !targets.iter().any(|(_, url)| url == typed_url) {
+        targets.push((MANUAL_WEBHOOK_NAME.to_string(), typed_url.to_string()));
+    }
+    targets
+}
+
+fn set_delivery(name: &str, delivery: WebhookDelivery) {
+    if let Some(entry) = STATE.webhook_deliveries.lock().unwrap().iter_mut().find(|(n, _)| n == name) {
+        entry.1 = delivery;
+    }
+}
+
+/// Shows how the last send to this webhook went after the current item
+fn render_delivery(ui: &Ui, name: &str) {
+    let delivery = STATE
+        .webhook_deliveries
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, delivery)| delivery.clone());
+    let Some(delivery) = delivery else {
+        return;
+    };
+
+    ui.same_line();
+    match delivery {
+        WebhookDelivery::Waiting => ui.text_colored([0.7, 0.7, 0.7, 1.0], "Waiting"),
+        WebhookDelivery::Sending => ui.text_colored([1.0, 1.0, 0.0, 1.0], "Sending..."),
+        WebhookDelivery::Sent => ui.text_colored([0.0, 1.0, 0.0, 1.0], "Sent"),
+        WebhookDelivery::Failed(error) => {
+            ui.text_colored([1.0, 0.5, 0.0, 1.0], "Failed");
+            if ui.is_item_hovered() {
+                ui.tooltip_text(crate::ui::streamer::redact(&error));
+            }
+        }
+    }
+}
+
+/// Lists the session's JSON and CSV files with a download button each
+fn render_artifacts(ui: &Ui) {
+    let artifacts = STATE.report_artifacts.lock().unwrap().clone();
+    if artifacts.is_empty() {
+        return;
+    }
+
+    ui.spacing();
+    ui.text("Other files:");
+    for artifact in artifacts.iter() {
+        ui.bullet_text(&artifact.name);
+        ui.same_line();
+        if ui.small_button(format!("Download##{}", artifact.url)) {
+            start_artifact_download(artifact.clone());
+        }
+        ui.same_line();
+        if ui.small_button(format!("Copy URL##{}", artifact.url)) {
+            ui.set_clipboard_text(&artifact.url);
+        }
+
+        let status = STATE
+            .artifact_downloads
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(url, _)| *url == artifact.url)
+            .map(|(_, status)| status.clone());
+        if let Some(status) = status {
+            ui.same_line();
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], crate::ui::streamer::redact(&status));
+        }
+    }
+
+    ui.text("Save to:");
+    ui.same_line();
+    ui.set_next_item_width(300.0);
+    DOWNLOAD_DIR_BUFFER.with_borrow_mut(|buffer| {
+        let buffer = buffer.get_or_insert_with(|| Settings::get().artifact_download_dir.clone());
+        ui.input_text("##artifact_download_dir", buffer)
+            .hint("Downloads folder")
+            .build();
+        // Saved once the field is left, not on every keystroke
+        if ui.is_item_deactivated_after_edit() {
+            let mut settings = Settings::get();
+            settings.artifact_download_dir = buffer.trim().to_string();
+            if let Err(e) = settings.store(crate::config_path()) {
+                log::error!("Failed to save settings: {}", e);
+            }
+        }
+    });
+}
+
+fn set_artifact_status(url: &str, status: String) {
+    let mut downloads = STATE.artifact_downloads.lock().unwrap();
+    match downloads.iter_mut().find(|(u, _)| u == url) {
+        Some(entry) => entry.1 = status,
+        None => downloads.push((url.to_string(), status)),
+    }
+}
+
+fn start_artifact_download(artifact: crate::report_history::ReportArtifact) {
+    set_artifact_status(&artifact.url, "Downloading...".to_string());
+    let folder = crate::upload::artifact_download_dir();
+
+    crate::threads::spawn("artifact-download", move || {
+        let _activity = crate::threads::activity("Downloading report file");
+        let status = match crate::upload::download_artifact(&artifact, &folder) {
+            Ok(path) => format!("Saved to {}", path.display()),
+            Err(e) => {
+                log::error!("Failed to download {}: {}", artifact.name, e);
+                format!("Failed: {}", e)
+            }
+        };
+        set_artifact_status(&artifact.url, status);
+    });
+}
+
+/// Starts a background health check for each report URL not checked yet
+fn start_url_checks(report_urls: &[String]) {
+    let mut health = STATE.report_url_health.lock().unwrap();
+
+    for url in report_urls.iter() {
+        if health.iter().any(|(checked, _)| checked == url) {
+            continue;
+        }
+        health.push((url.clone(), UrlHealth::Checking));
+
+        let url = url.clone();
+        crate::threads::spawn("url-check", move || {
+            let result = match check_report_url(&url) {
+                Ok(()) => UrlHealth::Live,
+                Err(e) => {
+                    log::warn!("Report URL is not reachable yet: {}", e);
+                    UrlHealth::Unavailable(e.to_string())
+                }
+            };
+
+            if let Some(entry) = STATE
+                .report_url_health
+                .lock()
+                .unwrap()
+                .iter_mut()
+                .find(|(checked, _)| *checked == url)
+            {
+                entry.1 = result;
+            }
+        });
+    }
+}
+
+/// Shows a check or cross after the current item for a checked report URL
+/// Clicking the cross checks the URL again
+fn render_url_health(ui: &Ui, url: &str) {
+    let health = STATE
+        .report_url_health
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(checked, _)| checked == url)
+        .map(|(_, health)| health.clone());
+
+    let Some(health) = health else {
+        return;
+    };
+
+    ui.same_line();
+    match health {
+        UrlHealth::Checking => {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], "...");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Checking if the report is live");
+            }
+        }
+        UrlHealth::Live => {
+            ui.text_colored([0.0, 1.0, 0.0, 1.0], "✓");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Report is live");
+            }
+        }
+        UrlHealth::Unavailable(error) => {
+            ui.text_colored([1.0, 0.0, 0.0, 1.0], "✗");
+            if ui.is_item_hovered() {
+                ui.tooltip_text(format!(
+                    "Report is not reachable yet: {}\nClick to check again",
+                    crate::ui::streamer::redact(&error)
+                ));
+            }
+            if ui.is_item_clicked() {
+                // Dropping the entry makes the next frame start a fresh check
+                STATE
+                    .report_url_health
+                    .lock()
+                    .unwrap()
+                    .retain(|(checked, _)| checked != url);
+            }
+        }
+    }
+}
+
 fn show_webhook_message(message: &str, is_error: bool) {
+    show_webhook_message_for(message, is_error, std::time::Duration::from_secs(3));
+}
+
+/// Shows a status message in the webhook modal for the given time
+fn show_webhook_message_for(message: &str, is_error: bool, duration: std::time::Duration) {
     // Create the values we need first
     let message_string = message.to_string();
-    let until_time = Some(std::time::Instant::now() + std::time::Duration::from_secs(3));
+    let until_time = Some(std::time::Instant::now() + duration);
     
     // Do all locks in sequence, dropping each immediately to prevent deadlock
     {
@@ -408,7 +763,7 @@ fn mark_uploaded_logs() {
     for log in logs.iter() {
         if log.selected && log.uploaded {
             if !uploaded.is_uploaded(&log.filename) {
-                uploaded.add_log(log.filename.clone());
+                uploaded.add_uploaded(log);
                 newly_added += 1;
             }
         }
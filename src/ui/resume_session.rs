@@ -0,0 +1,68 @@
+use nexus::imgui::Ui;
+
+use crate::state::STATE;
+
+/// Most files listed in the prompt, the rest is summed up
+const MAX_LISTED_FILES: usize = 5;
+
+/// Renders the prompt offering to continue the session left behind by a crash or reload
+pub fn render_resume_prompt(ui: &Ui) {
+    let Some(saved) = STATE.resumable_session.lock().unwrap().clone() else {
+        return;
+    };
+
+    ui.open_popup("resume_session");
+
+    ui.popup_modal("resume_session")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text_colored([1.0, 0.8, 0.2, 1.0], "Previous Session Found");
+            ui.spacing();
+            ui.text_wrapped(&format!(
+                "The last session was not processed before the game or addon closed. It has {} uploaded.",
+                crate::formatting::plural(saved.files.len() as u64, "log", "logs")
+            ));
+            ui.spacing();
+
+            for file in saved.files.iter().take(MAX_LISTED_FILES) {
+                let name = file
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.timestamp.clone())
+                    .unwrap_or_else(|| file.filename.clone());
+                ui.bullet_text(&name);
+                if let Some(metadata) = &file.metadata {
+                    ui.same_line();
+                    ui.text_colored(metadata.map_color, &metadata.map_abbr);
+                }
+            }
+            if saved.files.len() > MAX_LISTED_FILES {
+                ui.text_colored(
+                    [0.7, 0.7, 0.7, 1.0],
+                    &format!("...and {} more", saved.files.len() - MAX_LISTED_FILES),
+                );
+            }
+
+            ui.spacing();
+            ui.separator();
+            ui.spacing();
+
+            if ui.button("Resume Session") {
+                crate::session::resume(saved.clone());
+                ui.close_current_popup();
+            }
+
+            ui.same_line();
+
+            if ui.button("Discard") {
+                crate::session::discard();
+                ui.close_current_popup();
+            }
+
+            ui.spacing();
+            ui.text_colored(
+                [0.6, 0.6, 0.6, 1.0],
+                "Discarded sessions can still be found under Cleanup > Unprocessed Server Sessions",
+            );
+        });
+}
@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use nexus::imgui::Ui;
+
+use crate::settings::{Settings, SETTINGS_SECTIONS};
+use crate::state::STATE;
+
+thread_local! {
+    static SAFE_MODE_MESSAGE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    // Section check of the damaged file, cached so it isn't re-read every frame
+    static SECTION_STATUS: std::cell::RefCell<Option<Option<Vec<&'static str>>>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Renders the safe mode screen shown when settings.json could not be parsed
+pub fn render_safe_mode(ui: &Ui, config_path: &Path) {
+    let error = STATE.settings_safe_mode.lock().unwrap().clone().unwrap_or_default();
+
+    ui.text_colored([1.0, 0.3, 0.3, 1.0], "Settings could not be loaded");
+    ui.spacing();
+    ui.text_wrapped(
+        "settings.json is damaged. Nothing has been overwritten - WvW Insights is running on \
+         defaults until the file is fixed, and no settings are saved in the meantime.",
+    );
+    ui.spacing();
+
+    ui.text("Error:");
+    ui.text_colored([1.0, 0.5, 0.5, 1.0], &crate::ui::streamer::redact(&error));
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Restore the last settings that loaded fine
+    let backup_path = Settings::backup_path(config_path);
+    let backup_time = std::fs::metadata(&backup_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+
+    match backup_time {
+        Some(timestamp) => {
+            if ui.button("Restore Latest Backup") {
+                match std::fs::copy(&backup_path, config_path) {
+                    Ok(_) => {
+                        log::info!("Restored settings from backup");
+                        try_reload(config_path);
+                    }
+                    Err(e) => set_message(format!("Failed to restore backup: {}", e)),
+                }
+            }
+            ui.same_line();
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                &format!(
+                    "Saved {}",
+                    crate::formatting::format_report_timestamp(timestamp)
+                ),
+            );
+        }
+        None => {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], "No backup available");
+        }
+    }
+
+    ui.spacing();
+
+    // Fix the file by hand
+    if ui.button("Open Settings Folder") {
+        if let Some(folder) = config_path.parent() {
+            if let Err(e) = open::that_detached(folder) {
+                log::error!("Failed to open settings folder: {}", e);
+            }
+        }
+    }
+    ui.same_line();
+    if ui.button("Retry Loading") {
+        try_reload(config_path);
+    }
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Edit settings.json in a text editor, then retry.",
+    );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Reset only what is broken
+    let status = SECTION_STATUS.with_borrow_mut(|status| {
+        status
+            .get_or_insert_with(|| {
+                std::fs::read_to_string(config_path)
+                    .ok()
+                    .and_then(|contents| Settings::invalid_sections(&contents))
+            })
+            .clone()
+    });
+
+    ui.text("Sections:");
+    match status {
+        Some(invalid) => {
+            for (name, _) in SETTINGS_SECTIONS.iter() {
+                let is_invalid = invalid.contains(name);
+                if is_invalid {
+                    ui.text_colored([1.0, 0.4, 0.4, 1.0], &format!("{} - unreadable", name));
+                } else {
+                    ui.text_colored([0.4, 1.0, 0.4, 1.0], &format!("{} - OK", name));
+                }
+                ui.same_line();
+                if ui.small_button(&format!("Reset##reset_section_{}", name)) {
+                    match Settings::reset_section(config_path, name) {
+                        Ok(()) => try_reload(config_path),
+                        Err(e) => set_message(format!("Failed to reset {}: {}", name, e)),
+                    }
+                }
+            }
+        }
+        None => {
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                "The file is not valid JSON, so single sections can't be reset.",
+            );
+        }
+    }
+
+    ui.spacing();
+
+    if ui.button("Reset Everything") {
+        ui.open_popup("safe_mode_reset_all");
+    }
+
+    ui.popup_modal("safe_mode_reset_all")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text("Start over with default settings?");
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                "The damaged file is kept as settings.corrupt.json.",
+            );
+            ui.spacing();
+
+            if ui.button("Yes, Reset") {
+                ui.close_current_popup();
+                reset_everything(config_path);
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+        });
+
+    let message = SAFE_MODE_MESSAGE.with_borrow(|m| m.clone());
+    if !message.is_empty() {
+        ui.spacing();
+        ui.text_colored([1.0, 1.0, 0.0, 1.0], &message);
+    }
+}
+
+/// Loads settings.json again, leaving safe mode if it parses now
+fn try_reload(config_path: &Path) {
+    SECTION_STATUS.set(None);
+
+    match Settings::from_path(config_path) {
+        Ok(()) => {
+            *STATE.settings_safe_mode.lock().unwrap() = None;
+            set_message(String::new());
+            log::info!("Settings loaded, leaving safe mode");
+
+            if Settings::get().mouse_lock_enabled {
+                crate::qol::enable_mouse_lock();
+            }
+        }
+        Err(e) => {
+            *STATE.settings_safe_mode.lock().unwrap() = Some(e.to_string());
+            set_message("Settings still can't be read".to_string());
+        }
+    }
+}
+
+/// Moves the damaged file aside and starts with first-launch defaults
+fn reset_everything(config_path: &Path) {
+    let corrupt_path = config_path.with_file_name("settings.corrupt.json");
+    if let Err(e) = std::fs::rename(config_path, &corrupt_path) {
+        set_message(format!("Failed to move damaged settings aside: {}", e));
+        return;
+    }
+    log::info!("Moved damaged settings to {:?}", corrupt_path);
+
+    // Safe mode has to end first, loading a missing file saves fresh defaults
+    *STATE.settings_safe_mode.lock().unwrap() = None;
+    try_reload(config_path);
+}
+
+fn set_message(message: String) {
+    SAFE_MODE_MESSAGE.set(message);
+}
@@ -0,0 +1,142 @@
+use nexus::imgui::{ChildWindow, TreeNodeFlags, Ui};
+
+use crate::attendance::AttendanceLedger;
+use crate::formatting::{format_report_timestamp, plural};
+
+thread_local! {
+    static ATTENDANCE_MESSAGE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+}
+
+/// Renders the attendance tab: who was in the squad across processed sessions
+pub fn render_attendance_tab(ui: &Ui) {
+    ui.text("Raid Attendance:");
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Squad members are read from the logs of each processed session",
+    );
+    ui.spacing();
+
+    let mut enabled = AttendanceLedger::get().enabled;
+    if ui.checkbox("Record attendance for new reports", &mut enabled) {
+        let mut ledger = AttendanceLedger::get();
+        ledger.enabled = enabled;
+        if let Err(e) = ledger.store(crate::attendance_path()) {
+            log::error!("Failed to save attendance: {}", e);
+        }
+    }
+
+    if !enabled {
+        return;
+    }
+
+    ui.spacing();
+
+    if ui.button("Import from Report History") {
+        let queued = crate::attendance::import_report_history();
+        ATTENDANCE_MESSAGE.set(if queued == 0 {
+            "Every session in the report history is already recorded".to_string()
+        } else {
+            format!("Reading the logs of {}", plural(queued as u64, "session", "sessions"))
+        });
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Adds earlier sessions whose logs are still in the log directory");
+    }
+
+    ui.same_line();
+
+    if ui.button("Export CSV") {
+        match crate::attendance::export_csv() {
+            Ok(path) => {
+                ATTENDANCE_MESSAGE.set(format!("Exported to {}", path.display()));
+                if let Some(dir) = path.parent() {
+                    if let Err(e) = open::that_detached(dir) {
+                        log::error!("Failed to open attendance folder: {}", e);
+                    }
+                }
+            }
+            Err(e) => ATTENDANCE_MESSAGE.set(format!("Export failed: {}", e)),
+        }
+    }
+
+    ui.same_line();
+
+    if ui.button("Copy CSV") {
+        ui.set_clipboard_text(AttendanceLedger::get().to_csv());
+        ATTENDANCE_MESSAGE.set("Copied to clipboard".to_string());
+    }
+
+    if crate::attendance::is_busy() {
+        ui.text_colored([0.7, 0.9, 1.0, 1.0], "Reading logs...");
+    }
+    let message = ATTENDANCE_MESSAGE.with_borrow(|m| m.clone());
+    if !message.is_empty() {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], &crate::ui::streamer::redact(&message));
+    }
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    let ledger = AttendanceLedger::get().clone();
+    if ledger.sessions.is_empty() {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], "No sessions recorded yet");
+        return;
+    }
+
+    let summary = ledger.summary();
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        &format!(
+            "{} across {}",
+            plural(summary.len() as u64, "account", "accounts"),
+            plural(ledger.sessions.len() as u64, "session", "sessions")
+        ),
+    );
+
+    ChildWindow::new("AttendanceList")
+        .size([0.0, 300.0])
+        .build(ui, || {
+            if ui.collapsing_header("Accounts", TreeNodeFlags::DEFAULT_OPEN) {
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Account");
+                ui.same_line_with_pos(250.0);
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Sessions");
+                ui.same_line_with_pos(330.0);
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Fights");
+                ui.same_line_with_pos(400.0);
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Last seen");
+
+                for account in &summary {
+                    ui.text(&account.account);
+                    ui.same_line_with_pos(250.0);
+                    ui.text(account.sessions.to_string());
+                    ui.same_line_with_pos(330.0);
+                    ui.text(account.fights.to_string());
+                    ui.same_line_with_pos(400.0);
+                    ui.text(format_report_timestamp(account.last_seen));
+                }
+            }
+
+            if ui.collapsing_header("Sessions", TreeNodeFlags::empty()) {
+                let mut sessions = ledger.sessions.clone();
+                sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+                for session in &sessions {
+                    ui.text(format!(
+                        "{} - {}, {}",
+                        format_report_timestamp(session.timestamp),
+                        plural(session.attendees.len() as u64, "account", "accounts"),
+                        plural(session.fights as u64, "fight", "fights")
+                    ));
+                    ui.same_line();
+                    if ui.small_button(&format!("Remove##attendance_{}", session.session_id)) {
+                        let mut ledger = AttendanceLedger::get();
+                        ledger.remove_session(&session.session_id);
+                        if let Err(e) = ledger.store(crate::attendance_path()) {
+                            log::error!("Failed to save attendance: {}", e);
+                        }
+                    }
+                }
+            }
+        });
+}
@@ -1,6 +1,9 @@
-use nexus::imgui::Ui;
+use nexus::imgui::{ChildWindow, Ui};
 
-use crate::cleanup::cleanup_old_logs;
+use crate::capabilities::Capability;
+use crate::cleanup::{
+    cleanup_old_logs, count_stranded_logs, recycle_stranded_folder, restore_stranded_folder,
+};
 use crate::settings::Settings;
 use crate::state::STATE;
 
@@ -215,7 +218,8 @@ pub fn render_cleanup_tab(ui: &Ui) {
                     let days_to_delete = days as u32;
                     let dir_to_clean = log_dir.clone();
 
-                    std::thread::spawn(move || {
+                    crate::threads::spawn_tracked("cleanup", move || {
+                        let _activity = crate::threads::activity("Cleaning up old logs");
                         let result = cleanup_old_logs(&dir_to_clean, days_to_delete);
                         *STATE.cleanup_result.lock().unwrap() = Some(result);
                         *STATE.cleanup_in_progress.lock().unwrap() = false;
@@ -268,6 +272,24 @@ pub fn render_cleanup_tab(ui: &Ui) {
     ui.separator();
     ui.spacing();
 
+    render_organize_section(ui);
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    render_archive_section(ui);
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    render_server_cleanup_section(ui);
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
     // Upload history management
     ui.text_colored([1.0, 1.0, 0.0, 1.0], "Upload History");
     ui.spacing();
@@ -312,4 +334,695 @@ pub fn render_cleanup_tab(ui: &Ui) {
                 ui.close_current_popup();
             }
         });
-}
\ No newline at end of file
+
+    ui.spacing();
+    render_upload_archive_section(ui);
+
+    super::render_advanced_section(ui, "cleanup", || {
+        render_pending_sessions_section(ui);
+
+        ui.spacing();
+        ui.separator();
+        ui.spacing();
+
+        render_upload_audit_section(ui);
+    });
+}
+
+/// Renders the opt-in step that moves a session's logs away once its report is ready
+fn render_archive_section(ui: &Ui) {
+    thread_local! {
+        static ARCHIVE_DIR_BUFFER: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+    }
+
+    ui.text_colored([1.0, 1.0, 0.0, 1.0], "Archive Uploaded Logs");
+    ui.spacing();
+
+    let settings = Settings::get();
+    let mut enabled = settings.archive_uploaded_logs;
+    let saved_dir = settings.archive_directory.clone();
+    drop(settings);
+
+    if ui.checkbox("Move logs to an archive folder after their report is ready", &mut enabled) {
+        let mut settings = Settings::get();
+        settings.archive_uploaded_logs = enabled;
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+
+    ui.text("Archive directory:");
+    ui.set_next_item_width(300.0);
+    ARCHIVE_DIR_BUFFER.with_borrow_mut(|buffer| {
+        let buffer = buffer.get_or_insert_with(|| saved_dir.clone());
+        ui.input_text("##archive_directory", buffer)
+            .hint("Uploaded folder in the log directory")
+            .build();
+        // Saved once the field is left, not on every keystroke
+        if ui.is_item_deactivated_after_edit() {
+            let mut settings = Settings::get();
+            settings.archive_directory = buffer.trim().to_string();
+            if let Err(e) = settings.store(crate::config_path()) {
+                log::error!("Failed to save settings: {}", e);
+            }
+            drop(settings);
+            // Scans skip the archive folder, the old one has to be walked again
+            crate::scanning::forget_directory_fingerprints();
+        }
+    });
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Each day gets its own folder, e.g. Uploaded\\2025-10-10",
+    );
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Archived logs are left out of scans, live mode, cleanup and organizing",
+    );
+}
+
+/// Renders the action that sorts the log directory into dated subfolders
+fn render_organize_section(ui: &Ui) {
+    thread_local! {
+        static PATTERN_BUFFER: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+        static PATTERN_ERROR: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    }
+
+    ui.text_colored([1.0, 1.0, 0.0, 1.0], "Organize Logs");
+    ui.spacing();
+    ui.text_wrapped("Move logs into dated subfolders of the log directory to keep it manageable. Logs queued for upload stay where they are.");
+    ui.spacing();
+
+    let settings = Settings::get();
+    let saved_pattern = settings.organize_pattern.clone();
+    let log_dir = settings.log_directory.clone();
+    drop(settings);
+    // Formatting a broken pattern panics, so only a valid one is shown as an example
+    let example = crate::cleanup::validate_organize_pattern(&saved_pattern)
+        .is_ok()
+        .then(|| chrono::Local::now().format(&saved_pattern).to_string());
+
+    ui.text("Folder pattern:");
+    ui.set_next_item_width(200.0);
+    PATTERN_BUFFER.with_borrow_mut(|buffer| {
+        let buffer = buffer.get_or_insert_with(|| saved_pattern.clone());
+        if ui.input_text("##organize_pattern", buffer).build() {
+            match crate::cleanup::validate_organize_pattern(buffer) {
+                Ok(()) => {
+                    PATTERN_ERROR.with_borrow_mut(|e| e.clear());
+                    let mut settings = Settings::get();
+                    settings.organize_pattern = buffer.clone();
+                    if let Err(e) = settings.store(crate::config_path()) {
+                        log::error!("Failed to save settings: {}", e);
+                    }
+                }
+                Err(e) => PATTERN_ERROR.set(e),
+            }
+        }
+    });
+    ui.same_line();
+    if ui.small_button("Reset##organize_pattern") {
+        let pattern = crate::settings::default_organize_pattern();
+        PATTERN_BUFFER.set(Some(pattern.clone()));
+        PATTERN_ERROR.with_borrow_mut(|e| e.clear());
+        let mut settings = Settings::get();
+        settings.organize_pattern = pattern;
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+
+    let error = PATTERN_ERROR.with_borrow(|e| e.clone());
+    if !error.is_empty() {
+        ui.text_colored([1.0, 0.5, 0.0, 1.0], &error);
+    } else if let Some(ref example) = example {
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], &format!("(%Y year, %m month, %d day - e.g. {})", example));
+    }
+
+    ui.spacing();
+
+    let is_running = *STATE.organize_running.lock().unwrap();
+    if is_running {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+        let _style2 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+        let _style3 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+        ui.button("Organizing...");
+    } else if ui.button("Organize Logs") && example.is_some() {
+        ui.open_popup("confirm_organize_logs");
+    }
+
+    ui.popup_modal("confirm_organize_logs")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text("Move every log into dated subfolders?");
+            ui.spacing();
+            let target = std::path::Path::new(&log_dir).join(example.as_deref().unwrap_or_default());
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], &format!("e.g. {}", target.display()));
+            ui.spacing();
+
+            if ui.button("Yes, Organize") {
+                ui.close_current_popup();
+                crate::cleanup::start_organize_logs();
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+        });
+
+    let summary = STATE.organize_summary.lock().unwrap().clone();
+    if !summary.is_empty() {
+        ui.text_colored([0.0, 1.0, 0.0, 1.0], &summary);
+    }
+}
+
+/// Whether the configured server may have the endpoint a section needs
+fn server_supports(capability: Capability) -> bool {
+    crate::capabilities::supports(&Settings::get().api_endpoint, capability)
+}
+
+/// Shown in place of a section whose server endpoint is missing
+fn render_unsupported(ui: &Ui) {
+    ui.text_colored([0.5, 0.5, 0.5, 1.0], "Not supported by this server");
+}
+
+/// Renders the settings for deleting old reports from the server
+fn render_server_cleanup_section(ui: &Ui) {
+    ui.text_colored([1.0, 1.0, 0.0, 1.0], "Server Report Cleanup");
+    ui.spacing();
+    ui.text_wrapped("Delete your old reports from the parser website. Runs at most once a week when the plugin loads.");
+    ui.spacing();
+
+    if !server_supports(Capability::DeleteReport) {
+        render_unsupported(ui);
+        return;
+    }
+
+    let settings = Settings::get();
+    let mut enabled = settings.server_cleanup_enabled;
+    let mut months = settings.server_cleanup_months as i32;
+    let last_run = settings.last_server_cleanup;
+    drop(settings);
+
+    if ui.checkbox("Delete my reports from the server when they get old", &mut enabled) {
+        let mut settings = Settings::get();
+        settings.server_cleanup_enabled = enabled;
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+
+    ui.text("Delete reports older than:");
+    ui.set_next_item_width(100.0);
+    if ui.input_int("##server_cleanup_months", &mut months).build() {
+        months = months.clamp(1, 120);
+        let mut settings = Settings::get();
+        settings.server_cleanup_months = months as u32;
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+    ui.same_line();
+    ui.text("months");
+
+    ui.spacing();
+
+    let is_running = *STATE.server_cleanup_running.lock().unwrap();
+    if is_running {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+        let _style2 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+        let _style3 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+        ui.button("Deleting...");
+    } else if ui.button("Run Now") {
+        ui.open_popup("confirm_server_cleanup");
+    }
+
+    if last_run > 0 {
+        ui.same_line();
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            &format!("Last run {}", crate::formatting::format_report_timestamp(last_run)),
+        );
+    }
+
+    ui.popup_modal("confirm_server_cleanup")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text(format!(
+                "Delete reports older than {} from the server?",
+                crate::formatting::plural(months.max(1) as u64, "month", "months")
+            ));
+            ui.spacing();
+            ui.text_colored([1.0, 1.0, 0.0, 1.0], "This cannot be undone!");
+            ui.spacing();
+
+            if ui.button("Yes, Delete") {
+                ui.close_current_popup();
+                crate::cleanup::start_server_cleanup();
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+        });
+
+    let summary = STATE.server_cleanup_summary.lock().unwrap().clone();
+    if !summary.is_empty() {
+        ui.text_colored([0.0, 1.0, 0.0, 1.0], &summary);
+    }
+}
+
+/// Renders the upload audit, listing mismatches between tracked uploads, local files and the server
+fn render_upload_audit_section(ui: &Ui) {
+    ui.text_colored([1.0, 1.0, 0.0, 1.0], "Upload Audit");
+    ui.spacing();
+    ui.text_wrapped("Cross-checks logs marked as uploaded against the log folder, your report history and unprocessed server sessions.");
+    ui.spacing();
+
+    let is_running = *STATE.audit_running.lock().unwrap();
+    if is_running {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+        let _style2 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+        let _style3 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+        ui.button("Auditing...");
+    } else if ui.button("Run Audit") {
+        crate::audit::start_upload_audit();
+    }
+
+    let message = STATE.audit_message.lock().unwrap().clone();
+    if !message.is_empty() {
+        ui.same_line();
+        ui.text_colored([1.0, 0.8, 0.2, 1.0], &crate::ui::streamer::redact(&message));
+    }
+
+    let Some(findings) = STATE.audit_findings.lock().unwrap().clone() else {
+        return;
+    };
+
+    ui.spacing();
+
+    if findings.is_empty() {
+        ui.text_colored([0.0, 1.0, 0.0, 1.0], "No mismatches found");
+        return;
+    }
+
+    ui.text(format!("{} mismatch(es) found", findings.len()));
+    ui.same_line();
+    if ui.small_button("Apply All Fixes") {
+        apply_audit_fixes(&findings);
+    }
+
+    let mut fixed = None;
+    ChildWindow::new("UploadAuditList")
+        .size([0.0, 200.0])
+        .build(ui, || {
+            for (index, finding) in findings.iter().enumerate() {
+                ui.text(finding.filename());
+                ui.same_line();
+                ui.text_colored([1.0, 0.6, 0.3, 1.0], finding.description());
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], finding.suggestion());
+
+                if ui.small_button(&format!("{}##audit_fix_{}", finding.fix_label(), index)) {
+                    fixed = Some(index);
+                }
+                ui.separator();
+            }
+        });
+
+    if let Some(index) = fixed {
+        apply_audit_fixes(&findings[index..=index]);
+    }
+}
+
+/// Renders the retention setting and search of uploads that are no longer tracked
+fn render_upload_archive_section(ui: &Ui) {
+    thread_local! {
+        static ARCHIVE_QUERY: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    }
+
+    let mut days = Settings::get().uploaded_archive_days as i32;
+    ui.text("Keep older uploads searchable for:");
+    ui.set_next_item_width(100.0);
+    if ui.input_int("##uploaded_archive_days", &mut days).build() {
+        days = days.clamp(0, 365);
+        let mut settings = Settings::get();
+        settings.uploaded_archive_days = days as u32;
+        if let Err(e) = settings.store(crate::config_path()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+    ui.same_line();
+    ui.text("days");
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Logs leave the list above after 72 hours and are archived here, 0 forgets them",
+    );
+
+    let uploaded = crate::uploaded_logs::UploadedLogs::get();
+    if uploaded.archive.is_empty() {
+        return;
+    }
+
+    ui.spacing();
+    ui.text(format!("Search {} archived uploads:", uploaded.archive.len()));
+    ARCHIVE_QUERY.with_borrow_mut(|query| {
+        ui.set_next_item_width(250.0);
+        ui.input_text("##archive_query", query)
+            .hint("e.g. tuesday, oct 14 or 20251014")
+            .build();
+    });
+
+    let query = ARCHIVE_QUERY.with_borrow(|q| q.trim().to_string());
+    if query.is_empty() {
+        return;
+    }
+
+    let matches = uploaded.search_archive(&query);
+    if matches.is_empty() {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], "No archived uploads match");
+        return;
+    }
+
+    ui.text_colored(
+        [0.4, 0.8, 0.4, 1.0],
+        &format!("{} matching upload(s)", matches.len()),
+    );
+    ChildWindow::new("UploadArchiveResults")
+        .size([0.0, 150.0])
+        .build(ui, || {
+            for (filename, archived) in &matches {
+                let time = crate::formatting::format_timestamp(filename).unwrap_or_else(|| filename.to_string());
+                ui.text(&time);
+                ui.same_line();
+                ui.text_colored(
+                    [0.5, 0.5, 0.5, 1.0],
+                    &format!(
+                        "{} (archived {})",
+                        filename,
+                        crate::formatting::format_report_timestamp(*archived)
+                    ),
+                );
+            }
+        });
+}
+
+/// Applies the local fixes of the given findings, saves the tracking and drops them from the list
+fn apply_audit_fixes(findings: &[crate::audit::AuditFinding]) {
+    let mut uploaded = crate::uploaded_logs::UploadedLogs::get();
+    for finding in findings.iter() {
+        finding.apply_fix(&mut uploaded);
+    }
+    if let Err(e) = uploaded.store(crate::uploaded_logs_path()) {
+        log::error!("Failed to save upload tracking after audit fixes: {}", e);
+        return;
+    }
+    drop(uploaded);
+
+    log::info!("Applied {} audit fix(es)", findings.len());
+    if let Some(remaining) = STATE.audit_findings.lock().unwrap().as_mut() {
+        remaining.retain(|f| !findings.contains(f));
+    }
+}
+
+/// Renders the list of server sessions that were uploaded to but never processed
+fn render_pending_sessions_section(ui: &Ui) {
+    ui.text_colored([1.0, 1.0, 0.0, 1.0], "Unprocessed Server Sessions");
+    ui.spacing();
+    ui.text_wrapped("Sessions with uploaded logs that were never processed (e.g. after a crash). Resume them or delete them so they don't count against your quota.");
+    ui.spacing();
+
+    if !server_supports(Capability::PendingSessions) {
+        render_unsupported(ui);
+        return;
+    }
+
+    let is_loading = *STATE.pending_sessions_loading.lock().unwrap();
+    if is_loading {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+        let _style2 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+        let _style3 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+        ui.button("Checking...");
+    } else if ui.button("Check Server") {
+        refresh_pending_sessions();
+    }
+
+    let message = STATE.pending_sessions_message.lock().unwrap().clone();
+    if !message.is_empty() {
+        ui.same_line();
+        ui.text_colored([0.7, 0.9, 1.0, 1.0], &crate::ui::streamer::redact(&message));
+    }
+
+    let sessions = STATE.pending_sessions.lock().unwrap().clone();
+    let has_active_session = !STATE.session_id.lock().unwrap().is_empty();
+    let can_delete = server_supports(Capability::DeleteSession);
+
+    for session in sessions.iter() {
+        ui.spacing();
+        crate::ui::streamer::sensitive_text(
+            ui,
+            &format!("pending_session_{}", session.session_id),
+            &session.session_id,
+            [0.8, 0.8, 1.0, 1.0],
+            false,
+        );
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            &format!(
+                "{} file(s) - created {}",
+                session.files.len(),
+                crate::formatting::format_report_timestamp(session.created)
+            ),
+        );
+
+        if has_active_session {
+            let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+            let _style2 =
+                ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+            let _style3 =
+                ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+            ui.small_button(&format!("Resume##resume_{}", session.session_id));
+            drop(_style3);
+            drop(_style2);
+            drop(_style);
+
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Finish or cancel your current session first");
+            }
+        } else if ui.small_button(&format!("Resume##resume_{}", session.session_id)) {
+            resume_pending_session(session);
+        }
+
+        if can_delete {
+            ui.same_line();
+
+            if ui.small_button(&format!("Delete##delete_{}", session.session_id)) {
+                delete_pending_session(session.clone());
+            }
+        }
+
+        ui.separator();
+    }
+}
+
+/// Asks the server for unprocessed sessions of the current token
+fn refresh_pending_sessions() {
+    let settings = Settings::get();
+    let api_endpoint = settings.api_endpoint.clone();
+    let history_token = settings.history_token.clone();
+    drop(settings);
+
+    if history_token.is_empty() {
+        *STATE.pending_sessions_message.lock().unwrap() = "Enter a history token first".to_string();
+        return;
+    }
+
+    *STATE.pending_sessions_loading.lock().unwrap() = true;
+    STATE.pending_sessions_message.lock().unwrap().clear();
+
+    crate::threads::spawn("pending-sessions", move || {
+        let _activity = crate::threads::activity("Loading server sessions");
+        match crate::upload::list_pending_sessions(&api_endpoint, &history_token) {
+            Ok(sessions) => {
+                *STATE.pending_sessions_message.lock().unwrap() = if sessions.is_empty() {
+                    "No unprocessed sessions".to_string()
+                } else {
+                    format!("{} unprocessed session(s)", sessions.len())
+                };
+                *STATE.pending_sessions.lock().unwrap() = sessions;
+            }
+            Err(e) => {
+                log::error!("Failed to list unprocessed sessions: {}", e);
+                *STATE.pending_sessions_message.lock().unwrap() = format!("Failed: {}", e);
+            }
+        }
+        *STATE.pending_sessions_loading.lock().unwrap() = false;
+    });
+}
+
+/// Makes an unprocessed server session the active one and opens the review screen
+fn resume_pending_session(session: &crate::upload::PendingSession) {
+    use crate::upload_review::UploadedFileInfo;
+
+    log::info!("Resuming unprocessed session {}", session.session_id);
+
+    *STATE.session_id.lock().unwrap() = session.session_id.clone();
+    *STATE.ownership_token.lock().unwrap() = session.ownership_token.clone();
+    *STATE.uploaded_files.lock().unwrap() = session
+        .files
+        .iter()
+        .map(|f| UploadedFileInfo {
+            filename: f.name.clone(),
+            size: format!("{:.2} MB", f.size as f64 / 1024.0 / 1024.0),
+            metadata: None,
+            session_id: session.session_id.clone(),
+            upload_timing: None,
+        })
+        .collect();
+    STATE.split_sessions.lock().unwrap().clear();
+
+    STATE
+        .pending_sessions
+        .lock()
+        .unwrap()
+        .retain(|s| s.session_id != session.session_id);
+
+    *STATE.show_settings.lock().unwrap() = false;
+    *STATE.show_upload_review.lock().unwrap() = true;
+}
+
+/// Deletes an unprocessed session from the server
+fn delete_pending_session(session: crate::upload::PendingSession) {
+    let api_endpoint = Settings::get().api_endpoint.clone();
+
+    crate::threads::spawn("session-delete", move || {
+        match crate::upload::delete_session(&api_endpoint, &session.session_id, &session.ownership_token) {
+            Ok(message) => {
+                log::info!("Deleted unprocessed session: {}", message);
+                STATE
+                    .pending_sessions
+                    .lock()
+                    .unwrap()
+                    .retain(|s| s.session_id != session.session_id);
+                *STATE.pending_sessions_message.lock().unwrap() = "Session deleted".to_string();
+            }
+            Err(e) => {
+                log::error!("Failed to delete session {}: {}", session.session_id, e);
+                *STATE.pending_sessions_message.lock().unwrap() = format!("Failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Renders the recovery prompt for cleanup folders stranded by a crash mid-cleanup
+/// Offers to finish the recycle, restore the logs, or keep the folder as it is
+pub fn render_stranded_cleanup_prompt(ui: &Ui) {
+    let folders = STATE.stranded_cleanup_folders.lock().unwrap().clone();
+    if folders.is_empty() {
+        return;
+    }
+
+    ui.open_popup("stranded_cleanup");
+
+    ui.popup_modal("stranded_cleanup")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text_colored([1.0, 0.8, 0.2, 1.0], "Interrupted Cleanup Found");
+            ui.spacing();
+            ui.text_wrapped("A previous log cleanup was interrupted before the files reached the Recycle Bin.");
+            ui.text_wrapped("Choose what to do with the logs that were left behind:");
+            ui.spacing();
+            ui.separator();
+            ui.spacing();
+
+            let is_busy = *STATE.stranded_cleanup_busy.lock().unwrap();
+
+            for folder in folders.iter() {
+                let folder_name = folder
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                ui.text(&folder_name);
+                ui.same_line();
+                ui.text_colored(
+                    [0.7, 0.7, 0.7, 1.0],
+                    &format!("({} logs)", count_stranded_logs(folder)),
+                );
+
+                if is_busy {
+                    ui.text_colored([1.0, 1.0, 0.0, 1.0], "Working...");
+                } else {
+                    if ui.button(&format!("Move to Recycle Bin##{}", folder_name)) {
+                        run_stranded_action(folder.clone(), false);
+                    }
+
+                    ui.same_line();
+
+                    if ui.button(&format!("Restore Logs##{}", folder_name)) {
+                        run_stranded_action(folder.clone(), true);
+                    }
+
+                    ui.same_line();
+
+                    if ui.button(&format!("Keep##{}", folder_name)) {
+                        log::info!("Keeping stranded cleanup folder {:?}", folder);
+                        STATE
+                            .stranded_cleanup_folders
+                            .lock()
+                            .unwrap()
+                            .retain(|f| f != folder);
+                    }
+                }
+
+                ui.spacing();
+            }
+
+            let message = STATE.stranded_cleanup_message.lock().unwrap().clone();
+            if !message.is_empty() {
+                ui.separator();
+                ui.text_colored([1.0, 0.5, 0.0, 1.0], &message);
+            }
+        });
+}
+
+/// Recycles or restores a stranded cleanup folder on a background thread
+fn run_stranded_action(folder: std::path::PathBuf, restore: bool) {
+    *STATE.stranded_cleanup_busy.lock().unwrap() = true;
+    STATE.stranded_cleanup_message.lock().unwrap().clear();
+
+    crate::threads::spawn_tracked("stranded-cleanup", move || {
+        let result = if restore {
+            let log_dir = Settings::get().log_directory.clone();
+            restore_stranded_folder(&folder, &log_dir)
+        } else {
+            recycle_stranded_folder(&folder)
+        };
+
+        match result {
+            Ok(_) => {
+                STATE
+                    .stranded_cleanup_folders
+                    .lock()
+                    .unwrap()
+                    .retain(|f| f != &folder);
+            }
+            Err(e) => {
+                log::error!("Failed to recover cleanup folder {:?}: {}", folder, e);
+                *STATE.stranded_cleanup_message.lock().unwrap() = e;
+            }
+        }
+
+        *STATE.stranded_cleanup_busy.lock().unwrap() = false;
+    });
+}
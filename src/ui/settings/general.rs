@@ -1,15 +1,29 @@
 use nexus::imgui::Ui;
 
 use crate::arcdps::sync_with_arcdps;
-use crate::settings::Settings;
+use crate::hooks::{HookAction, PostProcessHook};
+use crate::maps::WvwRegion;
+use crate::settings::{ResultsAction, Settings};
 use crate::state::STATE;
 
 // Move thread_local to module level so both functions can access them
 thread_local! {
     static LOG_DIR_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static EXCLUDE_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
     static API_ENDPOINT_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
     static SHOW_FORMATTED: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
     static ENABLE_LEGACY_PARSER: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static LIVE_MODE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static AUTO_PROCESS: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static AUTO_PROCESS_MINUTES: std::cell::Cell<i32> = const { std::cell::Cell::new(15) };
+    static INBOX_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static SELECT_ALL_THRESHOLD: std::cell::Cell<i32> = const { std::cell::Cell::new(50) };
+    static MIN_FIGHT_SECONDS: std::cell::Cell<i32> = const { std::cell::Cell::new(0) };
+    static MIN_SQUAD_SIZE: std::cell::Cell<i32> = const { std::cell::Cell::new(0) };
+    static MIN_LOG_SIZE_KB: std::cell::Cell<i32> = const { std::cell::Cell::new(0) };
+    static WVW_REGION: std::cell::Cell<WvwRegion> = const { std::cell::Cell::new(WvwRegion::Eu) };
+    static RESULTS_ACTIONS: std::cell::RefCell<Vec<ResultsAction>> = const { std::cell::RefCell::new(Vec::new()) };
+    static HOOKS: std::cell::RefCell<Vec<PostProcessHook>> = const { std::cell::RefCell::new(Vec::new()) };
     static INITIALIZED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
 }
 
@@ -18,9 +32,21 @@ pub fn render_general_tab(ui: &Ui, _config_path: &std::path::Path) {
     if !INITIALIZED.get() {
         let settings = Settings::get();
         LOG_DIR_BUFFER.set(settings.log_directory.clone());
+        EXCLUDE_BUFFER.set(settings.scan_exclude_patterns.join("\n"));
         API_ENDPOINT_BUFFER.set(settings.api_endpoint.clone());
         SHOW_FORMATTED.set(settings.show_formatted_timestamps);
         ENABLE_LEGACY_PARSER.set(settings.enable_legacy_parser);
+        LIVE_MODE.set(settings.live_mode);
+        AUTO_PROCESS.set(settings.auto_process_enabled);
+        AUTO_PROCESS_MINUTES.set(settings.auto_process_minutes as i32);
+        INBOX_ENABLED.set(settings.inbox_enabled);
+        SELECT_ALL_THRESHOLD.set(settings.select_all_threshold as i32);
+        MIN_FIGHT_SECONDS.set(settings.min_fight_seconds as i32);
+        MIN_SQUAD_SIZE.set(settings.min_squad_size as i32);
+        MIN_LOG_SIZE_KB.set(settings.min_log_size_kb as i32);
+        WVW_REGION.set(settings.wvw_region);
+        RESULTS_ACTIONS.set(settings.results_actions.clone());
+        HOOKS.set(settings.post_process_hooks.clone());
         INITIALIZED.set(true);
     }
 
@@ -29,6 +55,7 @@ pub fn render_general_tab(ui: &Ui, _config_path: &std::path::Path) {
     if let Some(result) = sync_result {
         match result {
             Ok(path) => {
+                crate::scanning::start_directory_estimate(path.clone());
                 LOG_DIR_BUFFER.set(path);
                 *STATE.sync_arcdps_message.lock().unwrap() = "Synced successfully!".to_string();
                 *STATE.sync_arcdps_message_is_error.lock().unwrap() = false;
@@ -67,7 +94,7 @@ pub fn render_general_tab(ui: &Ui, _config_path: &std::path::Path) {
     } else {
         if ui.button("Sync with ArcDPS") {
             *STATE.sync_arcdps_pending.lock().unwrap() = true;
-            std::thread::spawn(|| {
+            crate::threads::spawn("arcdps-sync", || {
                 let result = sync_with_arcdps();
                 *STATE.sync_arcdps_result.lock().unwrap() = Some(result);
                 *STATE.sync_arcdps_pending.lock().unwrap() = false;
@@ -98,13 +125,29 @@ pub fn render_general_tab(ui: &Ui, _config_path: &std::path::Path) {
 
     ui.text_colored(
         [0.6, 0.6, 0.6, 1.0],
-        "The folder containing your .zevtc log files",
+        "The folder containing your .zevtc (or .evtc, .evtc.zip) log files",
     );
     ui.text_colored(
         [0.6, 0.6, 0.6, 1.0],
         "Subdirectories will be scanned recursively",
     );
 
+    ui.spacing();
+    ui.text_colored([0.9, 0.9, 0.9, 1.0], "Exclude From Scans:");
+    EXCLUDE_BUFFER.with_borrow_mut(|patterns| {
+        ui.input_text_multiline("##scan_exclude", patterns, [0.0, 60.0]).build();
+    });
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "One folder name or glob per line, e.g. Archive, old_* or Archive/2023*",
+    );
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Matching folders and logs are skipped by scans, live mode and cleanup",
+    );
+
+    render_directory_estimate(ui);
+
     ui.spacing();
     ui.separator();
     ui.spacing();
@@ -120,61 +163,454 @@ pub fn render_general_tab(ui: &Ui, _config_path: &std::path::Path) {
     );
 
     ui.spacing();
-    ui.separator();
+
+    let mut threshold = SELECT_ALL_THRESHOLD.get();
+    ui.text("Confirm Select All above:");
+    ui.set_next_item_width(100.0);
+    if ui.input_int("##select_all_threshold", &mut threshold).build() {
+        SELECT_ALL_THRESHOLD.set(threshold.clamp(0, 1000));
+    }
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Asks first when Select All would pick more logs than this over 48 hours or longer",
+    );
+
     ui.spacing();
 
-    ui.text_colored([0.9, 0.9, 0.9, 1.0], "API Endpoint:");
-    API_ENDPOINT_BUFFER.with_borrow_mut(|endpoint| {
-        ui.input_text("##apiendpoint", endpoint).build();
-    });
+    let mut region = WVW_REGION.get();
+    ui.text("WvW region:");
+    for option in WvwRegion::ALL {
+        ui.same_line();
+        if ui.radio_button(&format!("{}##wvw_region", option.label()), &mut region, option) {
+            WVW_REGION.set(region);
+        }
+    }
     ui.text_colored(
         [0.6, 0.6, 0.6, 1.0],
-        "(Leave as default unless instructed otherwise)",
+        &format!("Selected logs from before the last matchup reset ({}) get a warning", region.reset_label()),
     );
 
     ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    ui.text("Hide Short Fights:");
+
+    let mut min_seconds = MIN_FIGHT_SECONDS.get();
+    ui.text("Minimum fight duration (seconds):");
+    ui.set_next_item_width(100.0);
+    if ui.input_int("##min_fight_seconds", &mut min_seconds).build() {
+        MIN_FIGHT_SECONDS.set(min_seconds.clamp(0, 3600));
+    }
 
-    if ui.button("Reset to Default") {
-        API_ENDPOINT_BUFFER.set("https://parser.rethl.net/api.php".to_string());
+    let mut min_squad = MIN_SQUAD_SIZE.get();
+    ui.text("Minimum squad size:");
+    ui.set_next_item_width(100.0);
+    if ui.input_int("##min_squad_size", &mut min_squad).build() {
+        MIN_SQUAD_SIZE.set(min_squad.clamp(0, 100));
     }
 
+    let mut min_size = MIN_LOG_SIZE_KB.get();
+    ui.text("Minimum file size (KB):");
+    ui.set_next_item_width(100.0);
+    if ui.input_int("##min_log_size_kb", &mut min_size).build() {
+        MIN_LOG_SIZE_KB.set(min_size.clamp(0, 100_000));
+    }
+
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Logs below any of these are left out of the log list, 0 turns a limit off",
+    );
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Takes effect with the next refresh of the list",
+    );
+
     ui.spacing();
     ui.separator();
     ui.spacing();
 
-    // Legacy Parser option - with strong warning
-    ui.text_colored([1.0, 0.4, 0.0, 1.0], "Advanced Options:");
+    ui.text("Live Mode:");
+    let mut live_mode = LIVE_MODE.get();
+    if ui.checkbox("Upload new logs automatically", &mut live_mode) {
+        LIVE_MODE.set(live_mode);
+
+        // Apply immediately so the watcher starts or stops without saving
+        Settings::get().live_mode = live_mode;
+    }
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "New WvW logs are uploaded to the current session once arcdps finishes writing them",
+    );
+
     ui.spacing();
-    
-    let mut enable_legacy = ENABLE_LEGACY_PARSER.get();
-    if ui.checkbox("Enable Legacy Parser", &mut enable_legacy) {
-        ENABLE_LEGACY_PARSER.set(enable_legacy);
+
+    let mut auto_process = AUTO_PROCESS.get();
+    if ui.checkbox("Process automatically when the raid ends", &mut auto_process) {
+        AUTO_PROCESS.set(auto_process);
+        Settings::get().auto_process_enabled = auto_process;
+    }
+
+    if auto_process {
+        let mut minutes = AUTO_PROCESS_MINUTES.get();
+        ui.text("Minutes without new logs:");
+        ui.set_next_item_width(100.0);
+        if ui.input_int("##auto_process_minutes", &mut minutes).build() {
+            minutes = minutes.clamp(1, 120);
+            AUTO_PROCESS_MINUTES.set(minutes);
+            Settings::get().auto_process_minutes = minutes as u32;
+        }
     }
-    
-    ui.text_colored([1.0, 0.3, 0.0, 1.0], "WARNING - NOT RECOMMENDED");
     ui.text_colored(
         [0.6, 0.6, 0.6, 1.0],
-        "Legacy reports are outdated and double processing time.",
+        "Starts processing once live mode hasn't uploaded a log for this long",
+    );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    render_results_actions(ui);
+
+    super::render_advanced_section(ui, "general", || {
+        let endpoint_locked = crate::lock::is_locked();
+
+        ui.text_colored([0.9, 0.9, 0.9, 1.0], "API Endpoint:");
+        API_ENDPOINT_BUFFER.with_borrow_mut(|endpoint| {
+            ui.input_text("##apiendpoint", endpoint)
+                .read_only(endpoint_locked)
+                .build();
+        });
+        ui.text_colored(
+            [0.6, 0.6, 0.6, 1.0],
+            "(Leave as default unless instructed otherwise)",
+        );
+
+        ui.spacing();
+
+        if endpoint_locked {
+            crate::ui::lock::render_lock_notice(ui, "The API endpoint");
+        } else if ui.button("Reset to Default") {
+            API_ENDPOINT_BUFFER.set("https://parser.rethl.net/api.php".to_string());
+        }
+
+        ui.spacing();
+        ui.separator();
+        ui.spacing();
+
+        // Legacy Parser option - with strong warning
+        let mut enable_legacy = ENABLE_LEGACY_PARSER.get();
+        if ui.checkbox("Enable Legacy Parser", &mut enable_legacy) {
+            ENABLE_LEGACY_PARSER.set(enable_legacy);
+        }
+
+        ui.text_colored([1.0, 0.3, 0.0, 1.0], "WARNING - NOT RECOMMENDED");
+        ui.text_colored(
+            [0.6, 0.6, 0.6, 1.0],
+            "Legacy reports are outdated and double processing time.",
+        );
+        ui.text_colored(
+            [0.6, 0.6, 0.6, 1.0],
+            "The default Log Combiner is sufficient for all use cases.",
+        );
+        ui.text_colored(
+            [0.6, 0.6, 0.6, 1.0],
+            "Enable only if you absolutely can't live without it. :(",
+        );
+        if enable_legacy && Settings::get().low_bandwidth_mode {
+            ui.text_colored(
+                [0.9, 0.7, 0.3, 1.0],
+                "Skipped while low bandwidth mode is on (QoL tab)",
+            );
+        }
+
+        ui.spacing();
+        ui.separator();
+        ui.spacing();
+
+        let mut inbox_enabled = INBOX_ENABLED.get();
+        if ui.checkbox("Accept commands from other programs", &mut inbox_enabled) {
+            INBOX_ENABLED.set(inbox_enabled);
+            Settings::get().inbox_enabled = inbox_enabled;
+        }
+        ui.same_line();
+        ui.text_colored([0.5, 0.5, 1.0, 1.0], "(?)");
+        if ui.is_item_hovered() {
+            ui.tooltip_text(
+                "Drop a .json file into the inbox folder, e.g.\n\
+                 { \"files\": [\"20250101-200000.zevtc\"], \"process\": true }\n\n\
+                 Relative paths are looked up in the log directory.\n\
+                 The outcome is written to <name>.result.json.",
+            );
+        }
+        ui.text_colored(
+            [0.6, 0.6, 0.6, 1.0],
+            &format!("Inbox: {}", crate::inbox_path().display()),
+        );
+
+        ui.spacing();
+        ui.separator();
+        ui.spacing();
+
+        render_post_process_hooks(ui);
+    });
+}
+
+/// Renders the ordered list of actions run after every completed report
+fn render_post_process_hooks(ui: &Ui) {
+    ui.text("Post-Processing Hooks:");
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        "Run in order after every completed report, e.g. to feed your own guild bot",
+    );
+
+    let webhooks = crate::webhooks::WebhookSettings::get().get_webhooks_sorted();
+
+    HOOKS.with_borrow_mut(|hooks| {
+        let mut remove = None;
+        let mut swap = None;
+        let count = hooks.len();
+
+        for (index, hook) in hooks.iter_mut().enumerate() {
+            ui.spacing();
+            ui.checkbox(&format!("{}. {}##hook_{}", index + 1, hook.action.label(), index), &mut hook.enabled);
+            if index > 0 {
+                ui.same_line();
+                if ui.small_button(&format!("Up##hook_{}", index)) {
+                    swap = Some((index, index - 1));
+                }
+            }
+            if index + 1 < count {
+                ui.same_line();
+                if ui.small_button(&format!("Down##hook_{}", index)) {
+                    swap = Some((index, index + 1));
+                }
+            }
+            ui.same_line();
+            if ui.small_button(&format!("Remove##hook_{}", index)) {
+                remove = Some(index);
+            }
+
+            ui.indent();
+            match &mut hook.action {
+                HookAction::OpenUrls => {}
+                HookAction::CopyToClipboard { template } => {
+                    ui.input_text_multiline(&format!("##hook_template_{}", index), template, [0.0, 40.0])
+                        .build();
+                }
+                HookAction::PostJson { url } => {
+                    ui.input_text(&format!("##hook_url_{}", index), url)
+                        .hint("https://my-guild-bot.example/hook")
+                        .build();
+                }
+                HookAction::Webhook { webhook, template } => {
+                    if webhooks.is_empty() {
+                        ui.text_colored([1.0, 0.6, 0.0, 1.0], "Save a webhook in the Webhooks tab first");
+                    } else {
+                        ui.text("Post to:");
+                        for saved in webhooks.iter() {
+                            ui.same_line();
+                            let style = (saved.name == *webhook).then(|| {
+                                ui.push_style_color(nexus::imgui::StyleColor::Button, [0.2, 0.5, 0.2, 1.0])
+                            });
+                            if ui.small_button(&format!("{}##hook_{}_{}", saved.name, index, saved.name)) {
+                                *webhook = saved.name.clone();
+                            }
+                            drop(style);
+                        }
+                    }
+                    ui.input_text_multiline(&format!("##hook_template_{}", index), template, [0.0, 40.0])
+                        .build();
+                }
+            }
+            ui.unindent();
+        }
+
+        if let Some((a, b)) = swap {
+            hooks.swap(a, b);
+        }
+        if let Some(index) = remove {
+            hooks.remove(index);
+        }
+
+        ui.spacing();
+        ui.text("Add:");
+        for action in HookAction::kinds() {
+            ui.same_line();
+            if ui.small_button(&format!("{}##add_hook", action.label())) {
+                hooks.push(PostProcessHook { enabled: true, action });
+            }
+        }
+    });
+
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        &format!("Template placeholders: {}", crate::hooks::TEMPLATE_PLACEHOLDERS),
     );
     ui.text_colored(
         [0.6, 0.6, 0.6, 1.0],
-        "The default Log Combiner is sufficient for all use cases.",
+        "POST hooks send the report links, session id, guild, fights and maps as JSON",
     );
+}
+
+/// Renders the list of results screen buttons with show/hide and reorder controls
+fn render_results_actions(ui: &Ui) {
+    ui.text("Results Screen Buttons:");
+
+    RESULTS_ACTIONS.with_borrow_mut(|actions| {
+        // Shown buttons first in their configured order, hidden ones after
+        let hidden: Vec<ResultsAction> = ResultsAction::ALL
+            .iter()
+            .copied()
+            .filter(|a| !actions.contains(a))
+            .collect();
+        let rows: Vec<ResultsAction> = actions.iter().copied().chain(hidden).collect();
+        let shown_count = actions.len();
+
+        for (index, action) in rows.into_iter().enumerate() {
+            let mut shown = index < shown_count;
+            if ui.checkbox(&format!("{}##results_action", action.label()), &mut shown) {
+                if shown {
+                    actions.push(action);
+                } else {
+                    actions.retain(|a| *a != action);
+                }
+                continue;
+            }
+
+            if index < shown_count {
+                if index > 0 {
+                    ui.same_line();
+                    if ui.small_button(&format!("Up##results_action_{}", index)) {
+                        actions.swap(index, index - 1);
+                    }
+                }
+                if index + 1 < shown_count {
+                    ui.same_line();
+                    if ui.small_button(&format!("Down##results_action_{}", index)) {
+                        actions.swap(index, index + 1);
+                    }
+                }
+            }
+        }
+    });
+
     ui.text_colored(
         [0.6, 0.6, 0.6, 1.0],
-        "Enable only if you absolutely can't live without it. :(",
+        "Unticked buttons are hidden from the results screen",
+    );
+}
+
+/// Renders the size estimate of a newly configured log directory with suggested settings
+/// Suggestions only fill in the fields here or open the cleanup tab, nothing is deleted from here
+fn render_directory_estimate(ui: &Ui) {
+    use crate::scanning::{ESTIMATE_CLEANUP_DAYS, SHORT_FIGHT_SECS};
+
+    let pending = *STATE.dir_estimate_pending.lock().unwrap();
+    let estimate = STATE.dir_estimate.lock().unwrap().clone();
+
+    ui.spacing();
+    if pending {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], "Estimating the logs in this folder...");
+        return;
+    }
+
+    let Some(estimate) = estimate else {
+        if ui.small_button("Estimate Folder Size") {
+            crate::scanning::start_directory_estimate(LOG_DIR_BUFFER.with_borrow(|dir| dir.clone()));
+        }
+        return;
+    };
+
+    ui.text_colored(
+        [0.9, 0.9, 0.9, 1.0],
+        &format!(
+            "You have {} / {} in this folder",
+            crate::formatting::plural(estimate.log_count as u64, "log", "logs"),
+            crate::formatting::format_size(estimate.total_bytes)
+        ),
     );
+    ui.same_line();
+    if ui.small_button("Dismiss##dir_estimate") {
+        *STATE.dir_estimate.lock().unwrap() = None;
+    }
+
+    if estimate.recommends_short_fight_filter() && MIN_FIGHT_SECONDS.get() == 0 {
+        ui.text_colored(
+            [0.9, 0.7, 0.3, 1.0],
+            &format!(
+                "{} of {} sampled fights were shorter than {} seconds",
+                estimate.short_fights, estimate.sampled_fights, SHORT_FIGHT_SECS
+            ),
+        );
+        ui.same_line();
+        if ui.small_button("Hide Them##dir_estimate") {
+            MIN_FIGHT_SECONDS.set(SHORT_FIGHT_SECS as i32);
+        }
+    }
+
+    if estimate.recommends_cleanup() && !Settings::get().auto_cleanup_enabled {
+        ui.text_colored(
+            [0.9, 0.7, 0.3, 1.0],
+            &format!(
+                "{} ({}) are older than {} days, enable auto-cleanup?",
+                crate::formatting::plural(estimate.old_count as u64, "log", "logs"),
+                crate::formatting::format_size(estimate.old_bytes),
+                ESTIMATE_CLEANUP_DAYS
+            ),
+        );
+        ui.same_line();
+        if ui.small_button("Set Up##dir_estimate_cleanup") {
+            Settings::get().auto_cleanup_days = ESTIMATE_CLEANUP_DAYS;
+            super::set_active_settings_tab(4);
+        }
+    }
+
+    if estimate.directory != LOG_DIR_BUFFER.with_borrow(|dir| dir.clone()) {
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], &format!("Estimated for {}", estimate.directory));
+    }
 }
 
 /// Saves the general settings to config
 pub fn save_general_settings(config_path: &std::path::Path) {
+    // Check before taking the settings lock - is_locked() reads settings itself
+    let endpoint_locked = crate::lock::is_locked();
+
     LOG_DIR_BUFFER.with_borrow(|dir| {
         API_ENDPOINT_BUFFER.with_borrow(|endpoint| {
             let mut settings = Settings::get();
+            if settings.log_directory != *dir {
+                crate::scanning::start_directory_estimate(dir.clone());
+            }
             settings.log_directory = dir.clone();
-            settings.api_endpoint = endpoint.clone();
+            let exclude: Vec<String> = EXCLUDE_BUFFER.with_borrow(|patterns| {
+                patterns
+                    .lines()
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect()
+            });
+            if settings.scan_exclude_patterns != exclude {
+                // Cached directory walks still hold what the old patterns let through
+                crate::scanning::forget_directory_fingerprints();
+                settings.scan_exclude_patterns = exclude;
+            }
+            if !endpoint_locked {
+                settings.api_endpoint = endpoint.clone();
+            }
             settings.show_formatted_timestamps = SHOW_FORMATTED.get();
             settings.enable_legacy_parser = ENABLE_LEGACY_PARSER.get();
+            settings.live_mode = LIVE_MODE.get();
+            settings.auto_process_enabled = AUTO_PROCESS.get();
+            settings.auto_process_minutes = AUTO_PROCESS_MINUTES.get() as u32;
+            settings.inbox_enabled = INBOX_ENABLED.get();
+            settings.select_all_threshold = SELECT_ALL_THRESHOLD.get() as u32;
+            settings.min_fight_seconds = MIN_FIGHT_SECONDS.get() as u32;
+            settings.min_squad_size = MIN_SQUAD_SIZE.get() as u32;
+            settings.min_log_size_kb = MIN_LOG_SIZE_KB.get() as u32;
+            settings.wvw_region = WVW_REGION.get();
+            settings.results_actions = RESULTS_ACTIONS.with_borrow(|actions| actions.clone());
+            settings.post_process_hooks = HOOKS.with_borrow(|hooks| hooks.clone());
 
             if let Err(e) = settings.store(config_path) {
                 log::error!("Failed to save settings: {}", e);
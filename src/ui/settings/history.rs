@@ -1,28 +1,80 @@
-use nexus::imgui::{ChildWindow, Ui};
+use std::collections::HashSet;
 
-use crate::formatting::format_report_timestamp;
-use crate::report_history::ReportHistory;
-use crate::settings::Settings;
+use nexus::imgui::{ChildWindow, TreeNodeFlags, Ui};
 
-/// Renders the report history tab
-pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
-    thread_local! {
-        static REPORT_TO_DELETE: std::cell::Cell<Option<usize>> = const { std::cell::Cell::new(None) };
+use crate::formatting::{format_report_timestamp, plural};
+use crate::report_history::{HistoryAction, ReportEntry, ReportHistory};
+use crate::settings::{SavedToken, Settings};
+use crate::state::STATE;
+
+thread_local! {
+    static REPORT_TO_DELETE: std::cell::Cell<Option<usize>> = const { std::cell::Cell::new(None) };
+    static SELECTED_SESSIONS: std::cell::RefCell<HashSet<String>> = std::cell::RefCell::new(HashSet::new());
+    static SEARCH_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static DATE_FROM_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static DATE_TO_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static EXPORT_NAME_BUFFER: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+    static EXPORT_AS_JSON: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static EXPORT_MESSAGE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static CHART_SHOWS_FIGHTS: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    /// Session being renamed and the name typed so far
+    static RENAMING: std::cell::RefCell<Option<(String, String)>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Format of the date range inputs
+const FILTER_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// How far back the activity chart goes
+const CHART_SPAN_SECS: u64 = 180 * 24 * 60 * 60;
+const CHART_HEIGHT: f32 = 40.0;
+/// Bars don't get wider than this with only a few sessions
+const CHART_MAX_BAR_WIDTH: f32 = 12.0;
+
+/// What the history list is narrowed down to
+struct HistoryFilter {
+    /// Lowercase text the session id or name has to contain
+    search: String,
+    from: Option<chrono::NaiveDate>,
+    to: Option<chrono::NaiveDate>,
+}
+
+impl HistoryFilter {
+    fn is_active(&self) -> bool {
+        !self.search.is_empty() || self.from.is_some() || self.to.is_some()
     }
 
+    fn matches(&self, entry: &ReportEntry) -> bool {
+        if !self.search.is_empty()
+            && !entry.session_id.to_lowercase().contains(&self.search)
+            && !entry.label.to_lowercase().contains(&self.search)
+        {
+            return false;
+        }
+
+        let date = chrono::DateTime::from_timestamp(entry.timestamp as i64, 0)
+            .map(|dt| dt.with_timezone(&chrono::Local).date_naive());
+        match date {
+            Some(date) => self.from.map_or(true, |from| date >= from) && self.to.map_or(true, |to| date <= to),
+            None => self.from.is_none() && self.to.is_none(),
+        }
+    }
+}
+
+/// Renders the report history tab
+pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
     ui.text("Your Report History:");
     ui.spacing();
 
     let settings = Settings::get();
     let current_token = settings.history_token.clone();
+    let saved_tokens = settings.saved_tokens.clone();
     drop(settings);
 
     let history = ReportHistory::get();
     let mut reports = history.reports.clone();
     drop(history);
 
-    // Sort by timestamp (newest first)
-    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    sort_for_display(&mut reports);
 
     if reports.is_empty() {
         ui.text_colored([0.7, 0.7, 0.7, 1.0], "No reports yet");
@@ -36,6 +88,15 @@ pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
             [0.7, 0.7, 0.7, 1.0],
             &format!("Total sessions: {}", reports.len()),
         );
+
+        let reparse_message = STATE.reparse_message.lock().unwrap().clone();
+        if !reparse_message.is_empty() {
+            ui.text_colored([1.0, 1.0, 0.0, 1.0], &reparse_message);
+        }
+        let export_message = EXPORT_MESSAGE.with_borrow(|m| m.clone());
+        if !export_message.is_empty() {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], &crate::ui::streamer::redact(&export_message));
+        }
         ui.spacing();
 
         if ui.button("Clear All History") {
@@ -46,6 +107,7 @@ pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
             .always_auto_resize(true)
             .build(ui, || {
                 ui.text("Are you sure you want to clear all report history?");
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Favorite sessions are kept.");
                 ui.spacing();
                 ui.text_colored([1.0, 1.0, 0.0, 1.0], "This cannot be undone!");
                 ui.spacing();
@@ -67,76 +129,159 @@ pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
                 }
             });
 
+        ui.same_line();
+
+        if ui.button("Export History") {
+            ui.open_popup("export_history");
+        }
+        render_export_popup(ui);
+
+        ui.same_line();
+
+        // Bulk re-send of the checked sessions
+        let selected: Vec<ReportEntry> = SELECTED_SESSIONS.with_borrow(|selected| {
+            reports
+                .iter()
+                .filter(|e| selected.contains(&e.session_id))
+                .cloned()
+                .collect()
+        });
+
+        if selected.is_empty() {
+            let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+            let _style2 =
+                ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+            let _style3 =
+                ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+            ui.button("Send Selected to Discord");
+            drop(_style3);
+            drop(_style2);
+            drop(_style);
+
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Tick the sessions you want to send first");
+            }
+        } else {
+            if ui.button(&format!("Send Selected to Discord ({})", selected.len())) {
+                send_to_discord(&selected);
+            }
+
+            ui.same_line();
+
+            if ui.button(&format!("Delete Selected ({})", selected.len())) {
+                ui.open_popup("bulk_delete_confirmation");
+            }
+
+            ui.same_line();
+
+            if ui.button("Clear Selection") {
+                SELECTED_SESSIONS.with_borrow_mut(|s| s.clear());
+            }
+        }
+        render_bulk_delete_confirmation(ui, &selected);
+
         ui.spacing();
         ui.separator();
         ui.spacing();
 
+        render_activity_chart(ui, &reports);
+
+        let filter = render_filters(ui);
+        if filter.is_active() {
+            let shown = reports.iter().filter(|e| filter.matches(e)).count();
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                &format!("Showing {} of {}", shown, plural(reports.len() as u64, "session", "sessions")),
+            );
+        }
+        ui.spacing();
+
+        // Group sessions by the token that produced them, keeping first-seen (newest) order
+        // Pinned sessions get their own group above the rest
+        // Indices stay those of the full list, deleting goes by them
+        let mut groups: Vec<String> = Vec::new();
+        for entry in reports.iter().filter(|e| !e.pinned && filter.matches(e)) {
+            if !groups.contains(&entry.token_key) {
+                groups.push(entry.token_key.clone());
+            }
+        }
+
         ChildWindow::new("ReportHistoryList")
             .size([0.0, 350.0])
             .build(ui, || {
-                for (index, entry) in reports.iter().enumerate() {
-                    let timestamp_str = format_report_timestamp(entry.timestamp);
+                let pinned: Vec<(usize, &ReportEntry)> = reports
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| e.pinned && filter.matches(e))
+                    .collect();
 
-                    ui.text_colored([0.8, 0.8, 1.0, 1.0], &timestamp_str);
-                    ui.text_colored(
-                        [0.6, 0.6, 0.6, 1.0],
-                        &format!("Session: {}", entry.session_id),
-                    );
-                    ui.spacing();
+                if !pinned.is_empty() {
+                    let header = format!("Pinned ({})##group_pinned", pinned.len());
+                    if ui.collapsing_header(&header, TreeNodeFlags::DEFAULT_OPEN) {
+                        ui.indent();
+                        for (index, entry) in pinned {
+                            render_report_entry(ui, index, entry);
+                        }
+                        ui.unindent();
+                    }
+                }
 
-                    // Main Report section
-                    ui.text_colored([0.9, 0.9, 1.0, 1.0], "Main Report:");
-                    ui.same_line();
+                if filter.is_active() && groups.is_empty() && pinned_count(&reports, &filter) == 0 {
+                    ui.text_colored([0.7, 0.7, 0.7, 1.0], "No sessions match the filters");
+                }
 
-                    if ui.small_button(&format!("Copy URL##copy_main_{}", index)) {
-                        ui.set_clipboard_text(&entry.main_report_url);
-                        log::info!("Copied main report URL to clipboard");
-                    }
+                for key in groups.iter() {
+                    let entries: Vec<(usize, &ReportEntry)> = reports
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, e)| !e.pinned && &e.token_key == key && filter.matches(e))
+                        .collect();
 
-                    ui.same_line();
+                    let header = format!(
+                        "{} ({})##group_{}",
+                        token_label(key, &saved_tokens),
+                        plural(entries.len() as u64, "session", "sessions"),
+                        key
+                    );
 
-                    if ui.small_button(&format!("Open##open_main_{}", index)) {
-                        if let Err(e) = open::that_detached(&entry.main_report_url) {
-                            log::error!("Failed to open browser: {}", e);
-                        }
+                    if !ui.collapsing_header(&header, TreeNodeFlags::DEFAULT_OPEN) {
+                        continue;
                     }
 
-                    // Legacy Report section (if it exists)
-                    if let Some(ref legacy_url) = entry.legacy_report_url {
-                        ui.text_colored([0.8, 0.8, 0.6, 1.0], "Legacy Report:");
-                        ui.same_line();
+                    ui.indent();
 
-                        if ui.small_button(&format!("Copy URL##copy_legacy_{}", index)) {
-                            ui.set_clipboard_text(legacy_url);
-                            log::info!("Copied legacy report URL to clipboard");
-                        }
-
-                        ui.same_line();
-
-                        if ui.small_button(&format!("Open##open_legacy_{}", index)) {
-                            if let Err(e) = open::that_detached(legacy_url) {
-                                log::error!("Failed to open browser: {}", e);
-                            }
+                    // Only for tokens still saved somewhere, the history keeps just a key of the token
+                    if let Some(token) = crate::tokens::resolve_token_key(key) {
+                        if ui.small_button(&format!("Open All on Website##group_open_{}", key)) {
+                            open_token_on_website(&token);
                         }
+                        ui.spacing();
                     }
 
-                    // Delete button for the entire session
-                    if ui.small_button(&format!("Delete Session##del_{}", index)) {
-                        REPORT_TO_DELETE.set(Some(index));
+                    for (index, entry) in entries {
+                        render_report_entry(ui, index, entry);
                     }
 
-                    ui.spacing();
-                    ui.separator();
-                    ui.spacing();
+                    ui.unindent();
                 }
             });
     }
 
+    // Outside the list so it stays open when the last sessions are deleted
+    render_bulk_delete_progress(ui);
+
+    render_changelog(ui);
+
+    // Webhook modal shared with the results screen
+    if *crate::state::STATE.show_webhook_modal.lock().unwrap() {
+        crate::ui::results::render_webhook_modal(ui);
+    }
+
     // Handle deletion
     if let Some(index_to_delete) = REPORT_TO_DELETE.get() {
         let mut history = ReportHistory::get();
         // Sort the same way to match indices
-        history.reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        sort_for_display(&mut history.reports);
         history.remove_report(index_to_delete);
         if let Err(e) = history.store(crate::report_history_path()) {
             log::error!("Failed to save history after deletion: {}", e);
@@ -159,12 +304,7 @@ pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
 
     if !current_token.is_empty() {
         if ui.button("View All Reports on Website") {
-            let url = format!("https://parser.rethl.net/?hisToken={}", current_token);
-            if let Err(e) = open::that_detached(&url) {
-                log::error!("Failed to open browser: {}", e);
-            } else {
-                log::info!("Opening all reports on website with token");
-            }
+            open_token_on_website(&current_token);
         }
 
         ui.same_line();
@@ -189,4 +329,742 @@ pub fn render_history_tab(ui: &Ui, _config_path: &std::path::Path) {
             ui.tooltip_text("Enter a history token first");
         }
     }
-}
\ No newline at end of file
+}
+
+/// Renders one bar per session of the last months, as tall as its squad or fight count
+fn render_activity_chart(ui: &Ui, reports: &[ReportEntry]) {
+    if !ui.collapsing_header("Raid Activity", TreeNodeFlags::DEFAULT_OPEN) {
+        return;
+    }
+
+    let mut shows_fights = CHART_SHOWS_FIGHTS.get();
+    ui.radio_button("Squad size", &mut shows_fights, false);
+    ui.same_line();
+    ui.radio_button("Fights", &mut shows_fights, true);
+    CHART_SHOWS_FIGHTS.set(shows_fights);
+
+    let cutoff = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        .saturating_sub(CHART_SPAN_SECS);
+    let mut sessions: Vec<&ReportEntry> = reports.iter().filter(|r| r.timestamp >= cutoff).collect();
+    sessions.sort_by_key(|r| r.timestamp);
+
+    if sessions.is_empty() {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], "No sessions in the last 6 months");
+        ui.spacing();
+        return;
+    }
+
+    let value = |entry: &ReportEntry| match (&entry.details, shows_fights) {
+        (Some(details), true) => details.log_count,
+        (None, true) => entry.log_files.len(),
+        (Some(details), false) => details.squad_size,
+        (None, false) => 0,
+    };
+    let highest = sessions.iter().map(|e| value(e)).max().unwrap_or(0);
+    let max = highest.max(1);
+
+    let origin = ui.cursor_screen_pos();
+    let width = ui.content_region_avail()[0];
+    let bar_width = (width / sessions.len() as f32).min(CHART_MAX_BAR_WIDTH);
+    let bottom = origin[1] + CHART_HEIGHT;
+
+    let draw_list = ui.get_window_draw_list();
+    draw_list
+        .add_rect(origin, [origin[0] + width, bottom], [0.2, 0.2, 0.2, 0.3])
+        .filled(true)
+        .rounding(2.0)
+        .build();
+
+    for (i, entry) in sessions.iter().enumerate() {
+        let height = value(entry) as f32 / max as f32 * CHART_HEIGHT;
+        let left = origin[0] + i as f32 * bar_width;
+        // A pixel of space between bars while there is room for it
+        let gap = if bar_width > 3.0 { 1.0 } else { 0.0 };
+        draw_list
+            .add_rect([left, bottom - height], [left + bar_width - gap, bottom], [0.3, 0.6, 1.0, 0.9])
+            .filled(true)
+            .build();
+    }
+    drop(draw_list);
+
+    ui.dummy([width, CHART_HEIGHT]);
+
+    if ui.is_item_hovered() {
+        let index = ((ui.io().mouse_pos[0] - origin[0]) / bar_width) as usize;
+        if let Some(entry) = sessions.get(index) {
+            let name = if entry.label.is_empty() {
+                format_report_timestamp(entry.timestamp)
+            } else {
+                format!("{} ({})", entry.label, format_report_timestamp(entry.timestamp))
+            };
+            let amount = if shows_fights {
+                plural(value(entry) as u64, "fight", "fights")
+            } else if value(entry) == 0 {
+                "Squad size not recorded".to_string()
+            } else {
+                format!("Squad of {}", value(entry))
+            };
+            ui.tooltip_text(format!("{}\n{}", name, amount));
+        }
+    }
+
+    let peak = if shows_fights {
+        format!("most {}", plural(highest as u64, "fight", "fights"))
+    } else if highest == 0 {
+        "squad sizes not recorded yet".to_string()
+    } else {
+        format!("largest squad {}", highest)
+    };
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        &format!("Last 6 months: {}, {}", plural(sessions.len() as u64, "session", "sessions"), peak),
+    );
+    ui.spacing();
+}
+
+/// Renders the popup asking for the file name and format of a history export
+fn render_export_popup(ui: &Ui) {
+    ui.popup_modal("export_history")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text("Export every session with its details:");
+            ui.spacing();
+
+            ui.text("File name:");
+            ui.set_next_item_width(250.0);
+            EXPORT_NAME_BUFFER.with_borrow_mut(|name| {
+                let name = name.get_or_insert_with(|| {
+                    format!("report_history_{}", chrono::Local::now().format("%Y-%m-%d"))
+                });
+                ui.input_text("##export_name", name).build();
+            });
+
+            let mut as_json = EXPORT_AS_JSON.get();
+            ui.radio_button("CSV", &mut as_json, false);
+            ui.same_line();
+            ui.radio_button("JSON", &mut as_json, true);
+            EXPORT_AS_JSON.set(as_json);
+            let format = if as_json {
+                crate::report_history::ExportFormat::Json
+            } else {
+                crate::report_history::ExportFormat::Csv
+            };
+
+            ui.text_colored(
+                [0.6, 0.6, 0.6, 1.0],
+                "Saved to the exports folder in the addon directory",
+            );
+            ui.spacing();
+
+            if ui.button("Export") {
+                let name = EXPORT_NAME_BUFFER.with_borrow(|n| n.clone().unwrap_or_default());
+                match crate::report_history::export(&name, format) {
+                    Ok(path) => {
+                        EXPORT_MESSAGE.set(format!("Exported to {}", path.display()));
+                        if let Some(dir) = path.parent() {
+                            if let Err(e) = open::that_detached(dir) {
+                                log::error!("Failed to open export folder: {}", e);
+                            }
+                        }
+                        EXPORT_NAME_BUFFER.set(None);
+                        ui.close_current_popup();
+                    }
+                    Err(e) => EXPORT_MESSAGE.set(format!("Export failed: {}", e)),
+                }
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+
+            let message = EXPORT_MESSAGE.with_borrow(|m| m.clone());
+            if message.starts_with("Export failed") {
+                ui.text_colored([1.0, 0.5, 0.0, 1.0], &message);
+            }
+        });
+}
+
+/// Renders the search and date range inputs, returns the filter they describe
+/// Dates that don't parse are ignored and marked until they are fixed
+fn render_filters(ui: &Ui) -> HistoryFilter {
+    ui.text("Search:");
+    ui.same_line();
+    ui.set_next_item_width(200.0);
+    let search = SEARCH_BUFFER.with_borrow_mut(|search| {
+        ui.input_text("##history_search", search)
+            .hint("Name or session id")
+            .build();
+        search.trim().to_lowercase()
+    });
+
+    let date_input = |label: &str, id: &str, buffer: &'static std::thread::LocalKey<std::cell::RefCell<String>>| {
+        ui.same_line();
+        ui.text(label);
+        ui.same_line();
+        ui.set_next_item_width(90.0);
+        buffer.with_borrow_mut(|text| {
+            ui.input_text(id, text).hint("YYYY-MM-DD").build();
+            let text = text.trim();
+            if text.is_empty() {
+                return None;
+            }
+            let date = chrono::NaiveDate::parse_from_str(text, FILTER_DATE_FORMAT).ok();
+            if date.is_none() && ui.is_item_hovered() {
+                ui.tooltip_text("Not a date, use YYYY-MM-DD");
+            }
+            date
+        })
+    };
+    let from = date_input("From:", "##history_from", &DATE_FROM_BUFFER);
+    let to = date_input("To:", "##history_to", &DATE_TO_BUFFER);
+
+    let invalid = [&DATE_FROM_BUFFER, &DATE_TO_BUFFER].iter().any(|buffer| {
+        buffer.with_borrow(|text| {
+            !text.trim().is_empty() && chrono::NaiveDate::parse_from_str(text.trim(), FILTER_DATE_FORMAT).is_err()
+        })
+    });
+
+    let filter = HistoryFilter { search, from, to };
+    if filter.is_active() || invalid {
+        ui.same_line();
+        if ui.small_button("Clear##history_filters") {
+            SEARCH_BUFFER.with_borrow_mut(|s| s.clear());
+            DATE_FROM_BUFFER.with_borrow_mut(|s| s.clear());
+            DATE_TO_BUFFER.with_borrow_mut(|s| s.clear());
+        }
+    }
+    if invalid {
+        ui.text_colored([1.0, 0.6, 0.0, 1.0], "Dates are entered as YYYY-MM-DD, e.g. 2025-10-10");
+    }
+
+    filter
+}
+
+fn pinned_count(reports: &[ReportEntry], filter: &HistoryFilter) -> usize {
+    reports.iter().filter(|e| e.pinned && filter.matches(e)).count()
+}
+
+/// Names a session and saves the history
+fn rename_session(session_id: &str, label: &str) {
+    let mut history = ReportHistory::get();
+    history.set_label(session_id, label);
+    if let Err(e) = history.store(crate::report_history_path()) {
+        log::error!("Failed to save history: {}", e);
+    }
+}
+
+/// Sorts sessions the way the history tab lists them, pinned first, then newest first
+fn sort_for_display(reports: &mut [ReportEntry]) {
+    reports.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.timestamp.cmp(&a.timestamp)));
+}
+
+/// Pins or favorites a session and saves the history
+fn update_flags(session_id: &str, pinned: Option<bool>, favorite: Option<bool>) {
+    let mut history = ReportHistory::get();
+    if let Some(pinned) = pinned {
+        history.set_pinned(session_id, pinned);
+    }
+    if let Some(favorite) = favorite {
+        history.set_favorite(session_id, favorite);
+    }
+    if let Err(e) = history.store(crate::report_history_path()) {
+        log::error!("Failed to save history: {}", e);
+    }
+}
+
+/// Renders the list of recent changes to the report history, newest first
+fn render_changelog(ui: &Ui) {
+    let changelog = ReportHistory::get().changelog.clone();
+    if changelog.is_empty() {
+        return;
+    }
+
+    ui.spacing();
+
+    if !ui.collapsing_header(
+        &format!("Recent Changes ({})", changelog.len()),
+        TreeNodeFlags::empty(),
+    ) {
+        return;
+    }
+
+    ChildWindow::new("ReportHistoryChangelog")
+        .size([0.0, 150.0])
+        .build(ui, || {
+            for (index, change) in changelog.iter().enumerate().rev() {
+                ui.text_colored(
+                    [0.6, 0.6, 0.6, 1.0],
+                    &format_report_timestamp(change.timestamp),
+                );
+                ui.same_line();
+
+                let color = match change.action {
+                    HistoryAction::Added | HistoryAction::Migrated => [0.4, 1.0, 0.4, 1.0],
+                    HistoryAction::Deleted | HistoryAction::Cleared => [1.0, 0.5, 0.4, 1.0],
+                    HistoryAction::Renamed => [0.8, 0.8, 1.0, 1.0],
+                };
+                ui.text_colored(color, change.action.label());
+
+                if !change.session_id.is_empty() {
+                    ui.same_line();
+                    crate::ui::streamer::sensitive_text(
+                        ui,
+                        &format!("changelog_session_{}", index),
+                        &change.session_id,
+                        [0.8, 0.8, 1.0, 1.0],
+                        false,
+                    );
+                }
+
+                ui.same_line();
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], &change.detail);
+            }
+        });
+}
+
+/// Returns the display name for a history token group, from the token's key
+fn token_label(key: &str, saved_tokens: &[SavedToken]) -> String {
+    if key.is_empty() {
+        return "Unknown token".to_string();
+    }
+
+    if let Some(saved) = saved_tokens.iter().find(|t| crate::tokens::token_key(&t.token) == key) {
+        return saved.name.clone();
+    }
+
+    format!("Token {}", key)
+}
+
+/// Opens the website listing every report parsed with the given token
+fn open_token_on_website(token: &str) {
+    let url = format!("https://parser.rethl.net/?hisToken={}", token);
+    if let Err(e) = open::that_detached(&url) {
+        log::error!("Failed to open browser: {}", e);
+    } else {
+        log::info!("Opening all reports on website with token");
+    }
+}
+
+/// Renders a single report session entry
+fn render_report_entry(ui: &Ui, index: usize, entry: &ReportEntry) {
+    let timestamp_str = format_report_timestamp(entry.timestamp);
+
+    let mut is_selected = SELECTED_SESSIONS.with_borrow(|s| s.contains(&entry.session_id));
+    if ui.checkbox(&format!("##select_{}", index), &mut is_selected) {
+        SELECTED_SESSIONS.with_borrow_mut(|s| {
+            if is_selected {
+                s.insert(entry.session_id.clone());
+            } else {
+                s.remove(&entry.session_id);
+            }
+        });
+    }
+    ui.same_line();
+    if !entry.label.is_empty() {
+        ui.text_colored([1.0, 1.0, 1.0, 1.0], &entry.label);
+        ui.same_line();
+    }
+    ui.text_colored([0.8, 0.8, 1.0, 1.0], &timestamp_str);
+    if entry.favorite {
+        ui.same_line();
+        ui.text_colored([1.0, 0.8, 0.2, 1.0], "[Favorite]");
+    }
+    ui.text_colored([0.6, 0.6, 0.6, 1.0], "Session:");
+    ui.same_line();
+    crate::ui::streamer::sensitive_text(
+        ui,
+        &format!("history_session_{}", entry.session_id),
+        &entry.session_id,
+        [0.6, 0.6, 0.6, 1.0],
+        false,
+    );
+    if let Some(ref details) = entry.details {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], &details.summary());
+
+        let mut context = Vec::new();
+        if !details.guild.is_empty() {
+            context.push(format!("Guild: {}", details.guild));
+        }
+        if !details.token_name.is_empty() {
+            context.push(format!("Token: {}", details.token_name));
+        }
+        if !context.is_empty() {
+            ui.text_colored([0.6, 0.6, 0.6, 1.0], &context.join(" | "));
+        }
+        if !details.endpoint_override.is_empty() {
+            ui.text_colored([1.0, 0.7, 0.3, 1.0], &format!("Endpoint: {}", details.endpoint_override));
+        }
+    }
+    ui.spacing();
+
+    render_rename_row(ui, entry);
+
+    if entry.dps_report_urls.is_empty() {
+        // Main Report section
+        ui.text_colored([0.9, 0.9, 1.0, 1.0], "Main Report:");
+        ui.same_line();
+
+        if ui.small_button(&format!("Copy URL##copy_main_{}", index)) {
+            ui.set_clipboard_text(&entry.main_report_url);
+            log::info!("Copied main report URL to clipboard");
+        }
+
+        ui.same_line();
+
+        if ui.small_button(&format!("Open##open_main_{}", index)) {
+            if let Err(e) = open::that_detached(&entry.main_report_url) {
+                log::error!("Failed to open browser: {}", e);
+            }
+        }
+    } else {
+        render_dps_report_links(ui, index, entry);
+    }
+
+    // Legacy Report section (if it exists)
+    if let Some(ref legacy_url) = entry.legacy_report_url {
+        ui.text_colored([0.8, 0.8, 0.6, 1.0], "Legacy Report:");
+        ui.same_line();
+
+        if ui.small_button(&format!("Copy URL##copy_legacy_{}", index)) {
+            ui.set_clipboard_text(legacy_url);
+            log::info!("Copied legacy report URL to clipboard");
+        }
+
+        ui.same_line();
+
+        if ui.small_button(&format!("Open##open_legacy_{}", index)) {
+            if let Err(e) = open::that_detached(legacy_url) {
+                log::error!("Failed to open browser: {}", e);
+            }
+        }
+    }
+
+    // JSON and CSV files the server produced alongside the reports
+    for (artifact_index, artifact) in entry.artifacts.iter().enumerate() {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], &format!("{}:", artifact.name));
+        ui.same_line();
+
+        if ui.small_button(&format!("Copy URL##copy_artifact_{}_{}", index, artifact_index)) {
+            ui.set_clipboard_text(&artifact.url);
+        }
+
+        ui.same_line();
+
+        if ui.small_button(&format!("Open##open_artifact_{}_{}", index, artifact_index)) {
+            if let Err(e) = open::that_detached(&artifact.url) {
+                log::error!("Failed to open browser: {}", e);
+            }
+        }
+    }
+
+    if ui.small_button(&format!("Send to Discord##send_{}", index)) {
+        send_to_discord(std::slice::from_ref(entry));
+    }
+
+    ui.same_line();
+
+    render_reparse_button(ui, index, entry);
+
+    ui.same_line();
+
+    let pin_label = if entry.pinned { "Unpin" } else { "Pin" };
+    if ui.small_button(&format!("{}##pin_{}", pin_label, index)) {
+        update_flags(&entry.session_id, Some(!entry.pinned), None);
+    }
+
+    ui.same_line();
+
+    let favorite_label = if entry.favorite { "Unfavorite" } else { "Favorite" };
+    if ui.small_button(&format!("{}##favorite_{}", favorite_label, index)) {
+        update_flags(&entry.session_id, None, Some(!entry.favorite));
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Favorites are kept when clearing the history");
+    }
+
+    ui.same_line();
+
+    if ui.small_button(&format!("Rename##rename_{}", index)) {
+        RENAMING.set(Some((entry.session_id.clone(), entry.label.clone())));
+    }
+
+    ui.same_line();
+
+    // Delete button for the entire session
+    if ui.small_button(&format!("Delete Session##del_{}", index)) {
+        REPORT_TO_DELETE.set(Some(index));
+    }
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+}
+
+/// Renders the name input while the session is being renamed
+fn render_rename_row(ui: &Ui, entry: &ReportEntry) {
+    let Some((session_id, mut name)) = RENAMING.with_borrow(|r| r.clone()) else {
+        return;
+    };
+    if session_id != entry.session_id {
+        return;
+    }
+
+    ui.set_next_item_width(250.0);
+    let entered = ui
+        .input_text(&format!("##rename_input_{}", session_id), &mut name)
+        .hint("Name, empty to remove it")
+        .enter_returns_true(true)
+        .build();
+    ui.same_line();
+    let save = ui.small_button(&format!("Save##rename_save_{}", session_id));
+    ui.same_line();
+    let cancel = ui.small_button(&format!("Cancel##rename_cancel_{}", session_id));
+
+    if entered || save {
+        rename_session(&session_id, &name);
+        RENAMING.set(None);
+    } else if cancel {
+        RENAMING.set(None);
+    } else {
+        RENAMING.set(Some((session_id, name)));
+    }
+    ui.spacing();
+}
+
+/// Renders one line per dps.report permalink of a session
+fn render_dps_report_links(ui: &Ui, index: usize, entry: &ReportEntry) {
+    ui.text_colored([0.9, 0.9, 1.0, 1.0], "dps.report:");
+
+    for (i, url) in entry.dps_report_urls.iter().enumerate() {
+        // The log's filename is its timestamp, the easiest way to tell the fights apart
+        let name = entry
+            .log_files
+            .get(i)
+            .and_then(|f| crate::formatting::format_timestamp(f))
+            .unwrap_or_else(|| format!("Log {}", i + 1));
+        ui.text(&format!("  {}", name));
+        ui.same_line();
+
+        if ui.small_button(&format!("Copy URL##copy_dps_{}_{}", index, i)) {
+            ui.set_clipboard_text(url);
+            log::info!("Copied dps.report URL to clipboard");
+        }
+
+        ui.same_line();
+
+        if ui.small_button(&format!("Open##open_dps_{}_{}", index, i)) {
+            if let Err(e) = open::that_detached(url) {
+                log::error!("Failed to open browser: {}", e);
+            }
+        }
+    }
+}
+
+/// Renders the button that uploads the session's logs again for updated reports
+fn render_reparse_button(ui: &Ui, index: usize, entry: &ReportEntry) {
+    let busy = *STATE.reparse_busy.lock().unwrap();
+    let disabled_reason = if entry.log_files.is_empty() {
+        Some("The logs of this session weren't recorded")
+    } else if busy {
+        Some("Looking for the logs of another session")
+    } else {
+        None
+    };
+
+    match disabled_reason {
+        Some(reason) => {
+            let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+            let _style_hovered =
+                ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+            let _style_active =
+                ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+            ui.small_button(&format!("Re-parse##reparse_{}", index));
+            if ui.is_item_hovered() {
+                ui.tooltip_text(reason);
+            }
+        }
+        None => {
+            if ui.small_button(&format!("Re-parse##reparse_{}", index)) {
+                crate::reparse::start_reparse(entry.session_id.clone(), entry.log_files.clone());
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text(format!(
+                    "Upload the {} again as a new session",
+                    plural(entry.log_files.len() as u64, "log", "logs")
+                ));
+            }
+        }
+    }
+}
+
+/// Asks whether the selected sessions are deleted from the history only or from the server as well
+fn render_bulk_delete_confirmation(ui: &Ui, selected: &[ReportEntry]) {
+    // Servers without the delete endpoint only get the local delete
+    let server_delete = crate::capabilities::supports(
+        &Settings::get().api_endpoint,
+        crate::capabilities::Capability::DeleteReport,
+    );
+
+    ui.popup_modal("bulk_delete_confirmation")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text(&format!(
+                "Delete {}?",
+                plural(selected.len() as u64, "selected session", "selected sessions")
+            ));
+            if server_delete {
+                ui.text_colored(
+                    [0.7, 0.7, 0.7, 1.0],
+                    "Deleting from the server removes the reports for everyone with the link.",
+                );
+                ui.text_colored(
+                    [0.7, 0.7, 0.7, 1.0],
+                    "Sessions the server can't delete stay in the history.",
+                );
+            }
+            ui.spacing();
+            ui.text_colored([1.0, 1.0, 0.0, 1.0], "This cannot be undone!");
+            ui.spacing();
+
+            if ui.button("Delete from History") {
+                ui.close_current_popup();
+                let mut history = ReportHistory::get();
+                for entry in selected {
+                    if let Some(index) = history.reports.iter().position(|r| r.session_id == entry.session_id) {
+                        history.remove_report(index);
+                    }
+                }
+                if let Err(e) = history.store(crate::report_history_path()) {
+                    log::error!("Failed to save history after deletion: {}", e);
+                } else {
+                    log::info!("Deleted {} sessions from history", selected.len());
+                }
+                SELECTED_SESSIONS.with_borrow_mut(|s| s.clear());
+            }
+
+            if server_delete {
+                ui.same_line();
+
+                if ui.button("Delete from Server and History") {
+                    ui.close_current_popup();
+                    crate::cleanup::start_bulk_delete(selected.to_vec());
+                }
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+        });
+}
+
+/// Shows how far a bulk delete got and what happened to each session
+fn render_bulk_delete_progress(ui: &Ui) {
+    let running = *STATE.bulk_delete_running.lock().unwrap();
+    let results = STATE.bulk_delete_results.lock().unwrap().clone();
+    if results.is_empty() {
+        return;
+    }
+    if !ui.is_popup_open("bulk_delete_progress") {
+        ui.open_popup("bulk_delete_progress");
+    }
+
+    ui.popup_modal("bulk_delete_progress")
+        .always_auto_resize(true)
+        .build(ui, || {
+            use crate::cleanup::BulkDeleteOutcome;
+
+            let done = results
+                .iter()
+                .filter(|r| r.outcome != BulkDeleteOutcome::Waiting)
+                .count();
+            ui.text(if running { "Deleting from server..." } else { "Deletion finished" });
+            nexus::imgui::ProgressBar::new(done as f32 / results.len() as f32)
+                .size([300.0, 0.0])
+                .overlay_text(&format!("{} / {}", done, results.len()))
+                .build(ui);
+            ui.spacing();
+
+            ChildWindow::new("bulk_delete_results")
+                .size([400.0, 200.0])
+                .build(ui, || {
+                    for result in results.iter() {
+                        let name = crate::ui::streamer::redact(&result.name);
+                        match &result.outcome {
+                            BulkDeleteOutcome::Waiting => {
+                                ui.text_colored([0.7, 0.7, 0.7, 1.0], &format!("{} - waiting", name));
+                            }
+                            BulkDeleteOutcome::Deleted => {
+                                ui.text_colored([0.0, 1.0, 0.0, 1.0], &format!("{} - deleted", name));
+                            }
+                            BulkDeleteOutcome::Skipped(reason) => {
+                                ui.text_colored(
+                                    [1.0, 0.6, 0.0, 1.0],
+                                    &format!("{} - skipped, {}", name, reason),
+                                );
+                            }
+                            BulkDeleteOutcome::Failed(error) => {
+                                ui.text_colored([1.0, 0.3, 0.3, 1.0], &format!("{} - failed", name));
+                                if ui.is_item_hovered() {
+                                    ui.tooltip_text(error);
+                                }
+                            }
+                        }
+                    }
+                });
+
+            if !running {
+                ui.spacing();
+                if ui.button("Close") {
+                    STATE.bulk_delete_results.lock().unwrap().clear();
+                    SELECTED_SESSIONS.with_borrow_mut(|s| s.clear());
+                    ui.close_current_popup();
+                }
+            }
+        });
+}
+
+/// Opens the webhook modal with the reports of the given sessions
+fn send_to_discord(entries: &[ReportEntry]) {
+    let mut urls = Vec::new();
+    for entry in entries.iter() {
+        if !entry.dps_report_urls.is_empty() {
+            urls.extend(entry.dps_report_urls.iter().cloned());
+            continue;
+        }
+        urls.push(entry.main_report_url.clone());
+        if let Some(ref legacy_url) = entry.legacy_report_url {
+            urls.push(legacy_url.clone());
+        }
+    }
+
+    // A single session keeps the date it was parsed on, several get a generic name
+    let report_name = match entries {
+        [entry] if !entry.label.is_empty() => entry.label.clone(),
+        [entry] => {
+            let date = chrono::DateTime::from_timestamp(entry.timestamp as i64, 0)
+                .map(|dt| dt.with_timezone(&chrono::Local).format("%d.%m.%y").to_string())
+                .unwrap_or_default();
+            let token = crate::tokens::resolve_token_key(&entry.token_key)
+                .unwrap_or_else(|| crate::settings::Settings::get().history_token.clone());
+            crate::tokens::report_name(&token, &date)
+        }
+        _ => "WvW Report".to_string(),
+    };
+
+    // A duration across several sessions would mostly be the time between them
+    let summary = match entries {
+        [entry] => crate::webhooks::ReportSummary::from_logs(&entry.log_files, Vec::new()),
+        _ => crate::webhooks::ReportSummary {
+            fights: entries.iter().map(|e| e.log_files.len()).sum(),
+            ..Default::default()
+        },
+    };
+    crate::ui::results::open_webhook_modal(urls, report_name, summary);
+}
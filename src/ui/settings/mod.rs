@@ -1,3 +1,4 @@
+pub mod attendance;
 pub mod cleanup;
 pub mod general;
 pub mod history;
@@ -5,8 +6,9 @@ pub mod qol;
 pub mod tokens;
 pub mod webhooks;
 
-use nexus::imgui::Ui;
+use nexus::imgui::{TreeNodeFlags, Ui};
 
+use crate::settings::Settings;
 use crate::state::STATE;
 
 thread_local! {
@@ -18,7 +20,22 @@ pub fn set_active_settings_tab(tab: usize) {
     ACTIVE_TAB.set(tab);
 }
 
-/// Renders the settings screen with tabs
+/// Renders a collapsible "Advanced" block at the end of a tab
+/// Nothing is shown unless advanced settings are switched on
+pub fn render_advanced_section(ui: &Ui, id: &str, contents: impl FnOnce()) {
+    if !Settings::get().show_advanced_settings {
+        return;
+    }
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    if ui.collapsing_header(&format!("Advanced##advanced_{}", id), TreeNodeFlags::empty()) {
+        contents();
+    }
+}
+
 /// Renders the settings screen with tabs
 pub fn render_settings(ui: &Ui, config_path: &std::path::Path) {
     ui.text("Settings");
@@ -128,6 +145,34 @@ pub fn render_settings(ui: &Ui, config_path: &std::path::Path) {
         }
     }
 
+    ui.same_line();
+
+    // Attendance button
+    if active_tab == 6 {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.4, 0.4, 0.5, 1.0]);
+        let _style2 = ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.45, 0.45, 0.55, 1.0]);
+        let _style3 = ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.5, 0.5, 0.6, 1.0]);
+        ui.button("Attendance");
+    } else {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.25, 0.25, 0.3, 0.6]);
+        let _style2 = ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.35, 0.8]);
+        if ui.button("Attendance") {
+            active_tab = 6;
+            ACTIVE_TAB.set(6);
+        }
+    }
+
+    ui.spacing();
+
+    let mut show_advanced = Settings::get().show_advanced_settings;
+    if ui.checkbox("Show advanced settings", &mut show_advanced) {
+        let mut settings = Settings::get();
+        settings.show_advanced_settings = show_advanced;
+        if let Err(e) = settings.store(config_path) {
+            log::error!("Failed to save advanced settings toggle: {}", e);
+        }
+    }
+
     ui.spacing();
     ui.separator();
     ui.spacing();
@@ -140,6 +185,7 @@ pub fn render_settings(ui: &Ui, config_path: &std::path::Path) {
         3 => webhooks::render_webhooks_tab(ui, config_path),
         4 => cleanup::render_cleanup_tab(ui),
         5 => qol::render_qol_tab(ui, config_path),
+        6 => attendance::render_attendance_tab(ui),
         _ => {}
     }
 
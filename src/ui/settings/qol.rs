@@ -4,7 +4,17 @@ use crate::settings::Settings;
 
 thread_local! {
     static MOUSE_LOCK_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static STREAMER_MODE_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static DEFER_IN_COMBAT_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static ADAPTIVE_THROTTLING_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static LOW_PRIORITY_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+    static CHECK_REPORT_URLS_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static SPEAK_STATUS_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static LOW_BANDWIDTH_ENABLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
     static INITIALIZED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static NEW_PIN_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static CONFIRM_PIN_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static PIN_MESSAGE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
 }
 
 /// Renders the QoL settings tab
@@ -12,6 +22,13 @@ pub fn render_qol_tab(ui: &Ui, _config_path: &std::path::Path) {
     if !INITIALIZED.get() {
         let settings = Settings::get();
         MOUSE_LOCK_ENABLED.set(settings.mouse_lock_enabled);
+        STREAMER_MODE_ENABLED.set(settings.streamer_mode);
+        DEFER_IN_COMBAT_ENABLED.set(settings.defer_work_in_combat);
+        ADAPTIVE_THROTTLING_ENABLED.set(settings.adaptive_throttling);
+        LOW_PRIORITY_ENABLED.set(settings.low_priority_background);
+        CHECK_REPORT_URLS_ENABLED.set(settings.check_report_urls);
+        SPEAK_STATUS_ENABLED.set(settings.speak_status_changes);
+        LOW_BANDWIDTH_ENABLED.set(settings.low_bandwidth_mode);
         INITIALIZED.set(true);
     }
 
@@ -43,12 +60,385 @@ pub fn render_qol_tab(ui: &Ui, _config_path: &std::path::Path) {
         [0.7, 0.7, 0.7, 1.0],
         "Automatically disabled when you tab out or lose focus",
     );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Combat deferral option
+    let mut defer_in_combat = DEFER_IN_COMBAT_ENABLED.get();
+    if ui.checkbox("Pause uploads and scans during combat", &mut defer_in_combat) {
+        DEFER_IN_COMBAT_ENABLED.set(defer_in_combat);
+
+        // Apply immediately so a running upload picks it up
+        Settings::get().defer_work_in_combat = defer_in_combat;
+    }
+
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Waits until you leave combat before uploading or auto-refreshing logs",
+    );
+
+    let location = if crate::mumble::is_in_wvw() { "WvW map" } else { "Not in WvW" };
+    if crate::mumble::is_in_combat() {
+        ui.text_colored([1.0, 0.6, 0.0, 1.0], &format!("Status: In combat ({})", location));
+    } else {
+        ui.text_colored([0.0, 1.0, 0.0, 1.0], &format!("Status: Out of combat ({})", location));
+    }
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Streamer mode option
+    let mut streamer_mode = STREAMER_MODE_ENABLED.get();
+    if ui.checkbox("Streamer mode", &mut streamer_mode) {
+        STREAMER_MODE_ENABLED.set(streamer_mode);
+
+        // Apply immediately so nothing leaks before the settings are saved
+        Settings::get().streamer_mode = streamer_mode;
+        crate::ui::streamer::hide_all();
+    }
+
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Hides tokens, session IDs, webhook URLs and report links on screen",
+    );
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Click a hidden value to reveal it temporarily",
+    );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Report link check option
+    let mut check_report_urls = CHECK_REPORT_URLS_ENABLED.get();
+    if ui.checkbox("Check report links before sharing", &mut check_report_urls) {
+        CHECK_REPORT_URLS_ENABLED.set(check_report_urls);
+        Settings::get().check_report_urls = check_report_urls;
+    }
+
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Confirms each report link is live before you copy or post it",
+    );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Spoken status option
+    let mut speak_status = SPEAK_STATUS_ENABLED.get();
+    if ui.checkbox("Read status changes aloud", &mut speak_status) {
+        SPEAK_STATUS_ENABLED.set(speak_status);
+        Settings::get().speak_status_changes = speak_status;
+    }
+    ui.same_line();
+    if ui.button("Test Voice") {
+        crate::speech::speak("WvW Insights status announcements are working");
+    }
+
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Announces finished uploads, finished processing and failures with Windows text-to-speech",
+    );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    // Adaptive throttling option
+    let mut adaptive_throttling = ADAPTIVE_THROTTLING_ENABLED.get();
+    if ui.checkbox("Slow down background work when the game is struggling", &mut adaptive_throttling) {
+        ADAPTIVE_THROTTLING_ENABLED.set(adaptive_throttling);
+        Settings::get().adaptive_throttling = adaptive_throttling;
+    }
+
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Pauses uploads and slows scanning while frames take much longer than usual (large fights)",
+    );
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Uploads continue after 30 seconds even if the game is still struggling",
+    );
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+
+    render_settings_lock_section(ui);
+
+    super::render_advanced_section(ui, "qol", || {
+        // Background thread priority option
+        let mut low_priority = LOW_PRIORITY_ENABLED.get();
+        if ui.checkbox("Run background work at low priority", &mut low_priority) {
+            LOW_PRIORITY_ENABLED.set(low_priority);
+            Settings::get().low_priority_background = low_priority;
+        }
+
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "Uploads and scans yield CPU time to the game's render threads",
+        );
+
+        ui.spacing();
+
+        // Low bandwidth preset
+        let mut low_bandwidth = LOW_BANDWIDTH_ENABLED.get();
+        if ui.checkbox("Low bandwidth mode", &mut low_bandwidth) {
+            LOW_BANDWIDTH_ENABLED.set(low_bandwidth);
+            Settings::get().low_bandwidth_mode = low_bandwidth;
+        }
+
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "For slow connections: one upload at a time, .evtc logs compressed first, longer timeouts",
+        );
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "Also skips the legacy parser and dps.report forwarding while it is on",
+        );
+
+        ui.spacing();
+
+        // Upload concurrency option, low bandwidth mode pins it to 1
+        let mut concurrency = Settings::get().upload_concurrency() as i32;
+        ui.text("Concurrent uploads:");
+        if low_bandwidth {
+            ui.same_line();
+            ui.text_colored([0.9, 0.7, 0.3, 1.0], "1 (low bandwidth mode)");
+        } else {
+            ui.set_next_item_width(100.0);
+            if ui.input_int("##upload_concurrency", &mut concurrency).build() {
+                concurrency = concurrency.clamp(1, crate::settings::MAX_UPLOAD_CONCURRENCY as i32);
+                Settings::get().upload_concurrency = concurrency as u32;
+            }
+        }
+
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            &format!(
+                "Logs uploaded at the same time (1-{}), lower it on slow connections",
+                crate::settings::MAX_UPLOAD_CONCURRENCY
+            ),
+        );
+
+        ui.spacing();
+
+        // Upload order option
+        let mut smallest_first = Settings::get().smallest_uploads_first;
+        if ui.checkbox("Upload smallest logs first", &mut smallest_first) {
+            Settings::get().smallest_uploads_first = smallest_first;
+        }
+
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "Short fights finish early and long ones like a big keep defense upload last",
+        );
+
+        ui.spacing();
+
+        // Compression of raw logs from older arcdps versions
+        let mut compress_evtc = Settings::get().compress_evtc_uploads || low_bandwidth;
+        if ui.checkbox("Compress .evtc logs before upload", &mut compress_evtc) && !low_bandwidth {
+            Settings::get().compress_evtc_uploads = compress_evtc;
+        }
+
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "Uncompressed logs from older arcdps versions are zipped into .zevtc first",
+        );
+
+        ui.spacing();
+
+        // Upload speed limit option
+        let mut limit = Settings::get().upload_limit_mbps.min(crate::settings::MAX_UPLOAD_LIMIT_MBPS) as i32;
+        ui.text("Upload speed limit:");
+        ui.set_next_item_width(200.0);
+        let format = if limit == 0 { "Unlimited" } else { "%d Mbps" };
+        if ui
+            .slider_config("##upload_limit_mbps", 0, crate::settings::MAX_UPLOAD_LIMIT_MBPS as i32)
+            .display_format(format)
+            .build(&mut limit)
+        {
+            Settings::get().upload_limit_mbps = limit.max(0) as u32;
+        }
+
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "Caps all uploads together so they don't cause lag spikes while you play, 0 for unlimited",
+        );
+
+        ui.spacing();
+        render_upload_stats(ui);
+    });
+}
+
+/// Renders upload speeds measured across sessions, with the latest uploads exportable as CSV
+fn render_upload_stats(ui: &Ui) {
+    thread_local! {
+        static STATS_MESSAGE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    }
+
+    ui.text("Upload statistics:");
+    let stats = crate::upload_stats::UploadStats::get();
+    let Some(average) = stats.average_bytes_per_sec() else {
+        drop(stats);
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], "No uploads measured yet");
+        return;
+    };
+    let mb = |bytes_per_sec: f64| bytes_per_sec / 1024.0 / 1024.0;
+    ui.text_colored(
+        [0.7, 0.9, 1.0, 1.0],
+        &format!(
+            "{} uploads, {} in {}, average {:.2} MB/s",
+            stats.uploads,
+            crate::formatting::format_size(stats.bytes),
+            crate::formatting::format_duration(stats.millis / 1000),
+            mb(average)
+        ),
+    );
+    if let (Some(fastest), Some(slowest)) = (stats.fastest, stats.slowest) {
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            &format!("Fastest {:.2} MB/s, slowest {:.2} MB/s", mb(fastest), mb(slowest)),
+        );
+    }
+    drop(stats);
+
+    if ui.small_button("Export CSV##upload_stats") {
+        match crate::upload_stats::export_csv() {
+            Ok(path) => {
+                STATS_MESSAGE.set(format!("Exported to {}", path.display()));
+                if let Some(dir) = path.parent() {
+                    if let Err(e) = open::that_detached(dir) {
+                        log::error!("Failed to open statistics folder: {}", e);
+                    }
+                }
+            }
+            Err(e) => STATS_MESSAGE.set(format!("Export failed: {}", e)),
+        }
+    }
+    ui.same_line();
+    if ui.small_button("Reset##upload_stats") {
+        let mut stats = crate::upload_stats::UploadStats::get();
+        stats.reset();
+        if let Err(e) = stats.store(crate::upload_stats_path()) {
+            log::error!("Failed to save upload statistics: {}", e);
+        }
+        STATS_MESSAGE.set(String::new());
+    }
+
+    let message = STATS_MESSAGE.with_borrow(|m| m.clone());
+    if !message.is_empty() {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], &message);
+    }
+}
+
+/// Renders the PIN lock controls protecting tokens, webhooks and the API endpoint
+fn render_settings_lock_section(ui: &Ui) {
+    ui.text("Settings Lock");
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Require a PIN before tokens, webhooks or the API endpoint can be changed",
+    );
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Useful for streaming or shared guild computers",
+    );
+    ui.spacing();
+
+    if !crate::lock::has_pin() {
+        ui.text_colored([0.9, 0.9, 0.9, 1.0], "New PIN:");
+        ui.set_next_item_width(120.0);
+        NEW_PIN_BUFFER.with_borrow_mut(|pin| {
+            ui.input_text("##new_lock_pin", pin).password(true).build();
+        });
+
+        ui.text_colored([0.9, 0.9, 0.9, 1.0], "Confirm PIN:");
+        ui.set_next_item_width(120.0);
+        CONFIRM_PIN_BUFFER.with_borrow_mut(|pin| {
+            ui.input_text("##confirm_lock_pin", pin).password(true).build();
+        });
+
+        ui.spacing();
+
+        if ui.button("Enable Settings Lock") {
+            let new_pin = NEW_PIN_BUFFER.with_borrow(|pin| pin.trim().to_string());
+            let confirm_pin = CONFIRM_PIN_BUFFER.with_borrow(|pin| pin.trim().to_string());
+
+            if new_pin.len() < crate::lock::MIN_PIN_LENGTH {
+                PIN_MESSAGE.set(format!(
+                    "PIN must be at least {} characters",
+                    crate::lock::MIN_PIN_LENGTH
+                ));
+            } else if new_pin != confirm_pin {
+                PIN_MESSAGE.set("PINs don't match".to_string());
+            } else {
+                match crate::lock::hash_pin(&new_pin) {
+                    Ok(hash) => {
+                        let mut settings = Settings::get();
+                        settings.lock_pin_hash = hash;
+                        if let Err(e) = settings.store(crate::config_path()) {
+                            log::error!("Failed to save settings lock: {}", e);
+                            PIN_MESSAGE.set(format!("Failed to save: {}", e));
+                        } else {
+                            drop(settings);
+                            crate::lock::lock();
+                            PIN_MESSAGE.set(String::new());
+                            log::info!("Settings lock enabled");
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Failed to hash settings lock PIN: {}", e);
+                        PIN_MESSAGE.set(format!("Failed to set PIN: {}", e));
+                    }
+                }
+                NEW_PIN_BUFFER.set(String::new());
+                CONFIRM_PIN_BUFFER.set(String::new());
+            }
+        }
+    } else if crate::lock::is_locked() {
+        crate::ui::lock::render_lock_notice(ui, "Protected settings");
+    } else {
+        ui.text_colored([0.0, 1.0, 0.0, 1.0], "Unlocked for this session");
+        ui.spacing();
+
+        if ui.button("Lock Now") {
+            crate::lock::lock();
+        }
+
+        ui.same_line();
+
+        if ui.button("Remove PIN") {
+            let mut settings = Settings::get();
+            settings.lock_pin_hash.clear();
+            if let Err(e) = settings.store(crate::config_path()) {
+                log::error!("Failed to remove settings lock: {}", e);
+            } else {
+                log::info!("Settings lock removed");
+            }
+        }
+    }
+
+    let message = PIN_MESSAGE.with_borrow(|m| m.clone());
+    if !message.is_empty() {
+        ui.text_colored([1.0, 0.3, 0.0, 1.0], &message);
+    }
 }
 
 /// Saves QoL settings
 pub fn save_qol_settings(config_path: &std::path::Path) {
     let mut settings = Settings::get();
     settings.mouse_lock_enabled = MOUSE_LOCK_ENABLED.get();
+    settings.streamer_mode = STREAMER_MODE_ENABLED.get();
+    settings.defer_work_in_combat = DEFER_IN_COMBAT_ENABLED.get();
+    settings.adaptive_throttling = ADAPTIVE_THROTTLING_ENABLED.get();
+    settings.low_priority_background = LOW_PRIORITY_ENABLED.get();
+    settings.check_report_urls = CHECK_REPORT_URLS_ENABLED.get();
     
     if let Err(e) = settings.store(config_path) {
         log::error!("Failed to save QoL settings: {}", e);
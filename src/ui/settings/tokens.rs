@@ -35,7 +35,12 @@ pub fn render_tokens_tab(ui: &Ui, config_path: &std::path::Path) {
         }
     }
 
-// Sub-tab navigation with subtle highlighting
+    if crate::lock::is_locked() {
+        crate::ui::lock::render_lock_notice(ui, "Saved tokens");
+        return;
+    }
+
+    // Sub-tab navigation with subtle highlighting
     let active_sub_tab = ACTIVE_SUB_TAB.get();
     
     // History Tokens button
@@ -83,6 +88,74 @@ pub fn render_tokens_tab(ui: &Ui, config_path: &std::path::Path) {
     }
 }
 
+/// Edits the webhook and report name a saved history token switches to
+fn render_token_defaults(ui: &Ui, config_path: &std::path::Path, index: usize, saved_token: &SavedToken) {
+    thread_local! {
+        static REPORT_NAME: std::cell::RefCell<(String, String)> = const { std::cell::RefCell::new((String::new(), String::new())) };
+    }
+
+    ui.spacing();
+    ui.text(&format!("Defaults for '{}':", saved_token.name));
+
+    let webhook_names: Vec<String> = crate::webhooks::WebhookSettings::get()
+        .saved_webhooks
+        .iter()
+        .map(|w| w.name.clone())
+        .collect();
+    let mut items = vec!["(None)".to_string()];
+    items.extend(webhook_names.iter().cloned());
+    let mut current = webhook_names
+        .iter()
+        .position(|name| *name == saved_token.webhook)
+        .map_or(0, |i| i + 1);
+
+    ui.set_next_item_width(200.0);
+    let webhook_changed = ui.combo_simple_string("Webhook##token_webhook", &mut current, &items);
+    if !saved_token.webhook.is_empty() && current == 0 {
+        ui.text_colored(
+            [1.0, 0.6, 0.2, 1.0],
+            &format!("Webhook '{}' is not saved anymore", saved_token.webhook),
+        );
+    }
+
+    let mut name_changed = false;
+    REPORT_NAME.with_borrow_mut(|(editing, name)| {
+        // Keyed by name so the buffer refills when another token is opened
+        if *editing != saved_token.name {
+            *editing = saved_token.name.clone();
+            *name = saved_token.report_name.clone();
+        }
+        ui.set_next_item_width(200.0);
+        if ui
+            .input_text("Report name##token_report_name", name)
+            .hint("WvW: {date}")
+            .build()
+        {
+            name_changed = true;
+        }
+    });
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "{date} is the parse date. Switching to this token selects the webhook.",
+    );
+
+    if webhook_changed || name_changed {
+        let mut settings = Settings::get();
+        if let Some(token) = settings.saved_tokens.get_mut(index) {
+            if webhook_changed {
+                token.webhook = if current > 0 { webhook_names[current - 1].clone() } else { String::new() };
+            }
+            if name_changed {
+                token.report_name = REPORT_NAME.with_borrow(|(_, name)| name.trim().to_string());
+            }
+        }
+        if let Err(e) = settings.store(config_path) {
+            log::error!("Failed to save token defaults: {}", e);
+        }
+    }
+    ui.spacing();
+}
+
 /// Renders the History Tokens section
 fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
     thread_local! {
@@ -90,6 +163,7 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
         static NEW_TOKEN_VALUE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
         static TOKEN_TO_DELETE: std::cell::Cell<Option<usize>> = const { std::cell::Cell::new(None) };
         static DUPLICATE_NAME_ERROR: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+        static TOKEN_TO_EDIT: std::cell::Cell<Option<usize>> = const { std::cell::Cell::new(None) };
     }
 
     ui.text_colored([0.9, 0.7, 0.2, 1.0], "History Tokens (Parser API)");
@@ -102,6 +176,7 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
     let saved_tokens = settings.saved_tokens.clone();
     let current_token = settings.history_token.clone();
     drop(settings);
+    let streamer_mode = crate::ui::streamer::is_enabled();
 
     if saved_tokens.is_empty() {
         ui.text_colored([0.7, 0.7, 0.7, 1.0], "No saved history tokens yet");
@@ -113,15 +188,7 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                     ui.text(&saved_token.name);
                     ui.same_line();
 
-                    let masked = if saved_token.token.len() > 8 {
-                        format!(
-                            "{}...{}",
-                            &saved_token.token[..4],
-                            &saved_token.token[saved_token.token.len() - 4..]
-                        )
-                    } else {
-                        "****".to_string()
-                    };
+                    let masked = crate::ui::streamer::mask_token(&saved_token.token, streamer_mode);
                     ui.text_colored([0.5, 0.5, 0.5, 1.0], &masked);
 
                     ui.same_line();
@@ -145,6 +212,8 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                                 log::error!("Failed to save settings: {}", e);
                             } else {
                                 log::info!("Switched to token: {}", saved_token.name);
+                                drop(settings);
+                                crate::tokens::apply_token_defaults(&saved_token.token);
                                 
                                 // Set the token in STATE so token_input.rs picks it up
                                 *STATE.generated_token.lock().unwrap() = saved_token.token.clone();
@@ -159,6 +228,13 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
 
                     ui.same_line();
 
+                    if ui.small_button(&format!("Defaults##edit_{}", index)) {
+                        let editing = TOKEN_TO_EDIT.get() == Some(index);
+                        TOKEN_TO_EDIT.set(if editing { None } else { Some(index) });
+                    }
+
+                    ui.same_line();
+
                     if ui.small_button(&format!("Delete##del_{}", index)) {
                         TOKEN_TO_DELETE.set(Some(index));
                     }
@@ -180,6 +256,15 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
             }
         }
         TOKEN_TO_DELETE.set(None);
+        TOKEN_TO_EDIT.set(None);
+    }
+
+    if let Some(index) = TOKEN_TO_EDIT.get() {
+        if index < saved_tokens.len() {
+            render_token_defaults(ui, config_path, index, &saved_tokens[index]);
+        } else {
+            TOKEN_TO_EDIT.set(None);
+        }
     }
 
     ui.spacing();
@@ -199,7 +284,9 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
 
     ui.text_colored([0.9, 0.9, 0.9, 1.0], "Token Value:");
     NEW_TOKEN_VALUE.with_borrow_mut(|token| {
-        ui.input_text("##newTokenValue", token).build();
+        ui.input_text("##newTokenValue", token)
+            .password(crate::ui::streamer::is_enabled())
+            .build();
     });
     ui.text_colored([0.6, 0.6, 0.6, 1.0], "(Paste your history token here)");
 
@@ -225,7 +312,7 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                 [0.0, 1.0, 0.0, 1.0]
             };
 
-            ui.text_colored(color, &message);
+            ui.text_colored(color, &crate::ui::streamer::redact(&message));
         } else {
             *STATE.save_token_validation_message_until.lock().unwrap() = None;
         }
@@ -258,7 +345,7 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                 STATE.save_token_validation_message.lock().unwrap().clear();
                 *STATE.save_token_validation_message_until.lock().unwrap() = None;
                 
-                std::thread::spawn(move || {
+                crate::threads::spawn("token-validation", move || {
                     log::info!("Validating token before saving: {}", token_name);
                     
                     match validate_token(&api_endpoint, &token_to_validate) {
@@ -269,6 +356,7 @@ fn render_history_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                             settings.saved_tokens.push(SavedToken {
                                 name: token_name.clone(),
                                 token: token_to_validate,
+                                ..Default::default()
                             });
                             
                             if let Err(e) = settings.store(&config_path) {
@@ -337,6 +425,7 @@ fn render_dps_tokens_section(ui: &Ui, config_path: &std::path::Path) {
     let saved_dps_tokens = settings.saved_dps_tokens.clone();
     let current_dps_token = settings.dps_report_token.clone();
     drop(settings);
+    let streamer_mode = crate::ui::streamer::is_enabled();
 
     if saved_dps_tokens.is_empty() {
         ui.text_colored([0.7, 0.7, 0.7, 1.0], "No saved dps.report tokens yet");
@@ -348,15 +437,7 @@ fn render_dps_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                     ui.text(&saved_token.name);
                     ui.same_line();
 
-                    let masked = if saved_token.token.len() > 8 {
-                        format!(
-                            "{}...{}",
-                            &saved_token.token[..4],
-                            &saved_token.token[saved_token.token.len() - 4..]
-                        )
-                    } else {
-                        "****".to_string()
-                    };
+                    let masked = crate::ui::streamer::mask_token(&saved_token.token, streamer_mode);
                     ui.text_colored([0.5, 0.5, 0.5, 1.0], &masked);
 
                     ui.same_line();
@@ -430,7 +511,9 @@ fn render_dps_tokens_section(ui: &Ui, config_path: &std::path::Path) {
 
     ui.text_colored([0.9, 0.9, 0.9, 1.0], "Token Value:");
     NEW_DPS_TOKEN_VALUE.with_borrow_mut(|token| {
-        ui.input_text("##newDpsTokenValue", token).build();
+        ui.input_text("##newDpsTokenValue", token)
+            .password(crate::ui::streamer::is_enabled())
+            .build();
     });
     ui.text_colored([0.6, 0.6, 0.6, 1.0], "(Paste your dps.report token here)");
 
@@ -463,6 +546,7 @@ fn render_dps_tokens_section(ui: &Ui, config_path: &std::path::Path) {
                 settings.saved_dps_tokens.push(SavedToken {
                     name: token_name.clone(),
                     token: token_value,
+                    ..Default::default()
                 });
                 
                 if let Err(e) = settings.store(config_path) {
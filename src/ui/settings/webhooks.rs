@@ -1,5 +1,6 @@
-use nexus::imgui::Ui;
+use nexus::imgui::{ChildWindow, Ui};
 use std::cell::RefCell;
+use crate::commanders::CommanderNotes;
 use crate::webhooks::WebhookSettings;
 
 thread_local! {
@@ -9,6 +10,10 @@ thread_local! {
     static STATUS_MESSAGE_UNTIL: std::cell::Cell<Option<std::time::Instant>> = std::cell::Cell::new(None);
     static STATUS_IS_ERROR: std::cell::Cell<bool> = std::cell::Cell::new(false);
     static DELETE_CONFIRM_WEBHOOK: std::cell::RefCell<String> = RefCell::new(String::new());
+    static ANNOUNCE_TEMPLATE_BUFFER: std::cell::RefCell<Option<String>> = const { RefCell::new(None) };
+    static WEBHOOK_IS_CUSTOM: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static AUTH_HEADER_BUFFER: std::cell::RefCell<String> = const { RefCell::new(String::new()) };
+    static BODY_TEMPLATE_BUFFER: std::cell::RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
 pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
@@ -19,6 +24,11 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
     ui.separator();
     ui.spacing();
 
+    if crate::lock::is_locked() {
+        crate::ui::lock::render_lock_notice(ui, "Saved webhooks");
+        return;
+    }
+
     // Show temporary status message
     let message_until = STATUS_MESSAGE_UNTIL.get();
     if let Some(until) = message_until {
@@ -32,7 +42,7 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
                     } else {
                         [0.0, 1.0, 0.0, 1.0]
                     };
-                    ui.text_colored(color, &*msg_str);
+                    ui.text_colored(color, &crate::ui::streamer::redact(&msg_str));
                     ui.spacing();
                 }
             });
@@ -54,15 +64,30 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
     });
     ui.text_colored([0.6, 0.6, 0.6, 1.0], "(e.g., Main Guild, WvW Squad)");
 
+    ui.spacing();
+
+    let mut is_custom = WEBHOOK_IS_CUSTOM.get();
+    ui.radio_button("Discord webhook", &mut is_custom, false);
+    ui.same_line();
+    ui.radio_button("Custom HTTP endpoint", &mut is_custom, true);
+    WEBHOOK_IS_CUSTOM.set(is_custom);
+
     ui.spacing();
     
     // Label for URL field - matching tokens.rs style
     ui.text_colored([0.9, 0.9, 0.9, 1.0], "Webhook URL:");
     WEBHOOK_URL_BUFFER.with(|url| {
         let mut url_mut = url.borrow_mut();
-        ui.input_text("##webhook_url", &mut *url_mut).build();
+        ui.input_text("##webhook_url", &mut *url_mut)
+            .password(crate::ui::streamer::is_enabled())
+            .build();
     });
-    ui.text_colored([0.6, 0.6, 0.6, 1.0], "(https://discord.com/api/webhooks/...)");
+    if is_custom {
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], "(https://your-bot.example.com/reports)");
+        render_custom_endpoint_fields(ui);
+    } else {
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], "(https://discord.com/api/webhooks/...)");
+    }
 
     ui.spacing();
 
@@ -70,17 +95,32 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
         // Get values without holding borrows
         let name = WEBHOOK_NAME_BUFFER.with(|n| n.borrow().trim().to_string());
         let url = WEBHOOK_URL_BUFFER.with(|u| u.borrow().trim().to_string());
+        let custom = is_custom.then(|| crate::webhooks::CustomEndpoint {
+            auth_header: AUTH_HEADER_BUFFER.with_borrow(|h| h.trim().to_string()),
+            body_template: BODY_TEMPLATE_BUFFER
+                .with_borrow(|b| b.clone())
+                .unwrap_or_else(crate::webhooks::default_custom_body),
+        });
+        let body_error = custom
+            .as_ref()
+            .and_then(|c| crate::webhooks::check_body_template(&c.body_template).err())
+            .map(|e| e.to_string());
 
         if name.is_empty() {
             show_message("Please enter a webhook name", true);
         } else if url.is_empty() {
             show_message("Please enter a webhook URL", true);
-        } else if !url.starts_with("https://discord.com/api/webhooks/") 
+        } else if is_custom && crate::webhooks::validate_custom_url(&url).is_err() {
+            show_message("Endpoint URL must start with https:// or http://", true);
+        } else if !is_custom
+            && !url.starts_with("https://discord.com/api/webhooks/") 
             && !url.starts_with("https://discordapp.com/api/webhooks/") {
             show_message("Invalid Discord webhook URL", true);
+        } else if let Some(error) = body_error {
+            show_message(&error, true);
         } else {
             let mut webhook_settings = WebhookSettings::get();
-            match webhook_settings.add_webhook(name, url) {
+            match webhook_settings.add_webhook(name, url, custom) {
                 Ok(_) => {
                     if let Err(e) = webhook_settings.store(crate::webhooks_path()) {
                         log::error!("Failed to save webhook settings: {}", e);
@@ -89,6 +129,8 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
                         show_message("Webhook saved successfully!", false);
                         WEBHOOK_NAME_BUFFER.with(|n| n.borrow_mut().clear());
                         WEBHOOK_URL_BUFFER.with(|u| u.borrow_mut().clear());
+                        AUTH_HEADER_BUFFER.with_borrow_mut(|h| h.clear());
+                        BODY_TEMPLATE_BUFFER.set(None);
                     }
                 }
                 Err(e) => {
@@ -131,11 +173,34 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
             } else {
                 webhook.url.clone()
             };
-            ui.text_colored([0.6, 0.6, 0.6, 1.0], &url_preview);
+            crate::ui::streamer::sensitive_text(
+                ui,
+                &format!("webhook_url_{}", webhook.name),
+                &url_preview,
+                [0.6, 0.6, 0.6, 1.0],
+                false,
+            );
             
+            if webhook.custom.is_some() {
+                ui.text_colored([0.6, 0.8, 1.0, 1.0], "Custom HTTP endpoint");
+            }
+
             // Last used
             let last_used = format_timestamp(webhook.last_used);
             ui.text_colored([0.5, 0.5, 0.5, 1.0], &format!("Last used: {}", last_used));
+
+            let mut auto_post = webhook.auto_post;
+            if ui.checkbox(&format!("Auto-post reports when processing completes##auto_post_{}", webhook.name), &mut auto_post) {
+                let mut webhook_settings = WebhookSettings::get();
+                webhook_settings.set_auto_post(&webhook.name, auto_post);
+                if let Err(e) = webhook_settings.store(crate::webhooks_path()) {
+                    log::error!("Failed to save webhook settings: {}", e);
+                }
+            }
+
+            if webhook.custom.is_none() {
+                render_post_identity(ui, webhook);
+            }
             
             // Delete button
             let delete_id = format!("Delete##{}", webhook.name);
@@ -148,6 +213,18 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
         }
     }
 
+    ui.spacing();
+    render_pending_posts(ui, &webhooks);
+
+    ui.spacing();
+    render_embed_section(ui);
+
+    ui.spacing();
+    render_announcement_section(ui, &webhooks);
+
+    ui.spacing();
+    render_commander_notes_section(ui);
+
     // Delete confirmation popup
     ui.popup_modal("delete_webhook_confirm")
         .always_auto_resize(true)
@@ -185,6 +262,348 @@ pub fn render_webhooks_tab(ui: &Ui, _config_path: &std::path::Path) {
         });
 }
 
+/// Renders the auth header and JSON body inputs of a new custom endpoint
+fn render_custom_endpoint_fields(ui: &Ui) {
+    ui.spacing();
+    ui.text_colored([0.9, 0.9, 0.9, 1.0], "Auth Header (optional):");
+    AUTH_HEADER_BUFFER.with_borrow_mut(|header| {
+        ui.input_text("##webhook_auth_header", header)
+            .password(crate::ui::streamer::is_enabled())
+            .build();
+    });
+    ui.text_colored([0.6, 0.6, 0.6, 1.0], "(e.g., Authorization: Bearer your-token)");
+
+    ui.spacing();
+    ui.text_colored([0.9, 0.9, 0.9, 1.0], "JSON Body:");
+    BODY_TEMPLATE_BUFFER.with_borrow_mut(|buffer| {
+        let buffer = buffer.get_or_insert_with(crate::webhooks::default_custom_body);
+        ui.input_text_multiline("##webhook_body_template", buffer, [0.0, 100.0])
+            .build();
+    });
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        &format!("Placeholders: {}", crate::webhooks::CUSTOM_BODY_PLACEHOLDERS),
+    );
+    if ui.small_button("Reset Body") {
+        BODY_TEMPLATE_BUFFER.set(None);
+    }
+}
+
+/// Renders the Discord posts that failed and are waiting to be sent again
+fn render_pending_posts(ui: &Ui, webhooks: &[crate::webhooks::SavedWebhook]) {
+    let posts = crate::pending_posts::PendingPosts::get().posts.clone();
+    if posts.is_empty() {
+        return;
+    }
+
+    ui.text_colored([1.0, 0.6, 0.0, 1.0], &format!("Failed Posts ({}):", posts.len()));
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "These posts could not be delivered and are retried every few minutes",
+    );
+    ui.spacing();
+
+    for (i, post) in posts.iter().enumerate() {
+        let target = webhooks
+            .iter()
+            .find(|w| w.url == post.webhook_url)
+            .map(|w| w.name.clone())
+            .unwrap_or_else(|| "Unsaved webhook".to_string());
+        ui.text(&format!("{} - {}", target, format_timestamp(post.created)));
+
+        // The first line is enough to tell the posts apart
+        let preview = post.message.lines().next().unwrap_or_default();
+        let preview = if preview.len() > 60 {
+            format!("{}...", preview.chars().take(60).collect::<String>())
+        } else {
+            preview.to_string()
+        };
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], &preview);
+
+        let attempts = crate::formatting::plural(post.attempts as u64, "attempt", "attempts");
+        ui.text_colored(
+            [1.0, 0.5, 0.5, 1.0],
+            &format!("{} ({})", post.last_error, attempts),
+        );
+        if post.attempts >= crate::pending_posts::MAX_AUTO_ATTEMPTS {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], "No longer retried automatically");
+        }
+
+        if ui.small_button(&format!("Discard##pending_{}", i)) {
+            let mut pending = crate::pending_posts::PendingPosts::get();
+            pending.remove(post);
+            if let Err(e) = pending.store(crate::pending_posts_path()) {
+                log::error!("Failed to save pending Discord posts: {}", e);
+            }
+        }
+        ui.separator();
+    }
+
+    if crate::pending_posts::is_retrying() {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+        let _style2 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+        let _style3 =
+            ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+        ui.button("Retrying...");
+    } else if ui.button("Retry failed posts") {
+        crate::pending_posts::retry_now();
+    }
+
+    ui.spacing();
+    ui.separator();
+}
+
+/// Renders the toggle and color for posting reports as rich embeds
+fn render_embed_section(ui: &Ui) {
+    ui.text("Report Style:");
+
+    let settings = WebhookSettings::get();
+    let mut use_embeds = settings.use_embeds;
+    let mut color = settings.embed_color;
+    drop(settings);
+
+    if ui.checkbox("Post reports as rich embeds", &mut use_embeds) {
+        let mut settings = WebhookSettings::get();
+        settings.use_embeds = use_embeds;
+        if let Err(e) = settings.store(crate::webhooks_path()) {
+            log::error!("Failed to save webhook settings: {}", e);
+        }
+    }
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Adds fight count, duration and maps to the post instead of bare links",
+    );
+
+    if !use_embeds {
+        return;
+    }
+
+    ui.set_next_item_width(200.0);
+    if ui.color_edit3("Embed color##embed_color", &mut color) {
+        WebhookSettings::get().embed_color = color;
+    }
+    // Saved once the picker is let go, not on every drag step
+    if ui.is_item_deactivated_after_edit() {
+        let settings = WebhookSettings::get();
+        if let Err(e) = settings.store(crate::webhooks_path()) {
+            log::error!("Failed to save webhook settings: {}", e);
+        }
+    }
+}
+
+/// Renders the settings for the message posted when a session gets its first log
+fn render_announcement_section(ui: &Ui, webhooks: &[crate::webhooks::SavedWebhook]) {
+    ui.text("Raid Start Announcement:");
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Posts a message when the first log of a session is uploaded",
+    );
+    ui.spacing();
+
+    let settings = WebhookSettings::get();
+    let mut enabled = settings.announce_enabled;
+    let selected = settings.announce_webhook.clone();
+    let template = settings.announce_template.clone();
+    let mut auto_post = settings.auto_post_reports;
+    drop(settings);
+
+    if ui.checkbox("Announce when a session starts", &mut enabled) {
+        let mut settings = WebhookSettings::get();
+        settings.announce_enabled = enabled;
+        if let Err(e) = settings.store(crate::webhooks_path()) {
+            log::error!("Failed to save webhook settings: {}", e);
+        }
+    }
+
+    if !enabled {
+        return;
+    }
+
+    if webhooks.is_empty() {
+        ui.text_colored([1.0, 0.6, 0.0, 1.0], "Save a webhook above to post announcements");
+        return;
+    }
+
+    ui.text("Post to:");
+    for webhook in webhooks.iter() {
+        ui.same_line();
+        let label = format!("{}##announce_{}", webhook.name, webhook.name);
+        let is_selected = webhook.name == selected;
+        let style = is_selected.then(|| {
+            ui.push_style_color(nexus::imgui::StyleColor::Button, [0.2, 0.5, 0.2, 1.0])
+        });
+        if ui.small_button(&label) {
+            let mut settings = WebhookSettings::get();
+            settings.announce_webhook = webhook.name.clone();
+            if let Err(e) = settings.store(crate::webhooks_path()) {
+                log::error!("Failed to save webhook settings: {}", e);
+            }
+        }
+        drop(style);
+    }
+
+    if selected.is_empty() || !webhooks.iter().any(|w| w.name == selected) {
+        ui.text_colored([1.0, 0.6, 0.0, 1.0], "Pick the webhook to post to");
+    }
+
+    if ui.checkbox("Post the report when auto-processing finishes", &mut auto_post) {
+        let mut settings = WebhookSettings::get();
+        settings.auto_post_reports = auto_post;
+        if let Err(e) = settings.store(crate::webhooks_path()) {
+            log::error!("Failed to save webhook settings: {}", e);
+        }
+    }
+
+    ui.spacing();
+    ui.text("Message:");
+    ANNOUNCE_TEMPLATE_BUFFER.with_borrow_mut(|buffer| {
+        let buffer = buffer.get_or_insert(template);
+        ui.input_text_multiline("##announce_template", buffer, [0.0, 60.0])
+            .build();
+    });
+    ui.text_colored(
+        [0.6, 0.6, 0.6, 1.0],
+        &format!("Placeholders: {}", crate::webhooks::ANNOUNCE_PLACEHOLDERS),
+    );
+
+    if ui.button("Save Message") {
+        let message = ANNOUNCE_TEMPLATE_BUFFER.with_borrow(|b| b.clone().unwrap_or_default());
+        let mut settings = WebhookSettings::get();
+        settings.announce_template = if message.trim().is_empty() {
+            crate::webhooks::default_announce_template()
+        } else {
+            message
+        };
+        match settings.store(crate::webhooks_path()) {
+            Ok(_) => show_message("Announcement saved", false),
+            Err(e) => {
+                log::error!("Failed to save webhook settings: {}", e);
+                show_message("Failed to save announcement", true);
+            }
+        }
+        ANNOUNCE_TEMPLATE_BUFFER.set(None);
+    }
+
+    ui.same_line();
+
+    if ui.button("Reset Message") {
+        ANNOUNCE_TEMPLATE_BUFFER.set(Some(crate::webhooks::default_announce_template()));
+    }
+}
+
+/// Renders the name and avatar overrides of a saved Discord webhook
+fn render_post_identity(ui: &Ui, webhook: &crate::webhooks::SavedWebhook) {
+    let mut username = webhook.username.clone();
+    let mut avatar_url = webhook.avatar_url.clone();
+
+    ui.set_next_item_width(250.0);
+    let username_changed = ui
+        .input_text(&format!("Post as##webhook_username_{}", webhook.name), &mut username)
+        .hint("WvW Insights Parser")
+        .build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Name the posts show, {guild} is replaced with your guild name");
+    }
+    ui.set_next_item_width(250.0);
+    let avatar_changed = ui
+        .input_text(&format!("Avatar URL##webhook_avatar_{}", webhook.name), &mut avatar_url)
+        .hint("https://...")
+        .build();
+
+    if username_changed || avatar_changed {
+        let mut webhook_settings = WebhookSettings::get();
+        webhook_settings.set_identity(&webhook.name, &username, &avatar_url);
+        if let Err(e) = webhook_settings.store(crate::webhooks_path()) {
+            log::error!("Failed to save webhook settings: {}", e);
+        }
+    }
+
+    if username.chars().count() > crate::webhooks::MAX_USERNAME_LENGTH {
+        ui.text_colored(
+            [1.0, 0.6, 0.0, 1.0],
+            &format!("Discord cuts names off after {} characters", crate::webhooks::MAX_USERNAME_LENGTH),
+        );
+    }
+    let avatar_url = avatar_url.trim();
+    if !avatar_url.is_empty() && !avatar_url.starts_with("https://") {
+        ui.text_colored([1.0, 0.6, 0.0, 1.0], "The avatar needs an https:// link, the default is used");
+    }
+}
+
+/// Renders the notes kept about commander tags seen in logs, and whether report posts list them
+fn render_commander_notes_section(ui: &Ui) {
+    ui.separator();
+    ui.text("Commander Notes:");
+    ui.text_colored(
+        [0.7, 0.7, 0.7, 1.0],
+        "Notes and build links for commander tags, shown when hovering a tag in the log list",
+    );
+
+    let mut include = CommanderNotes::get().include_in_posts;
+    if ui.checkbox("List the session's commanders with their notes under report posts", &mut include) {
+        let mut notes = CommanderNotes::get();
+        notes.include_in_posts = include;
+        if let Err(e) = notes.store(crate::commanders_path()) {
+            log::error!("Failed to save commander notes: {}", e);
+        }
+    }
+
+    // Tags with a note plus every tag in the scanned logs
+    let mut names: Vec<String> = CommanderNotes::get().notes.iter().map(|n| n.name.clone()).collect();
+    for commander in crate::state::STATE.logs.lock().unwrap().iter().filter_map(|l| l.commander.as_ref()) {
+        if !names.contains(commander) {
+            names.push(commander.clone());
+        }
+    }
+    names.sort();
+
+    if names.is_empty() {
+        ui.text_colored([0.6, 0.6, 0.6, 1.0], "No commander tags in the scanned logs yet");
+        return;
+    }
+
+    ChildWindow::new("CommanderNotes").size([0.0, 200.0]).build(ui, || {
+        for name in names.iter() {
+            let (mut note, mut link) = CommanderNotes::get()
+                .find(name)
+                .map(|n| (n.note.clone(), n.link.clone()))
+                .unwrap_or_default();
+
+            ui.text_colored([1.0, 0.9, 0.6, 1.0], name);
+            ui.set_next_item_width(200.0);
+            let note_changed = ui
+                .input_text(&format!("##commander_note_{}", name), &mut note)
+                .hint("Note")
+                .build();
+            ui.same_line();
+            ui.set_next_item_width(200.0);
+            let link_changed = ui
+                .input_text(&format!("##commander_link_{}", name), &mut link)
+                .hint("Build or wiki link")
+                .build();
+
+            if note_changed || link_changed {
+                let mut notes = CommanderNotes::get();
+                notes.set(name, &note, &link);
+                if let Err(e) = notes.store(crate::commanders_path()) {
+                    log::error!("Failed to save commander notes: {}", e);
+                }
+            }
+
+            let link = link.trim();
+            if link.starts_with("https://") || link.starts_with("http://") {
+                ui.same_line();
+                if ui.small_button(&format!("Open##commander_open_{}", name)) {
+                    if let Err(e) = open::that_detached(link) {
+                        log::error!("Failed to open {}: {}", link, e);
+                    }
+                }
+            }
+        }
+    });
+}
+
 fn show_message(message: &str, is_error: bool) {
     STATUS_MESSAGE.with(|msg| *msg.borrow_mut() = message.to_string());
     STATUS_IS_ERROR.set(is_error);
@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use nexus::imgui::{StyleColor, Ui};
+
+use crate::settings::Settings;
+use crate::state::STATE;
+use crate::webhooks::WebhookSettings;
+
+/// Placeholder shown instead of sensitive values while streamer mode is on
+pub const HIDDEN_LABEL: &str = "[hidden - click to reveal]";
+
+thread_local! {
+    static REVEALED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Returns true if streamer mode is enabled
+/// Must not be called while holding the Settings lock
+pub fn is_enabled() -> bool {
+    Settings::get().streamer_mode
+}
+
+/// Hides every value that was revealed with a click
+pub fn hide_all() {
+    REVEALED.with_borrow_mut(|revealed| revealed.clear());
+}
+
+/// Masks a token for list display, fully hiding it in streamer mode
+pub fn mask_token(token: &str, streamer_mode: bool) -> String {
+    if !streamer_mode && token.len() > 8 {
+        format!("{}...{}", &token[..4], &token[token.len() - 4..])
+    } else {
+        "****".to_string()
+    }
+}
+
+/// Renders a sensitive value (token, session id, URL) that is masked in streamer mode
+/// Clicking the masked text reveals it, clicking the revealed text hides it again
+pub fn sensitive_text(ui: &Ui, id: &str, value: &str, color: [f32; 4], wrapped: bool) {
+    let enabled = is_enabled();
+    let revealed = REVEALED.with_borrow(|r| r.contains(id));
+
+    if enabled && !revealed {
+        ui.text_colored([0.5, 0.5, 0.5, 1.0], HIDDEN_LABEL);
+        if ui.is_item_clicked() {
+            REVEALED.with_borrow_mut(|r| {
+                r.insert(id.to_string());
+            });
+        }
+        return;
+    }
+
+    if wrapped {
+        let _color = ui.push_style_color(StyleColor::Text, color);
+        ui.text_wrapped(value);
+    } else {
+        ui.text_colored(color, value);
+    }
+
+    if enabled {
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Click to hide");
+        }
+        if ui.is_item_clicked() {
+            REVEALED.with_borrow_mut(|r| {
+                r.remove(id);
+            });
+        }
+    }
+}
+
+/// Replaces known sensitive values in a message before it is shown on screen
+/// Returns the message unchanged when streamer mode is off
+pub fn redact(message: &str) -> String {
+    let settings = Settings::get();
+    if !settings.streamer_mode {
+        return message.to_string();
+    }
+
+    let mut secrets: Vec<String> = vec![
+        settings.history_token.clone(),
+        settings.dps_report_token.clone(),
+    ];
+    secrets.extend(settings.saved_tokens.iter().map(|t| t.token.clone()));
+    secrets.extend(settings.saved_dps_tokens.iter().map(|t| t.token.clone()));
+    drop(settings);
+
+    let webhook_settings = WebhookSettings::get();
+    secrets.extend(webhook_settings.saved_webhooks.iter().map(|w| w.url.clone()));
+    secrets.push(webhook_settings.last_webhook_url.clone());
+    drop(webhook_settings);
+
+    secrets.push(STATE.webhook_url_input.lock().unwrap().clone());
+    secrets.push(STATE.session_id.lock().unwrap().clone());
+    secrets.push(STATE.ownership_token.lock().unwrap().clone());
+    secrets.extend(STATE.report_urls.lock().unwrap().iter().cloned());
+
+    // Replace longest values first so URLs containing tokens are hidden whole
+    secrets.retain(|s| s.len() >= 4);
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+
+    let mut redacted = message.to_string();
+    for secret in secrets.iter() {
+        redacted = redacted.replace(secret.as_str(), "[hidden]");
+    }
+    redacted
+}
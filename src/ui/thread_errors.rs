@@ -0,0 +1,52 @@
+use nexus::imgui::{StyleColor, TreeNodeFlags, Ui};
+
+use crate::state::STATE;
+
+/// Renders the panics caught in background threads, if there are any
+pub fn render_thread_errors(ui: &Ui) {
+    let panics = STATE.thread_panics.lock().unwrap().clone();
+    if panics.is_empty() {
+        return;
+    }
+
+    let header = format!("Background Errors ({})###thread_errors", panics.len());
+    let color = ui.push_style_color(StyleColor::Text, [1.0, 0.4, 0.4, 1.0]);
+    let open = ui.collapsing_header(&header, TreeNodeFlags::DEFAULT_OPEN);
+    color.pop();
+
+    if open {
+        ui.text_colored(
+            [0.7, 0.7, 0.7, 1.0],
+            "A background task crashed. The details are also in the Nexus log.",
+        );
+        ui.spacing();
+
+        for panic in panics.iter().rev() {
+            ui.text_colored(
+                [1.0, 0.5, 0.5, 1.0],
+                &format!("[{}] {}", panic.time, panic.thread),
+            );
+            ui.text_wrapped(&crate::ui::streamer::redact(&panic.message));
+            if !panic.location.is_empty() {
+                ui.text_colored([0.6, 0.6, 0.6, 1.0], &panic.location);
+            }
+            ui.spacing();
+        }
+
+        if ui.button("Copy Details") {
+            let details: Vec<String> = panics
+                .iter()
+                .map(|p| format!("[{}] {} at {}: {}", p.time, p.thread, p.location, p.message))
+                .collect();
+            ui.set_clipboard_text(details.join("\n"));
+        }
+
+        ui.same_line();
+
+        if ui.button("Dismiss") {
+            STATE.thread_panics.lock().unwrap().clear();
+        }
+    }
+
+    ui.separator();
+}
@@ -14,6 +14,8 @@ thread_local! {
     static SHOW_NAME_MODAL: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
     static NEW_TOKEN_NAME: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
     static PENDING_TOKEN: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static PROFILE_NAME_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+    static PROFILE_MESSAGE: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
 }
 
 pub fn reset_initialization() {
@@ -62,12 +64,38 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
         render_name_modal(ui, config_path);
     }
 
+    let locked = crate::lock::is_locked();
+    let streamer_mode = crate::ui::streamer::is_enabled();
+
+    render_profile_picker(ui, locked);
+
+    let current_token = TOKEN_BUFFER.with_borrow(|token| token.clone());
+    let token_invalid = crate::tokens::is_known_invalid(&current_token);
+
     ui.text("Enter your History Token");
+    if token_invalid {
+        ui.same_line();
+        ui.text_colored([1.0, 0.3, 0.0, 1.0], "(No longer valid)");
+        if ui.is_item_hovered() {
+            ui.tooltip_text("The server doesn't know this token anymore, it may have been deleted.");
+        }
+    }
+    if locked {
+        ui.same_line();
+        ui.text_colored([1.0, 0.6, 0.2, 1.0], "(Locked)");
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Tokens are protected by the settings lock PIN.\nUnlock in Settings > QoL.");
+        }
+    }
     ui.spacing();
 
     let mut token_changed = false;
     TOKEN_BUFFER.with_borrow_mut(|token| {
-        if ui.input_text("##token", token).build() {
+        if ui
+            .input_text("##token", token)
+            .read_only(locked)
+            .password(streamer_mode)
+            .build() {
             token_changed = true;
         }
     });
@@ -82,6 +110,8 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
             } else {
                 log::debug!("Token saved in real-time: {}", token);
             }
+            drop(settings);
+            crate::tokens::apply_token_defaults(token);
         });
     }
 
@@ -93,6 +123,25 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
         }
     }
 
+    if token_invalid && !token_changed {
+        ui.text_colored(
+            [1.0, 0.5, 0.0, 1.0],
+            "This token was rejected by the server, sessions can't be created with it",
+        );
+        if ui.small_button("Switch Token") {
+            *STATE.show_token_input.lock().unwrap() = false;
+            *STATE.show_settings.lock().unwrap() = true;
+            crate::ui::settings::set_active_settings_tab(1);
+        }
+        if !locked {
+            ui.same_line();
+            if ui.small_button("Generate New Token##invalid_token") {
+                SHOW_NAME_MODAL.set(true);
+                NEW_TOKEN_NAME.set(String::new());
+            }
+        }
+    }
+
     ui.spacing();
     ui.spacing();
 
@@ -130,7 +179,11 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
 
     let mut dps_token_changed = false;
     DPS_REPORT_TOKEN_BUFFER.with_borrow_mut(|dps_token| {
-        if ui.input_text("##dpsreporttoken", dps_token).build() {
+        if ui
+            .input_text("##dpsreporttoken", dps_token)
+            .read_only(locked)
+            .password(streamer_mode)
+            .build() {
             dps_token_changed = true;
         }
     });
@@ -180,7 +233,7 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
                 [0.0, 1.0, 0.0, 1.0] // Green for valid
             };
 
-            ui.text_colored(color, &message);
+            ui.text_colored(color, &crate::ui::streamer::redact(&message));
         } else {
             // Message expired, clear it
             *STATE.token_validation_message_until.lock().unwrap() = None;
@@ -207,11 +260,10 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
         ui.text_colored([1.0, 1.0, 0.0, 1.0], "Generating token...");
     }
     
-    let error = STATE.token_generation_error.lock().unwrap();
+    let error = STATE.token_generation_error.lock().unwrap().clone();
     if !error.is_empty() {
-        ui.text_colored([1.0, 0.0, 0.0, 1.0], &*error);
+        ui.text_colored([1.0, 0.0, 0.0, 1.0], &crate::ui::streamer::redact(&error));
     }
-    drop(error);
 
     ui.spacing();
 
@@ -232,12 +284,13 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
             STATE.token_validation_message.lock().unwrap().clear();
             *STATE.token_validation_message_until.lock().unwrap() = None;
             
-            std::thread::spawn(move || {
+            crate::threads::spawn("token-validation", move || {
                 log::info!("Validating token...");
                 
                 match validate_token(&api_endpoint, &token_to_validate) {
                     Ok(true) => {
                         log::info!("Token validation successful");
+                        crate::tokens::record_check(&token_to_validate, true);
                         
                         // Token is already saved in real-time, just scan for logs
                         scan_for_logs();
@@ -250,6 +303,7 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
                     }
                     Ok(false) => {
                         log::warn!("Token validation failed - invalid token");
+                        crate::tokens::record_check(&token_to_validate, false);
                         *STATE.token_validation_message.lock().unwrap() = 
                             "Invalid token! Try another or generate new".to_string();
                         *STATE.token_validation_is_error.lock().unwrap() = true;
@@ -304,7 +358,7 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
     ui.spacing();
 
     // Generate key button - only enabled if token field is empty and not currently generating
-    let button_enabled = token_is_empty && !is_generating;
+    let button_enabled = token_is_empty && !is_generating && !locked;
     
     if button_enabled {
         if ui.button("Generate New Token") {
@@ -324,6 +378,81 @@ pub fn render_token_input(ui: &Ui, config_path: &std::path::Path) {
     }
 }
 
+/// Renders the profile dropdown and the controls to save or delete profiles
+/// Switching fills in the token, guild, webhook and filters saved with the profile
+fn render_profile_picker(ui: &Ui, locked: bool) {
+    let settings = Settings::get();
+    let names: Vec<String> = settings.profiles.iter().map(|p| p.name.clone()).collect();
+    let active = settings.active_profile.clone();
+    drop(settings);
+
+    ui.text("Profile");
+    if locked {
+        ui.same_line();
+        ui.text_colored([1.0, 0.6, 0.2, 1.0], "(Locked)");
+    }
+    ui.spacing();
+
+    if !names.is_empty() {
+        let mut items = vec!["(None)".to_string()];
+        items.extend(names.iter().cloned());
+        let mut current = names.iter().position(|name| *name == active).map_or(0, |i| i + 1);
+
+        ui.set_next_item_width(200.0);
+        if ui.combo_simple_string("##profile", &mut current, &items) && current > 0 && !locked {
+            match crate::profiles::apply(&names[current - 1]) {
+                Ok(()) => {
+                    // Buffers are filled from the new settings on the next frame
+                    reset_initialization();
+                    PROFILE_MESSAGE.set(format!("Switched to {}", names[current - 1]));
+                }
+                Err(e) => PROFILE_MESSAGE.set(format!("Failed to switch profile: {}", e)),
+            }
+        }
+
+        if current > 0 && !locked {
+            ui.same_line();
+            if ui.small_button("Delete##profile") {
+                match crate::profiles::delete(&names[current - 1]) {
+                    Ok(()) => PROFILE_MESSAGE.set(format!("Deleted {}", names[current - 1])),
+                    Err(e) => PROFILE_MESSAGE.set(format!("Failed to delete profile: {}", e)),
+                }
+            }
+        }
+    }
+
+    if !locked {
+        PROFILE_NAME_BUFFER.with_borrow_mut(|name| {
+            ui.set_next_item_width(200.0);
+            ui.input_text("##profile_name", name).hint("Profile name").build();
+        });
+        ui.same_line();
+        if ui.small_button("Save as Profile") {
+            let name = PROFILE_NAME_BUFFER.with_borrow(|name| name.trim().to_string());
+            match crate::profiles::save_current(&name) {
+                Ok(()) => {
+                    PROFILE_NAME_BUFFER.set(String::new());
+                    PROFILE_MESSAGE.set(format!("Saved {}", name));
+                }
+                Err(e) => PROFILE_MESSAGE.set(e),
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Saves the current tokens, guild name, default webhook and log filters under this name");
+        }
+    }
+
+    PROFILE_MESSAGE.with_borrow(|message| {
+        if !message.is_empty() {
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], message);
+        }
+    });
+
+    ui.spacing();
+    ui.separator();
+    ui.spacing();
+}
+
 /// Renders the modal for naming a new token
 fn render_name_modal(ui: &Ui, config_path: &std::path::Path) {
     thread_local! {
@@ -388,11 +517,10 @@ fn render_name_modal(ui: &Ui, config_path: &std::path::Path) {
                 ui.text_colored([1.0, 1.0, 0.0, 1.0], "Generating token...");
             }
             
-            let error = STATE.token_generation_error.lock().unwrap();
+            let error = STATE.token_generation_error.lock().unwrap().clone();
             if !error.is_empty() {
-                ui.text_colored([1.0, 0.0, 0.0, 1.0], &*error);
+                ui.text_colored([1.0, 0.0, 0.0, 1.0], &crate::ui::streamer::redact(&error));
             }
-            drop(error);
             
             ui.spacing();
             
@@ -421,7 +549,7 @@ fn render_name_modal(ui: &Ui, config_path: &std::path::Path) {
                         *STATE.token_generating.lock().unwrap() = true;
                         STATE.token_generation_error.lock().unwrap().clear();
                         
-                        std::thread::spawn(move || {
+                        crate::threads::spawn("token-generation", move || {
                             log::info!("Generating new token from server");
                             
                             match generate_token() {
@@ -433,6 +561,7 @@ fn render_name_modal(ui: &Ui, config_path: &std::path::Path) {
                                     settings.saved_tokens.push(SavedToken {
                                         name: token_name.clone(),
                                         token: new_token.clone(),
+                                        ..Default::default()
                                     });
                                     
                                     // Also set as current token
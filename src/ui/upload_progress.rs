@@ -1,8 +1,27 @@
 use nexus::imgui::{ChildWindow, ProgressBar, Ui};
 
-use crate::settings::Settings;
+use crate::formatting::format_duration;
 use crate::state::{ProcessingState, STATE};
 
+/// Seconds between throughput samples of the upload batch
+const RATE_SAMPLE_SECS: f64 = 1.0;
+
+/// Weight of the newest throughput sample, the rest comes from the previous estimate
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Measured upload speed of the running batch
+#[derive(Clone, Copy)]
+struct BatchRate {
+    last_sample: std::time::Instant,
+    last_bytes: u64,
+    /// None until the first file of the batch is done
+    bytes_per_sec: Option<f64>,
+}
+
+thread_local! {
+    static BATCH_RATE: std::cell::Cell<Option<BatchRate>> = const { std::cell::Cell::new(None) };
+}
+
 /// File processing status for individual files
 #[derive(Clone, Debug)]
 enum FileStatus {
@@ -11,13 +30,87 @@ enum FileStatus {
     Complete,
 }
 
+/// Renders the time left for the whole upload batch, from the speed measured so far
+/// The speed is smoothed so it follows changes in concurrency or connection without jumping around
+fn render_batch_eta(ui: &Ui, state: ProcessingState) {
+    if state != ProcessingState::Uploading {
+        BATCH_RATE.set(None);
+        return;
+    }
+
+    let (done_bytes, total_bytes, files_done) = {
+        let logs = STATE.logs.lock().unwrap();
+        let mut done_bytes = 0;
+        let mut total_bytes = 0;
+        let mut files_done = 0;
+        for log in logs.iter().filter(|l| l.selected) {
+            total_bytes += log.size;
+            if log.upload_finished() {
+                done_bytes += log.size;
+                files_done += 1;
+            } else if let Some((sent, total)) = log.upload_progress.filter(|(_, total)| *total > 0)
+            {
+                // The request body can differ from the file size, so count the fraction sent
+                done_bytes += (log.size as f64 * (sent.min(total) as f64 / total as f64)) as u64;
+            }
+        }
+        (done_bytes, total_bytes, files_done)
+    };
+
+    let now = std::time::Instant::now();
+    let mut rate = BATCH_RATE.get().unwrap_or(BatchRate {
+        last_sample: now,
+        last_bytes: done_bytes,
+        bytes_per_sec: None,
+    });
+
+    let elapsed = now.duration_since(rate.last_sample).as_secs_f64();
+    if elapsed >= RATE_SAMPLE_SECS {
+        let sample = done_bytes.saturating_sub(rate.last_bytes) as f64 / elapsed;
+        rate.bytes_per_sec = match rate.bytes_per_sec {
+            Some(previous) => Some(previous * (1.0 - RATE_SMOOTHING) + sample * RATE_SMOOTHING),
+            None if files_done > 0 => Some(sample),
+            None => None,
+        };
+        rate.last_sample = now;
+        rate.last_bytes = done_bytes;
+    }
+    BATCH_RATE.set(Some(rate));
+
+    let remaining = total_bytes.saturating_sub(done_bytes);
+    if remaining == 0 {
+        return;
+    }
+
+    match rate.bytes_per_sec {
+        Some(bytes_per_sec) if bytes_per_sec > 0.0 => {
+            let seconds = (remaining as f64 / bytes_per_sec).ceil() as u64;
+            ui.text_colored(
+                [0.7, 0.9, 1.0, 1.0],
+                &format!(
+                    "About {} left for {:.1} MB at {:.1} MB/s",
+                    format_duration(seconds),
+                    remaining as f64 / 1024.0 / 1024.0,
+                    bytes_per_sec / 1024.0 / 1024.0
+                ),
+            );
+        }
+        Some(_) => ui.text_colored([0.7, 0.7, 0.7, 1.0], "Waiting for uploads to continue..."),
+        None => ui.text_colored([0.7, 0.7, 0.7, 1.0], "Estimating time left..."),
+    }
+}
+
 /// Renders the upload progress screen with individual file tracking
 pub fn render_upload_progress(ui: &Ui) {
     let state = *STATE.processing_state.lock().unwrap();
-    
+
     // Show total files in session at the top
     let total_files = STATE.uploaded_files.lock().unwrap().len();
-    ui.text(format!("Upload Progress - {} file(s) in session", total_files));
+    ui.text(format!(
+        "Upload Progress - {} file(s) in session",
+        total_files
+    ));
+    render_batch_eta(ui, state);
     ui.separator();
 
     ChildWindow::new("UploadStatus")
@@ -25,25 +118,14 @@ pub fn render_upload_progress(ui: &Ui) {
         .build(ui, || {
             // During uploading, show the logs being uploaded with their status
             if state == ProcessingState::Uploading {
-                let logs = STATE.logs.lock().unwrap();
-                let has_selected = logs.iter().any(|l| l.selected);
-                
-                if has_selected {
-                    for log in logs.iter() {
-                        if log.selected {
-                            ui.text(format!("{}: {}", log.filename, log.status));
-                        }
-                    }
-                } else {
-                    ui.text_colored([0.7, 0.7, 0.7, 1.0], "No files selected for upload");
-                }
+                render_upload_queue(ui);
             } else if state == ProcessingState::Processing {
                 // Show file-by-file progress during processing
                 render_file_processing_status(ui);
             } else {
                 // Show all files in the current session (Idle/Complete/Failed states)
                 let uploaded_files = STATE.uploaded_files.lock().unwrap();
-                
+
                 if uploaded_files.is_empty() {
                     ui.text_colored([0.7, 0.7, 0.7, 1.0], "No files in session");
                 } else {
@@ -53,16 +135,20 @@ pub fn render_upload_progress(ui: &Ui) {
                         } else {
                             "Uploaded"
                         };
-                        
+
                         let status_color = if state == ProcessingState::Complete {
                             [0.0, 1.0, 0.0, 1.0]
                         } else {
                             [0.7, 0.9, 1.0, 1.0]
                         };
-                        
+
                         ui.text(&file.filename);
                         ui.same_line();
                         ui.text_colored(status_color, &format!("- {}", status_text));
+                        if let Some(timing) = file.upload_timing {
+                            ui.same_line();
+                            ui.text_colored([0.5, 0.7, 0.9, 1.0], &timing.summary());
+                        }
                     }
                 }
             }
@@ -75,56 +161,40 @@ pub fn render_upload_progress(ui: &Ui) {
     match state {
         ProcessingState::Uploading => {
             ui.text("Uploading files...");
+            if *STATE.work_deferred.lock().unwrap() {
+                ui.text_colored([1.0, 0.6, 0.0, 1.0], "Paused - waiting for combat to end");
+            } else if crate::throttle::is_throttled() {
+                ui.text_colored([1.0, 0.6, 0.0, 1.0], "Throttled due to combat load");
+            }
             ui.spacing();
 
             if ui.button("Cancel Upload") {
-                std::thread::spawn(|| {
+                crate::threads::spawn("upload-cancel", || {
                     log::info!("User cancelled upload");
+                    crate::upload::cancel_active_session();
                     reset_upload_state();
                     *STATE.show_log_selection.lock().unwrap() = false;
                     *STATE.show_token_input.lock().unwrap() = true;
                 });
             }
         }
-            ProcessingState::Idle => {
-                let logs = STATE.logs.lock().unwrap();
-                let selected_logs: Vec<_> = logs.iter().filter(|l| l.selected).collect();
-                let total = selected_logs.len();
-                let uploaded = selected_logs
-                    .iter()
-                    .filter(|l| l.uploaded || l.status.starts_with("Failed"))
-                    .count();
-                drop(logs);
-
-                if uploaded >= total && total > 0 {
-                    ui.text_colored([0.0, 1.0, 0.0, 1.0], "All files uploaded successfully!");
-                    ui.spacing();
+        ProcessingState::Idle => {
+            let logs = STATE.logs.lock().unwrap();
+            let selected_logs: Vec<_> = logs.iter().filter(|l| l.selected).collect();
+            let total = selected_logs.len();
+            let uploaded = selected_logs.iter().filter(|l| l.upload_finished()).count();
+            drop(logs);
+
+            if uploaded >= total && total > 0 {
+                ui.text_colored([0.0, 1.0, 0.0, 1.0], "All files uploaded successfully!");
+                ui.spacing();
+
+                if ui.button("Start Processing") {
+                    *STATE.processing_state.lock().unwrap() = ProcessingState::Processing;
 
-                    if ui.button("Start Processing") {
-                        *STATE.processing_state.lock().unwrap() = ProcessingState::Processing;
-
-                        std::thread::spawn(|| {
-                            let settings = Settings::get();
-                            let api_endpoint = settings.api_endpoint.clone();
-                            let history_token = settings.history_token.clone();
-                            let guild_name = settings.guild_name.clone();
-                            let enable_legacy_parser = settings.enable_legacy_parser;
-                            let dps_report_token = settings.dps_report_token.clone(); // ADD THIS LINE
-                            drop(settings);
-
-                            let session_id = STATE.session_id.lock().unwrap().clone();
-                            let ownership_token = STATE.ownership_token.lock().unwrap().clone();
-
-                            log::info!("Starting processing with guild name: '{}', legacy parser: {}", guild_name, enable_legacy_parser);
-                            match crate::upload::start_processing(
-                                &api_endpoint,
-                                &session_id,
-                                &history_token,
-                                &ownership_token,
-                                &guild_name,
-                                enable_legacy_parser,
-                                &dps_report_token,
-                            ) {
+                    crate::threads::spawn("start-processing", || {
+                        let _activity = crate::threads::activity("Starting processing");
+                        match crate::upload_review::start_session_processing() {
                             Ok(server_message) => {
                                 log::info!("Processing started successfully: {}", server_message);
                                 *STATE.last_status_check.lock().unwrap() =
@@ -133,7 +203,8 @@ pub fn render_upload_progress(ui: &Ui) {
                             Err(e) => {
                                 log::error!("Failed to start processing: {}", e);
                                 *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
-                                *STATE.report_urls.lock().unwrap() = vec![format!("Server error: {}", e)];
+                                *STATE.report_urls.lock().unwrap() =
+                                    vec![format!("Server error: {}", e)];
                             }
                         }
                     });
@@ -142,8 +213,9 @@ pub fn render_upload_progress(ui: &Ui) {
                 ui.same_line();
 
                 if ui.button("Cancel") {
-                    std::thread::spawn(|| {
+                    crate::threads::spawn("upload-cancel", || {
                         log::info!("User cancelled before processing");
+                        crate::upload::cancel_active_session();
                         reset_upload_state();
                         *STATE.show_log_selection.lock().unwrap() = false;
                         *STATE.show_token_input.lock().unwrap() = true;
@@ -161,9 +233,15 @@ pub fn render_upload_progress(ui: &Ui) {
             if progress == 0.0 && phase.contains("Queued") {
                 ui.text_colored([1.0, 1.0, 0.0, 1.0], &phase);
                 ui.spacing();
-                ui.text_colored([0.7, 0.9, 1.0, 1.0], "Your session is waiting in the processing queue...");
+                ui.text_colored(
+                    [0.7, 0.9, 1.0, 1.0],
+                    "Your session is waiting in the processing queue...",
+                );
                 ui.spacing();
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Processing will begin automatically when a slot becomes available.");
+                ui.text_colored(
+                    [0.7, 0.7, 0.7, 1.0],
+                    "Processing will begin automatically when a slot becomes available.",
+                );
             } else {
                 if !phase.is_empty() {
                     ui.text(&phase);
@@ -176,47 +254,39 @@ pub fn render_upload_progress(ui: &Ui) {
                 // Progress bar
                 let progress_fraction = progress / 100.0;
                 ui.text(format!("Progress: {:.0}%", progress));
-                ProgressBar::new(progress_fraction).size([0.0, 0.0]).build(ui);
+                ProgressBar::new(progress_fraction)
+                    .size([0.0, 0.0])
+                    .build(ui);
 
                 // Show time estimate countdown if available
                 let time_estimate = *STATE.processing_time_estimate.lock().unwrap();
                 let timer_start = *STATE.processing_time_estimate_start.lock().unwrap();
-                
+
                 if let (Some(estimate_seconds), Some(start_time)) = (time_estimate, timer_start) {
                     ui.spacing();
-                    
+
                     let elapsed = start_time.elapsed().as_secs() as u32;
-                    
+
                     if elapsed < estimate_seconds {
                         // Countdown mode - still within estimate
                         let remaining = estimate_seconds - elapsed;
-                        
-                        if remaining < 60 {
-                            ui.text_colored([0.7, 0.9, 1.0, 1.0], &format!("Estimated: ~{} seconds remaining", remaining));
-                        } else {
-                            let minutes = remaining / 60;
-                            let seconds = remaining % 60;
-                            if seconds > 0 {
-                                ui.text_colored([0.7, 0.9, 1.0, 1.0], &format!("Estimated: ~{} min {} sec remaining", minutes, seconds));
-                            } else {
-                                ui.text_colored([0.7, 0.9, 1.0, 1.0], &format!("Estimated: ~{} minutes remaining", minutes));
-                            }
-                        }
+                        ui.text_colored(
+                            [0.7, 0.9, 1.0, 1.0],
+                            &format!(
+                                "Estimated: ~{} remaining",
+                                format_duration(remaining as u64)
+                            ),
+                        );
                     } else {
                         // Overdue mode - exceeded estimate
                         let overdue = elapsed - estimate_seconds;
-                        
-                        if overdue < 60 {
-                            ui.text_colored([1.0, 0.8, 0.2, 1.0], &format!("Overdue by {} seconds (still processing...)", overdue));
-                        } else {
-                            let minutes = overdue / 60;
-                            let seconds = overdue % 60;
-                            if seconds > 0 {
-                                ui.text_colored([1.0, 0.8, 0.2, 1.0], &format!("Overdue by {} min {} sec (still processing...)", minutes, seconds));
-                            } else {
-                                ui.text_colored([1.0, 0.8, 0.2, 1.0], &format!("Overdue by {} minutes (still processing...)", minutes));
-                            }
-                        }
+                        ui.text_colored(
+                            [1.0, 0.8, 0.2, 1.0],
+                            &format!(
+                                "Overdue by {} (still processing...)",
+                                format_duration(overdue as u64)
+                            ),
+                        );
                     }
                 }
 
@@ -229,7 +299,7 @@ pub fn render_upload_progress(ui: &Ui) {
             ui.spacing();
 
             if ui.button("Cancel Processing") {
-                std::thread::spawn(|| {
+                crate::threads::spawn("processing-cancel", || {
                     log::info!("User cancelled processing");
                     reset_upload_state();
                     *STATE.show_log_selection.lock().unwrap() = false;
@@ -239,19 +309,27 @@ pub fn render_upload_progress(ui: &Ui) {
         }
         ProcessingState::Complete => {
             ui.text_colored([0.0, 1.0, 0.0, 1.0], "Processing complete!");
-            
-            let report_urls = STATE.report_urls.lock().unwrap();
+
+            let report_urls = STATE.report_urls.lock().unwrap().clone();
             if !report_urls.is_empty() {
                 ui.spacing();
                 ui.text("Report URLs:");
-                
+
                 for url in report_urls.iter() {
                     let label = if url.contains("Legacy") || url.to_lowercase().contains("legacy") {
                         "Legacy Report:"
                     } else {
                         "Report:"
                     };
-                    ui.text_colored([0.0, 1.0, 1.0, 1.0], &format!("{} {}", label, url));
+                    ui.text_colored([0.0, 1.0, 1.0, 1.0], label);
+                    ui.same_line();
+                    crate::ui::streamer::sensitive_text(
+                        ui,
+                        &format!("progress_url_{}", url),
+                        url,
+                        [0.0, 1.0, 1.0, 1.0],
+                        false,
+                    );
                 }
             } else {
                 ui.spacing();
@@ -260,53 +338,47 @@ pub fn render_upload_progress(ui: &Ui) {
 
             ui.spacing();
             if ui.button("Back to Log Selection") {
-                std::thread::spawn(|| {
+                crate::threads::spawn("upload-reset", || {
                     log::info!("Back to Log Selection clicked - spawning reset");
                     reset_upload_state();
                     log::info!("Reset complete");
                 });
             }
         }
-            ProcessingState::Failed => {
-                let report_urls = STATE.report_urls.lock().unwrap();
-                let error_message = report_urls.first().cloned().unwrap_or_default();
-                drop(report_urls);
+        ProcessingState::Failed => {
+            let report_urls = STATE.report_urls.lock().unwrap();
+            let error_message = report_urls.first().cloned().unwrap_or_default();
+            drop(report_urls);
+
+            ui.text_colored([1.0, 0.0, 0.0, 1.0], "Processing failed!");
+            ui.spacing();
 
-                ui.text_colored([1.0, 0.0, 0.0, 1.0], "Processing failed!");
+            if !error_message.is_empty() {
+                ui.text("Server response:");
+                ui.text_colored(
+                    [1.0, 0.5, 0.5, 1.0],
+                    &crate::ui::streamer::redact(&error_message),
+                );
                 ui.spacing();
 
-                if !error_message.is_empty() {
-                    ui.text("Server response:");
-                    ui.text_colored([1.0, 0.5, 0.5, 1.0], &error_message);
+                if let Some(failure) = crate::failures::classify(&error_message) {
+                    ui.text_colored([1.0, 1.0, 0.0, 1.0], &failure.title);
+                    ui.text_wrapped(&failure.explanation);
+                    ui.text_colored([0.7, 0.9, 1.0, 1.0], "What to do:");
+                    ui.same_line();
+                    ui.text_wrapped(&failure.suggestion);
                     ui.spacing();
                 }
+            }
 
-                if ui.button("Retry Processing") {
-                    *STATE.processing_state.lock().unwrap() = ProcessingState::Processing;
-                    STATE.report_urls.lock().unwrap().clear();
-
-                    std::thread::spawn(|| {
-                        let settings = Settings::get();
-                        let api_endpoint = settings.api_endpoint.clone();
-                        let history_token = settings.history_token.clone();
-                        let guild_name = settings.guild_name.clone();
-                        let enable_legacy_parser = settings.enable_legacy_parser;
-                        let dps_report_token = settings.dps_report_token.clone(); // ADD THIS LINE
-                        drop(settings);
-
-                        let session_id = STATE.session_id.lock().unwrap().clone();
-                        let ownership_token = STATE.ownership_token.lock().unwrap().clone();
-
-                        log::info!("Retrying processing with guild name: '{}', legacy parser: {}", guild_name, enable_legacy_parser);
-                        match crate::upload::start_processing(
-                            &api_endpoint,
-                            &session_id,
-                            &history_token,
-                            &ownership_token,
-                            &guild_name,
-                            enable_legacy_parser,
-                            &dps_report_token,
-                        ) {
+            if ui.button("Retry Processing") {
+                *STATE.processing_state.lock().unwrap() = ProcessingState::Processing;
+                STATE.report_urls.lock().unwrap().clear();
+                STATE.report_url_health.lock().unwrap().clear();
+
+                crate::threads::spawn("start-processing", || {
+                    let _activity = crate::threads::activity("Starting processing");
+                    match crate::upload_review::start_session_processing() {
                         Ok(server_message) => {
                             log::info!("Processing started successfully: {}", server_message);
                             *STATE.last_status_check.lock().unwrap() =
@@ -315,7 +387,8 @@ pub fn render_upload_progress(ui: &Ui) {
                         Err(e) => {
                             log::error!("Failed to start processing: {}", e);
                             *STATE.processing_state.lock().unwrap() = ProcessingState::Failed;
-                            *STATE.report_urls.lock().unwrap() = vec![format!("Server error: {}", e)];
+                            *STATE.report_urls.lock().unwrap() =
+                                vec![format!("Server error: {}", e)];
                         }
                     }
                 });
@@ -324,7 +397,7 @@ pub fn render_upload_progress(ui: &Ui) {
             ui.same_line();
 
             if ui.button("Back to Log Selection") {
-                std::thread::spawn(|| {
+                crate::threads::spawn("upload-reset", || {
                     log::info!("Back to Log Selection clicked - spawning reset");
                     reset_upload_state();
                     log::info!("Reset complete");
@@ -334,27 +407,171 @@ pub fn render_upload_progress(ui: &Ui) {
     }
 }
 
+/// A change to the upload queue picked in the queue view
+enum QueueAction {
+    MoveToFront(usize),
+    Cancel(usize),
+}
+
+/// Renders the logs being uploaded, the ones still waiting in the queue and the finished ones
+/// Waiting logs can be moved to the front or taken out, and the whole queue can be paused
+fn render_upload_queue(ui: &Ui) {
+    let pending = crate::upload::pending_uploads();
+    let paused = crate::upload::is_queue_paused();
+    let logs = STATE.logs.lock().unwrap();
+
+    if !logs.iter().any(|l| l.selected) {
+        ui.text_colored([0.7, 0.7, 0.7, 1.0], "No files selected for upload");
+        return;
+    }
+
+    let mut action = None;
+
+    if ui.small_button(if paused {
+        "Resume Queue"
+    } else {
+        "Pause Queue"
+    }) {
+        crate::upload::set_queue_paused(!paused);
+    }
+    if paused {
+        ui.same_line();
+        ui.text_colored(
+            [1.0, 0.7, 0.3, 1.0],
+            "Paused, uploads already running still finish",
+        );
+    }
+    ui.spacing();
+
+    // Uploads a worker has started
+    for (index, log) in logs.iter().enumerate() {
+        if !log.selected || log.upload_finished() || pending.contains(&index) {
+            continue;
+        }
+
+        match log.upload_progress {
+            Some((sent, total)) if total > 0 => {
+                let fraction = sent as f32 / total as f32;
+                ui.text(&log.filename);
+                ProgressBar::new(fraction).size([200.0, 0.0]).build(ui);
+                ui.same_line();
+                ui.text(format!(
+                    "{:.0}% of {:.1} MB",
+                    fraction * 100.0,
+                    total as f64 / 1024.0 / 1024.0
+                ));
+            }
+            _ => ui.text(format!("{}: {}", log.filename, log.status)),
+        }
+    }
+
+    if !pending.is_empty() {
+        ui.spacing();
+        ui.text_colored(
+            [0.8, 0.8, 0.8, 1.0],
+            &format!("Waiting ({})", pending.len()),
+        );
+        for (position, &index) in pending.iter().enumerate() {
+            let Some(log) = logs.get(index) else {
+                continue;
+            };
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                &format!(
+                    "{}. {} ({})",
+                    position + 1,
+                    log.filename,
+                    crate::formatting::format_size(log.size)
+                ),
+            );
+            if position > 0 {
+                ui.same_line();
+                if ui.small_button(&format!("Move to Front##queue_front_{}", index)) {
+                    action = Some(QueueAction::MoveToFront(index));
+                }
+            }
+            ui.same_line();
+            if ui.small_button(&format!("Cancel##queue_cancel_{}", index)) {
+                action = Some(QueueAction::Cancel(index));
+            }
+        }
+    }
+
+    let finished: Vec<_> = logs
+        .iter()
+        .filter(|l| l.selected && l.upload_finished())
+        .collect();
+    if !finished.is_empty() {
+        ui.spacing();
+        ui.text_colored([0.8, 0.8, 0.8, 1.0], &format!("Done ({})", finished.len()));
+        for log in finished {
+            let color = if log.uploaded {
+                [0.0, 1.0, 0.0, 1.0]
+            } else if log.status == crate::upload::CANCELLED_STATUS {
+                [0.6, 0.6, 0.6, 1.0]
+            } else {
+                [1.0, 0.3, 0.0, 1.0]
+            };
+            ui.text_colored(color, &format!("{}: {}", log.filename, log.status));
+            if let Some(timing) = log.upload_timing {
+                ui.same_line();
+                ui.text_colored([0.5, 0.7, 0.9, 1.0], &timing.summary());
+            }
+        }
+    }
+    drop(logs);
+
+    match action {
+        Some(QueueAction::MoveToFront(index)) => crate::upload::move_to_front(index),
+        Some(QueueAction::Cancel(index)) => cancel_queued_upload(index),
+        None => {}
+    }
+}
+
+/// Takes a waiting log out of the queue and out of the session's file list
+/// Does nothing once a worker has started uploading it
+fn cancel_queued_upload(index: usize) {
+    if !crate::upload::remove_pending(index) {
+        return;
+    }
+
+    let mut logs = STATE.logs.lock().unwrap();
+    let Some(log) = logs.get_mut(index) else {
+        return;
+    };
+    log.status = crate::upload::CANCELLED_STATUS.to_string();
+    let filename = log.filename.clone();
+    drop(logs);
+
+    log::info!("Removed {} from the upload queue", filename);
+    STATE
+        .uploaded_files
+        .lock()
+        .unwrap()
+        .retain(|f| f.filename != filename);
+}
+
 /// Renders file-by-file processing status during the Processing state
 fn render_file_processing_status(ui: &Ui) {
     let uploaded_files = STATE.uploaded_files.lock().unwrap();
     let phase = STATE.processing_phase.lock().unwrap();
     let progress = *STATE.processing_progress.lock().unwrap();
-    
+
     // Extract file progress from the phase string
     // Format: "Processing logs with Elite Insights (3/4)"
     let (current_file, total_files) = extract_file_progress(&phase);
-    
+
     if uploaded_files.is_empty() {
         ui.text_colored([0.7, 0.7, 0.7, 1.0], "No files in session");
         return;
     }
-    
+
     let total_uploaded = uploaded_files.len();
-    
+
     // Determine status for each file
     for (index, file) in uploaded_files.iter().enumerate() {
         let file_number = index + 1;
-        
+
         let status = if current_file > 0 && total_files > 0 {
             // We have file tracking info from Elite Insights
             if file_number < current_file {
@@ -375,33 +592,39 @@ fn render_file_processing_status(ui: &Ui) {
                 FileStatus::Pending
             }
         };
-        
+
         render_file_item(ui, file, &status, file_number, total_uploaded);
     }
 }
 
 /// Renders a single file item with its processing status
-fn render_file_item(ui: &Ui, file: &crate::upload_review::UploadedFileInfo, status: &FileStatus, file_num: usize, total: usize) {
+fn render_file_item(
+    ui: &Ui,
+    file: &crate::upload_review::UploadedFileInfo,
+    status: &FileStatus,
+    file_num: usize,
+    total: usize,
+) {
     let (icon, color) = match status {
         FileStatus::Complete => ("[OK]", [0.0, 1.0, 0.0, 1.0]),
         FileStatus::Processing => ("[>>]", [1.0, 0.8, 0.2, 1.0]),
         FileStatus::Pending => ("[ ]", [0.5, 0.5, 0.5, 1.0]),
     };
-    
+
     let status_text = match status {
         FileStatus::Complete => "Complete".to_string(),
         FileStatus::Processing => format!("Processing ({}/{})", file_num, total),
         FileStatus::Pending => "Pending".to_string(),
     };
-    
+
     // Icon
     ui.text_colored(color, icon);
     ui.same_line();
-    
+
     // Filename
     ui.text(&file.filename);
     ui.same_line();
-    
+
     // Status
     ui.text_colored(color, &format!("- {}", status_text));
 }
@@ -417,15 +640,17 @@ fn extract_file_progress(phase: &str) -> (usize, usize) {
                 if let Some(slash_pos) = progress_str.find('/') {
                     let current_str = &progress_str[..slash_pos];
                     let total_str = &progress_str[slash_pos + 1..];
-                    
-                    if let (Ok(current), Ok(total)) = (current_str.parse::<usize>(), total_str.parse::<usize>()) {
+
+                    if let (Ok(current), Ok(total)) =
+                        (current_str.parse::<usize>(), total_str.parse::<usize>())
+                    {
                         return (current, total);
                     }
                 }
             }
         }
     }
-    
+
     (0, 0)
 }
 
@@ -433,6 +658,10 @@ fn extract_file_progress(phase: &str) -> (usize, usize) {
 pub fn reset_upload_state() {
     log::info!("reset_upload_state: Starting");
 
+    // Stop queued uploads and in-flight status polls of the old session
+    crate::cancel::cancel_uploads();
+    crate::upload::clear_queue();
+
     log::info!("reset_upload_state: Resetting show_upload_progress");
     *STATE.show_upload_progress.lock().unwrap() = false;
 
@@ -447,6 +676,8 @@ pub fn reset_upload_state() {
 
     log::info!("reset_upload_state: Clearing report_urls");
     STATE.report_urls.lock().unwrap().clear();
+    STATE.report_artifacts.lock().unwrap().clear();
+    STATE.artifact_downloads.lock().unwrap().clear();
 
     log::info!("reset_upload_state: Clearing session_id");
     STATE.session_id.lock().unwrap().clear();
@@ -457,6 +688,12 @@ pub fn reset_upload_state() {
     log::info!("reset_upload_state: Clearing uploaded_files");
     STATE.uploaded_files.lock().unwrap().clear();
 
+    log::info!("reset_upload_state: Clearing split_sessions");
+    STATE.split_sessions.lock().unwrap().clear();
+    STATE.endpoint_override.lock().unwrap().clear();
+    STATE.dps_report_uploads.lock().unwrap().clear();
+    STATE.time_split_message.lock().unwrap().clear();
+
     log::info!("reset_upload_state: Resetting last_status_check");
     *STATE.last_status_check.lock().unwrap() = None;
 
@@ -465,13 +702,17 @@ pub fn reset_upload_state() {
 
     log::info!("reset_upload_state: Clearing processing_phase");
     STATE.processing_phase.lock().unwrap().clear();
-    
+
     log::info!("reset_upload_state: Clearing processing_time_estimate");
     *STATE.processing_time_estimate.lock().unwrap() = None;
-    
+
     log::info!("reset_upload_state: Clearing processing_time_estimate_start");
     *STATE.processing_time_estimate_start.lock().unwrap() = None;
 
+    log::info!("reset_upload_state: Resetting auto-processing");
+    *STATE.auto_processing.lock().unwrap() = false;
+    *STATE.live_last_upload.lock().unwrap() = None;
+
     log::info!("reset_upload_state: Locking logs for reset");
     let mut logs = STATE.logs.lock().unwrap();
     log::info!(
@@ -482,6 +723,8 @@ pub fn reset_upload_state() {
         log.selected = false;
         log.uploaded = false;
         log.status = "Ready".to_string();
+        log.upload_progress = None;
+        log.upload_timing = None;
     }
     drop(logs);
     log::info!("reset_upload_state: Logs reset complete");
@@ -490,4 +733,4 @@ pub fn reset_upload_state() {
     *STATE.show_log_selection.lock().unwrap() = true;
 
     log::info!("reset_upload_state: Complete");
-}
\ No newline at end of file
+}
@@ -1,12 +1,65 @@
 use anyhow::{anyhow, Result};
-use serde::Deserialize;
-use std::path::PathBuf;
-use std::sync::mpsc::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::{Condvar, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::cancel::CancelToken;
+use crate::capabilities::{self, Capability};
 use crate::common::WorkerMessage;
+use crate::report_history::ReportArtifact;
+use crate::settings::{Settings, MAX_UPLOAD_CONCURRENCY};
+use crate::state::{ProcessingState, STATE};
 
-pub type UploadJob = (usize, PathBuf, String, String, String);
+pub type UploadJob = (usize, PathBuf, String, String, String, CancelToken, UploadBackend);
+
+/// Where the logs of a session are uploaded to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UploadBackend {
+    /// The WvW Insights parser, the logs are combined into one report
+    Parser,
+    /// dps.report, every log gets its own permalink
+    DpsReport,
+}
+
+impl UploadBackend {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UploadBackend::Parser => "WvW Insights",
+            UploadBackend::DpsReport => "dps.report",
+        }
+    }
+}
+
+const DPS_REPORT_URL: &str = "https://dps.report";
+
+/// A log uploaded to dps.report
+#[derive(Debug, Clone)]
+pub struct DpsReportUpload {
+    pub filename: String,
+    pub permalink: String,
+    /// Fight name and duration from the Elite Insights JSON, None when it couldn't be fetched
+    pub fight: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DpsReportResponse {
+    id: Option<String>,
+    permalink: Option<String>,
+    error: Option<String>,
+    encounter: Option<DpsReportEncounter>,
+}
+
+/// The few encounter fields of an upload shown next to its permalink
+#[derive(Debug, Deserialize)]
+struct DpsReportEncounter {
+    boss: Option<String>,
+    /// Fight length in seconds
+    #[serde(default)]
+    duration: u64,
+}
 
 thread_local! {
     static CLIENT: ureq::Agent = ureq::agent()
@@ -70,7 +123,34 @@ struct Heartbeat {
     component: Option<String>,
 }
 
+/// A server-side session that has uploaded files but was never processed
+#[derive(Debug, Clone, Deserialize)]
+pub struct PendingSession {
+    pub session_id: String,
+    pub ownership_token: String,
+    #[serde(default)]
+    pub files: Vec<PendingFile>,
+    /// Unix timestamp the session was created at
+    #[serde(default)]
+    pub created: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PendingFile {
+    pub name: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PendingSessionsResponse {
+    success: bool,
+    sessions: Option<Vec<PendingSession>>,
+    message: Option<String>,
+}
+
 pub fn create_session(api_endpoint: &str, history_token: &str) -> Result<(String, String)> {  // REMOVE dps_report_token parameter
+    crate::consent::require()?;
     let url = format!("{}?endpoint=nexus-session", api_endpoint);
     
     let response = CLIENT.with(|c| {
@@ -82,6 +162,10 @@ pub fn create_session(api_endpoint: &str, history_token: &str) -> Result<(String
     let session: SessionResponse = response.into_json()?;
     
     log::info!("Session creation response: {:?}", session);
+    crate::crash::record_response(
+        "nexus-session",
+        if session.success { "success" } else { session.message.as_deref().unwrap_or("failed") },
+    );
     
     if session.success {
         let session_id = session.session_id.ok_or_else(|| anyhow!("No session_id in response"))?;
@@ -92,47 +176,696 @@ pub fn create_session(api_endpoint: &str, history_token: &str) -> Result<(String
     }
 }
 
-pub fn run(
-    inc: Receiver<UploadJob>,
-    out: Sender<WorkerMessage>,
-) -> thread::JoinHandle<()> {
-    thread::Builder::new()
-        .name("wvw-insights-thread".to_string())
-        .spawn(move || {
-            for (index, location, api_endpoint, session_id, history_token) in inc {
-                log::info!("Uploading {:?}", location);
-                
-                let result = upload_file(location, &api_endpoint, &session_id, &history_token);
-                
-                if let Err(e) = out.send(WorkerMessage::upload_result(index, result)) {
-                    log::error!("Failed to send upload result: {e}");
+/// Endpoint the current session talks to, the session's override or else the configured one
+pub fn session_endpoint() -> String {
+    let endpoint_override = STATE.endpoint_override.lock().unwrap().clone();
+    if endpoint_override.is_empty() {
+        Settings::get().api_endpoint.clone()
+    } else {
+        endpoint_override
+    }
+}
+
+/// Checks an endpoint typed in for a single session
+pub fn validate_endpoint_override(endpoint: &str) -> Result<()> {
+    if !endpoint.starts_with("https://") && !endpoint.starts_with("http://") {
+        return Err(anyhow!("The endpoint has to start with https:// or http://"));
+    }
+    if endpoint.contains(char::is_whitespace) {
+        return Err(anyhow!("The endpoint can't contain spaces"));
+    }
+    Ok(())
+}
+
+/// How often an idle worker checks whether the concurrency setting was raised or the queue changed
+const IDLE_WORKER_POLL: Duration = Duration::from_millis(500);
+
+/// Status shown for a queued log that was taken out of the queue before it was uploaded
+pub const CANCELLED_STATUS: &str = "Cancelled";
+
+/// A log waiting for a worker
+struct QueuedUpload {
+    size: u64,
+    /// Moved to the front by hand, taken before everything else
+    front: bool,
+    job: UploadJob,
+}
+
+/// Uploads waiting for a worker, shown and managed on the upload progress screen
+struct UploadQueue {
+    /// In the order they were queued, logs moved to the front first
+    pending: Vec<QueuedUpload>,
+    /// No new uploads are started while paused, running ones finish
+    paused: bool,
+    /// Set on unload so the workers exit
+    closed: bool,
+}
+
+static QUEUE: Mutex<UploadQueue> = Mutex::new(UploadQueue {
+    pending: Vec::new(),
+    paused: false,
+    closed: false,
+});
+
+/// Wakes waiting workers when a log is queued or the queue is resumed or closed
+static QUEUE_CHANGED: Condvar = Condvar::new();
+
+/// What a worker gets when it asks the queue for work
+enum NextUpload {
+    Job(UploadJob),
+    Idle,
+    Closed,
+}
+
+/// Adds a log to the upload queue
+pub fn enqueue(job: UploadJob) {
+    let size = std::fs::metadata(&job.1).map(|m| m.len()).unwrap_or(0);
+    QUEUE.lock().unwrap().pending.push(QueuedUpload { size, front: false, job });
+    QUEUE_CHANGED.notify_all();
+}
+
+/// Picks the next upload, waiting up to IDLE_WORKER_POLL for one
+/// Logs moved to the front come first, then the smallest waiting log or the oldest one
+fn next_upload() -> NextUpload {
+    let smallest_first = Settings::get().smallest_uploads_first;
+    let mut queue = QUEUE.lock().unwrap();
+    if !queue.closed && (queue.paused || queue.pending.is_empty()) {
+        queue = QUEUE_CHANGED.wait_timeout(queue, IDLE_WORKER_POLL).unwrap().0;
+    }
+    if queue.closed {
+        return NextUpload::Closed;
+    }
+    if queue.paused {
+        return NextUpload::Idle;
+    }
+
+    let front = queue.pending.iter().position(|queued| queued.front);
+    let position = match front {
+        Some(position) => Some(position),
+        None if smallest_first => queue
+            .pending
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, queued)| queued.size)
+            .map(|(position, _)| position),
+        None => (!queue.pending.is_empty()).then_some(0),
+    };
+    match position {
+        Some(position) => NextUpload::Job(queue.pending.remove(position).job),
+        None => NextUpload::Idle,
+    }
+}
+
+/// Log indices of the waiting uploads, in the order the workers will take them
+pub fn pending_uploads() -> Vec<usize> {
+    let smallest_first = Settings::get().smallest_uploads_first;
+    let queue = QUEUE.lock().unwrap();
+    let mut order: Vec<&QueuedUpload> = queue.pending.iter().collect();
+    // Stable, so logs moved to the front keep their order and equal sizes stay in queue order
+    order.sort_by_key(|queued| (!queued.front, if smallest_first { queued.size } else { 0 }));
+    order.iter().map(|queued| queued.job.0).collect()
+}
+
+/// Makes a waiting log the next one to be uploaded
+pub fn move_to_front(index: usize) {
+    let mut queue = QUEUE.lock().unwrap();
+    if let Some(position) = queue.pending.iter().position(|queued| queued.job.0 == index) {
+        let mut queued = queue.pending.remove(position);
+        queued.front = true;
+        queue.pending.insert(0, queued);
+    }
+}
+
+/// Takes a waiting log out of the queue, false when a worker already started it
+pub fn remove_pending(index: usize) -> bool {
+    let mut queue = QUEUE.lock().unwrap();
+    let before = queue.pending.len();
+    queue.pending.retain(|queued| queued.job.0 != index);
+    queue.pending.len() != before
+}
+
+pub fn set_queue_paused(paused: bool) {
+    QUEUE.lock().unwrap().paused = paused;
+    QUEUE_CHANGED.notify_all();
+}
+
+pub fn is_queue_paused() -> bool {
+    QUEUE.lock().unwrap().paused
+}
+
+/// Drops every waiting upload and resumes the queue, used when a session is reset
+pub fn clear_queue() {
+    let mut queue = QUEUE.lock().unwrap();
+    queue.pending.clear();
+    queue.paused = false;
+}
+
+/// Stops the workers on unload
+pub fn close_queue() {
+    QUEUE.lock().unwrap().closed = true;
+    QUEUE_CHANGED.notify_all();
+}
+
+/// Starts the upload worker pool
+/// Every worker takes jobs from the same queue, only as many as the concurrency setting allows are active
+pub fn run(out: Sender<WorkerMessage>) -> Vec<thread::JoinHandle<()>> {
+    (0..MAX_UPLOAD_CONCURRENCY)
+        .map(|worker| {
+            let out = out.clone();
+            crate::threads::spawn(&format!("upload-{}", worker + 1), move || run_worker(worker, &out))
+        })
+        .collect()
+}
+
+fn run_worker(worker: usize, out: &Sender<WorkerMessage>) {
+    loop {
+        if crate::cancel::is_shutting_down() {
+            return;
+        }
+
+        // Workers above the configured limit stay idle, read every time so changes apply right away
+        if worker >= Settings::get().upload_concurrency() {
+            thread::sleep(IDLE_WORKER_POLL);
+            continue;
+        }
+
+        // The queue lock is only held while picking the next job, not during the upload
+        let (index, location, api_endpoint, session_id, history_token, cancel, backend) = match next_upload() {
+            NextUpload::Job(job) => job,
+            NextUpload::Idle => continue,
+            NextUpload::Closed => return,
+        };
+
+        crate::throttle::apply_background_priority();
+        crate::mumble::wait_until_out_of_combat(&cancel);
+        crate::throttle::wait_while_throttled(&cancel);
+
+        // Jobs of a cancelled session are dropped instead of uploaded
+        if cancel.is_cancelled() {
+            log::info!("Skipping cancelled upload {:?}", location);
+            continue;
+        }
+
+        log::info!("Uploading {:?}", location);
+        let _activity = crate::threads::activity("Uploading");
+
+        let file_size = std::fs::metadata(&location).map(|m| m.len()).unwrap_or(0);
+        let started = std::time::Instant::now();
+        // Size of what was actually sent, compressed copies are smaller than the log
+        let sent_size = std::cell::Cell::new(0);
+        let on_progress = |sent, total| {
+            sent_size.set(total);
+            // Losing a progress update only makes the bar lag, the result still arrives
+            let _ = out.send(WorkerMessage::upload_progress(index, sent, total));
+        };
+
+        let result = match backend {
+            UploadBackend::Parser => {
+                upload_file(location, &api_endpoint, &session_id, &history_token, &cancel, on_progress)
+            }
+            UploadBackend::DpsReport => upload_to_dps_report(location, &cancel, on_progress).map(|upload| {
+                STATE.dps_report_uploads.lock().unwrap().push(upload);
+                "Uploaded".to_string()
+            }),
+        };
+
+        // Same as a job dropped before it started, the cancelled session is cleared by whoever cancelled it
+        if result.is_err() && cancel.is_cancelled() {
+            log::info!("Stopped cancelled upload {}", index);
+            continue;
+        }
+
+        let endpoint = match backend {
+            UploadBackend::Parser => "nexus-upload",
+            UploadBackend::DpsReport => "dps.report upload",
+        };
+        match &result {
+            Ok(_) => crate::crash::record_response(endpoint, "success"),
+            Err(e) => crate::crash::record_response(endpoint, &e.to_string()),
+        }
+
+        if result.is_ok() {
+            let timing = crate::upload_stats::UploadTiming {
+                bytes: if sent_size.get() > 0 { sent_size.get() } else { file_size },
+                millis: started.elapsed().as_millis() as u64,
+            };
+            log::info!("Upload {} took {}", index, timing.summary());
+            let _ = out.send(WorkerMessage::upload_timing(index, timing));
+        }
+
+        if let Err(e) = out.send(WorkerMessage::upload_result(index, result)) {
+            log::error!("Failed to send upload result: {e}");
+        }
+    }
+}
+
+/// Result of checking whether a report URL is being served yet
+#[derive(Debug, Clone, PartialEq)]
+pub enum UrlHealth {
+    Checking,
+    Live,
+    Unavailable(String),
+}
+
+/// Low bandwidth mode waits this many times longer before a request times out
+const LOW_BANDWIDTH_TIMEOUT_FACTOR: u32 = 3;
+
+/// Timeout for a request that normally takes `secs` at most, longer on slow connections
+fn request_timeout(secs: u64) -> Duration {
+    let timeout = Duration::from_secs(secs);
+    if Settings::get().low_bandwidth_mode {
+        timeout * LOW_BANDWIDTH_TIMEOUT_FACTOR
+    } else {
+        timeout
+    }
+}
+
+/// Sends a HEAD request to a report URL
+/// The server can report completion before the CDN has the file
+pub fn check_report_url(url: &str) -> Result<()> {
+    crate::consent::require()?;
+    CLIENT.with(|c| {
+        c.head(url)
+            .timeout(request_timeout(10))
+            .call()
+    })?;
+    Ok(())
+}
+
+/// Size of the pieces the request body is handed to the connection in
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Request body that reports how much of it has been sent
+/// Reports at most once per percent so the UI channel isn't flooded
+/// Stops with an error once the upload is cancelled, so Cancel and unload don't wait for the whole file
+struct ProgressReader<'a, F: Fn(u64, u64)> {
+    data: &'a [u8],
+    pos: usize,
+    last_percent: Option<u64>,
+    cancel: &'a CancelToken,
+    on_progress: F,
+}
+
+/// When the upload speed limit allows the next piece to be sent, shared by all workers
+static NEXT_SEND: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Waits until `bytes` more fit within the upload speed limit
+/// Time left unused while nothing was uploading doesn't build up into a burst
+/// Returns false if the upload was cancelled while waiting
+fn throttle_upload(bytes: usize, cancel: &CancelToken) -> bool {
+    let limit_mbps = Settings::get().upload_limit_mbps.min(crate::settings::MAX_UPLOAD_LIMIT_MBPS);
+    if limit_mbps == 0 || bytes == 0 {
+        return true;
+    }
+
+    let bytes_per_sec = limit_mbps as f64 * 1_000_000.0 / 8.0;
+    let cost = Duration::from_secs_f64(bytes as f64 / bytes_per_sec);
+
+    let start = {
+        let mut next_send = NEXT_SEND.lock().unwrap();
+        let now = Instant::now();
+        let start = next_send.filter(|next| *next > now).unwrap_or(now);
+        *next_send = Some(start + cost);
+        start
+    };
+
+    let wait = start.saturating_duration_since(Instant::now());
+    wait.is_zero() || cancel.sleep(wait)
+}
+
+/// Error a cancelled upload stops with
+/// Not ErrorKind::Interrupted, io::copy retries reads that fail with it and would spin instead of stopping
+fn upload_cancelled() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::ConnectionAborted, CANCELLED_STATUS)
+}
+
+impl<F: Fn(u64, u64)> std::io::Read for ProgressReader<'_, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = &self.data[self.pos..];
+        let n = remaining.len().min(buf.len()).min(UPLOAD_CHUNK_SIZE);
+        if self.cancel.is_cancelled() || !throttle_upload(n, self.cancel) {
+            return Err(upload_cancelled());
+        }
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+
+        let total = self.data.len() as u64;
+        let sent = self.pos as u64;
+        let percent = if total > 0 { sent * 100 / total } else { 100 };
+        if self.last_percent != Some(percent) {
+            self.last_percent = Some(percent);
+            (self.on_progress)(sent, total);
+        }
+
+        Ok(n)
+    }
+}
+
+/// Files above this size are sent in parts when the server supports it
+const CHUNKED_UPLOAD_THRESHOLD: u64 = 10 * 1024 * 1024;
+
+/// Size of one part of a chunked upload
+const CHUNKED_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Failed parts in a row before a chunked upload gives up
+/// The server keeps the parts it has, so the next attempt resumes from there
+const CHUNKED_PART_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Deserialize)]
+struct ChunkedUploadResponse {
+    success: bool,
+    upload_id: Option<String>,
+    /// Bytes of the file the server has so far
+    #[serde(default)]
+    received: u64,
+    message: Option<String>,
+}
+
+/// Starts a chunked upload, or looks up the one already started for this file
+/// Returns None when the server doesn't support chunked uploads
+fn init_chunked_upload(
+    api_endpoint: &str,
+    session_id: &str,
+    history_token: &str,
+    filename: &str,
+    size: u64,
+) -> Result<Option<ChunkedUploadResponse>> {
+    let url = format!("{}?endpoint=nexus-upload-init", api_endpoint);
+    let size = size.to_string();
+    let continues = crate::scanning::continued_log(Path::new(filename));
+
+    let mut form = vec![
+        ("session_id", session_id),
+        ("history_token", history_token),
+        ("filename", filename),
+        ("size", size.as_str()),
+    ];
+    if let Some(ref previous) = continues {
+        form.push(("continues", previous.as_str()));
+    }
+
+    let result = CLIENT.with(|c| c.post(&url).send_form(&form));
+
+    let init: ChunkedUploadResponse = match result {
+        Ok(response) => response.into_json()?,
+        // Older servers don't know the endpoint
+        Err(ureq::Error::Status(400 | 404 | 405 | 501, _)) => {
+            capabilities::mark_unsupported(api_endpoint, Capability::ChunkedUpload);
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if init.success && init.upload_id.is_some() {
+        Ok(Some(init))
+    } else {
+        log::info!(
+            "Chunked upload not available: {}",
+            init.message.as_deref().unwrap_or("no upload id")
+        );
+        Ok(None)
+    }
+}
+
+/// Sends one part of a chunked upload, returns how many bytes the server has now
+fn send_part(
+    api_endpoint: &str,
+    upload_id: &str,
+    offset: u64,
+    part: &[u8],
+    total: u64,
+    cancel: &CancelToken,
+    on_progress: &impl Fn(u64, u64),
+) -> Result<u64> {
+    let url = format!(
+        "{}?endpoint=nexus-upload-chunk&upload_id={}&offset={}",
+        api_endpoint, upload_id, offset
+    );
+
+    let body = ProgressReader {
+        data: part,
+        pos: 0,
+        last_percent: None,
+        cancel,
+        on_progress: |sent, _| on_progress(offset + sent, total),
+    };
+
+    let response = CLIENT.with(|c| {
+        c.post(&url)
+            .set("Content-Type", "application/octet-stream")
+            .set("Content-Length", &part.len().to_string())
+            .send(body)
+    })?;
+
+    let part_resp: ChunkedUploadResponse = response.into_json()?;
+    if !part_resp.success {
+        return Err(anyhow!("{}", part_resp.message.unwrap_or_else(|| "Part rejected".to_string())));
+    }
+    if part_resp.received <= offset {
+        return Err(anyhow!("Server did not accept the part at {}", offset));
+    }
+    Ok(part_resp.received)
+}
+
+/// Uploads a large log in parts, resuming from whatever the server already has
+/// Returns None when the server doesn't support it and a single-shot upload is needed
+fn upload_chunked(
+    location: &Path,
+    api_endpoint: &str,
+    session_id: &str,
+    history_token: &str,
+    cancel: &CancelToken,
+    on_progress: &impl Fn(u64, u64),
+) -> Result<Option<String>> {
+    let filename = location
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let data = std::fs::read(location)?;
+    let total = data.len() as u64;
+
+    let Some(init) = init_chunked_upload(api_endpoint, session_id, history_token, &filename, total)? else {
+        return Ok(None);
+    };
+    let upload_id = init.upload_id.unwrap_or_default();
+
+    let mut offset = init.received.min(total);
+    if offset > 0 {
+        log::info!("Resuming upload of {} at {} of {} bytes", filename, offset, total);
+    }
+
+    let mut failures = 0;
+    while offset < total {
+        // The server keeps the parts it has, a later attempt resumes from there
+        if cancel.is_cancelled() {
+            return Err(anyhow!("{} at {} of {} bytes", CANCELLED_STATUS, offset, total));
+        }
+
+        let end = (offset as usize + CHUNKED_PART_SIZE).min(data.len());
+        match send_part(api_endpoint, &upload_id, offset, &data[offset as usize..end], total, cancel, on_progress) {
+            Ok(received) => {
+                offset = received.min(total);
+                failures = 0;
+            }
+            Err(e) => {
+                failures += 1;
+                if failures >= CHUNKED_PART_ATTEMPTS {
+                    return Err(anyhow!(
+                        "Upload stopped at {} of {} bytes: {}",
+                        offset,
+                        total,
+                        e
+                    ));
+                }
+                log::warn!("Part of {} failed ({}), retrying: {}", filename, failures, e);
+                if !cancel.sleep(Duration::from_secs(2 * failures as u64)) {
+                    continue;
+                }
+
+                // Part of it may have arrived before the connection dropped
+                if let Ok(Some(status)) =
+                    init_chunked_upload(api_endpoint, session_id, history_token, &filename, total)
+                {
+                    offset = status.received.min(total);
                 }
             }
-        })
-        .expect("Could not create upload thread")
+        }
+    }
+
+    let url = format!("{}?endpoint=nexus-upload-finalize", api_endpoint);
+    let response = CLIENT.with(|c| {
+        c.post(&url).send_form(&[
+            ("upload_id", upload_id.as_str()),
+            ("session_id", session_id),
+            ("history_token", history_token),
+        ])
+    })?;
+
+    let upload_resp: UploadResponse = response.into_json()?;
+    if upload_resp.success {
+        Ok(Some("Uploaded".to_string()))
+    } else {
+        Err(anyhow!("Upload failed: {}", upload_resp.message.unwrap_or_default()))
+    }
+}
+
+/// Writes an uncompressed .evtc log into a temporary .zevtc, the same zip arcdps itself writes
+fn compress_evtc(location: &Path) -> Result<PathBuf> {
+    use flate2::write::DeflateEncoder;
+    use flate2::{Compression, Crc};
+    use std::io::Write;
+
+    let data = std::fs::read(location)?;
+    let name = location
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "log.evtc".to_string());
+
+    let mut crc = Crc::new();
+    crc.update(&data);
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&data)?;
+    let compressed = encoder.finish()?;
+
+    // Common part of the local and central headers: version, flags, deflate, 1980-01-01, crc, sizes
+    let mut fields = Vec::with_capacity(26);
+    fields.extend_from_slice(&20u16.to_le_bytes());
+    fields.extend_from_slice(&0u16.to_le_bytes());
+    fields.extend_from_slice(&8u16.to_le_bytes());
+    fields.extend_from_slice(&0u16.to_le_bytes());
+    fields.extend_from_slice(&0x21u16.to_le_bytes());
+    fields.extend_from_slice(&crc.sum().to_le_bytes());
+    fields.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+    fields.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    fields.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+
+    let mut zip = Vec::with_capacity(compressed.len() + 2 * name.len() + 128);
+    zip.extend_from_slice(&0x04034b50u32.to_le_bytes());
+    zip.extend_from_slice(&fields);
+    zip.extend_from_slice(name.as_bytes());
+    zip.extend_from_slice(&compressed);
+
+    let central_offset = zip.len() as u32;
+    zip.extend_from_slice(&0x02014b50u32.to_le_bytes());
+    zip.extend_from_slice(&20u16.to_le_bytes()); // version made by
+    zip.extend_from_slice(&fields);
+    zip.extend_from_slice(&[0; 10]); // comment length, disk, attributes
+    zip.extend_from_slice(&0u32.to_le_bytes()); // local header offset
+    zip.extend_from_slice(name.as_bytes());
+    let central_size = zip.len() as u32 - central_offset;
+
+    zip.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    zip.extend_from_slice(&[0; 4]); // disk numbers
+    zip.extend_from_slice(&1u16.to_le_bytes());
+    zip.extend_from_slice(&1u16.to_le_bytes());
+    zip.extend_from_slice(&central_size.to_le_bytes());
+    zip.extend_from_slice(&central_offset.to_le_bytes());
+    zip.extend_from_slice(&0u16.to_le_bytes()); // comment length
+
+    let target = std::env::temp_dir().join(location.with_extension("zevtc").file_name().unwrap_or_default());
+    std::fs::write(&target, &zip)?;
+    log::info!(
+        "Compressed {} from {} to {} bytes before uploading",
+        name,
+        data.len(),
+        zip.len()
+    );
+    Ok(target)
+}
+
+/// Copies a .evtc.zip log under a .zevtc name, it is the same archive with a different ending
+fn rename_evtc_zip(location: &Path) -> Result<PathBuf> {
+    let name = location
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let target = std::env::temp_dir().join(format!("{}.zevtc", crate::logfile::log_stem(&name)));
+    std::fs::copy(location, &target)?;
+    Ok(target)
+}
+
+/// Uploads a log to the session, `on_progress` is called with the bytes sent and the total
+/// Large logs go up in resumable parts when the server supports it
+/// Uncompressed .evtc logs are compressed first unless turned off, .evtc.zip logs go up as .zevtc
+pub fn upload_file(
+    location: PathBuf,
+    api_endpoint: &str,
+    session_id: &str,
+    history_token: &str,
+    cancel: &CancelToken,
+    on_progress: impl Fn(u64, u64),
+) -> Result<String> {
+    crate::consent::require()?;
+
+    let settings = Settings::get();
+    let compress = settings.compress_evtc_uploads || settings.low_bandwidth_mode;
+    drop(settings);
+
+    let copy = if crate::logfile::is_uncompressed(&location) && compress {
+        Some(compress_evtc(&location)?)
+    } else if location.to_string_lossy().to_ascii_lowercase().ends_with(".evtc.zip") {
+        Some(rename_evtc_zip(&location)?)
+    } else {
+        None
+    };
+
+    if let Some(copy) = copy {
+        let result = upload_log(copy.clone(), api_endpoint, session_id, history_token, cancel, on_progress);
+        if let Err(e) = std::fs::remove_file(&copy) {
+            log::warn!("Failed to remove temporary copy {:?}: {}", copy, e);
+        }
+        return result;
+    }
+
+    upload_log(location, api_endpoint, session_id, history_token, cancel, on_progress)
 }
 
-fn upload_file(
+fn upload_log(
     location: PathBuf,
     api_endpoint: &str,
     session_id: &str,
     history_token: &str,
+    cancel: &CancelToken,
+    on_progress: impl Fn(u64, u64),
 ) -> Result<String> {
     log::info!("Uploading {}", location.display());
 
+    let size = std::fs::metadata(&location).map(|m| m.len()).unwrap_or(0);
+    if size > CHUNKED_UPLOAD_THRESHOLD && capabilities::supports(api_endpoint, Capability::ChunkedUpload) {
+        match upload_chunked(&location, api_endpoint, session_id, history_token, cancel, &on_progress)? {
+            Some(message) => return Ok(message),
+            None => log::info!("Chunked upload not used, sending the log in one piece"),
+        }
+    }
+
     let url = format!("{}?endpoint=nexus-upload", api_endpoint);
+    // Tells the server this log is the second half of a split fight
+    let continues = crate::scanning::continued_log(&location);
 
     CLIENT.with(|c| {
-        let (content_type, data) = ureq_multipart::MultipartBuilder::new()
+        let mut form = ureq_multipart::MultipartBuilder::new()
             .add_text("session_id", session_id)?
-            .add_text("history_token", history_token)?
-            .add_file("file", &location)?
-            .finish()?;
+            .add_text("history_token", history_token)?;
+        if let Some(ref previous) = continues {
+            form = form.add_text("continues", previous)?;
+        }
+        let (content_type, data) = form.add_file("file", &location)?.finish()?;
         
+        let body = ProgressReader {
+            data: &data,
+            pos: 0,
+            last_percent: None,
+            cancel,
+            on_progress,
+        };
+
+        // With the length known up front ureq sends the body as is instead of chunked
         let response = c
             .post(&url)
             .set("Content-Type", &content_type)
-            .send_bytes(&data)?;
+            .set("Content-Length", &data.len().to_string())
+            .send(body)?;
 
         let upload_resp: UploadResponse = response.into_json()?;
         
@@ -144,11 +877,74 @@ fn upload_file(
     })
 }
 
+/// Uploads a log to dps.report with the saved user token
+pub fn upload_to_dps_report(
+    location: PathBuf,
+    cancel: &CancelToken,
+    on_progress: impl Fn(u64, u64),
+) -> Result<DpsReportUpload> {
+    crate::consent::require()?;
+    log::info!("Uploading {} to dps.report", location.display());
+
+    let user_token = Settings::get().dps_report_token.clone();
+    let url = format!("{}/uploadContent?json=1&generator=ei", DPS_REPORT_URL);
+
+    let filename = location
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let response: DpsReportResponse = CLIENT.with(|c| -> Result<DpsReportResponse> {
+        let (content_type, data) = ureq_multipart::MultipartBuilder::new()
+            .add_file("file", &location)?
+            .finish()?;
+
+        let body = ProgressReader {
+            data: &data,
+            pos: 0,
+            last_percent: None,
+            cancel,
+            on_progress,
+        };
+
+        let mut request = c.post(&url);
+        if !user_token.is_empty() {
+            request = request.query("userToken", &user_token);
+        }
+        let response = request
+            .set("Content-Type", &content_type)
+            .set("Content-Length", &data.len().to_string())
+            .send(body)?;
+        Ok(response.into_json()?)
+    })?;
+
+    if let Some(error) = response.error {
+        return Err(anyhow!("dps.report: {}", error));
+    }
+    let (Some(id), Some(permalink)) = (response.id, response.permalink) else {
+        return Err(anyhow!("dps.report returned no permalink"));
+    };
+
+    // The upload answer already names the fight, no need to download the whole Elite Insights JSON
+    let fight = response.encounter.and_then(|encounter| {
+        let boss = encounter.boss.filter(|b| !b.is_empty())?;
+        Some(format!("{} ({})", boss, crate::formatting::format_duration(encounter.duration)))
+    });
+    log::info!("Uploaded {} to dps.report as {}", filename, id);
+
+    Ok(DpsReportUpload {
+        filename,
+        permalink,
+        fight,
+    })
+}
+
 pub fn delete_file(
     api_endpoint: &str,
     session_id: &str,
     filename: &str,
 ) -> Result<String> {
+    crate::consent::require()?;
     log::info!("Deleting file: {} from session: {}", filename, session_id);
 
     let url = format!("{}?endpoint=delete-upload", api_endpoint);
@@ -174,6 +970,263 @@ pub fn delete_file(
     })
 }
 
+/// Downloads the current table of known server failures
+pub fn fetch_failure_patterns(api_endpoint: &str) -> Result<Vec<crate::failures::FailurePattern>> {
+    crate::consent::require()?;
+    if !capabilities::supports(api_endpoint, Capability::FailurePatterns) {
+        return Err(capabilities::unsupported_error(Capability::FailurePatterns));
+    }
+    let url = format!("{}?endpoint=nexus-failure-patterns", api_endpoint);
+
+    let response = CLIENT.with(|c| {
+        c.get(&url)
+            .timeout(request_timeout(10))
+            .call()
+    })
+    .map_err(|e| capabilities::check(api_endpoint, Capability::FailurePatterns, e))?;
+
+    Ok(response.into_json()?)
+}
+
+/// Lists sessions created with the given token that have uploads but were never processed
+pub fn list_pending_sessions(api_endpoint: &str, history_token: &str) -> Result<Vec<PendingSession>> {
+    crate::consent::require()?;
+    if !capabilities::supports(api_endpoint, Capability::PendingSessions) {
+        return Err(capabilities::unsupported_error(Capability::PendingSessions));
+    }
+    let url = format!("{}?endpoint=nexus-pending-sessions", api_endpoint);
+
+    let response = CLIENT.with(|c| {
+        c.post(&url).send_form(&[
+            ("history_token", history_token),
+        ])
+    })
+    .map_err(|e| capabilities::check(api_endpoint, Capability::PendingSessions, e))?;
+
+    let resp: PendingSessionsResponse = response.into_json()?;
+
+    if resp.success {
+        let sessions = resp.sessions.unwrap_or_default();
+        log::info!("Server reports {} unprocessed session(s)", sessions.len());
+        Ok(sessions)
+    } else {
+        Err(anyhow!("Listing sessions failed: {}", resp.message.unwrap_or_default()))
+    }
+}
+
+/// Deletes an unprocessed session and all of its uploaded files from the server
+pub fn delete_session(
+    api_endpoint: &str,
+    session_id: &str,
+    ownership_token: &str,
+) -> Result<String> {
+    crate::consent::require()?;
+    if !capabilities::supports(api_endpoint, Capability::DeleteSession) {
+        return Err(capabilities::unsupported_error(Capability::DeleteSession));
+    }
+    log::info!("Deleting session: {}", session_id);
+
+    let url = format!("{}?endpoint=delete-session", api_endpoint);
+
+    CLIENT.with(|c| {
+        let response = c
+            .post(&url)
+            .send_form(&[
+                ("session_id", session_id),
+                ("ownership_token", ownership_token),
+            ])
+            .map_err(|e| capabilities::check(api_endpoint, Capability::DeleteSession, e))?;
+
+        let delete_resp: DeleteResponse = response.into_json()?;
+
+        if delete_resp.success {
+            Ok(delete_resp.message.unwrap_or_else(|| "Session deleted".to_string()))
+        } else {
+            let error = delete_resp.message.unwrap_or_else(|| "Unknown error".to_string());
+            Err(anyhow!("Delete failed: {}", error))
+        }
+    })
+}
+
+/// Tells the server an unprocessed session was given up so it can free the uploaded files right away
+pub fn cancel_session(
+    api_endpoint: &str,
+    session_id: &str,
+    ownership_token: &str,
+) -> Result<String> {
+    crate::consent::require()?;
+    if !capabilities::supports(api_endpoint, Capability::CancelSession) {
+        return Err(capabilities::unsupported_error(Capability::CancelSession));
+    }
+    log::info!("Cancelling session: {}", session_id);
+
+    let url = format!("{}?endpoint=cancel-session", api_endpoint);
+
+    CLIENT.with(|c| {
+        let response = c
+            .post(&url)
+            .timeout(request_timeout(10))
+            .send_form(&[
+                ("session_id", session_id),
+                ("ownership_token", ownership_token),
+            ])
+            .map_err(|e| capabilities::check(api_endpoint, Capability::CancelSession, e))?;
+
+        let cancel_resp: DeleteResponse = response.into_json()?;
+
+        if cancel_resp.success {
+            Ok(cancel_resp.message.unwrap_or_else(|| "Session cancelled".to_string()))
+        } else {
+            let error = cancel_resp.message.unwrap_or_else(|| "Unknown error".to_string());
+            Err(anyhow!("Cancel failed: {}", error))
+        }
+    })
+}
+
+/// Session ids and ownership tokens of the active session and its split parts
+pub fn active_sessions() -> Vec<(String, String)> {
+    let mut sessions = vec![(
+        STATE.session_id.lock().unwrap().clone(),
+        STATE.ownership_token.lock().unwrap().clone(),
+    )];
+    for split in STATE.split_sessions.lock().unwrap().iter() {
+        sessions.push((split.session_id.clone(), split.ownership_token.clone()));
+    }
+    sessions.retain(|(session_id, ownership_token)| !session_id.is_empty() && !ownership_token.is_empty());
+    sessions.dedup_by(|a, b| a.0 == b.0);
+    sessions
+}
+
+/// Cancels the given sessions on the server, best effort
+/// Whatever fails is removed by the server's own cleanup later
+pub fn cancel_sessions(api_endpoint: &str, sessions: &[(String, String)]) {
+    for (session_id, ownership_token) in sessions {
+        if let Err(e) = cancel_session(api_endpoint, session_id, ownership_token) {
+            log::warn!("Failed to cancel session {} on the server: {}", session_id, e);
+        }
+    }
+}
+
+/// Cancels the active session on the server before it is cleared locally
+pub fn cancel_active_session() {
+    cancel_sessions(&session_endpoint(), &active_sessions());
+}
+
+/// A session left without uploads at unload, cancelled on the server on the next start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AbandonedSession {
+    api_endpoint: String,
+    session_id: String,
+    #[serde(serialize_with = "crate::settings::protected::serialize", deserialize_with = "crate::settings::protected::deserialize")]
+    ownership_token: String,
+}
+
+fn read_abandoned(path: &Path) -> Vec<AbandonedSession> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// On unload, queues a session that was created but never got a file uploaded for cancelling
+/// Sessions with uploads are kept so they can be resumed on the next start
+/// Only written to disk here, unload must not wait on the network
+pub fn cancel_abandoned_session(path: impl AsRef<Path>) {
+    let state = *STATE.processing_state.lock().unwrap();
+    if !matches!(state, ProcessingState::Uploading | ProcessingState::Idle) {
+        return;
+    }
+    if !STATE.uploaded_files.lock().unwrap().is_empty() {
+        return;
+    }
+    let sessions = active_sessions();
+    if sessions.is_empty() {
+        return;
+    }
+
+    let path = path.as_ref();
+    let api_endpoint = session_endpoint();
+    let mut queued = read_abandoned(path);
+    queued.extend(sessions.into_iter().map(|(session_id, ownership_token)| AbandonedSession {
+        api_endpoint: api_endpoint.clone(),
+        session_id,
+        ownership_token,
+    }));
+
+    log::info!("Queued abandoned session for cancelling on the next start");
+    let result = serde_json::to_string(&queued)
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| Ok(std::fs::write(path, contents)?));
+    if let Err(e) = result {
+        log::warn!("Failed to queue abandoned session: {}", e);
+    }
+}
+
+/// Cancels the sessions queued at the last unload, in the background
+/// Whatever fails is removed by the server's own cleanup later
+pub fn cancel_queued_sessions(path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if !path.exists() || !crate::consent::is_granted() {
+        return;
+    }
+
+    let queued = read_abandoned(path);
+    if let Err(e) = std::fs::remove_file(path) {
+        log::warn!("Failed to remove the abandoned session queue: {}", e);
+    }
+    if queued.is_empty() {
+        return;
+    }
+
+    crate::threads::spawn("cancel-abandoned", move || {
+        let _activity = crate::threads::activity("Cancelling abandoned sessions");
+        for session in queued {
+            if crate::cancel::is_shutting_down() {
+                return;
+            }
+            if let Err(e) = cancel_session(&session.api_endpoint, &session.session_id, &session.ownership_token) {
+                log::warn!("Failed to cancel abandoned session {}: {}", session.session_id, e);
+            }
+        }
+    });
+}
+
+/// Deletes a processed session and its reports from the server
+pub fn delete_report(
+    api_endpoint: &str,
+    session_id: &str,
+    history_token: &str,
+    ownership_token: &str,
+) -> Result<String> {
+    crate::consent::require()?;
+    if !capabilities::supports(api_endpoint, Capability::DeleteReport) {
+        return Err(capabilities::unsupported_error(Capability::DeleteReport));
+    }
+    log::info!("Deleting report: {}", session_id);
+
+    let url = format!("{}?endpoint=delete-report", api_endpoint);
+
+    CLIENT.with(|c| {
+        let response = c
+            .post(&url)
+            .send_form(&[
+                ("session_id", session_id),
+                ("history_token", history_token),
+                ("ownership_token", ownership_token),
+            ])
+            .map_err(|e| capabilities::check(api_endpoint, Capability::DeleteReport, e))?;
+
+        let delete_resp: DeleteResponse = response.into_json()?;
+
+        if delete_resp.success {
+            Ok(delete_resp.message.unwrap_or_else(|| "Report deleted".to_string()))
+        } else {
+            let error = delete_resp.message.unwrap_or_else(|| "Unknown error".to_string());
+            Err(anyhow!("Delete failed: {}", error))
+        }
+    })
+}
+
 pub fn start_processing(
     api_endpoint: &str,
     session_id: &str,
@@ -183,6 +1236,7 @@ pub fn start_processing(
     enable_legacy_parser: bool,
     dps_report_token: &str,
 ) -> Result<String> {
+    crate::consent::require()?;
     let url = format!("{}?endpoint=nexus-process", api_endpoint);
     
     let final_guild_name = if guild_name.trim().is_empty() {
@@ -214,6 +1268,7 @@ pub fn start_processing(
     let resp: serde_json::Value = response.into_json()?;
     
     log::info!("Processing API response: {:?}", resp);
+    crate::crash::record_response("nexus-process", resp["message"].as_str().unwrap_or("no message"));
     
     if resp["success"].as_bool().unwrap_or(false) {
         let message = resp["message"].as_str().unwrap_or("Processing started").to_string();
@@ -224,37 +1279,45 @@ pub fn start_processing(
     }
 }
 
-pub fn check_status(api_endpoint: &str, session_id: &str) -> Result<(String, Option<Vec<String>>, f32, Option<String>)> {
+/// Status, report URLs once complete, progress, phase message and the JSON/CSV artifacts once complete
+pub fn check_status(
+    api_endpoint: &str,
+    session_id: &str,
+) -> Result<(String, Option<Vec<String>>, f32, Option<String>, Vec<ReportArtifact>)> {
+    crate::consent::require()?;
     let url = format!("{}?endpoint=process-status&session_id={}", api_endpoint, session_id);
     
     let response = CLIENT.with(|c| c.get(&url).call())?;
     let status_resp: StatusResponse = response.into_json()?;
     
     log::info!("Status: {} - Progress: {:?}", status_resp.status, status_resp.progress);
+    crate::crash::record_response("process-status", &status_resp.status);
     
     // Handle queued status
     if status_resp.status == "queued" {
         let position = status_resp.queue_position.unwrap_or(0);
         let per_user_minutes = status_resp.avg_service_time.unwrap_or(1.0);
         let estimated_minutes = (position as f32 * per_user_minutes).round() as i32;
+        let per_user = crate::formatting::plural(per_user_minutes.round().max(0.0) as u64, "minute", "minutes");
         
         let wait_text = if position <= 0 {
-            format!("Starting soon (~{:.0} minute)", per_user_minutes)
-        } else if estimated_minutes == 1 {
-            "Estimated wait: ~1 minute".to_string()
+            format!("Starting soon (~{})", per_user)
         } else {
-            format!("Estimated wait: ~{} minutes", estimated_minutes)
+            format!(
+                "Estimated wait: ~{}",
+                crate::formatting::plural(estimated_minutes.max(0) as u64, "minute", "minutes")
+            )
         };
         
         let phase = Some(format!(
-            "Queued for processing (Position: {}) - {} — typically ~{:.0} minute per user",
-            position, wait_text, per_user_minutes
+            "Queued for processing (Position: {}) - {} — typically ~{} per user",
+            position, wait_text, per_user
         ));
         
         log::info!("In queue at position {} - estimated wait: {} minutes", position, estimated_minutes);
         
         // Return queued status with 0% progress and the queue message
-        return Ok((status_resp.status, None, 0.0, phase));
+        return Ok((status_resp.status, None, 0.0, phase, Vec::new()));
     }
     
     let raw_progress = status_resp.progress.unwrap_or(0.0);
@@ -279,7 +1342,7 @@ pub fn check_status(api_endpoint: &str, session_id: &str) -> Result<(String, Opt
     
     // Get legacy parser setting from STATE (do this ONCE at the start)
     let settings = crate::settings::Settings::get();
-    let enable_legacy_parser = settings.enable_legacy_parser;
+    let enable_legacy_parser = settings.legacy_parser_enabled();
     drop(settings);
     
     // Check if we've already set initial estimate by checking STATE instead of thread_local
@@ -356,6 +1419,21 @@ pub fn check_status(api_endpoint: &str, session_id: &str) -> Result<(String, Opt
         get_phase_message(&c, progress)
     });
     
+    let artifacts = if status_resp.status == "complete" {
+        status_resp
+            .files
+            .iter()
+            .flatten()
+            .filter(|f| is_artifact(&f.name))
+            .map(|f| ReportArtifact {
+                name: f.name.clone(),
+                url: f.url.clone(),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     let report_urls = if status_resp.status == "complete" {
         status_resp.files
             .map(|files| {
@@ -373,7 +1451,45 @@ pub fn check_status(api_endpoint: &str, session_id: &str) -> Result<(String, Opt
         None
     };
     
-    Ok((status_resp.status, report_urls, progress, phase))
+    Ok((status_resp.status, report_urls, progress, phase, artifacts))
+}
+
+/// Whether a file the server produced is a JSON or CSV artifact worth listing
+fn is_artifact(name: &str) -> bool {
+    let name = name.to_lowercase();
+    [".json", ".json.gz", ".csv"].iter().any(|ext| name.ends_with(ext))
+}
+
+/// Folder report files are downloaded to, the configured one or the user's Downloads folder
+pub fn artifact_download_dir() -> PathBuf {
+    let configured = Settings::get().artifact_download_dir.trim().to_string();
+    if !configured.is_empty() {
+        return PathBuf::from(configured);
+    }
+    std::env::var_os("USERPROFILE")
+        .map(|home| PathBuf::from(home).join("Downloads"))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Downloads an artifact into the folder under its own name, returns where it was saved
+pub fn download_artifact(artifact: &ReportArtifact, folder: &Path) -> Result<PathBuf> {
+    crate::consent::require()?;
+
+    // Only the last part of the name, so a name with folders can't write outside the target
+    let file_name = artifact
+        .name
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|n| !n.is_empty() && *n != "..")
+        .ok_or_else(|| anyhow!("Invalid file name: {}", artifact.name))?;
+    std::fs::create_dir_all(folder)?;
+    let target = folder.join(file_name);
+
+    let response = CLIENT.with(|c| c.get(&artifact.url).timeout(request_timeout(120)).call())?;
+    let mut file = std::fs::File::create(&target)?;
+    let bytes = std::io::copy(&mut response.into_reader(), &mut file)?;
+    log::info!("Downloaded {} ({} bytes) to {:?}", artifact.name, bytes, target);
+    Ok(target)
 }
 
 fn extract_time_estimate_from_log(message: &str) -> Option<u32> {
@@ -1,17 +1,88 @@
+use chrono::NaiveDateTime;
 use nexus::imgui::{ChildWindow, Ui};
+use serde::{Deserialize, Serialize};
 
+use crate::formatting::parse_log_timestamp;
 use crate::settings::Settings;
 use crate::state::{ProcessingState, STATE};
 use crate::upload;
 
-#[derive(Debug, Clone)]
+thread_local! {
+    static SPLIT_BOUNDARY: std::cell::Cell<usize> = const { std::cell::Cell::new(1) };
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UploadedFileInfo {
     pub filename: String,
     pub size: String,
     pub metadata: Option<FileMetadata>,
+    /// Server session the file was uploaded to
+    pub session_id: String,
+    /// How long the upload took, None while it runs or for files from before this was measured
+    #[serde(default)]
+    pub upload_timing: Option<crate::upload_stats::UploadTiming>,
 }
 
-#[derive(Debug, Clone)]
+impl UploadedFileInfo {
+    /// Describes a log uploaded to the given session
+    pub fn from_log(log: &crate::logfile::LogFile, session_id: String) -> Self {
+        Self {
+            filename: log.filename.clone(),
+            size: format!("{:.2} MB", log.size as f64 / 1024.0 / 1024.0),
+            metadata: Some(FileMetadata {
+                map_abbr: log.map_type.display_name(),
+                map_color: log.map_type.color(),
+                recorder: log.recorder.clone(),
+                commander: log.commander.clone(),
+                timestamp: crate::formatting::format_timestamp(&log.filename),
+            }),
+            session_id,
+            upload_timing: log.upload_timing,
+        }
+    }
+}
+
+/// One server session per part when reports are split by map or by time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitSession {
+    pub label: String,
+    /// Whether this part was created per map (as opposed to a time slice)
+    pub by_map: bool,
+    pub session_id: String,
+    #[serde(serialize_with = "crate::settings::protected::serialize", deserialize_with = "crate::settings::protected::deserialize")]
+    pub ownership_token: String,
+    pub report_urls: Vec<String>,
+    pub progress: f32,
+    pub complete: bool,
+    pub failed: bool,
+    /// Processing was started on the server, a retry after another part failed to start skips it
+    #[serde(default)]
+    pub started: bool,
+}
+
+/// Returns the part a report URL belongs to when the current session is split
+/// For dps.report sessions this is the fight the permalink shows
+pub fn split_label_for_report_url(url: &str) -> Option<String> {
+    let split_label = STATE
+        .split_sessions
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|s| s.report_urls.iter().any(|u| u == url))
+        .map(|s| s.label.clone());
+
+    split_label.or_else(|| {
+        STATE
+            .dps_report_uploads
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|u| u.permalink == url)
+            .and_then(|u| u.fight.clone())
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FileMetadata {
     pub map_abbr: String,
     pub map_color: [f32; 4],
@@ -46,6 +117,14 @@ pub fn render_upload_review(ui: &Ui) {
     
     let file_count = uploaded_files.len();
     ui.text(format!("Total files: {}", file_count));
+
+    let parts: Vec<String> = STATE.split_sessions.lock().unwrap().iter().map(|s| s.label.clone()).collect();
+    if !parts.is_empty() {
+        ui.text_colored(
+            [0.7, 0.9, 1.0, 1.0],
+            &format!("Separate reports: {}", parts.join(", ")),
+        );
+    }
     
     ui.spacing();
     
@@ -55,7 +134,8 @@ pub fn render_upload_review(ui: &Ui) {
         if file_count > 0 {
             if ui.button("Start Processing") {
                 log::info!("Starting processing for {} files", file_count);
-                std::thread::spawn(|| {
+                crate::threads::spawn("start-processing", || {
+                    let _activity = crate::threads::activity("Starting processing");
                     start_processing_wrapper();
                 });
             }
@@ -90,6 +170,10 @@ pub fn render_upload_review(ui: &Ui) {
             *STATE.show_upload_review.lock().unwrap() = false;
             *STATE.show_log_selection.lock().unwrap() = true;
         }
+
+        ui.same_line();
+
+        render_time_split(ui, &uploaded_files);
         
         ui.spacing();
         ui.separator();
@@ -98,7 +182,8 @@ pub fn render_upload_review(ui: &Ui) {
         // Cancel button - simplified, no popup
         if ui.button("Cancel") {
             log::info!("User cancelled upload session");
-            std::thread::spawn(|| {
+            crate::threads::spawn("session-cancel", || {
+                crate::upload::cancel_active_session();
                 clear_session();
                 *STATE.show_upload_review.lock().unwrap() = false;
                 *STATE.show_token_input.lock().unwrap() = true;
@@ -109,6 +194,271 @@ pub fn render_upload_review(ui: &Ui) {
     }
 }
 
+/// Renders the "Split by Time" button and its popup
+fn render_time_split(ui: &Ui, files: &[UploadedFileInfo]) {
+    let timed = timed_files(files);
+    let already_split = !STATE.split_sessions.lock().unwrap().is_empty();
+    let busy = *STATE.time_split_busy.lock().unwrap();
+
+    if already_split || busy || timed.len() < 2 {
+        let _style = ui.push_style_color(nexus::imgui::StyleColor::Button, [0.3, 0.3, 0.3, 0.5]);
+        let _style2 = ui.push_style_color(nexus::imgui::StyleColor::ButtonHovered, [0.3, 0.3, 0.3, 0.5]);
+        let _style3 = ui.push_style_color(nexus::imgui::StyleColor::ButtonActive, [0.3, 0.3, 0.3, 0.5]);
+        let _style4 = ui.push_style_color(nexus::imgui::StyleColor::Text, [0.5, 0.5, 0.5, 0.5]);
+
+        ui.button("Split by Time");
+
+        if ui.is_item_hovered() {
+            if busy {
+                ui.tooltip_text("Splitting session...");
+            } else if already_split {
+                ui.tooltip_text("This session is already split into separate reports");
+            } else {
+                ui.tooltip_text("Needs at least two logs to split");
+            }
+        }
+    } else if ui.button("Split by Time") {
+        SPLIT_BOUNDARY.set(largest_gap_boundary(&timed));
+        ui.open_popup("split_by_time");
+    }
+
+    let message = STATE.time_split_message.lock().unwrap().clone();
+    if !message.is_empty() {
+        ui.text_colored([0.7, 0.9, 1.0, 1.0], &message);
+    }
+
+    ui.popup_modal("split_by_time")
+        .always_auto_resize(true)
+        .build(ui, || {
+            ui.text("Split this session into two reports by time.");
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                "The boundary defaults to the largest gap between logs.",
+            );
+            ui.spacing();
+
+            if timed.len() < 2 {
+                ui.text_colored([1.0, 1.0, 0.0, 1.0], "Not enough logs to split");
+                if ui.button("Close") {
+                    ui.close_current_popup();
+                }
+                return;
+            }
+
+            if ui.button("Largest Gap") {
+                SPLIT_BOUNDARY.set(largest_gap_boundary(&timed));
+            }
+            ui.same_line();
+            if ui.button("Halfway") {
+                SPLIT_BOUNDARY.set(halfway_boundary(&timed));
+            }
+
+            let mut first_count = SPLIT_BOUNDARY.get().clamp(1, timed.len() - 1) as i32;
+            ui.text("Logs in first report:");
+            ui.same_line();
+            ui.set_next_item_width(100.0);
+            if ui.input_int("##split_boundary", &mut first_count).build() {
+                first_count = first_count.clamp(1, timed.len() as i32 - 1);
+            }
+            let boundary = first_count as usize;
+            SPLIT_BOUNDARY.set(boundary);
+
+            let gap_seconds = (timed[boundary].0 - timed[boundary - 1].0).num_seconds();
+            ui.text_colored(
+                [0.7, 0.7, 0.7, 1.0],
+                &format!(
+                    "Gap at boundary: {}",
+                    crate::formatting::format_duration(gap_seconds.max(0) as u64)
+                ),
+            );
+
+            let (first, second) = timed.split_at(boundary);
+            let first_label = part_label(1, first);
+            let second_label = part_label(2, second);
+
+            ui.spacing();
+            ui.text(format!("{}: {} log(s)", first_label, first.len()));
+            ui.text(format!("{}: {} log(s)", second_label, second.len()));
+
+            let untimed = files.len() - timed.len();
+            if untimed > 0 {
+                ui.text_colored(
+                    [1.0, 0.8, 0.2, 1.0],
+                    &format!("{} log(s) without a timestamp stay in the first report", untimed),
+                );
+            }
+
+            ui.spacing();
+
+            if ui.button("Split") {
+                ui.close_current_popup();
+                let second_files: Vec<String> = second.iter().map(|(_, f)| f.clone()).collect();
+                crate::threads::spawn_tracked("time-split", move || {
+                    let _activity = crate::threads::activity("Splitting session");
+                    split_session_by_time(second_files, first_label, second_label);
+                });
+            }
+
+            ui.same_line();
+
+            if ui.button("Cancel") {
+                ui.close_current_popup();
+            }
+        });
+}
+
+/// Returns the uploaded files that carry a timestamp, oldest first
+fn timed_files(files: &[UploadedFileInfo]) -> Vec<(NaiveDateTime, String)> {
+    let mut timed: Vec<(NaiveDateTime, String)> = files
+        .iter()
+        .filter_map(|f| parse_log_timestamp(&f.filename).map(|t| (t, f.filename.clone())))
+        .collect();
+    timed.sort();
+    timed
+}
+
+/// Index of the first log after the largest gap between consecutive logs
+fn largest_gap_boundary(timed: &[(NaiveDateTime, String)]) -> usize {
+    (1..timed.len())
+        .max_by_key(|&i| timed[i].0 - timed[i - 1].0)
+        .unwrap_or(1)
+}
+
+/// Index of the first log in the second half of the raid's time span
+fn halfway_boundary(timed: &[(NaiveDateTime, String)]) -> usize {
+    let (Some(first), Some(last)) = (timed.first(), timed.last()) else {
+        return 1;
+    };
+    let midpoint = first.0 + (last.0 - first.0) / 2;
+    timed
+        .iter()
+        .position(|(t, _)| *t > midpoint)
+        .unwrap_or(timed.len() - 1)
+        .clamp(1, timed.len().saturating_sub(1).max(1))
+}
+
+/// Label for one part of a time split, e.g. "Part 1 (20:05-21:40)"
+fn part_label(part: usize, timed: &[(NaiveDateTime, String)]) -> String {
+    match (timed.first(), timed.last()) {
+        (Some(first), Some(last)) => format!(
+            "Part {} ({}-{})",
+            part,
+            first.0.format("%H:%M"),
+            last.0.format("%H:%M")
+        ),
+        _ => format!("Part {}", part),
+    }
+}
+
+/// Moves the given files into a new session so the current one is processed as two reports
+/// Files are uploaded to the new session before they are removed from the current one
+fn split_session_by_time(second_files: Vec<String>, first_label: String, second_label: String) {
+    let api_endpoint = upload::session_endpoint();
+    let history_token = Settings::get().history_token.clone();
+
+    let session_id = STATE.session_id.lock().unwrap().clone();
+    let ownership_token = STATE.ownership_token.lock().unwrap().clone();
+    if session_id.is_empty() {
+        log::error!("Cannot split session: no active session");
+        return;
+    }
+
+    // Every moved log has to be re-uploaded, so the local file must still exist
+    let paths: Vec<(String, std::path::PathBuf)> = {
+        let logs = STATE.logs.lock().unwrap();
+        second_files
+            .iter()
+            .filter_map(|name| {
+                logs.iter()
+                    .find(|l| &l.filename == name)
+                    .map(|l| (name.clone(), l.path.clone()))
+            })
+            .collect()
+    };
+    if paths.len() != second_files.len() {
+        *STATE.time_split_message.lock().unwrap() =
+            "Split failed: some logs are no longer in the log list".to_string();
+        return;
+    }
+
+    *STATE.time_split_busy.lock().unwrap() = true;
+    *STATE.time_split_message.lock().unwrap() =
+        format!("Moving {} log(s) into a second report...", paths.len());
+
+    let result = move_files_to_new_session(&api_endpoint, &history_token, &session_id, &paths);
+
+    match result {
+        Ok((new_session_id, new_ownership_token)) => {
+            for file in STATE.uploaded_files.lock().unwrap().iter_mut() {
+                if second_files.contains(&file.filename) {
+                    file.session_id = new_session_id.clone();
+                } else {
+                    file.session_id = session_id.clone();
+                }
+            }
+
+            *STATE.split_sessions.lock().unwrap() = vec![
+                new_split_session(first_label, session_id, ownership_token),
+                new_split_session(second_label, new_session_id, new_ownership_token),
+            ];
+
+            log::info!("Session split into two reports by time");
+            *STATE.time_split_message.lock().unwrap() = "Session split into two reports".to_string();
+        }
+        Err(e) => {
+            log::error!("Failed to split session: {}", e);
+            *STATE.time_split_message.lock().unwrap() = format!("Split failed: {}", e);
+        }
+    }
+
+    *STATE.time_split_busy.lock().unwrap() = false;
+}
+
+/// Uploads the files to a fresh session, then removes them from the current one
+/// If an upload fails the new session is discarded and the current one is left untouched
+fn move_files_to_new_session(
+    api_endpoint: &str,
+    history_token: &str,
+    session_id: &str,
+    paths: &[(String, std::path::PathBuf)],
+) -> anyhow::Result<(String, String)> {
+    let (new_session_id, new_ownership_token) = upload::create_session(api_endpoint, history_token)?;
+    let cancel = crate::cancel::upload_token();
+
+    for (name, path) in paths.iter() {
+        if let Err(e) = upload::upload_file(path.clone(), api_endpoint, &new_session_id, history_token, &cancel, |_, _| {}) {
+            if let Err(cleanup_err) =
+                upload::delete_session(api_endpoint, &new_session_id, &new_ownership_token)
+            {
+                log::warn!("Failed to discard new session: {}", cleanup_err);
+            }
+            return Err(anyhow::anyhow!("{}: {}", name, e));
+        }
+    }
+
+    for (name, _) in paths.iter() {
+        if let Err(e) = upload::delete_file(api_endpoint, session_id, name) {
+            log::warn!("Failed to remove {} from the first part: {}", name, e);
+        }
+    }
+
+    Ok((new_session_id, new_ownership_token))
+}
+
+fn new_split_session(label: String, session_id: String, ownership_token: String) -> SplitSession {
+    SplitSession {
+        label,
+        by_map: false,
+        session_id,
+        ownership_token,
+        report_urls: Vec::new(),
+        progress: 0.0,
+        complete: false,
+        failed: false,
+        started: false,
+    }
+}
+
 /// Renders a single uploaded file item with delete button
 fn render_uploaded_file_item(ui: &Ui, file: &UploadedFileInfo) {
     let line_height = ui.text_line_height_with_spacing();
@@ -150,6 +500,10 @@ fn render_uploaded_file_item(ui: &Ui, file: &UploadedFileInfo) {
     
     // Size
     ui.text_colored([0.7, 0.7, 0.7, 1.0], &format!("({})", file.size));
+    if let Some(timing) = file.upload_timing {
+        ui.same_line();
+        ui.text_colored([0.5, 0.7, 0.9, 1.0], &timing.summary());
+    }
     
     // Second line - metadata
     if let Some(ref meta) = file.metadata {
@@ -179,7 +533,7 @@ fn render_uploaded_file_item(ui: &Ui, file: &UploadedFileInfo) {
     let delete_id = format!("Delete##{}", file.filename);
     if ui.small_button(&delete_id) {
         log::info!("Deleting file: {}", file.filename);
-        std::thread::spawn({
+        crate::threads::spawn("file-delete", {
             let filename = file.filename.clone();
             move || {
                 delete_uploaded_file(&filename);
@@ -191,15 +545,7 @@ fn render_uploaded_file_item(ui: &Ui, file: &UploadedFileInfo) {
 }
 
 /// Wrapper to start processing with proper state management
-fn start_processing_wrapper() {
-    let settings = Settings::get();
-    let api_endpoint = settings.api_endpoint.clone();
-    let history_token = settings.history_token.clone();
-    let guild_name = settings.guild_name.clone();
-    let enable_legacy = settings.enable_legacy_parser;
-    let dps_report_token = settings.dps_report_token.clone();
-    drop(settings);
-    
+pub fn start_processing_wrapper() {
     let session_id = STATE.session_id.lock().unwrap().clone();
     let ownership_token = STATE.ownership_token.lock().unwrap().clone();
     
@@ -212,15 +558,7 @@ fn start_processing_wrapper() {
     *STATE.processing_time_estimate.lock().unwrap() = None;
     *STATE.processing_time_estimate_start.lock().unwrap() = None;
     
-    match upload::start_processing(
-        &api_endpoint,
-        &session_id,
-        &history_token,
-        &ownership_token,
-        &guild_name,
-        enable_legacy,
-        &dps_report_token,
-    ) {
+    match start_session_processing() {
         Ok(message) => {
             log::info!("Processing started: {}", message);
             *STATE.processing_state.lock().unwrap() = ProcessingState::Processing;
@@ -238,13 +576,98 @@ fn start_processing_wrapper() {
     }
 }
 
-/// Deletes an uploaded file from the server session
-fn delete_uploaded_file(filename: &str) {
+/// Starts server-side processing for the current session
+/// When reports are split every part is started, named after its label
+pub fn start_session_processing() -> anyhow::Result<String> {
+    let api_endpoint = upload::session_endpoint();
     let settings = Settings::get();
-    let api_endpoint = settings.api_endpoint.clone();
+    let history_token = settings.history_token.clone();
+    let guild_name = settings.guild_name.clone();
+    let enable_legacy = settings.legacy_parser_enabled();
+    let dps_report_token = settings.forwarded_dps_report_token().to_string();
     drop(settings);
+
+    log::info!("Starting processing with guild name: '{}', legacy parser: {}", guild_name, enable_legacy);
+
+    let split_sessions = STATE.split_sessions.lock().unwrap().clone();
+    if split_sessions.is_empty() {
+        let session_id = STATE.session_id.lock().unwrap().clone();
+        let ownership_token = STATE.ownership_token.lock().unwrap().clone();
+
+        return upload::start_processing(
+            &api_endpoint,
+            &session_id,
+            &history_token,
+            &ownership_token,
+            &guild_name,
+            enable_legacy,
+            &dps_report_token,
+        );
+    }
+
+    let mut messages = Vec::new();
+    // Parts still processing from an earlier attempt are left running, failed ones start again
+    for split_session in split_sessions.iter().filter(|s| !s.complete && !(s.started && !s.failed)) {
+        let part_guild_name = if guild_name.trim().is_empty() {
+            format!("WvW Insights Parser (Nexus) - {}", split_session.label)
+        } else {
+            format!("{} - {}", guild_name, split_session.label)
+        };
+
+        let message = match upload::start_processing(
+            &api_endpoint,
+            &split_session.session_id,
+            &history_token,
+            &split_session.ownership_token,
+            &part_guild_name,
+            enable_legacy,
+            &dps_report_token,
+        ) {
+            Ok(message) => message,
+            Err(e) if messages.is_empty() => {
+                return Err(anyhow::anyhow!("{} session: {}", split_session.label, e));
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "{} session: {} ({} already started, retrying starts only the rest)",
+                    split_session.label,
+                    e,
+                    crate::formatting::plural(messages.len() as u64, "part", "parts")
+                ));
+            }
+        };
+        messages.push(format!("{}: {}", split_session.label, message));
+
+        // Fresh run of this part, forget results of a previous attempt
+        if let Some(part) = STATE
+            .split_sessions
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|s| s.session_id == split_session.session_id)
+        {
+            part.started = true;
+            part.failed = false;
+            part.progress = 0.0;
+        }
+    }
+
+    Ok(messages.join(", "))
+}
+
+/// Deletes an uploaded file from the server session
+fn delete_uploaded_file(filename: &str) {
+    let api_endpoint = upload::session_endpoint();
     
-    let session_id = STATE.session_id.lock().unwrap().clone();
+    // Split sessions keep each file in its own map session
+    let session_id = STATE
+        .uploaded_files
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|f| f.filename == filename && !f.session_id.is_empty())
+        .map(|f| f.session_id.clone())
+        .unwrap_or_else(|| STATE.session_id.lock().unwrap().clone());
     
     if session_id.is_empty() {
         log::error!("Cannot delete file: no active session");
@@ -258,7 +681,13 @@ fn delete_uploaded_file(filename: &str) {
             // Remove from local tracking
             let mut uploaded_files = STATE.uploaded_files.lock().unwrap();
             uploaded_files.retain(|f| f.filename != filename);
+            let session_empty = !uploaded_files.iter().any(|f| f.session_id == session_id);
             drop(uploaded_files);
+
+            // A map without files left has nothing to process
+            if session_empty {
+                STATE.split_sessions.lock().unwrap().retain(|s| s.session_id != session_id);
+            }
             
             // Also update the log status
             let mut logs = STATE.logs.lock().unwrap();
@@ -278,6 +707,8 @@ fn clear_session() {
     *STATE.session_id.lock().unwrap() = String::new();
     *STATE.ownership_token.lock().unwrap() = String::new();
     *STATE.uploaded_files.lock().unwrap() = Vec::new();
+    STATE.split_sessions.lock().unwrap().clear();
+    STATE.time_split_message.lock().unwrap().clear();
     *STATE.processing_state.lock().unwrap() = ProcessingState::Idle;
     
     // Reset all log statuses
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, File};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+/// Uploads kept individually, older ones only count towards the totals
+const MAX_RECENT_UPLOADS: usize = 500;
+
+/// How long one file took to upload
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UploadTiming {
+    /// Bytes sent, compressed copies count with their compressed size
+    pub bytes: u64,
+    pub millis: u64,
+}
+
+impl UploadTiming {
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.bytes as f64 / (self.millis.max(1) as f64 / 1000.0)
+    }
+
+    /// "12.3 s at 4.56 MB/s"
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.1} s at {:.2} MB/s",
+            self.millis as f64 / 1000.0,
+            self.bytes_per_sec() / 1024.0 / 1024.0
+        )
+    }
+}
+
+/// A finished upload with when it finished (unix seconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSample {
+    pub finished: u64,
+    pub bytes: u64,
+    pub millis: u64,
+}
+
+/// Upload speeds across sessions, to tell a slow connection from a slow server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadStats {
+    #[serde(default)]
+    pub uploads: u64,
+    #[serde(default)]
+    pub bytes: u64,
+    #[serde(default)]
+    pub millis: u64,
+    /// Fastest and slowest single upload in bytes per second
+    #[serde(default)]
+    pub fastest: Option<f64>,
+    #[serde(default)]
+    pub slowest: Option<f64>,
+    /// Latest uploads, oldest first
+    #[serde(default)]
+    pub recent: Vec<UploadSample>,
+}
+
+static UPLOAD_STATS: Mutex<UploadStats> = Mutex::new(UploadStats {
+    uploads: 0,
+    bytes: 0,
+    millis: 0,
+    fastest: None,
+    slowest: None,
+    recent: Vec::new(),
+});
+
+impl UploadStats {
+    pub fn get() -> MutexGuard<'static, Self> {
+        UPLOAD_STATS.lock().unwrap()
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            let stats: Self = serde_json::from_str(&contents)?;
+            log::info!("Loaded upload statistics for {} uploads", stats.uploads);
+            *UPLOAD_STATS.lock().unwrap() = stats;
+        }
+        Ok(())
+    }
+
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let prefix = path.parent().unwrap();
+        create_dir_all(prefix)?;
+        let mut file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, timing: UploadTiming) {
+        let speed = timing.bytes_per_sec();
+        self.uploads += 1;
+        self.bytes += timing.bytes;
+        self.millis += timing.millis;
+        self.fastest = Some(self.fastest.map_or(speed, |s| s.max(speed)));
+        self.slowest = Some(self.slowest.map_or(speed, |s| s.min(speed)));
+
+        self.recent.push(UploadSample {
+            finished: chrono::Utc::now().timestamp() as u64,
+            bytes: timing.bytes,
+            millis: timing.millis,
+        });
+        if self.recent.len() > MAX_RECENT_UPLOADS {
+            let excess = self.recent.len() - MAX_RECENT_UPLOADS;
+            self.recent.drain(..excess);
+        }
+    }
+
+    /// Average speed over all uploads in bytes per second, None before the first upload
+    pub fn average_bytes_per_sec(&self) -> Option<f64> {
+        (self.millis > 0).then(|| self.bytes as f64 / (self.millis as f64 / 1000.0))
+    }
+
+    pub fn reset(&mut self) {
+        *self = UploadStats {
+            uploads: 0,
+            bytes: 0,
+            millis: 0,
+            fastest: None,
+            slowest: None,
+            recent: Vec::new(),
+        };
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("finished,bytes,seconds,mb_per_sec\n");
+        for sample in &self.recent {
+            let finished = chrono::DateTime::from_timestamp(sample.finished as i64, 0)
+                .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            let timing = UploadTiming {
+                bytes: sample.bytes,
+                millis: sample.millis,
+            };
+            csv.push_str(&format!(
+                "{},{},{:.1},{:.2}\n",
+                finished,
+                sample.bytes,
+                sample.millis as f64 / 1000.0,
+                timing.bytes_per_sec() / 1024.0 / 1024.0
+            ));
+        }
+        csv
+    }
+}
+
+/// Writes the recent uploads as CSV next to the settings, returns where it went
+pub fn export_csv() -> Result<std::path::PathBuf> {
+    let csv = UploadStats::get().to_csv();
+    let path = crate::upload_stats_path().with_extension("csv");
+    std::fs::write(&path, csv)?;
+    log::info!("Exported upload statistics to {:?}", path);
+    Ok(path)
+}
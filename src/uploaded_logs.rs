@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{create_dir_all, File};
 use std::path::Path;
 use std::sync::{LazyLock, Mutex, MutexGuard};
@@ -8,6 +8,13 @@ use std::sync::{LazyLock, Mutex, MutexGuard};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadedLogs {
     pub filenames: HashSet<String>,
+    /// Content hash of each uploaded log by filename, so renamed or copied files are recognized
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
+    /// Logs dropped from tracking after 72 hours, by filename with when they were archived (unix seconds)
+    /// Kept for the configured number of days so older uploads can still be looked up
+    #[serde(default)]
+    pub archive: BTreeMap<String, u64>,
 }
 
 impl UploadedLogs {
@@ -48,29 +55,66 @@ impl UploadedLogs {
         self.filenames.insert(filename);
     }
 
+    /// Marks a log as uploaded, remembering its content hash when it is known
+    pub fn add_uploaded(&mut self, log: &crate::logfile::LogFile) {
+        if let Some(hash) = &log.content_hash {
+            self.hashes.insert(log.filename.clone(), hash.clone());
+        }
+        self.filenames.insert(log.filename.clone());
+    }
+
+    pub fn remove_log(&mut self, filename: &str) -> bool {
+        self.hashes.remove(filename);
+        self.archive.remove(filename);
+        self.filenames.remove(filename)
+    }
+
     pub fn is_uploaded(&self, filename: &str) -> bool {
         self.filenames.contains(filename)
     }
 
+    /// Filename the log's contents were already uploaded under, None if it wasn't or has the same name
+    pub fn duplicate_of(&self, log: &crate::logfile::LogFile) -> Option<&str> {
+        let hash = log.content_hash.as_ref()?;
+        if self.is_uploaded(&log.filename) {
+            return None;
+        }
+        self.hashes
+            .iter()
+            .find(|(filename, uploaded_hash)| *uploaded_hash == hash && **filename != log.filename)
+            .map(|(filename, _)| filename.as_str())
+    }
+
     pub fn clear(&mut self) {
         self.filenames.clear();
+        self.hashes.clear();
+        self.archive.clear();
     }
 
-    /// Removes uploaded log entries older than 72 hours
-    /// Returns the number of entries removed
-    pub fn cleanup_old_entries(&mut self) -> usize {
-        let cutoff_time = std::time::SystemTime::now()
-            - std::time::Duration::from_secs(72 * 60 * 60); // 72 hours in seconds
+    /// Moves uploaded log entries older than 72 hours to the archive and drops archived
+    /// entries older than `archive_days`, 0 removes old entries without archiving them
+    /// Returns the number of entries changed
+    pub fn cleanup_old_entries(&mut self, archive_days: u32) -> usize {
+        let now = std::time::SystemTime::now();
+        let cutoff_time = now - std::time::Duration::from_secs(72 * 60 * 60); // 72 hours in seconds
+        let now_secs = now
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
 
         let initial_count = self.filenames.len();
         
         // Filter out logs older than 72 hours
+        let mut expired = Vec::new();
         self.filenames.retain(|filename| {
             // Parse timestamp from filename (format: YYYYMMDD-HHMMSS)
             if let Some(timestamp_str) = extract_timestamp_from_filename(filename) {
                 if let Some(log_time) = parse_log_timestamp(&timestamp_str) {
                     // Keep the log if it's newer than cutoff
-                    return log_time >= cutoff_time;
+                    if log_time < cutoff_time {
+                        expired.push(filename.clone());
+                        return false;
+                    }
                 }
             }
             
@@ -78,6 +122,9 @@ impl UploadedLogs {
             true
         });
 
+        let filenames = &self.filenames;
+        self.hashes.retain(|filename, _| filenames.contains(filename));
+
         let removed_count = initial_count - self.filenames.len();
         
         if removed_count > 0 {
@@ -87,9 +134,62 @@ impl UploadedLogs {
                 self.filenames.len()
             );
         }
+
+        if archive_days > 0 {
+            for filename in expired {
+                self.archive.insert(filename, now_secs);
+            }
+        }
+
+        let archive_cutoff = now_secs.saturating_sub(archive_days as u64 * 24 * 60 * 60);
+        let archived_count = self.archive.len();
+        if archive_days == 0 {
+            self.archive.clear();
+        } else {
+            self.archive.retain(|_, archived| *archived >= archive_cutoff);
+        }
+        let dropped_count = archived_count - self.archive.len();
+
+        if dropped_count > 0 {
+            log::info!(
+                "Dropped {} archived upload entries older than {} days",
+                dropped_count,
+                archive_days
+            );
+        }
         
-        removed_count
+        removed_count + dropped_count
+    }
+
+    /// Archived logs matching every word of the query, newest first
+    /// Words are matched against the filename, date and weekday, e.g. "tuesday" or "oct 14"
+    pub fn search_archive(&self, query: &str) -> Vec<(&str, u64)> {
+        let words: Vec<String> = query.split_whitespace().map(|w| w.to_lowercase()).collect();
+        // Filenames start with the log's timestamp, so reversed they are newest first
+        self.archive
+            .iter()
+            .rev()
+            .filter(|(filename, _)| {
+                let searchable = searchable_text(filename);
+                words.iter().all(|word| searchable.contains(word.as_str()))
+            })
+            .map(|(filename, archived)| (filename.as_str(), *archived))
+            .collect()
+    }
+}
+
+/// The filename, formatted date and weekday of a log, lowercased for searching
+fn searchable_text(filename: &str) -> String {
+    let mut text = filename.to_lowercase();
+    if let Some(formatted) = crate::formatting::format_timestamp(filename) {
+        text.push(' ');
+        text.push_str(&formatted.to_lowercase());
+    }
+    if let Some(time) = crate::formatting::parse_log_timestamp(filename) {
+        text.push(' ');
+        text.push_str(&time.format("%A %Y-%m-%d").to_string().to_lowercase());
     }
+    text
 }
 
 /// Extracts the timestamp portion from a log filename
@@ -99,7 +199,7 @@ fn extract_timestamp_from_filename(filename: &str) -> Option<String> {
     let filename = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
     
     // Remove the extension if present
-    let without_ext = filename.strip_suffix(".zevtc").unwrap_or(filename);
+    let without_ext = crate::logfile::log_stem(filename);
     
     // The timestamp should be the first part (YYYYMMDD-HHMMSS)
     // It's 15 characters long: 8 for date + 1 for dash + 6 for time
@@ -142,5 +242,7 @@ fn parse_log_timestamp(timestamp: &str) -> Option<std::time::SystemTime> {
 static UPLOADED_LOGS: LazyLock<Mutex<UploadedLogs>> = LazyLock::new(|| {
     Mutex::new(UploadedLogs {
         filenames: HashSet::new(),
+        hashes: HashMap::new(),
+        archive: BTreeMap::new(),
     })
 });
\ No newline at end of file
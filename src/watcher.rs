@@ -0,0 +1,427 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::cancel::CancelToken;
+use crate::logfile::LogFile;
+use crate::settings::Settings;
+use crate::state::{ProcessingState, STATE};
+use crate::upload_review::UploadedFileInfo;
+use crate::uploaded_logs::UploadedLogs;
+
+/// How often the log directory is checked for new logs
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A log counts as finished once its size hasn't changed for this long
+const SETTLE_TIME: Duration = Duration::from_secs(10);
+
+/// Where a log found by live mode is at
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveStatus {
+    /// arcdps is still writing the file
+    Writing,
+    /// Finished, waiting for a session that accepts uploads
+    Waiting,
+    Uploading,
+    Uploaded,
+    Failed(String),
+}
+
+impl LiveStatus {
+    pub fn label(&self) -> String {
+        match self {
+            LiveStatus::Writing => "Writing".to_string(),
+            LiveStatus::Waiting => "Waiting".to_string(),
+            LiveStatus::Uploading => "Uploading".to_string(),
+            LiveStatus::Uploaded => "Uploaded".to_string(),
+            LiveStatus::Failed(reason) => format!("Failed: {}", reason),
+        }
+    }
+}
+
+/// A new log picked up by live mode
+#[derive(Debug, Clone)]
+pub struct LiveLog {
+    pub path: PathBuf,
+    pub filename: String,
+    pub status: LiveStatus,
+    size: u64,
+    last_change: Instant,
+    /// Filled in once the file is finished
+    log: Option<LogFile>,
+}
+
+/// Starts the thread that uploads new logs while live mode is on
+pub fn start() {
+    crate::threads::spawn_tracked("log-watcher", || {
+        // Never cancelled itself, only stops on unload
+        let idle = CancelToken::new();
+        // Known by filename, so logs moved into subfolders by the organizer aren't picked up again
+        let mut known: Option<HashSet<String>> = None;
+
+        while idle.sleep(POLL_INTERVAL) {
+            if !Settings::get().live_mode {
+                known = None;
+                continue;
+            }
+
+            let settings = Settings::get();
+            let log_directory = PathBuf::from(settings.log_directory.clone());
+            let exclude = settings.scan_exclude_patterns.clone();
+            drop(settings);
+            let mut files = Vec::new();
+            find_logs(&log_directory, &exclude, &mut files);
+
+            match known.as_mut() {
+                // Logs already on disk when live mode was switched on are left alone
+                None => known = Some(files.iter().map(|path| file_name(path)).collect()),
+                Some(known) => {
+                    for path in files {
+                        if known.insert(file_name(&path)) {
+                            queue_new_log(path);
+                        }
+                    }
+                }
+            }
+
+            update_writing_logs();
+
+            if !crate::mumble::should_defer_work() && !crate::throttle::is_throttled() && crate::consent::is_granted() {
+                upload_waiting_logs(&idle);
+            }
+
+            auto_process_if_quiet();
+        }
+    });
+}
+
+/// Recursively collects the log files below a directory, leaving out excluded folders
+fn find_logs(dir: &Path, exclude: &[String], files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if crate::scanning::is_excluded(&path, exclude) {
+            continue;
+        }
+        if path.is_dir() {
+            if !crate::archive::is_archive_root(&path) {
+                find_logs(&path, exclude, files);
+            }
+        } else if crate::logfile::is_log_file(&path) {
+            files.push(path);
+        }
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn queue_new_log(path: PathBuf) {
+    let filename = file_name(&path);
+    log::info!("Live mode picked up {}", filename);
+
+    STATE.live_queue.lock().unwrap().push(LiveLog {
+        path,
+        filename,
+        status: LiveStatus::Writing,
+        size: 0,
+        last_change: Instant::now(),
+        log: None,
+    });
+}
+
+/// Moves logs arcdps has finished writing on to waiting, dropping anything that isn't WvW
+fn update_writing_logs() {
+    let writing: Vec<(PathBuf, u64, Instant)> = STATE
+        .live_queue
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|l| l.status == LiveStatus::Writing)
+        .map(|l| (l.path.clone(), l.size, l.last_change))
+        .collect();
+
+    for (path, size, last_change) in writing {
+        let current_size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        if current_size != size {
+            if let Some(entry) = STATE.live_queue.lock().unwrap().iter_mut().find(|l| l.path == path) {
+                entry.size = current_size;
+                entry.last_change = Instant::now();
+            }
+            continue;
+        }
+
+        if last_change.elapsed() < SETTLE_TIME {
+            continue;
+        }
+
+        // Parsed outside the queue lock, this reads the whole file
+        let result = LogFile::new_fast(path.clone());
+
+        let mut queue = STATE.live_queue.lock().unwrap();
+        match result {
+            Ok(log) if log.invalid.is_some() => {
+                if let Some(entry) = queue.iter_mut().find(|l| l.path == path) {
+                    entry.status = LiveStatus::Failed(log.invalid.unwrap_or_default());
+                }
+            }
+            Ok(log) if !log.map_type.is_wvw() => {
+                log::info!("Live mode skipping non-WvW log {:?}", path);
+                queue.retain(|l| l.path != path);
+            }
+            Ok(log) => {
+                if let Some(entry) = queue.iter_mut().find(|l| l.path == path) {
+                    entry.status = LiveStatus::Waiting;
+                    entry.log = Some(log);
+                }
+            }
+            Err(e) => {
+                if let Some(entry) = queue.iter_mut().find(|l| l.path == path) {
+                    entry.status = LiveStatus::Failed(e.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Uploads finished logs to the active session, creating one if there is none
+fn upload_waiting_logs(cancel: &CancelToken) {
+    loop {
+        if cancel.is_cancelled() || !Settings::get().live_mode {
+            return;
+        }
+
+        let next = STATE
+            .live_queue
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|l| l.status == LiveStatus::Waiting)
+            .and_then(|l| l.log.clone());
+        let Some(log) = next else {
+            return;
+        };
+
+        let Some(session_id) = active_session() else {
+            return;
+        };
+
+        set_status(&log.path, LiveStatus::Uploading);
+
+        match upload_to_session(&log, &session_id, cancel) {
+            Ok(_) => {
+                log::info!("Live mode uploaded {}", log.filename);
+                set_status(&log.path, LiveStatus::Uploaded);
+                *STATE.live_last_upload.lock().unwrap() = Some(Instant::now());
+            }
+            Err(e) => {
+                log::warn!("Live mode failed to upload {}: {}", log.filename, e);
+                set_status(&log.path, LiveStatus::Failed(e.to_string()));
+            }
+        }
+    }
+}
+
+/// Uploads a log to the given session and adds it to the session's file list
+pub fn upload_to_session(log: &LogFile, session_id: &str, cancel: &CancelToken) -> anyhow::Result<()> {
+    let api_endpoint = crate::upload::session_endpoint();
+    let history_token = Settings::get().history_token.clone();
+
+    crate::upload::upload_file(log.path.clone(), &api_endpoint, session_id, &history_token, cancel, |_, _| {})?;
+
+    let mut uploaded_files = STATE.uploaded_files.lock().unwrap();
+    if !uploaded_files.iter().any(|f| f.filename == log.filename) {
+        uploaded_files.push(UploadedFileInfo::from_log(log, session_id.to_string()));
+    }
+    drop(uploaded_files);
+
+    crate::webhooks::announce_session_start();
+
+    let mut uploaded = UploadedLogs::get();
+    uploaded.add_uploaded(log);
+    if let Err(e) = uploaded.store(crate::uploaded_logs_path()) {
+        log::error!("Failed to save uploaded logs: {}", e);
+    }
+    Ok(())
+}
+
+/// The session background uploads go to, creating one if there is none
+/// None while no session can take new files
+pub fn active_session() -> Option<String> {
+    // A session being processed or showing results is finished, logs wait for the next one
+    let state = *STATE.processing_state.lock().unwrap();
+    if !matches!(state, ProcessingState::Idle | ProcessingState::Uploading) || *STATE.show_results.lock().unwrap() {
+        return None;
+    }
+
+    // Split sessions decide per log where it goes, background uploads don't take part in that
+    if !STATE.split_sessions.lock().unwrap().is_empty() {
+        return None;
+    }
+
+    // Wait until the user decided whether to continue the session from before the crash
+    if STATE.resumable_session.lock().unwrap().is_some() {
+        return None;
+    }
+
+    let existing = STATE.session_id.lock().unwrap().clone();
+    if !existing.is_empty() {
+        return Some(existing);
+    }
+
+    let api_endpoint = crate::upload::session_endpoint();
+    let history_token = Settings::get().history_token.clone();
+
+    if history_token.is_empty() {
+        return None;
+    }
+
+    match crate::upload::create_session(&api_endpoint, &history_token) {
+        Ok((session_id, ownership_token)) => {
+            log::info!("Created session {} for background uploads", session_id);
+            *STATE.session_id.lock().unwrap() = session_id.clone();
+            *STATE.ownership_token.lock().unwrap() = ownership_token;
+            Some(session_id)
+        }
+        Err(e) => {
+            log::error!("Failed to create a session for background uploads: {}", e);
+            None
+        }
+    }
+}
+
+/// Starts processing once no new log was uploaded for the configured time
+fn auto_process_if_quiet() {
+    let settings = Settings::get();
+    let enabled = settings.auto_process_enabled;
+    let quiet_time = Duration::from_secs(u64::from(settings.auto_process_minutes.max(1)) * 60);
+    drop(settings);
+
+    if !enabled {
+        return;
+    }
+
+    let Some(last_upload) = *STATE.live_last_upload.lock().unwrap() else {
+        return;
+    };
+    if last_upload.elapsed() < quiet_time {
+        return;
+    }
+
+    if *STATE.processing_state.lock().unwrap() != ProcessingState::Idle || *STATE.show_results.lock().unwrap() {
+        return;
+    }
+
+    // A log still being written or uploaded means the raid isn't over yet
+    let pending = STATE
+        .live_queue
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|l| matches!(l.status, LiveStatus::Writing | LiveStatus::Waiting | LiveStatus::Uploading));
+    if pending || STATE.uploaded_files.lock().unwrap().is_empty() {
+        return;
+    }
+
+    log::info!(
+        "No new logs for {}, starting processing",
+        crate::formatting::format_duration(quiet_time.as_secs())
+    );
+    *STATE.live_last_upload.lock().unwrap() = None;
+    start_unattended_processing();
+}
+
+/// Starts processing the active session without the user clicking through the review screen
+/// Returns whether processing started
+pub fn start_unattended_processing() -> bool {
+    *STATE.auto_processing.lock().unwrap() = true;
+    *STATE.show_log_selection.lock().unwrap() = false;
+    *STATE.show_token_input.lock().unwrap() = false;
+    crate::upload_review::start_processing_wrapper();
+
+    // Starting failed, the report can still be processed by hand
+    let started = *STATE.processing_state.lock().unwrap() == ProcessingState::Processing;
+    if !started {
+        *STATE.auto_processing.lock().unwrap() = false;
+    }
+    started
+}
+
+/// Posts the report of an auto-processed session to the announcement webhook
+pub fn on_processing_complete() {
+    if !std::mem::take(&mut *STATE.auto_processing.lock().unwrap()) {
+        return;
+    }
+
+    let settings = crate::webhooks::WebhookSettings::get();
+    if !settings.announce_enabled || !settings.auto_post_reports {
+        return;
+    }
+    let Some(webhook) = settings
+        .saved_webhooks
+        .iter()
+        .find(|w| w.name == settings.announce_webhook)
+        .cloned()
+    else {
+        log::warn!("Announcement webhook '{}' is not saved anymore", settings.announce_webhook);
+        return;
+    };
+    drop(settings);
+
+    // Gets every report already
+    if webhook.auto_post {
+        return;
+    }
+
+    let report_urls = STATE.report_urls.lock().unwrap().clone();
+    if report_urls.is_empty() {
+        return;
+    }
+
+    let report_name = crate::tokens::current_report_name();
+    let summary = crate::webhooks::ReportSummary::current_session();
+    let message = crate::commanders::append_notes(
+        &crate::ui::results::build_report_message(&report_urls, &report_name),
+        &summary.commanders,
+    );
+    let embed = crate::webhooks::report_embed(&report_name, summary);
+    crate::threads::spawn("webhook-auto-post", move || {
+        let _activity = crate::threads::activity("Posting to Discord");
+        let cancel = crate::cancel::upload_token();
+        match crate::pending_posts::send_or_queue(&webhook.url, &message, embed, &cancel, |_| {}) {
+            Ok(_) => log::info!("Posted auto-processed report to {}", webhook.name),
+            Err(e) => log::warn!("Failed to post auto-processed report: {}", e),
+        }
+    });
+}
+
+fn set_status(path: &Path, status: LiveStatus) {
+    if let Some(entry) = STATE.live_queue.lock().unwrap().iter_mut().find(|l| l.path == path) {
+        entry.status = status;
+    }
+}
+
+/// Queues failed uploads again, logs that failed their checks stay failed
+pub fn retry_failed() {
+    for entry in STATE.live_queue.lock().unwrap().iter_mut() {
+        if matches!(entry.status, LiveStatus::Failed(_)) && entry.log.is_some() {
+            entry.status = LiveStatus::Waiting;
+        }
+    }
+}
+
+/// Removes uploaded and failed logs from the live queue
+pub fn clear_finished() {
+    STATE
+        .live_queue
+        .lock()
+        .unwrap()
+        .retain(|l| matches!(l.status, LiveStatus::Writing | LiveStatus::Waiting | LiveStatus::Uploading));
+}
@@ -3,6 +3,20 @@ use serde::{Deserialize, Serialize};
 use std::fs::{create_dir_all, File};
 use std::path::Path;
 use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::cancel::CancelToken;
+use crate::state::STATE;
+
+/// Times a rate-limited message is retried before giving up
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+/// Longest rate-limit delay waited out, longer ones are reported as failures
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+/// Name and avatar posts use when the webhook doesn't override them
+const DEFAULT_USERNAME: &str = "WvW Insights Parser";
+const DEFAULT_AVATAR_URL: &str = "https://parser.rethl.net/Assets/Avatar.png";
+/// Longest username Discord accepts for a webhook post
+pub const MAX_USERNAME_LENGTH: usize = 80;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavedWebhook {
@@ -10,8 +24,41 @@ pub struct SavedWebhook {
     pub url: String,
     pub created: u64,      // Unix timestamp
     pub last_used: u64,    // Unix timestamp
+    /// Post the report links here as soon as processing completes
+    #[serde(default)]
+    pub auto_post: bool,
+    /// Set for custom HTTP endpoints, None for Discord webhooks
+    #[serde(default)]
+    pub custom: Option<CustomEndpoint>,
+    /// Name posts show instead of the default, {guild} is replaced with the guild name
+    #[serde(default)]
+    pub username: String,
+    /// Image posts show instead of the default avatar
+    #[serde(default)]
+    pub avatar_url: String,
+}
+
+/// A non-Discord endpoint, e.g. a guild bot, that gets reports as a JSON body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomEndpoint {
+    /// Sent with every request, e.g. "Authorization: Bearer abc", empty sends none
+    #[serde(default)]
+    pub auth_header: String,
+    #[serde(default = "default_custom_body")]
+    pub body_template: String,
 }
 
+/// Placeholders filled in with JSON values when a custom endpoint is posted to
+pub const CUSTOM_BODY_PLACEHOLDERS: &str = "{urls}, {session_id}, {timestamp}, {guild}, {message}";
+
+pub fn default_custom_body() -> String {
+    "{\n  \"report_urls\": {urls},\n  \"session_id\": {session_id},\n  \"timestamp\": {timestamp},\n  \"guild\": {guild}\n}"
+        .to_string()
+}
+
+/// Longest a custom endpoint gets to answer
+const CUSTOM_TIMEOUT: Duration = Duration::from_secs(15);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookSettings {
     #[serde(default)]
@@ -20,6 +67,191 @@ pub struct WebhookSettings {
     pub remember_last_webhook: bool,
     #[serde(default)]
     pub last_webhook_url: String,
+    /// Post a message when the first log of a session is uploaded
+    #[serde(default)]
+    pub announce_enabled: bool,
+    /// Name of the saved webhook the announcement goes to
+    #[serde(default)]
+    pub announce_webhook: String,
+    #[serde(default = "default_announce_template")]
+    pub announce_template: String,
+    /// Post the finished report to the announcement webhook after auto-processing
+    #[serde(default)]
+    pub auto_post_reports: bool,
+    /// Send reports as a rich embed instead of plain links
+    #[serde(default)]
+    pub use_embeds: bool,
+    #[serde(default = "default_embed_color")]
+    pub embed_color: [f32; 3],
+}
+
+/// Placeholders filled in when the announcement is sent
+pub const ANNOUNCE_PLACEHOLDERS: &str = "{guild}, {time}";
+
+pub fn default_announce_template() -> String {
+    "Parsing in progress for {guild}, the report follows later tonight!".to_string()
+}
+
+/// Discord's blurple
+const DEFAULT_EMBED_COLOR: [f32; 3] = [0.345, 0.396, 0.949];
+
+fn default_embed_color() -> [f32; 3] {
+    DEFAULT_EMBED_COLOR
+}
+
+/// Numbers about a session shown as fields of a report embed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportSummary {
+    pub fights: usize,
+    /// From the first to the last log, None when the filenames carry no timestamps
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+    #[serde(default)]
+    pub maps: Vec<String>,
+    /// Commander tags of the session, most logs first, empty when they aren't known
+    #[serde(default)]
+    pub commanders: Vec<String>,
+}
+
+impl ReportSummary {
+    /// Summary of logs by filename, `maps` is left empty when they aren't known
+    pub fn from_logs(filenames: &[String], maps: Vec<String>) -> Self {
+        let mut times: Vec<_> = filenames
+            .iter()
+            .filter_map(|name| crate::formatting::parse_log_timestamp(name))
+            .collect();
+        times.sort();
+
+        let duration_secs = match (times.first(), times.last()) {
+            (Some(first), Some(last)) if first != last => Some((*last - *first).num_seconds().max(0) as u64),
+            _ => None,
+        };
+
+        Self {
+            fights: filenames.len(),
+            duration_secs,
+            maps,
+            commanders: Vec::new(),
+        }
+    }
+
+    /// Summary of the logs uploaded in the current session
+    pub fn current_session() -> Self {
+        let files = STATE.uploaded_files.lock().unwrap().clone();
+        let filenames: Vec<String> = files.iter().map(|f| f.filename.clone()).collect();
+
+        let mut maps = Vec::new();
+        for map in files.iter().filter_map(|f| f.metadata.as_ref().map(|m| m.map_abbr.clone())) {
+            if !maps.contains(&map) {
+                maps.push(map);
+            }
+        }
+
+        Self {
+            commanders: crate::commanders::session_commanders(),
+            ..Self::from_logs(&filenames, maps)
+        }
+    }
+}
+
+/// A report posted as a Discord embed, kept with queued posts so a retry looks the same
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportEmbed {
+    pub title: String,
+    pub color: u32,
+    pub summary: ReportSummary,
+    /// RFC 3339 time the report was first posted
+    pub timestamp: String,
+}
+
+/// Builds the embed for a report post, None when reports are sent as plain links
+pub fn report_embed(title: &str, summary: ReportSummary) -> Option<ReportEmbed> {
+    let settings = WebhookSettings::get();
+    if !settings.use_embeds {
+        return None;
+    }
+    let [r, g, b] = settings.embed_color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u32);
+    drop(settings);
+
+    Some(ReportEmbed {
+        title: title.to_string(),
+        color: (r << 16) | (g << 8) | b,
+        summary,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Name and avatar a post to the webhook shows, the saved overrides or the defaults
+fn post_identity(webhook_url: &str) -> (String, String) {
+    let guild = crate::settings::Settings::get().guild_name.clone();
+    let settings = WebhookSettings::get();
+    let Some(webhook) = settings.saved_webhooks.iter().find(|w| w.url == webhook_url) else {
+        return (DEFAULT_USERNAME.to_string(), DEFAULT_AVATAR_URL.to_string());
+    };
+
+    let username: String = webhook
+        .username
+        .replace("{guild}", guild.trim())
+        .trim()
+        .chars()
+        .take(MAX_USERNAME_LENGTH)
+        .collect();
+    let username = if username.is_empty() { DEFAULT_USERNAME.to_string() } else { username };
+
+    // Discord only fetches avatars over https
+    let avatar_url = webhook.avatar_url.trim();
+    let avatar_url = if avatar_url.starts_with("https://") {
+        avatar_url.to_string()
+    } else {
+        DEFAULT_AVATAR_URL.to_string()
+    };
+
+    (username, avatar_url)
+}
+
+/// The JSON body of a webhook post, the message goes into the embed description when there is one
+fn build_payload(message_content: &str, embed: Option<&ReportEmbed>, identity: (String, String)) -> serde_json::Value {
+    let (username, avatar_url) = identity;
+    let Some(embed) = embed else {
+        return serde_json::json!({
+            "content": message_content,
+            "username": username,
+            "avatar_url": avatar_url
+        });
+    };
+
+    let mut fields = vec![serde_json::json!({
+        "name": "Fights",
+        "value": embed.summary.fights.to_string(),
+        "inline": true
+    })];
+    if let Some(duration) = embed.summary.duration_secs {
+        fields.push(serde_json::json!({
+            "name": "Duration",
+            "value": crate::formatting::format_duration(duration),
+            "inline": true
+        }));
+    }
+    if !embed.summary.maps.is_empty() {
+        fields.push(serde_json::json!({
+            "name": "Maps",
+            "value": embed.summary.maps.join(", "),
+            "inline": true
+        }));
+    }
+
+    serde_json::json!({
+        "username": username,
+        "avatar_url": avatar_url,
+        "embeds": [{
+            "title": embed.title,
+            "description": message_content,
+            "color": embed.color,
+            "fields": fields,
+            "timestamp": embed.timestamp,
+            "footer": { "text": format!("WvW Insights v{}", env!("CARGO_PKG_VERSION")) }
+        }]
+    })
 }
 
 impl WebhookSettings {
@@ -28,6 +260,12 @@ impl WebhookSettings {
             saved_webhooks: Vec::new(),
             remember_last_webhook: false,
             last_webhook_url: String::new(),
+            announce_enabled: false,
+            announce_webhook: String::new(),
+            announce_template: String::new(),
+            auto_post_reports: false,
+            use_embeds: false,
+            embed_color: DEFAULT_EMBED_COLOR,
         }
     }
 
@@ -35,6 +273,12 @@ impl WebhookSettings {
         self.saved_webhooks = Vec::new();
         self.remember_last_webhook = false;
         self.last_webhook_url = String::new();
+        self.announce_enabled = false;
+        self.announce_webhook = String::new();
+        self.announce_template = default_announce_template();
+        self.auto_post_reports = false;
+        self.use_embeds = false;
+        self.embed_color = DEFAULT_EMBED_COLOR;
     }
 
     pub fn get() -> MutexGuard<'static, Self> {
@@ -71,7 +315,7 @@ impl WebhookSettings {
         Ok(())
     }
 
-    pub fn add_webhook(&mut self, name: String, url: String) -> Result<(), String> {
+    pub fn add_webhook(&mut self, name: String, url: String, custom: Option<CustomEndpoint>) -> Result<(), String> {
         // Check for duplicate URL
         if self.saved_webhooks.iter().any(|w| w.url == url) {
             return Err("This webhook URL is already saved".to_string());
@@ -92,6 +336,10 @@ impl WebhookSettings {
             url,
             created: timestamp,
             last_used: timestamp,
+            auto_post: false,
+            custom,
+            username: String::new(),
+            avatar_url: String::new(),
         });
 
         Ok(())
@@ -112,6 +360,30 @@ impl WebhookSettings {
         }
     }
 
+    pub fn set_auto_post(&mut self, name: &str, auto_post: bool) {
+        if let Some(webhook) = self.saved_webhooks.iter_mut().find(|w| w.name == name) {
+            webhook.auto_post = auto_post;
+        }
+    }
+
+    /// Sets the name and avatar posts to the webhook show, empty values use the defaults
+    pub fn set_identity(&mut self, name: &str, username: &str, avatar_url: &str) {
+        if let Some(webhook) = self.saved_webhooks.iter_mut().find(|w| w.name == name) {
+            webhook.username = username.to_string();
+            webhook.avatar_url = avatar_url.to_string();
+        }
+    }
+
+    /// Makes a saved webhook the one reports and announcements go to, false if it isn't saved
+    pub fn select_default(&mut self, name: &str) -> bool {
+        let Some(url) = self.saved_webhooks.iter().find(|w| w.name == name).map(|w| w.url.clone()) else {
+            return false;
+        };
+        self.announce_webhook = name.to_string();
+        self.last_webhook_url = url;
+        true
+    }
+
     pub fn get_webhooks_sorted(&self) -> Vec<SavedWebhook> {
         let mut webhooks = self.saved_webhooks.clone();
         webhooks.sort_by(|a, b| b.last_used.cmp(&a.last_used));
@@ -119,10 +391,117 @@ impl WebhookSettings {
     }
 }
 
+/// Where a report post to one webhook stands, shown per webhook in the send modal
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookDelivery {
+    Waiting,
+    Sending,
+    Sent,
+    Failed(String),
+}
+
 static WEBHOOK_SETTINGS: Mutex<WebhookSettings> = Mutex::new(WebhookSettings::default());
 
+/// Fills the placeholders of the announcement template
+fn format_announcement(template: &str) -> String {
+    let guild = crate::settings::Settings::get().guild_name.clone();
+    let guild = if guild.is_empty() { "the squad".to_string() } else { guild };
+
+    template
+        .replace("{guild}", &guild)
+        .replace("{time}", &chrono::Local::now().format("%H:%M").to_string())
+}
+
+/// Posts the raid start announcement for the current session, at most once per session
+pub fn announce_session_start() {
+    let session_id = STATE.session_id.lock().unwrap().clone();
+    if session_id.is_empty() {
+        return;
+    }
+
+    let settings = WebhookSettings::get();
+    if !settings.announce_enabled {
+        return;
+    }
+    let Some(webhook) = settings
+        .saved_webhooks
+        .iter()
+        .find(|w| w.name == settings.announce_webhook)
+        .cloned()
+    else {
+        log::warn!("Announcement webhook '{}' is not saved anymore", settings.announce_webhook);
+        return;
+    };
+    let template = settings.announce_template.clone();
+    drop(settings);
+
+    {
+        let mut announced = STATE.announced_session.lock().unwrap();
+        if *announced == session_id {
+            return;
+        }
+        *announced = session_id;
+    }
+
+    let message = format_announcement(&template);
+    crate::threads::spawn("webhook-announce", move || {
+        let _activity = crate::threads::activity("Posting to Discord");
+        let cancel = crate::cancel::upload_token();
+        match crate::pending_posts::send_or_queue(&webhook.url, &message, None, &cancel, |_| {}) {
+            Ok(_) => log::info!("Posted session announcement to {}", webhook.name),
+            Err(e) => log::warn!("Failed to post session announcement: {}", e),
+        }
+    });
+}
+
+/// Posts the finished session's reports to every webhook set to auto-post
+/// Called once processing completes, failed posts are queued like any other
+pub fn auto_post_reports() {
+    let webhooks: Vec<SavedWebhook> = WebhookSettings::get()
+        .saved_webhooks
+        .iter()
+        .filter(|w| w.auto_post)
+        .cloned()
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let report_urls = STATE.report_urls.lock().unwrap().clone();
+    if report_urls.is_empty() {
+        return;
+    }
+
+    let report_name = crate::tokens::current_report_name();
+    let summary = ReportSummary::current_session();
+    let message = crate::commanders::append_notes(
+        &crate::ui::results::build_report_message(&report_urls, &report_name),
+        &summary.commanders,
+    );
+    let embed = report_embed(&report_name, summary);
+
+    crate::threads::spawn("webhook-auto-post", move || {
+        let _activity = crate::threads::activity("Posting to Discord");
+        let cancel = crate::cancel::upload_token();
+        for webhook in webhooks {
+            match crate::pending_posts::send_or_queue(&webhook.url, &message, embed.clone(), &cancel, |_| {}) {
+                Ok(_) => {
+                    log::info!("Auto-posted report to {}", webhook.name);
+                    WebhookSettings::get().update_webhook_usage(&webhook.url);
+                }
+                Err(e) => log::warn!("Failed to auto-post report to {}: {}", webhook.name, e),
+            }
+        }
+
+        let settings = WebhookSettings::get();
+        if let Err(e) = settings.store(crate::webhooks_path()) {
+            log::error!("Failed to save webhook settings: {}", e);
+        }
+    });
+}
+
 /// Validates a Discord webhook URL
-fn validate_webhook_url(webhook_url: &str) -> Result<()> {
+pub fn validate_webhook_url(webhook_url: &str) -> Result<()> {
     // Check if URL is empty
     if webhook_url.trim().is_empty() {
         return Err(anyhow::anyhow!("Webhook URL cannot be empty"));
@@ -149,8 +528,118 @@ fn validate_webhook_url(webhook_url: &str) -> Result<()> {
     Ok(())
 }
 
-/// Send a message to a Discord webhook
-pub fn send_to_discord(webhook_url: &str, message_content: &str) -> Result<()> {
+/// The custom endpoint settings of a saved webhook, None for Discord webhooks and unsaved URLs
+fn custom_endpoint(url: &str) -> Option<CustomEndpoint> {
+    WebhookSettings::get()
+        .saved_webhooks
+        .iter()
+        .find(|w| w.url == url)
+        .and_then(|w| w.custom.clone())
+}
+
+/// Validates a custom endpoint URL
+pub fn validate_custom_url(url: &str) -> Result<()> {
+    if url.trim().is_empty() {
+        return Err(anyhow::anyhow!("Endpoint URL cannot be empty"));
+    }
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(anyhow::anyhow!("Endpoint URL must start with https:// or http://"));
+    }
+    Ok(())
+}
+
+/// Validates a webhook URL against the kind it was saved as
+pub fn validate_target(url: &str) -> Result<()> {
+    match custom_endpoint(url) {
+        Some(_) => validate_custom_url(url),
+        None => validate_webhook_url(url),
+    }
+}
+
+/// Sends a message to a saved or entered webhook, Discord or custom endpoint
+pub fn send_message(
+    url: &str,
+    message_content: &str,
+    embed: Option<&ReportEmbed>,
+    cancel: &CancelToken,
+    on_rate_limited: impl Fn(Duration),
+) -> Result<()> {
+    match custom_endpoint(url) {
+        Some(endpoint) => send_to_custom(url, &endpoint, message_content, embed),
+        None => send_to_discord(url, message_content, embed, cancel, on_rate_limited),
+    }
+}
+
+/// Links in a report message, whether bare or inside markdown links
+fn message_urls(message: &str) -> Vec<String> {
+    message
+        .split(|c: char| c.is_whitespace() || "()<>[]".contains(c))
+        .filter(|part| part.starts_with("https://") || part.starts_with("http://"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Fills the body template of a custom endpoint, every placeholder becomes a JSON value
+fn build_custom_body(template: &str, message_content: &str, embed: Option<&ReportEmbed>) -> Result<serde_json::Value> {
+    let timestamp = embed
+        .map(|e| e.timestamp.clone())
+        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let guild = crate::settings::Settings::get().guild_name.clone();
+    let session_id = STATE.session_id.lock().unwrap().clone();
+
+    let body = template
+        .replace("{urls}", &serde_json::json!(message_urls(message_content)).to_string())
+        .replace("{session_id}", &serde_json::json!(session_id).to_string())
+        .replace("{timestamp}", &serde_json::json!(timestamp).to_string())
+        .replace("{guild}", &serde_json::json!(guild).to_string())
+        .replace("{message}", &serde_json::json!(message_content).to_string());
+
+    serde_json::from_str(&body).map_err(|e| anyhow::anyhow!("Body template is not valid JSON: {}", e))
+}
+
+/// Fails when a body template doesn't turn into valid JSON once its placeholders are filled
+pub fn check_body_template(template: &str) -> Result<()> {
+    build_custom_body(template, "", None).map(|_| ())
+}
+
+/// POSTs a message to a custom endpoint as the JSON body its template describes
+fn send_to_custom(
+    url: &str,
+    endpoint: &CustomEndpoint,
+    message_content: &str,
+    embed: Option<&ReportEmbed>,
+) -> Result<()> {
+    crate::consent::require()?;
+    validate_custom_url(url)?;
+
+    let body = build_custom_body(&endpoint.body_template, message_content, embed)?;
+
+    let mut request = ureq::post(url).timeout(CUSTOM_TIMEOUT);
+    if let Some((name, value)) = endpoint.auth_header.split_once(':') {
+        request = request.set(name.trim(), value.trim());
+    } else if !endpoint.auth_header.trim().is_empty() {
+        // A bare value is taken as the Authorization header
+        request = request.set("Authorization", endpoint.auth_header.trim());
+    }
+
+    match request.send_json(&body) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) => Err(anyhow::anyhow!("Endpoint returned status: {}", code)),
+        Err(e) => Err(anyhow::anyhow!("Failed to reach endpoint: {}", e)),
+    }
+}
+
+/// Send a message to a Discord webhook, as an embed when one is given
+/// Rate-limited messages are retried after the delay Discord asks for, `on_rate_limited` is told how long
+pub fn send_to_discord(
+    webhook_url: &str,
+    message_content: &str,
+    embed: Option<&ReportEmbed>,
+    cancel: &CancelToken,
+    on_rate_limited: impl Fn(Duration),
+) -> Result<()> {
+    crate::consent::require()?;
+
     // Validate the webhook URL first
     validate_webhook_url(webhook_url)?;
 
@@ -159,20 +648,34 @@ pub fn send_to_discord(webhook_url: &str, message_content: &str) -> Result<()> {
         return Err(anyhow::anyhow!("Message content cannot be empty"));
     }
 
-    let payload = serde_json::json!({
-        "content": message_content,
-        "username": "WvW Insights Parser",
-        "avatar_url": "https://parser.rethl.net/Assets/Avatar.png"
-    });
+    let payload = build_payload(message_content, embed, post_identity(webhook_url));
 
     // Send the HTTP request with proper error handling
-    let response = match ureq::post(webhook_url)
-        .set("Content-Type", "application/json")
-        .send_json(&payload) {
-        Ok(resp) => resp,
-        Err(e) => {
-            log::error!("Failed to send webhook request: {}", e);
-            return Err(anyhow::anyhow!("Failed to send webhook request: {}", e));
+    let mut retries = 0;
+    let response = loop {
+        match ureq::post(webhook_url)
+            .set("Content-Type", "application/json")
+            .send_json(&payload) {
+            Ok(resp) => break resp,
+            Err(ureq::Error::Status(429, resp)) if retries < MAX_RATE_LIMIT_RETRIES => {
+                let delay = rate_limit_delay(resp);
+                if delay > MAX_RATE_LIMIT_WAIT {
+                    return Err(anyhow::anyhow!(
+                        "Discord rate limit too long ({})",
+                        crate::formatting::format_duration(delay.as_secs())
+                    ));
+                }
+                retries += 1;
+                log::warn!("Rate limited by Discord, retrying in {:.1}s", delay.as_secs_f32());
+                on_rate_limited(delay);
+                if !cancel.sleep(delay) {
+                    return Err(anyhow::anyhow!("Cancelled"));
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to send webhook request: {}", e);
+                return Err(anyhow::anyhow!("Failed to send webhook request: {}", e));
+            }
         }
     };
 
@@ -182,4 +685,20 @@ pub fn send_to_discord(webhook_url: &str, message_content: &str) -> Result<()> {
     } else {
         Err(anyhow::anyhow!("Discord returned status: {}", response.status()))
     }
-}
\ No newline at end of file
+}
+
+/// Reads how long to wait from a 429 response
+/// Discord sends retry_after in seconds in the body, the Retry-After header is the fallback
+fn rate_limit_delay(response: ureq::Response) -> Duration {
+    let header_secs = response
+        .header("Retry-After")
+        .and_then(|v| v.parse::<f64>().ok());
+    let body_secs = response
+        .into_json::<serde_json::Value>()
+        .ok()
+        .and_then(|body| body.get("retry_after").and_then(|v| v.as_f64()));
+
+    let secs = body_secs.or(header_secs).unwrap_or(1.0).max(0.0);
+    // A little slack so the retry doesn't land right on the limit again
+    Duration::from_secs_f64(secs) + Duration::from_millis(250)
+}